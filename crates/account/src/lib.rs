@@ -20,6 +20,7 @@
 )]
 
 mod auth;
+mod policy;
 mod storage;
 mod storage_key;
 mod types;
@@ -33,6 +34,7 @@ pub use namada_core::storage::Key;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
+pub use policy::*;
 use serde::{Deserialize, Serialize};
 pub use storage::*;
 pub use storage_key::*;
@@ -55,6 +57,10 @@ pub struct Account {
     pub threshold: u8,
     /// The address corresponding to the account owner
     pub address: Address,
+    /// The parameters of the account's VP, e.g. its spending limits
+    pub vp_params: Option<VpParams>,
+    /// The vesting schedule of the account, if it's a vesting account
+    pub vesting: Option<VestingSchedule>,
 }
 
 impl Account {
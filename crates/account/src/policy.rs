@@ -0,0 +1,842 @@
+//! Policies configured by the VP parameters of an account and enforced by its
+//! VP on the tokens debited from the account.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use namada_core::address::Address;
+use namada_core::storage::Key;
+use namada_core::time::DateTimeUtc;
+use namada_core::token;
+use namada_storage::{Error, Result, StorageRead};
+
+use crate::{
+    add_withdrawal, current_day, current_time, daily_debit_key,
+    is_daily_debit_key, is_withdrawals_key, threshold, total_withdrawn,
+    vesting_schedule, vesting_schedule_key, vp_params, vp_params_key,
+    withdrawals_key, DailyDebit, VpParams, Withdrawal, WithdrawalLimit,
+};
+
+/// A token debited from an account by a transaction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenDebit {
+    /// The net amount debited from the account
+    pub amount: token::Amount,
+    /// The other accounts credited with the token in the same transaction
+    pub counterparties: BTreeSet<Address>,
+    /// The balance of the account after the debit
+    pub balance: token::Amount,
+}
+
+/// A limit on the debits of an account exceeded by a transaction. Unlike the
+/// other policy violations, exceeded limits can be bypassed by the signatures
+/// of the bypass threshold of the account's keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitExceeded {
+    /// The reason why a limit is exceeded
+    pub reason: String,
+    /// The number of signatures that can bypass the limit, if any
+    pub bypass_threshold: Option<u8>,
+}
+
+/// Check the tokens debited from an account by a transaction, by token
+/// address, against the policies of the account's VP parameters. The
+/// parameters are read from the `pre` state, so that a transaction cannot
+/// relax the policies it is subject to. The recorded debits are read from
+/// both states to check that they are updated with the debited amounts.
+///
+/// Returns an error if a policy is violated or the first limit that is
+/// exceeded, if any.
+pub fn check_debits<PRE, POST>(
+    pre: &PRE,
+    post: &POST,
+    owner: &Address,
+    debits: &BTreeMap<Address, TokenDebit>,
+) -> Result<Option<LimitExceeded>>
+where
+    PRE: StorageRead,
+    POST: StorageRead,
+{
+    let Some(params) = vp_params(pre, owner)? else {
+        return Ok(None);
+    };
+    let debits: BTreeMap<&Address, &TokenDebit> = debits
+        .iter()
+        .filter(|(_token, debit)| !debit.amount.is_zero())
+        .collect();
+
+    if !debits.is_empty() {
+        if let Some(time_lock) = params.time_lock {
+            check_time_lock(post, time_lock)?;
+        }
+    }
+    let mut exceeded = None;
+    for (token, debit) in &debits {
+        check_counterparties(&params, debit)?;
+        if let Some(limit) = params.spending_limits.get(*token) {
+            if debit.amount > *limit {
+                exceeded.get_or_insert(format!(
+                    "Debit of {} of token {token} exceeds the spending limit \
+                     of {}",
+                    debit.amount.to_string_native(),
+                    limit.to_string_native(),
+                ));
+            }
+        }
+    }
+    let debited = |token: &Address| {
+        debits
+            .get(token)
+            .map(|debit| debit.amount)
+            .unwrap_or_default()
+    };
+    for (token, cap) in &params.daily_caps {
+        let reason =
+            check_daily_debit(pre, post, owner, token, debited(token), *cap)?;
+        if let Some(reason) = reason {
+            exceeded.get_or_insert(reason);
+        }
+    }
+    for (token, limit) in &params.withdrawal_limits {
+        let reason =
+            check_withdrawals(pre, post, owner, token, debited(token), limit)?;
+        if let Some(reason) = reason {
+            exceeded.get_or_insert(reason);
+        }
+    }
+    Ok(exceeded.map(|reason| LimitExceeded {
+        reason,
+        bypass_threshold: params.bypass_threshold,
+    }))
+}
+
+/// Check a transaction applied to a vesting account against its vesting
+/// schedule, read from the `pre` state. The schedule can only be set when the
+/// account is initialized and the account's VP cannot be changed until all
+/// the tokens are vested. The balance of the vesting token cannot be debited
+/// below the locked amount. When the vesting token is the native token, the
+/// `bonded` tokens of the account held by PoS after the transaction are
+/// counted toward the locked amount, so that the locked tokens can be bonded
+/// but cannot leave the account via PoS.
+pub fn check_vesting<PRE, POST>(
+    pre: &PRE,
+    post: &POST,
+    owner: &Address,
+    keys_changed: &BTreeSet<Key>,
+    debits: &BTreeMap<Address, TokenDebit>,
+    bonded: token::Amount,
+) -> Result<()>
+where
+    PRE: StorageRead,
+    POST: StorageRead,
+{
+    if keys_changed.contains(&vesting_schedule_key(owner)) {
+        return Err(Error::new_const(
+            "The vesting schedule of an account cannot be changed",
+        ));
+    }
+    let Some(schedule) = vesting_schedule(pre, owner)? else {
+        return Ok(());
+    };
+    let now = current_time(post)?;
+    let vp_changed = keys_changed
+        .iter()
+        .any(|key| key.is_validity_predicate() == Some(owner));
+    if vp_changed && !schedule.is_fully_vested(now) {
+        return Err(Error::new_const(
+            "The VP of a vesting account cannot be changed before the end of \
+             its vesting schedule",
+        ));
+    }
+    let Some(debit) = debits.get(&schedule.token) else {
+        return Ok(());
+    };
+    if debit.amount.is_zero() {
+        return Ok(());
+    }
+    let held = if schedule.token == pre.get_native_token()? {
+        debit.balance.checked_add(bonded).ok_or_else(|| {
+            Error::new_const("Overflow in the amount held by the account")
+        })?
+    } else {
+        debit.balance
+    };
+    let locked = schedule.locked(now);
+    if held < locked {
+        return Err(Error::new_alloc(format!(
+            "The balance and bonds of {} of token {} would be below the \
+             locked amount of {}",
+            held.to_string_native(),
+            schedule.token,
+            locked.to_string_native(),
+        )));
+    }
+    Ok(())
+}
+
+/// Check a change of the VP parameters of an account. The bypass threshold of
+/// the new parameters, if any, must be higher than the account's signature
+/// threshold. While the account is time locked or vesting, the parameters
+/// cannot be relaxed, so that the restrictions cannot be lifted before they
+/// expire.
+///
+/// Returns the bypass threshold of the `pre` parameters, if any, as the
+/// number of signatures required to change them.
+pub fn check_vp_params_change<PRE, POST>(
+    pre: &PRE,
+    post: &POST,
+    owner: &Address,
+    keys_changed: &BTreeSet<Key>,
+) -> Result<Option<u8>>
+where
+    PRE: StorageRead,
+    POST: StorageRead,
+{
+    if !keys_changed.contains(&vp_params_key(owner)) {
+        return Ok(None);
+    }
+    let pre_params = vp_params(pre, owner)?.unwrap_or_default();
+    let post_params = vp_params(post, owner)?.unwrap_or_default();
+    if let Some(bypass_threshold) = post_params.bypass_threshold {
+        let threshold = threshold(post, owner)?.unwrap_or(1);
+        if bypass_threshold <= threshold {
+            return Err(Error::new_alloc(format!(
+                "The bypass threshold {bypass_threshold} must be higher than \
+                 the account's threshold {threshold}"
+            )));
+        }
+    }
+    let now = current_time(post)?;
+    let is_time_locked = pre_params
+        .time_lock
+        .is_some_and(|time_lock| now < time_lock);
+    let is_vesting = vesting_schedule(pre, owner)?
+        .is_some_and(|schedule| !schedule.is_fully_vested(now));
+    if (is_time_locked || is_vesting)
+        && !post_params.is_at_least_as_strict_as(&pre_params)
+    {
+        return Err(Error::new_const(
+            "The VP parameters of a time locked or vesting account cannot be \
+             relaxed",
+        ));
+    }
+    Ok(pre_params.bypass_threshold)
+}
+
+/// Check that the records of the debits of an account are only changed for
+/// the tokens with a daily cap or a withdrawal limit in the account's VP
+/// parameters, read from the `pre` state. The changes of these records are
+/// checked against the debited amounts by [`check_debits`].
+pub fn check_debit_records<PRE>(
+    pre: &PRE,
+    owner: &Address,
+    keys_changed: &BTreeSet<Key>,
+) -> Result<()>
+where
+    PRE: StorageRead,
+{
+    let mut daily_debits = BTreeSet::new();
+    let mut withdrawals = BTreeSet::new();
+    for key in keys_changed {
+        if let Some((key_owner, token)) = is_daily_debit_key(key) {
+            if key_owner == owner {
+                daily_debits.insert(token);
+            }
+        } else if let Some((key_owner, token)) = is_withdrawals_key(key) {
+            if key_owner == owner {
+                withdrawals.insert(token);
+            }
+        }
+    }
+    if daily_debits.is_empty() && withdrawals.is_empty() {
+        return Ok(());
+    }
+    let params = vp_params(pre, owner)?.unwrap_or_default();
+    let unexpected = daily_debits
+        .into_iter()
+        .find(|token| !params.daily_caps.contains_key(*token))
+        .or_else(|| {
+            withdrawals
+                .into_iter()
+                .find(|token| !params.withdrawal_limits.contains_key(*token))
+        });
+    match unexpected {
+        Some(token) => Err(Error::new_alloc(format!(
+            "Unexpected change of the debit records of token {token}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Check that the time of the current block is past the time lock
+fn check_time_lock<S>(storage: &S, time_lock: DateTimeUtc) -> Result<()>
+where
+    S: StorageRead,
+{
+    if current_time(storage)? < time_lock {
+        return Err(Error::new_alloc(format!(
+            "The account is time locked until {time_lock}"
+        )));
+    }
+    Ok(())
+}
+
+/// Check that the counterparties of a token debit are allowed
+fn check_counterparties(params: &VpParams, debit: &TokenDebit) -> Result<()> {
+    if params.allowed_counterparties.is_empty() {
+        return Ok(());
+    }
+    match debit
+        .counterparties
+        .iter()
+        .find(|addr| !params.allowed_counterparties.contains(addr))
+    {
+        Some(counterparty) => Err(Error::new_alloc(format!(
+            "Counterparty {counterparty} is not allowed"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Check that the daily debit of a token with a daily cap is updated with at
+/// least the net debited amount. The debits are recorded by the token storage
+/// for every decrease of the balance, so a transaction that also credits the
+/// token records more than the net debited amount, and the recorded amount is
+/// checked against the cap. Returns the reason why the cap is exceeded, if it
+/// is.
+fn check_daily_debit<PRE, POST>(
+    pre: &PRE,
+    post: &POST,
+    owner: &Address,
+    token: &Address,
+    amount: token::Amount,
+    cap: token::Amount,
+) -> Result<Option<String>>
+where
+    PRE: StorageRead,
+    POST: StorageRead,
+{
+    let key = daily_debit_key(owner, token);
+    let pre_debit: Option<DailyDebit> = pre.read(&key)?;
+    let post_debit: Option<DailyDebit> = post.read(&key)?;
+    let day = current_day(post)?;
+    let debited = pre_debit
+        .filter(|debit| debit.day == day)
+        .map(|debit| debit.amount)
+        .unwrap_or_default();
+    let recorded = if pre_debit == post_debit {
+        token::Amount::zero()
+    } else {
+        post_debit
+            .filter(|debit| debit.day == day)
+            .and_then(|debit| debit.amount.checked_sub(debited))
+            .ok_or_else(|| {
+                Error::new_alloc(format!(
+                    "Invalid change of the daily debit of token {token}"
+                ))
+            })?
+    };
+    if recorded < amount {
+        return Err(Error::new_alloc(format!(
+            "The debit of token {token} must be recorded in the account's \
+             daily debit"
+        )));
+    }
+    if recorded.is_zero() {
+        return Ok(None);
+    }
+    let total = debited
+        .checked_add(recorded)
+        .ok_or_else(|| Error::new_const("Daily debit overflow"))?;
+    Ok((total > cap).then(|| {
+        format!(
+            "Debits of {} of token {token} today exceed the daily cap of {}",
+            total.to_string_native(),
+            cap.to_string_native(),
+        )
+    }))
+}
+
+/// Check that the recent withdrawals of a token with a withdrawal limit are
+/// updated with at least the net debited amount, like the daily debit in
+/// [`check_daily_debit`]. Returns the reason why the limit is exceeded, if it
+/// is.
+fn check_withdrawals<PRE, POST>(
+    pre: &PRE,
+    post: &POST,
+    owner: &Address,
+    token: &Address,
+    amount: token::Amount,
+    limit: &WithdrawalLimit,
+) -> Result<Option<String>>
+where
+    PRE: StorageRead,
+    POST: StorageRead,
+{
+    let key = withdrawals_key(owner, token);
+    let pre_withdrawals: Option<Vec<Withdrawal>> = pre.read(&key)?;
+    let post_withdrawals: Option<Vec<Withdrawal>> = post.read(&key)?;
+    let recorded = if pre_withdrawals == post_withdrawals {
+        token::Amount::zero()
+    } else {
+        // The withdrawals out of the window are dropped and the debits are
+        // added to the withdrawal of the current block
+        let base = add_withdrawal(
+            pre_withdrawals.unwrap_or_default(),
+            limit,
+            current_time(post)?,
+            token::Amount::zero(),
+        )?;
+        post_withdrawals
+            .as_deref()
+            .and_then(|withdrawals| recorded_withdrawal(&base, withdrawals))
+            .ok_or_else(|| {
+                Error::new_alloc(format!(
+                    "Invalid change of the withdrawals of token {token}"
+                ))
+            })?
+    };
+    if recorded < amount {
+        return Err(Error::new_alloc(format!(
+            "The debit of token {token} must be recorded in the account's \
+             withdrawals"
+        )));
+    }
+    if recorded.is_zero() {
+        return Ok(None);
+    }
+    let withdrawn = total_withdrawn(&post_withdrawals.unwrap_or_default())?;
+    Ok((withdrawn > limit.amount).then(|| {
+        format!(
+            "Withdrawals of {} of token {token} in the last {} seconds \
+             exceed the limit of {}",
+            withdrawn.to_string_native(),
+            limit.window,
+            limit.amount.to_string_native(),
+        )
+    }))
+}
+
+/// Find the amount added to the withdrawal of the current block, the last of
+/// the `base` withdrawals, by the `post` withdrawals. Returns `None` if the
+/// other withdrawals are changed or the amount is decreased.
+fn recorded_withdrawal(
+    base: &[Withdrawal],
+    post: &[Withdrawal],
+) -> Option<token::Amount> {
+    let (base_last, base_rest) = base.split_last()?;
+    let (last, rest) = post.split_last()?;
+    if rest != base_rest || last.time != base_last.time {
+        return None;
+    }
+    last.amount.checked_sub(base_last.amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address;
+    use namada_core::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+        nam,
+    };
+    use namada_core::chain::BlockHeader;
+    use namada_core::time::DurationSecs;
+    use namada_storage::testing::TestStorage;
+    use namada_storage::StorageWrite;
+
+    use super::*;
+    use crate::{
+        init_vesting_schedule, record_debit, write_vp_params, VestingSchedule,
+    };
+
+    /// Init a storage at a block with the given time, with the VP params of
+    /// the owner and the given NAM debits recorded at the given times
+    fn storage_with(
+        time: i64,
+        owner: &Address,
+        params: &VpParams,
+        debits: &[(i64, u64)],
+    ) -> TestStorage {
+        let mut storage = TestStorage::default();
+        let height = storage.get_block_height().unwrap();
+        let set_time = |storage: &mut TestStorage, time: i64| {
+            storage.set_mock_block_header(
+                height,
+                BlockHeader {
+                    time: DateTimeUtc::from_unix_timestamp(time).unwrap(),
+                    ..BlockHeader::default()
+                },
+            );
+        };
+        write_vp_params(&mut storage, owner, params).unwrap();
+        for (time, amount) in debits {
+            set_time(&mut storage, *time);
+            let amount = token::Amount::native_whole(*amount);
+            record_debit(&mut storage, owner, &nam(), amount).unwrap();
+        }
+        set_time(&mut storage, time);
+        storage
+    }
+
+    fn debit(amount: u64, counterparty: &Address) -> TokenDebit {
+        TokenDebit {
+            amount: token::Amount::native_whole(amount),
+            counterparties: BTreeSet::from([counterparty.clone()]),
+            balance: token::Amount::zero(),
+        }
+    }
+
+    #[test]
+    fn test_check_debits_without_params() {
+        let owner = established_address_1();
+        let storage = storage_with(0, &owner, &VpParams::default(), &[]);
+        let debits = BTreeMap::from([(nam(), debit(100, &owner))]);
+        let exceeded =
+            check_debits(&storage, &storage, &owner, &debits).unwrap();
+        assert_eq!(exceeded, None);
+    }
+
+    #[test]
+    fn test_check_spending_limit_and_counterparties() {
+        let owner = established_address_1();
+        let allowed = established_address_2();
+        let other = established_address_3();
+        let params = VpParams {
+            spending_limits: BTreeMap::from([(
+                nam(),
+                token::Amount::native_whole(10),
+            )]),
+            allowed_counterparties: BTreeSet::from([allowed.clone()]),
+            bypass_threshold: Some(2),
+            ..VpParams::default()
+        };
+        let storage = storage_with(0, &owner, &params, &[]);
+
+        let check = |debit: TokenDebit| {
+            let debits = BTreeMap::from([(nam(), debit)]);
+            check_debits(&storage, &storage, &owner, &debits)
+        };
+        assert_eq!(check(debit(10, &allowed)).unwrap(), None);
+        let exceeded = check(debit(11, &allowed)).unwrap().unwrap();
+        assert_eq!(exceeded.bypass_threshold, Some(2));
+        assert!(check(debit(1, &other)).is_err());
+    }
+
+    #[test]
+    fn test_check_time_lock() {
+        let owner = established_address_1();
+        let target = established_address_2();
+        let params = VpParams {
+            time_lock: DateTimeUtc::from_unix_timestamp(200),
+            ..VpParams::default()
+        };
+        let debits = BTreeMap::from([(nam(), debit(1, &target))]);
+
+        let locked = storage_with(100, &owner, &params, &[]);
+        assert!(check_debits(&locked, &locked, &owner, &debits).is_err());
+        // Txs without debits are allowed while locked
+        check_debits(&locked, &locked, &owner, &BTreeMap::new()).unwrap();
+
+        let unlocked = storage_with(200, &owner, &params, &[]);
+        check_debits(&unlocked, &unlocked, &owner, &debits).unwrap();
+    }
+
+    #[test]
+    fn test_check_daily_cap() {
+        let owner = established_address_1();
+        let target = established_address_2();
+        let params = VpParams {
+            daily_caps: BTreeMap::from([(
+                nam(),
+                token::Amount::native_whole(10),
+            )]),
+            ..VpParams::default()
+        };
+        let debits = BTreeMap::from([(nam(), debit(6, &target))]);
+
+        let pre = storage_with(0, &owner, &params, &[]);
+        let post = storage_with(0, &owner, &params, &[(0, 6)]);
+        let exceeded = check_debits(&pre, &post, &owner, &debits).unwrap();
+        assert_eq!(exceeded, None);
+        // The debit must be recorded
+        assert!(check_debits(&pre, &pre, &owner, &debits).is_err());
+        // The record cannot be decreased
+        assert!(check_debits(&post, &pre, &owner, &BTreeMap::new()).is_err());
+
+        // A second debit in the same day exceeds the cap, without a bypass
+        let post_second = storage_with(0, &owner, &params, &[(0, 6), (0, 6)]);
+        let exceeded =
+            check_debits(&post, &post_second, &owner, &debits).unwrap();
+        assert_eq!(exceeded.unwrap().bypass_threshold, None);
+
+        // The debits are reset on the next day
+        let mut next_day = storage_with(86_400, &owner, &params, &[]);
+        next_day
+            .write(
+                &daily_debit_key(&owner, &nam()),
+                DailyDebit {
+                    day: 0,
+                    amount: token::Amount::native_whole(6),
+                },
+            )
+            .unwrap();
+        let post_next_day =
+            storage_with(86_400, &owner, &params, &[(86_400, 6)]);
+        let exceeded =
+            check_debits(&next_day, &post_next_day, &owner, &debits).unwrap();
+        assert_eq!(exceeded, None);
+    }
+
+    #[test]
+    fn test_check_withdrawal_limit() {
+        let owner = established_address_1();
+        let target = established_address_2();
+        let params = VpParams {
+            withdrawal_limits: BTreeMap::from([(
+                nam(),
+                WithdrawalLimit {
+                    amount: token::Amount::native_whole(10),
+                    window: DurationSecs(100),
+                },
+            )]),
+            bypass_threshold: Some(3),
+            ..VpParams::default()
+        };
+        let debits = BTreeMap::from([(nam(), debit(6, &target))]);
+        let check = |time: i64, recorded: &[(i64, u64)]| {
+            let mut with_debit = recorded.to_vec();
+            with_debit.push((time, 6));
+            let pre = storage_with(time, &owner, &params, recorded);
+            let post = storage_with(time, &owner, &params, &with_debit);
+            check_debits(&pre, &post, &owner, &debits).unwrap()
+        };
+
+        assert_eq!(check(0, &[]), None);
+        // The previous withdrawal is in the window
+        let exceeded = check(99, &[(0, 6)]).unwrap();
+        assert_eq!(exceeded.bypass_threshold, Some(3));
+        // The previous withdrawal is out of the window
+        assert_eq!(check(100, &[(0, 6)]), None);
+        assert_eq!(check(150, &[(0, 6), (60, 4)]), None);
+
+        // The withdrawals out of the window are dropped from storage
+        let post = storage_with(100, &owner, &params, &[(0, 6), (100, 6)]);
+        let withdrawals: Vec<Withdrawal> = post
+            .read(&withdrawals_key(&owner, &nam()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            withdrawals,
+            vec![Withdrawal {
+                time: DateTimeUtc::from_unix_timestamp(100).unwrap(),
+                amount: token::Amount::native_whole(6),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_debit_and_credit() {
+        let owner = established_address_1();
+        let target = established_address_2();
+        let params = VpParams {
+            daily_caps: BTreeMap::from([(
+                nam(),
+                token::Amount::native_whole(10),
+            )]),
+            withdrawal_limits: BTreeMap::from([(
+                nam(),
+                WithdrawalLimit {
+                    amount: token::Amount::native_whole(10),
+                    window: DurationSecs(100),
+                },
+            )]),
+            ..VpParams::default()
+        };
+        // A tx debits 6 and credits back 4, so the balance decreases by 2
+        // while the token storage records the debit of 6
+        let debits = BTreeMap::from([(nam(), debit(2, &target))]);
+        let check = |recorded: &[(i64, u64)], debited: &[(i64, u64)]| {
+            let mut with_debit = recorded.to_vec();
+            with_debit.extend_from_slice(debited);
+            let pre = storage_with(0, &owner, &params, recorded);
+            let post = storage_with(0, &owner, &params, &with_debit);
+            check_debits(&pre, &post, &owner, &debits)
+        };
+
+        assert_eq!(check(&[], &[(0, 6)]).unwrap(), None);
+        assert_eq!(check(&[(0, 4)], &[(0, 6)]).unwrap(), None);
+        // The recorded debits are checked against the limits
+        assert!(check(&[(0, 5)], &[(0, 6)]).unwrap().is_some());
+        // At least the decrease of the balance must be recorded
+        assert!(check(&[], &[(0, 1)]).is_err());
+        // A tx that credits back all the debited tokens records its debits
+        let post = storage_with(0, &owner, &params, &[(0, 6)]);
+        let pre = storage_with(0, &owner, &params, &[]);
+        let exceeded =
+            check_debits(&pre, &post, &owner, &BTreeMap::new()).unwrap();
+        assert_eq!(exceeded, None);
+    }
+
+    #[test]
+    fn test_check_vesting() {
+        let owner = established_address_1();
+        let target = established_address_2();
+        let time = |secs| DateTimeUtc::from_unix_timestamp(secs).unwrap();
+        let schedule = VestingSchedule {
+            token: nam(),
+            amount: token::Amount::native_whole(100),
+            start: time(0),
+            cliff: time(100),
+            end: time(400),
+        };
+        assert_eq!(schedule.vested(time(99)), token::Amount::zero());
+        assert_eq!(schedule.vested(time(100)), token::Amount::native_whole(25));
+        assert_eq!(schedule.vested(time(500)), schedule.amount);
+
+        let storage_at = |now: i64| {
+            let mut storage =
+                storage_with(now, &owner, &VpParams::default(), &[]);
+            init_vesting_schedule(&mut storage, &owner, &schedule).unwrap();
+            storage
+        };
+        let check = |now: i64, debit: TokenDebit, bonded: u64, keys: &[Key]| {
+            let storage = storage_at(now);
+            let debits = BTreeMap::from([(nam(), debit)]);
+            let keys_changed = keys.iter().cloned().collect();
+            let bonded = token::Amount::native_whole(bonded);
+            check_vesting(
+                &storage,
+                &storage,
+                &owner,
+                &keys_changed,
+                &debits,
+                bonded,
+            )
+        };
+        let with_balance = |debit: TokenDebit, balance: u64| TokenDebit {
+            balance: token::Amount::native_whole(balance),
+            ..debit
+        };
+
+        // Only the vested tokens can be spent
+        let spend = |balance| with_balance(debit(10, &target), balance);
+        assert!(check(100, spend(75), 0, &[]).is_ok());
+        assert!(check(100, spend(74), 0, &[]).is_err());
+        // The bonded tokens count toward the locked amount
+        assert!(check(100, spend(70), 5, &[]).is_ok());
+        assert!(check(100, spend(70), 4, &[]).is_err());
+        // Unvested tokens can be bonded, but cannot leave via PoS unbonded
+        let to_pos = debit(100, &address::POS);
+        assert!(check(100, to_pos.clone(), 100, &[]).is_ok());
+        assert!(check(100, to_pos, 0, &[]).is_err());
+        // The schedule cannot be changed
+        let schedule_key = vesting_schedule_key(&owner);
+        assert!(check(400, debit(0, &target), 0, &[schedule_key]).is_err());
+        // The VP cannot be changed before the end of the schedule
+        let vp_key = Key::validity_predicate(&owner);
+        assert!(check(399, debit(0, &target), 0, &[vp_key.clone()]).is_err());
+        assert!(check(400, debit(0, &target), 0, &[vp_key]).is_ok());
+    }
+
+    #[test]
+    fn test_check_vp_params_change() {
+        let owner = established_address_1();
+        let allowed = established_address_2();
+        let time = |secs| DateTimeUtc::from_unix_timestamp(secs).unwrap();
+        let params = VpParams {
+            spending_limits: BTreeMap::from([(
+                nam(),
+                token::Amount::native_whole(10),
+            )]),
+            time_lock: Some(time(200)),
+            allowed_counterparties: BTreeSet::from([allowed.clone()]),
+            bypass_threshold: Some(2),
+            ..VpParams::default()
+        };
+        let keys_changed = BTreeSet::from([vp_params_key(&owner)]);
+        let check = |now: i64, new_params: &VpParams| {
+            let pre = storage_with(now, &owner, &params, &[]);
+            let post = storage_with(now, &owner, new_params, &[]);
+            check_vp_params_change(&pre, &post, &owner, &keys_changed)
+        };
+
+        // The params can be made stricter while locked
+        let stricter = VpParams {
+            spending_limits: BTreeMap::from([(
+                nam(),
+                token::Amount::native_whole(5),
+            )]),
+            time_lock: Some(time(300)),
+            ..params.clone()
+        };
+        // The change requires the signatures of the bypass threshold
+        assert_eq!(check(100, &stricter).unwrap(), Some(2));
+        // which must be above the account's threshold
+        let bypass_at_threshold = VpParams {
+            bypass_threshold: Some(1),
+            ..params.clone()
+        };
+        assert!(check(300, &bypass_at_threshold).is_err());
+        // The params cannot be relaxed or removed
+        let relaxed = VpParams {
+            time_lock: Some(time(100)),
+            ..params.clone()
+        };
+        assert!(check(100, &relaxed).is_err());
+        let relaxed = VpParams {
+            allowed_counterparties: BTreeSet::new(),
+            ..params.clone()
+        };
+        assert!(check(100, &relaxed).is_err());
+        assert!(check(100, &VpParams::default()).is_err());
+        // The params can be changed once the lock expired
+        assert!(check(200, &VpParams::default()).is_ok());
+
+        // The params of a vesting account cannot be relaxed
+        let schedule = VestingSchedule {
+            token: nam(),
+            amount: token::Amount::native_whole(100),
+            start: time(0),
+            cliff: time(100),
+            end: time(400),
+        };
+        let unlocked = VpParams {
+            time_lock: None,
+            ..params.clone()
+        };
+        let check_vesting_account = |now: i64, new_params: &VpParams| {
+            let mut pre = storage_with(now, &owner, &unlocked, &[]);
+            init_vesting_schedule(&mut pre, &owner, &schedule).unwrap();
+            let post = storage_with(now, &owner, new_params, &[]);
+            check_vp_params_change(&pre, &post, &owner, &keys_changed)
+        };
+        assert!(check_vesting_account(300, &VpParams::default()).is_err());
+        assert!(check_vesting_account(400, &VpParams::default()).is_ok());
+    }
+
+    #[test]
+    fn test_check_debit_records() {
+        let owner = established_address_1();
+        let other = established_address_2();
+        let params = VpParams {
+            daily_caps: BTreeMap::from([(
+                nam(),
+                token::Amount::native_whole(10),
+            )]),
+            ..VpParams::default()
+        };
+        let storage = storage_with(0, &owner, &params, &[]);
+        let check = |key: Key| {
+            let keys_changed = BTreeSet::from([key]);
+            check_debit_records(&storage, &owner, &keys_changed)
+        };
+
+        // The records of the capped token can be changed, as checked with
+        // the debits
+        assert!(check(daily_debit_key(&owner, &nam())).is_ok());
+        // But not the records of other tokens or without a limit
+        assert!(check(daily_debit_key(&owner, &other)).is_err());
+        assert!(check(withdrawals_key(&owner, &nam())).is_err());
+        // The records of other accounts are checked by their VPs
+        assert!(check(withdrawals_key(&other, &nam())).is_ok());
+    }
+}
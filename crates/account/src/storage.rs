@@ -1,10 +1,15 @@
 //! Cryptographic signature keys storage API
 
 use namada_core::storage;
+use namada_core::time::DateTimeUtc;
+use namada_core::token;
 use namada_storage::{Result, ResultExt, StorageRead, StorageWrite};
 
 use super::*;
 
+/// The number of seconds in a day
+const SECONDS_PER_DAY: i64 = 86_400;
+
 /// Reveal a PK of an implicit account - the PK is written into the storage
 /// of the address derived from the PK.
 pub fn reveal_pk<S>(
@@ -120,3 +125,163 @@ where
     }
     Ok(())
 }
+
+/// Get the parameters of an account's VP, if any
+pub fn vp_params<S>(storage: &S, owner: &Address) -> Result<Option<VpParams>>
+where
+    S: StorageRead,
+{
+    storage.read(&vp_params_key(owner))
+}
+
+/// Set the parameters of an account's VP. Empty parameters are removed from
+/// storage.
+pub fn write_vp_params<S>(
+    storage: &mut S,
+    owner: &Address,
+    params: &VpParams,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let key = vp_params_key(owner);
+    if params.is_empty() {
+        storage.delete(&key)
+    } else {
+        storage.write(&key, params)
+    }
+}
+
+/// Get the time of the current block
+pub fn current_time<S>(storage: &S) -> Result<DateTimeUtc>
+where
+    S: StorageRead,
+{
+    let height = storage.get_block_height()?;
+    let header = storage.get_block_header(height)?.ok_or_else(|| {
+        namada_storage::Error::new_const("Missing the current block header")
+    })?;
+    Ok(header.time)
+}
+
+/// Get the current day, in days since the UNIX epoch, from the time of the
+/// current block
+pub fn current_day<S>(storage: &S) -> Result<i64>
+where
+    S: StorageRead,
+{
+    current_time(storage).map(day_of)
+}
+
+/// Get the day of the given time, in days since the UNIX epoch
+pub fn day_of(time: DateTimeUtc) -> i64 {
+    time.to_unix_timestamp().div_euclid(SECONDS_PER_DAY)
+}
+
+/// Record an amount of a token debited from an account, so that its VP can
+/// check it against the account's daily cap and withdrawal limit. This is a
+/// no-op unless the account's VP parameters set a daily cap or a withdrawal
+/// limit for the token. Every debit is recorded, so a transaction that debits
+/// and credits back a token records more than the decrease of the balance.
+pub fn record_debit<S>(
+    storage: &mut S,
+    owner: &Address,
+    token: &Address,
+    amount: token::Amount,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let Some(params) = vp_params(storage, owner)? else {
+        return Ok(());
+    };
+    if params.daily_caps.contains_key(token) {
+        let day = current_day(storage)?;
+        let key = daily_debit_key(owner, token);
+        let debited = storage
+            .read::<DailyDebit>(&key)?
+            .filter(|debit| debit.day == day)
+            .map(|debit| debit.amount)
+            .unwrap_or_default();
+        let amount = debited.checked_add(amount).ok_or_else(|| {
+            namada_storage::Error::new_const("Daily debit overflow")
+        })?;
+        storage.write(&key, DailyDebit { day, amount })?;
+    }
+    if let Some(limit) = params.withdrawal_limits.get(token) {
+        let now = current_time(storage)?;
+        let key = withdrawals_key(owner, token);
+        let withdrawals: Vec<Withdrawal> =
+            storage.read(&key)?.unwrap_or_default();
+        let withdrawals = add_withdrawal(withdrawals, limit, now, amount)?;
+        storage.write(&key, withdrawals)?;
+    }
+    Ok(())
+}
+
+/// Add a withdrawal made at the given time to the recent withdrawals of a
+/// token, dropping the ones that are out of the limit's window. The
+/// withdrawals made at the same time are merged.
+pub fn add_withdrawal(
+    mut withdrawals: Vec<Withdrawal>,
+    limit: &WithdrawalLimit,
+    now: DateTimeUtc,
+    amount: token::Amount,
+) -> Result<Vec<Withdrawal>> {
+    withdrawals.retain(|withdrawal| {
+        let elapsed = now
+            .to_unix_timestamp()
+            .saturating_sub(withdrawal.time.to_unix_timestamp());
+        u64::try_from(elapsed).map_or(true, |elapsed| elapsed < limit.window.0)
+    });
+    match withdrawals.last_mut() {
+        Some(last) if last.time == now => {
+            last.amount = last.amount.checked_add(amount).ok_or_else(|| {
+                namada_storage::Error::new_const("Withdrawal overflow")
+            })?;
+        }
+        _ => withdrawals.push(Withdrawal { time: now, amount }),
+    }
+    Ok(withdrawals)
+}
+
+/// The total amount of the given withdrawals
+pub fn total_withdrawn(withdrawals: &[Withdrawal]) -> Result<token::Amount> {
+    withdrawals
+        .iter()
+        .try_fold(token::Amount::zero(), |acc, withdrawal| {
+            acc.checked_add(withdrawal.amount)
+        })
+        .ok_or_else(|| namada_storage::Error::new_const("Withdrawal overflow"))
+}
+
+/// Get the vesting schedule of an account, if it's a vesting account
+pub fn vesting_schedule<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Option<VestingSchedule>>
+where
+    S: StorageRead,
+{
+    storage.read(&vesting_schedule_key(owner))
+}
+
+/// Set the vesting schedule of a new account
+pub fn init_vesting_schedule<S>(
+    storage: &mut S,
+    owner: &Address,
+    schedule: &VestingSchedule,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    if !schedule.is_valid() {
+        return Err(namada_storage::Error::new_const(
+            "The times of the vesting schedule must be ordered",
+        ));
+    }
+    storage.write(&vesting_schedule_key(owner), schedule)
+}
@@ -11,6 +11,10 @@ struct Keys {
     public_keys: &'static str,
     threshold: &'static str,
     protocol_public_keys: &'static str,
+    vp_params: &'static str,
+    daily_debits: &'static str,
+    withdrawals: &'static str,
+    vesting_schedule: &'static str,
 }
 
 /// Obtain a storage key for user's public key.
@@ -89,3 +93,99 @@ pub fn is_protocol_pk_key(key: &storage::Key) -> Option<&Address> {
         _ => None,
     }
 }
+
+/// Obtain the storage key for the parameters of a user's VP
+pub fn vp_params_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.vp_params.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key is a VP parameters key. If it is, returns
+/// the owner.
+pub fn is_vp_params_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.vp_params =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
+/// Obtain the storage key for the amount of a token debited from a user's
+/// account in the current day
+pub fn daily_debit_key(owner: &Address, token: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.daily_debits.to_string()),
+            DbKeySeg::AddressSeg(token.to_owned()),
+        ],
+    }
+}
+
+/// Check if the given storage key is a daily debit key. If it is, returns the
+/// owner and the token.
+pub fn is_daily_debit_key(key: &storage::Key) -> Option<(&Address, &Address)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(owner),
+            DbKeySeg::StringSeg(key),
+            DbKeySeg::AddressSeg(token),
+        ] if key.as_str() == Keys::VALUES.daily_debits => Some((owner, token)),
+        _ => None,
+    }
+}
+
+/// Obtain the storage key for the recent withdrawals of a token from a user's
+/// account with a withdrawal limit
+pub fn withdrawals_key(owner: &Address, token: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.withdrawals.to_string()),
+            DbKeySeg::AddressSeg(token.to_owned()),
+        ],
+    }
+}
+
+/// Check if the given storage key is a withdrawals key. If it is, returns the
+/// owner and the token.
+pub fn is_withdrawals_key(key: &storage::Key) -> Option<(&Address, &Address)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(owner),
+            DbKeySeg::StringSeg(key),
+            DbKeySeg::AddressSeg(token),
+        ] if key.as_str() == Keys::VALUES.withdrawals => Some((owner, token)),
+        _ => None,
+    }
+}
+
+/// Obtain the storage key for the vesting schedule of a user's account
+pub fn vesting_schedule_key(owner: &Address) -> storage::Key {
+    storage::Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.vesting_schedule.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key is a vesting schedule key. If it is,
+/// returns the owner.
+pub fn is_vesting_schedule_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(key)]
+            if key.as_str() == Keys::VALUES.vesting_schedule =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
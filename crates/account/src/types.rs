@@ -1,7 +1,12 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::hash::Hash;
 use namada_core::key::common;
+use namada_core::time::{DateTimeUtc, DurationSecs};
+use namada_core::token;
+use namada_core::uint::Uint;
 use namada_macros::BorshDeserializer;
 #[cfg(feature = "migrations")]
 use namada_migrations::*;
@@ -31,6 +36,104 @@ pub struct InitAccount {
     pub threshold: u8,
 }
 
+/// A tx data type to initialize a new established vesting account. The VP of
+/// a vesting account must be the user VP, which enforces the schedule.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct InitVestingAccount {
+    /// The new account
+    pub account: InitAccount,
+    /// The vesting schedule of the account's tokens
+    pub vesting: VestingSchedule,
+}
+
+/// A schedule of the vesting of a token held by an account. Nothing is vested
+/// before the cliff, then the tokens are released linearly from the start to
+/// the end of the schedule. The VP of a vesting account doesn't allow to
+/// spend its locked tokens, but they can be bonded.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct VestingSchedule {
+    /// The vesting token
+    pub token: Address,
+    /// The total amount of the token subject to vesting
+    pub amount: token::Amount,
+    /// The start of the linear release
+    pub start: DateTimeUtc,
+    /// The time before which nothing is vested
+    pub cliff: DateTimeUtc,
+    /// The time at which everything is vested
+    pub end: DateTimeUtc,
+}
+
+impl VestingSchedule {
+    /// Check that the times of the schedule are ordered
+    pub fn is_valid(&self) -> bool {
+        self.start <= self.cliff && self.cliff <= self.end
+    }
+
+    /// The amount vested at the given time. On overflow, nothing is
+    /// considered vested before the end of the schedule.
+    pub fn vested(&self, now: DateTimeUtc) -> token::Amount {
+        if now < self.cliff {
+            return token::Amount::zero();
+        }
+        if now >= self.end {
+            return self.amount;
+        }
+        let since = |time: DateTimeUtc| {
+            u64::try_from(
+                time.to_unix_timestamp()
+                    .saturating_sub(self.start.to_unix_timestamp()),
+            )
+            .ok()
+        };
+        since(now)
+            .zip(since(self.end))
+            .and_then(|(elapsed, duration)| {
+                self.amount
+                    .raw_amount()
+                    .checked_mul(Uint::from(elapsed))?
+                    .checked_div(Uint::from(duration))
+            })
+            .map(token::Amount::from)
+            .unwrap_or_default()
+    }
+
+    /// The amount still locked at the given time
+    pub fn locked(&self, now: DateTimeUtc) -> token::Amount {
+        self.amount
+            .checked_sub(self.vested(now))
+            .unwrap_or_default()
+    }
+
+    /// Check if all the tokens are vested at the given time
+    pub fn is_fully_vested(&self, now: DateTimeUtc) -> bool {
+        now >= self.end
+    }
+}
+
 /// A tx data type to update an account's validity predicate
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(
@@ -57,14 +160,195 @@ pub struct UpdateAccount {
     pub threshold: Option<u8>,
 }
 
+/// A tx data type to update the parameters of an account's VP
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpdateVpParams {
+    /// An address of the account
+    pub addr: Address,
+    /// The new parameters of the account's VP. Setting the default (empty)
+    /// parameters removes them from storage.
+    pub vp_params: VpParams,
+}
+
+/// Parameters of the user VPs, stored in an account's storage to configure
+/// the policies that its VP enforces on top of the signatures. This allows to
+/// customize the behaviour of the allowlisted `vp_user` and `vp_implicit`
+/// without deploying new VP code.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct VpParams {
+    /// The maximum amount of a token that can be debited from the account in
+    /// a single transaction, by token address
+    pub spending_limits: BTreeMap<Address, token::Amount>,
+    /// The maximum amount of a token that can be debited from the account in
+    /// a single day (UTC), by token address. The debits of a capped token
+    /// must be recorded with [`crate::record_debit`], as done by the token
+    /// storage functions for every decrease of a balance, or the transactions
+    /// are rejected.
+    pub daily_caps: BTreeMap<Address, token::Amount>,
+    /// If set, no tokens can be debited from the account before this time
+    pub time_lock: Option<DateTimeUtc>,
+    /// If not empty, tokens can only be sent to these addresses
+    pub allowed_counterparties: BTreeSet<Address>,
+    /// The maximum amount of a token that can be debited from the account in
+    /// a rolling window of time, by token address. The debits must be
+    /// recorded like for the daily caps.
+    pub withdrawal_limits: BTreeMap<Address, WithdrawalLimit>,
+    /// The number of signatures that allows a transaction to exceed the
+    /// spending limits, the daily caps and the withdrawal limits. It should
+    /// be higher than the account's signature threshold. When not set, the
+    /// limits cannot be bypassed.
+    pub bypass_threshold: Option<u8>,
+}
+
+impl VpParams {
+    /// Check if no policies are configured by these parameters
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Check if these parameters are at least as strict as the `other`
+    /// parameters, i.e. if every debit that they allow is also allowed by
+    /// the `other` parameters
+    pub fn is_at_least_as_strict_as(&self, other: &Self) -> bool {
+        let amounts_within =
+            |new: &BTreeMap<Address, token::Amount>,
+             old: &BTreeMap<Address, token::Amount>| {
+                old.iter().all(|(token, old_amount)| {
+                    new.get(token).is_some_and(|amount| amount <= old_amount)
+                })
+            };
+        let time_lock_within = match (self.time_lock, other.time_lock) {
+            (_, None) => true,
+            (Some(new), Some(old)) => new >= old,
+            (None, Some(_)) => false,
+        };
+        let counterparties_within = other.allowed_counterparties.is_empty()
+            || (!self.allowed_counterparties.is_empty()
+                && self
+                    .allowed_counterparties
+                    .is_subset(&other.allowed_counterparties));
+        let withdrawal_limits_within =
+            other.withdrawal_limits.iter().all(|(token, old)| {
+                self.withdrawal_limits.get(token).is_some_and(|new| {
+                    new.amount <= old.amount && new.window.0 >= old.window.0
+                })
+            });
+        let bypass_within =
+            match (self.bypass_threshold, other.bypass_threshold) {
+                (None, _) => true,
+                (Some(new), Some(old)) => new >= old,
+                (Some(_), None) => false,
+            };
+        amounts_within(&self.spending_limits, &other.spending_limits)
+            && amounts_within(&self.daily_caps, &other.daily_caps)
+            && time_lock_within
+            && counterparties_within
+            && withdrawal_limits_within
+            && bypass_within
+    }
+}
+
+/// A limit on the amount of a token that can be debited from an account in a
+/// rolling window of time
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct WithdrawalLimit {
+    /// The maximum amount debited in any window
+    pub amount: token::Amount,
+    /// The duration of the window
+    pub window: DurationSecs,
+}
+
+/// An amount of a token debited from an account with a withdrawal limit
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct Withdrawal {
+    /// The time of the block in which the amount was debited
+    pub time: DateTimeUtc,
+    /// The debited amount
+    pub amount: token::Amount,
+}
+
+/// The amount of a token debited from an account with a daily cap in the
+/// current day
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct DailyDebit {
+    /// The day of the debits, in days since the UNIX epoch
+    pub day: i64,
+    /// The total amount debited in the day
+    pub amount: token::Amount,
+}
+
 #[allow(clippy::cast_possible_truncation)]
 #[cfg(any(test, feature = "testing"))]
 /// Tests and strategies for accounts
 pub mod tests {
-    use namada_core::address::testing::arb_non_internal_address;
+    use namada_core::address::testing::{
+        arb_established_address, arb_non_internal_address,
+    };
     use namada_core::hash::testing::arb_hash;
     use namada_core::key::testing::arb_common_pk;
-    use proptest::prelude::Just;
+    use namada_core::token::testing::arb_amount;
+    use proptest::prelude::{Just, Strategy};
     use proptest::{collection, option, prop_compose};
 
     use super::*;
@@ -86,6 +370,35 @@ pub mod tests {
         }
     }
 
+    prop_compose! {
+        /// Generate an arbitrary vesting account initialization
+        pub fn arb_init_vesting_account()(
+            account in arb_init_account(),
+            vesting in arb_vesting_schedule(),
+        ) -> InitVestingAccount {
+            InitVestingAccount { account, vesting }
+        }
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary valid vesting schedule
+        pub fn arb_vesting_schedule()(
+            token in arb_token(),
+            amount in arb_amount(),
+            mut times in collection::vec(0..i64::from(u32::MAX), 3),
+        ) -> VestingSchedule {
+            times.sort();
+            let time = |secs| DateTimeUtc::from_unix_timestamp(secs).unwrap();
+            VestingSchedule {
+                token,
+                amount,
+                start: time(times[0]),
+                cliff: time(times[1]),
+                end: time(times[2]),
+            }
+        }
+    }
+
     prop_compose! {
         /// Generate an arbitrary account update
         pub fn arb_update_account()(
@@ -104,4 +417,69 @@ pub mod tests {
             }
         }
     }
+
+    prop_compose! {
+        /// Generate an arbitrary update of the VP parameters of an account
+        pub fn arb_update_vp_params()(
+            addr in arb_non_internal_address(),
+            vp_params in arb_vp_params(),
+        ) -> UpdateVpParams {
+            UpdateVpParams { addr, vp_params }
+        }
+    }
+
+    prop_compose! {
+        /// Generate arbitrary VP parameters
+        pub fn arb_vp_params()(
+            spending_limits in collection::btree_map(
+                arb_token(),
+                arb_amount(),
+                0..3,
+            ),
+            daily_caps in collection::btree_map(arb_token(), arb_amount(), 0..3),
+            time_lock in option::of(
+                (0..i64::from(u32::MAX)).prop_filter_map(
+                    "Invalid timestamp",
+                    DateTimeUtc::from_unix_timestamp,
+                ),
+            ),
+            allowed_counterparties in collection::btree_set(
+                arb_non_internal_address(),
+                0..3,
+            ),
+            withdrawal_limits in collection::btree_map(
+                arb_token(),
+                arb_withdrawal_limit(),
+                0..3,
+            ),
+            bypass_threshold in option::of(1..u8::MAX),
+        ) -> VpParams {
+            VpParams {
+                spending_limits,
+                daily_caps,
+                time_lock,
+                allowed_counterparties,
+                withdrawal_limits,
+                bypass_threshold,
+            }
+        }
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary withdrawal limit
+        pub fn arb_withdrawal_limit()(
+            amount in arb_amount(),
+            window in 1..u64::from(u32::MAX),
+        ) -> WithdrawalLimit {
+            WithdrawalLimit {
+                amount,
+                window: DurationSecs(window),
+            }
+        }
+    }
+
+    /// Generate an arbitrary token address
+    fn arb_token() -> impl Strategy<Value = Address> {
+        arb_established_address().prop_map(Address::Established)
+    }
 }
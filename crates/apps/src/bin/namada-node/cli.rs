@@ -58,6 +58,11 @@ pub fn main() -> Result<()> {
                 node::rollback(chain_ctx.config.ledger)
                     .wrap_err("Failed to rollback the Namada node")?;
             }
+            cmds::Ledger::WarmupWasmCache(_) => {
+                let chain_ctx = ctx.take_chain_or_exit();
+                node::warmup_wasm_caches(chain_ctx.config.ledger)
+                    .wrap_err("Failed to warm up the wasm caches")?;
+            }
             cmds::Ledger::UpdateDB(cmds::LedgerUpdateDB(args)) => {
                 #[cfg(not(feature = "migrations"))]
                 {
@@ -64,6 +64,7 @@ pub mod cmds {
         TxUnshieldingTransfer(TxUnshieldingTransfer),
         TxIbcTransfer(TxIbcTransfer),
         TxUpdateAccount(TxUpdateAccount),
+        TxUpdateVpParams(TxUpdateVpParams),
         TxInitProposal(TxInitProposal),
         TxVoteProposal(TxVoteProposal),
         TxRevealPk(TxRevealPk),
@@ -85,6 +86,7 @@ pub mod cmds {
                 .subcommand(TxUnshieldingTransfer::def().display_order(2))
                 .subcommand(TxIbcTransfer::def().display_order(2))
                 .subcommand(TxUpdateAccount::def().display_order(2))
+                .subcommand(TxUpdateVpParams::def().display_order(2))
                 .subcommand(TxInitProposal::def().display_order(2))
                 .subcommand(TxVoteProposal::def().display_order(2))
                 .subcommand(TxRevealPk::def().display_order(2))
@@ -109,6 +111,8 @@ pub mod cmds {
                 SubCmd::parse(matches).map(Self::TxIbcTransfer);
             let tx_update_account =
                 SubCmd::parse(matches).map(Self::TxUpdateAccount);
+            let tx_update_vp_params =
+                SubCmd::parse(matches).map(Self::TxUpdateVpParams);
             let tx_init_proposal =
                 SubCmd::parse(matches).map(Self::TxInitProposal);
             let tx_vote_proposal =
@@ -125,6 +129,7 @@ pub mod cmds {
                 .or(tx_unshielding_transfer)
                 .or(tx_ibc_transfer)
                 .or(tx_update_account)
+                .or(tx_update_vp_params)
                 .or(tx_init_proposal)
                 .or(tx_vote_proposal)
                 .or(tx_reveal_pk)
@@ -182,19 +187,22 @@ pub mod cmds {
     pub enum NamadaRelayer {
         EthBridgePool(EthBridgePool),
         ValidatorSet(ValidatorSet),
+        IbcRelay(IbcRelay),
     }
 
     impl Cmd for NamadaRelayer {
         fn add_sub(app: App) -> App {
             app.subcommand(EthBridgePool::def())
                 .subcommand(ValidatorSet::def())
+                .subcommand(IbcRelay::def())
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             let eth_bridge_pool =
                 SubCmd::parse(matches).map(Self::EthBridgePool);
             let validator_set = SubCmd::parse(matches).map(Self::ValidatorSet);
-            eth_bridge_pool.or(validator_set)
+            let ibc_relay = SubCmd::parse(matches).map(Self::IbcRelay);
+            eth_bridge_pool.or(validator_set).or(ibc_relay)
         }
     }
 
@@ -240,6 +248,7 @@ pub mod cmds {
                 .subcommand(TxUnshieldingTransfer::def().display_order(1))
                 .subcommand(TxIbcTransfer::def().display_order(1))
                 .subcommand(TxUpdateAccount::def().display_order(1))
+                .subcommand(TxUpdateVpParams::def().display_order(1))
                 .subcommand(TxInitAccount::def().display_order(1))
                 .subcommand(TxRevealPk::def().display_order(1))
                 // Governance transactions
@@ -261,6 +270,7 @@ pub mod cmds {
                 .subcommand(TxMetadataChange::def().display_order(2))
                 // Ethereum bridge transactions
                 .subcommand(AddToEthBridgePool::def().display_order(3))
+                .subcommand(CancelEthBridgePoolTransfer::def().display_order(3))
                 // PGF transactions
                 .subcommand(TxUpdateStewardCommission::def().display_order(4))
                 .subcommand(TxResignSteward::def().display_order(4))
@@ -269,6 +279,7 @@ pub mod cmds {
                 .subcommand(QueryNextEpochInfo::def().display_order(5))
                 .subcommand(QueryStatus::def().display_order(5))
                 .subcommand(QueryAccount::def().display_order(5))
+                .subcommand(QueryTxHistory::def().display_order(5))
                 .subcommand(QueryConversions::def().display_order(5))
                 .subcommand(QueryMaspRewardTokens::def().display_order(5))
                 .subcommand(QueryBlock::def().display_order(5))
@@ -291,11 +302,15 @@ pub mod cmds {
                 .subcommand(QueryRewards::def().display_order(5))
                 .subcommand(QueryMetaData::def().display_order(5))
                 .subcommand(QueryTotalSupply::def().display_order(5))
+                .subcommand(QueryNft::def().display_order(5))
+                .subcommand(QueryNftClass::def().display_order(5))
                 .subcommand(QueryEffNativeSupply::def().display_order(5))
                 .subcommand(QueryStakingRewardsRate::def().display_order(5))
                 // Actions
                 .subcommand(SignTx::def().display_order(6))
+                .subcommand(SignSession::def().display_order(6))
                 .subcommand(ShieldedSync::def().display_order(6))
+                .subcommand(DiscoverKeys::def().display_order(6))
                 .subcommand(GenIbcShieldingTransfer::def().display_order(6))
                 // Utils
                 .subcommand(ClientUtils::def().display_order(7))
@@ -315,6 +330,8 @@ pub mod cmds {
             let tx_ibc_transfer = Self::parse_with_ctx(matches, TxIbcTransfer);
             let tx_update_account =
                 Self::parse_with_ctx(matches, TxUpdateAccount);
+            let tx_update_vp_params =
+                Self::parse_with_ctx(matches, TxUpdateVpParams);
             let tx_init_account = Self::parse_with_ctx(matches, TxInitAccount);
             let tx_become_validator =
                 Self::parse_with_ctx(matches, TxBecomeValidator);
@@ -351,6 +368,8 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, QueryNextEpochInfo);
             let query_status = Self::parse_with_ctx(matches, QueryStatus);
             let query_account = Self::parse_with_ctx(matches, QueryAccount);
+            let query_tx_history =
+                Self::parse_with_ctx(matches, QueryTxHistory);
             let query_conversions =
                 Self::parse_with_ctx(matches, QueryConversions);
             let query_masp_reward_tokens =
@@ -368,6 +387,8 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, QueryDelegations);
             let query_total_supply =
                 Self::parse_with_ctx(matches, QueryTotalSupply);
+            let query_nft = Self::parse_with_ctx(matches, QueryNft);
+            let query_nft_class = Self::parse_with_ctx(matches, QueryNftClass);
             let query_native_supply =
                 Self::parse_with_ctx(matches, QueryEffNativeSupply);
             let query_staking_rewards_rate =
@@ -391,8 +412,12 @@ pub mod cmds {
             let query_metadata = Self::parse_with_ctx(matches, QueryMetaData);
             let add_to_eth_bridge_pool =
                 Self::parse_with_ctx(matches, AddToEthBridgePool);
+            let cancel_eth_bridge_pool_transfer =
+                Self::parse_with_ctx(matches, CancelEthBridgePoolTransfer);
             let sign_tx = Self::parse_with_ctx(matches, SignTx);
+            let sign_session = Self::parse_with_ctx(matches, SignSession);
             let shielded_sync = Self::parse_with_ctx(matches, ShieldedSync);
+            let discover_keys = Self::parse_with_ctx(matches, DiscoverKeys);
             let gen_ibc_shielding =
                 Self::parse_with_ctx(matches, GenIbcShieldingTransfer);
             let utils = SubCmd::parse(matches).map(Self::WithoutContext);
@@ -403,6 +428,7 @@ pub mod cmds {
                 .or(tx_unshielding_transfer)
                 .or(tx_ibc_transfer)
                 .or(tx_update_account)
+                .or(tx_update_vp_params)
                 .or(tx_init_account)
                 .or(tx_reveal_pk)
                 .or(tx_init_proposal)
@@ -421,6 +447,7 @@ pub mod cmds {
                 .or(redelegate)
                 .or(claim_rewards)
                 .or(add_to_eth_bridge_pool)
+                .or(cancel_eth_bridge_pool_transfer)
                 .or(tx_update_steward_commission)
                 .or(tx_resign_steward)
                 .or(query_epoch)
@@ -448,11 +475,16 @@ pub mod cmds {
                 .or(query_commission)
                 .or(query_metadata)
                 .or(query_total_supply)
+                .or(query_nft)
+                .or(query_nft_class)
                 .or(query_native_supply)
                 .or(query_staking_rewards_rate)
                 .or(query_account)
+                .or(query_tx_history)
                 .or(sign_tx)
+                .or(sign_session)
                 .or(shielded_sync)
+                .or(discover_keys)
                 .or(gen_ibc_shielding)
                 .or(utils)
         }
@@ -499,6 +531,7 @@ pub mod cmds {
         TxIbcTransfer(TxIbcTransfer),
         QueryResult(QueryResult),
         TxUpdateAccount(TxUpdateAccount),
+        TxUpdateVpParams(TxUpdateVpParams),
         TxInitAccount(TxInitAccount),
         TxBecomeValidator(TxBecomeValidator),
         TxInitValidator(TxInitValidator),
@@ -517,12 +550,14 @@ pub mod cmds {
         ClaimRewards(ClaimRewards),
         Redelegate(Redelegate),
         AddToEthBridgePool(AddToEthBridgePool),
+        CancelEthBridgePoolTransfer(CancelEthBridgePoolTransfer),
         TxUpdateStewardCommission(TxUpdateStewardCommission),
         TxResignSteward(TxResignSteward),
         QueryEpoch(QueryEpoch),
         QueryNextEpochInfo(QueryNextEpochInfo),
         QueryStatus(QueryStatus),
         QueryAccount(QueryAccount),
+        QueryTxHistory(QueryTxHistory),
         QueryConversions(QueryConversions),
         QueryMaspRewardTokens(QueryMaspRewardTokens),
         QueryBlock(QueryBlock),
@@ -535,6 +570,8 @@ pub mod cmds {
         QuerySlashes(QuerySlashes),
         QueryDelegations(QueryDelegations),
         QueryTotalSupply(QueryTotalSupply),
+        QueryNft(QueryNft),
+        QueryNftClass(QueryNftClass),
         QueryEffNativeSupply(QueryEffNativeSupply),
         QueryStakingRewardsRate(QueryStakingRewardsRate),
         QueryFindValidator(QueryFindValidator),
@@ -547,7 +584,9 @@ pub mod cmds {
         QueryValidatorState(QueryValidatorState),
         QueryRewards(QueryRewards),
         SignTx(SignTx),
+        SignSession(SignSession),
         ShieldedSync(ShieldedSync),
+        DiscoverKeys(DiscoverKeys),
         GenIbcShieldingTransfer(GenIbcShieldingTransfer),
     }
 
@@ -574,6 +613,16 @@ pub mod cmds {
         KeyAddrAdd(WalletAddKeyAddress),
         /// Key / address remove
         KeyAddrRemove(WalletRemoveKeyAddress),
+        /// Remote signer daemon
+        ServeSigner(WalletServeSigner),
+        /// Whole store encryption
+        Encrypt(WalletEncrypt),
+        /// Whole store decryption
+        Decrypt(WalletDecrypt),
+        /// Backup of the wallets and shielded context
+        Backup(WalletBackup),
+        /// Restore of a backup
+        Restore(WalletRestore),
     }
 
     impl Cmd for NamadaWallet {
@@ -588,6 +637,11 @@ pub mod cmds {
                 .subcommand(WalletImportKey::def())
                 .subcommand(WalletAddKeyAddress::def())
                 .subcommand(WalletRemoveKeyAddress::def())
+                .subcommand(WalletServeSigner::def())
+                .subcommand(WalletEncrypt::def())
+                .subcommand(WalletDecrypt::def())
+                .subcommand(WalletBackup::def())
+                .subcommand(WalletRestore::def())
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
@@ -602,6 +656,11 @@ pub mod cmds {
             let key_addr_add = SubCmd::parse(matches).map(Self::KeyAddrAdd);
             let key_addr_remove =
                 SubCmd::parse(matches).map(Self::KeyAddrRemove);
+            let serve_signer = SubCmd::parse(matches).map(Self::ServeSigner);
+            let encrypt = SubCmd::parse(matches).map(Self::Encrypt);
+            let decrypt = SubCmd::parse(matches).map(Self::Decrypt);
+            let backup = SubCmd::parse(matches).map(Self::Backup);
+            let restore = SubCmd::parse(matches).map(Self::Restore);
             gen.or(derive)
                 .or(pay_addr_gen)
                 .or(key_addr_list)
@@ -611,6 +670,11 @@ pub mod cmds {
                 .or(import)
                 .or(key_addr_add)
                 .or(key_addr_remove)
+                .or(serve_signer)
+                .or(encrypt)
+                .or(decrypt)
+                .or(backup)
+                .or(restore)
         }
     }
 
@@ -865,6 +929,122 @@ pub mod cmds {
         }
     }
 
+    /// Serve signing requests with keys from the wallet
+    #[derive(Clone, Debug)]
+    pub struct WalletServeSigner(pub args::ServeRemoteSigner);
+
+    impl SubCmd for WalletServeSigner {
+        const CMD: &'static str = "serve-signer";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::ServeRemoteSigner::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Run a local remote signer daemon serving the signing \
+                     requests of transactions submitted with `--signer` using \
+                     the given keys from the wallet. The daemon signs every \
+                     request for its keys without displaying the \
+                     transactions, so it only serves on loopback addresses \
+                     and on Unix sockets accessible to the current user."
+                ))
+                .add_args::<args::ServeRemoteSigner>()
+        }
+    }
+
+    /// Encrypt the whole wallet store at rest
+    #[derive(Clone, Debug)]
+    pub struct WalletEncrypt;
+
+    impl SubCmd for WalletEncrypt {
+        const CMD: &'static str = "encrypt";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_matches| Self)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD).about(wrap!(
+                "Encrypt the whole wallet file with a password, including its \
+                 aliases, addresses and viewing keys. The password is \
+                 prompted for whenever the wallet is loaded."
+            ))
+        }
+    }
+
+    /// Back up the wallets and the shielded context
+    #[derive(Clone, Debug)]
+    pub struct WalletBackup(pub args::WalletBackup);
+
+    impl SubCmd for WalletBackup {
+        const CMD: &'static str = "backup";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::WalletBackup::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Write an encrypted backup of the wallet, the validator \
+                     data and the shielded context of the chain, and of the \
+                     pre-genesis wallets."
+                ))
+                .add_args::<args::WalletBackup>()
+        }
+    }
+
+    /// Restore a backup of the wallets and the shielded context
+    #[derive(Clone, Debug)]
+    pub struct WalletRestore(pub args::WalletRestore);
+
+    impl SubCmd for WalletRestore {
+        const CMD: &'static str = "restore";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::WalletRestore::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Restore a backup. Its keys and addresses are added to \
+                     the wallet and the files that don't exist yet are \
+                     written. The shielded context and the validator data \
+                     are only restored for the chain they were backed up \
+                     from."
+                ))
+                .add_args::<args::WalletRestore>()
+        }
+    }
+
+    /// Decrypt the whole wallet store at rest
+    #[derive(Clone, Debug)]
+    pub struct WalletDecrypt;
+
+    impl SubCmd for WalletDecrypt {
+        const CMD: &'static str = "decrypt";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_matches| Self)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD).about(wrap!(
+                "Convert an encrypted wallet file back to plaintext. The keys \
+                 encrypted individually stay encrypted."
+            ))
+        }
+    }
+
     /// Generate a payment address from a viewing key or payment address
     #[derive(Clone, Debug)]
     pub struct WalletGenPaymentAddress(pub args::PayAddressGen<args::CliTypes>);
@@ -896,6 +1076,7 @@ pub mod cmds {
         UpdateDB(LedgerUpdateDB),
         QueryDB(LedgerQueryDB),
         RollBack(LedgerRollBack),
+        WarmupWasmCache(LedgerWarmupWasmCache),
     }
 
     impl SubCmd for Ledger {
@@ -910,12 +1091,15 @@ pub mod cmds {
                 let query_db = SubCmd::parse(matches).map(Self::QueryDB);
                 let rollback = SubCmd::parse(matches).map(Self::RollBack);
                 let run_until = SubCmd::parse(matches).map(Self::RunUntil);
+                let warmup_wasm_cache =
+                    SubCmd::parse(matches).map(Self::WarmupWasmCache);
                 run.or(reset)
                     .or(dump_db)
                     .or(update_db)
                     .or(query_db)
                     .or(rollback)
                     .or(run_until)
+                    .or(warmup_wasm_cache)
                     // The `run` command is the default if no sub-command given
                     .or(Some(Self::Run(LedgerRun(args::LedgerRun {
                         start_time: None,
@@ -939,6 +1123,7 @@ pub mod cmds {
                 .subcommand(LedgerUpdateDB::def())
                 .subcommand(LedgerQueryDB::def())
                 .subcommand(LedgerRollBack::def())
+                .subcommand(LedgerWarmupWasmCache::def())
         }
     }
 
@@ -1087,6 +1272,25 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct LedgerWarmupWasmCache;
+
+    impl SubCmd for LedgerWarmupWasmCache {
+        const CMD: &'static str = "warmup-wasm-cache";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_matches| Self)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD).about(wrap!(
+                "Pre-compile the allowlisted and stored wasm codes into the \
+                 node's compilation caches and check the integrity of the \
+                 cached modules. The node must not be running."
+            ))
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub enum Config {
         Gen(ConfigGen),
@@ -1458,6 +1662,29 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxUpdateVpParams(pub args::TxUpdateVpParams<args::CliTypes>);
+
+    impl SubCmd for TxUpdateVpParams {
+        const CMD: &'static str = "update-vp-params";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxUpdateVpParams(args::TxUpdateVpParams::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Send a signed transaction to update the parameters of \
+                     an account's validity predicate, e.g. its spending \
+                     limits."
+                ))
+                .add_args::<args::TxUpdateVpParams<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxInitAccount(pub args::TxInitAccount<args::CliTypes>);
 
@@ -1619,6 +1846,30 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct DiscoverKeys(pub args::DiscoverKeys<args::CliTypes>);
+
+    impl SubCmd for DiscoverKeys {
+        const CMD: &'static str = "discover-keys";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| DiscoverKeys(args::DiscoverKeys::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Discover the keys derived from a mnemonic code that were \
+                     used on chain and add them to the wallet. The keys are \
+                     derived at sequential indices until a number of \
+                     consecutive unused ones is reached."
+                ))
+                .add_args::<args::DiscoverKeys<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct Bond(pub args::Bond<args::CliTypes>);
 
@@ -1803,6 +2054,27 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryTxHistory(pub args::QueryTxHistory<args::CliTypes>);
+
+    impl SubCmd for QueryTxHistory {
+        const CMD: &'static str = "history";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryTxHistory(args::QueryTxHistory::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Query the hashes of the transactions that moved the                      tokens of a transparent account. Requires a node that                      maintains the transaction history index."
+                ))
+                .add_args::<args::QueryTxHistory<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryConversions(pub args::QueryConversions<args::CliTypes>);
 
@@ -1964,6 +2236,84 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub enum SignSession {
+        Add(SignSessionAdd),
+        Finalize(SignSessionFinalize),
+    }
+
+    impl SubCmd for SignSession {
+        const CMD: &'static str = "sign-session";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let add = SubCmd::parse(matches).map(Self::Add);
+                let finalize = SubCmd::parse(matches).map(Self::Finalize);
+                add.or(finalize)
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Collect the offline signatures of the owners of a \
+                     multisig account in a signing session started with \
+                     `--dump-tx`, then submit the transaction."
+                ))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(SignSessionAdd::def().display_order(1))
+                .subcommand(SignSessionFinalize::def().display_order(1))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct SignSessionAdd(pub args::SignSessionAdd<args::CliTypes>);
+
+    impl SubCmd for SignSessionAdd {
+        const CMD: &'static str = "add";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::SignSessionAdd::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Verify the transaction of a signing session, show its \
+                     summary and add the signatures of the wallet's keys of \
+                     the account to the session."
+                ))
+                .add_args::<args::SignSessionAdd<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct SignSessionFinalize(
+        pub args::SignSessionFinalize<args::CliTypes>,
+    );
+
+    impl SubCmd for SignSessionFinalize {
+        const CMD: &'static str = "finalize";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::SignSessionFinalize::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Check that the signatures of a signing session meet the \
+                     account threshold and submit its transaction."
+                ))
+                .add_args::<args::SignSessionFinalize<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryValidatorState(
         pub args::QueryValidatorState<args::CliTypes>,
@@ -2119,6 +2469,56 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryNft(pub args::QueryNft<args::CliTypes>);
+
+    impl SubCmd for QueryNft {
+        const CMD: &'static str = "query-nft";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryNft(args::QueryNft::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Query the IBC NFTs owned by the given address, or the \
+                     metadata of the given NFT."
+                ))
+                .add_args::<args::QueryNft<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryNftClass(pub args::QueryNftClass<args::CliTypes>);
+
+    impl SubCmd for QueryNftClass {
+        const CMD: &'static str = "query-nft-class";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryNftClass(args::QueryNftClass::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Query the given IBC NFT class, or all the NFT classes \
+                     known on chain if no class is given."
+                ))
+                .add_args::<args::QueryNftClass<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryEffNativeSupply(
         pub args::QueryEffNativeSupply<args::CliTypes>,
@@ -2916,6 +3316,8 @@ pub mod cmds {
         /// Check the confirmation status of `TransferToEthereum`
         /// events.
         QueryRelays(QueryRelayProgress),
+        /// Query the cost of relaying transfers in the pool.
+        QueryFees(QueryBridgePoolFees),
     }
 
     impl Cmd for EthBridgePool {
@@ -2926,6 +3328,7 @@ pub mod cmds {
                 .subcommand(QueryEthBridgePool::def().display_order(1))
                 .subcommand(QuerySignedBridgePool::def().display_order(1))
                 .subcommand(QueryRelayProgress::def().display_order(1))
+                .subcommand(QueryBridgePoolFees::def().display_order(1))
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
@@ -2939,6 +3342,7 @@ pub mod cmds {
             let query_pool = Self::parse_without_ctx(matches, QueryPool);
             let query_signed = Self::parse_without_ctx(matches, QuerySigned);
             let query_relays = Self::parse_without_ctx(matches, QueryRelays);
+            let query_fees = Self::parse_without_ctx(matches, QueryFees);
 
             construct_proof
                 .or(recommend)
@@ -2946,6 +3350,7 @@ pub mod cmds {
                 .or(query_pool)
                 .or(query_signed)
                 .or(query_relays)
+                .or(query_fees)
         }
     }
 
@@ -2988,6 +3393,7 @@ pub mod cmds {
                 .subcommand(QueryEthBridgePool::def().display_order(1))
                 .subcommand(QuerySignedBridgePool::def().display_order(1))
                 .subcommand(QueryRelayProgress::def().display_order(1))
+                .subcommand(QueryBridgePoolFees::def().display_order(1))
         }
     }
 
@@ -3011,6 +3417,34 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct CancelEthBridgePoolTransfer(
+        pub args::CancelEthereumBridgePool<args::CliTypes>,
+    );
+
+    impl SubCmd for CancelEthBridgePoolTransfer {
+        const CMD: &'static str = "cancel-erc20-transfer";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::CancelEthereumBridgePool::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Cancel a transfer pending in the Ethereum Bridge pool, \
+                     refunding its escrowed tokens and gas fees. Only \
+                     transfers that cannot be relayed as part of any Bridge \
+                     pool root can be cancelled, i.e. once the Bridge pool \
+                     nonce has advanced past all the roots including them."
+                ))
+                .arg_required_else_help(true)
+                .add_args::<args::CancelEthereumBridgePool<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct ConstructProof(pub args::BridgePoolProof<args::CliTypes>);
 
@@ -3142,6 +3576,30 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryBridgePoolFees(pub args::QueryWithoutCtx<args::CliTypes>);
+
+    impl SubCmd for QueryBridgePoolFees {
+        const CMD: &'static str = "query-fees";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::QueryWithoutCtx::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Get the cost of relaying a single Ethereum Bridge pool \
+                     transfer, based on the costs of recently relayed \
+                     batches, along with the pending transfers at risk of \
+                     expiring."
+                ))
+                .add_args::<args::QueryWithoutCtx<args::CliTypes>>()
+        }
+    }
+
     /// Used as sub-commands (`SubCmd` instance) in `namadar` binary.
     #[derive(Clone, Debug)]
     pub enum ValidatorSet {
@@ -3289,6 +3747,29 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct IbcRelay(pub args::IbcRelay<args::CliTypes>);
+
+    impl SubCmd for IbcRelay {
+        const CMD: &'static str = "ibc-relay";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::IbcRelay::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(wrap!(
+                    "Relay IBC packets, acknowledgements and timeouts between \
+                     this chain and a counterparty Namada chain, over an \
+                     established channel."
+                ))
+                .add_args::<args::IbcRelay<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct PkToTmAddress(pub args::PkToTmAddress);
 
@@ -3370,25 +3851,29 @@ pub mod args {
     use namada_sdk::dec::Dec;
     use namada_sdk::ethereum_events::EthAddress;
     use namada_sdk::hash::Hash;
+    use namada_sdk::ibc::apps::nft_transfer::types::PrefixedClassId;
     use namada_sdk::ibc::core::host::types::identifiers::{ChannelId, PortId};
     use namada_sdk::keccak::KeccakHash;
     use namada_sdk::key::*;
     use namada_sdk::masp::utils::RetryStrategy;
+    use namada_sdk::remote_signer::RemoteSignerAddr;
     use namada_sdk::storage::{self, BlockHeight, Epoch};
-    use namada_sdk::time::DateTimeUtc;
+    use namada_sdk::time::{DateTimeUtc, DurationSecs};
     use namada_sdk::token::NATIVE_MAX_DECIMAL_PLACES;
     use namada_sdk::tx::data::GasLimit;
     pub use namada_sdk::tx::{
         TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
-        TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-        TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-        TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-        TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-        TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+        TX_CANCEL_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+        TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+        TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
+        TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_INIT_VESTING_ACCOUNT_WASM,
+        TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+        TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
         TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-        TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-        VP_USER_WASM,
+        TX_UPDATE_STEWARD_COMMISSION, TX_UPDATE_VP_PARAMS_WASM,
+        TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM, VP_USER_WASM,
     };
+    use namada_sdk::wallet::discovery::DEFAULT_GAP_LIMIT;
     use namada_sdk::{token, DEFAULT_GAS_LIMIT};
 
     use super::context::*;
@@ -3423,6 +3908,8 @@ pub mod args {
     pub const BLOCK_BATCH: ArgDefault<usize> =
         arg_default("block-batch", DefaultFn(|| 10));
     pub const BLOCK_HEIGHT: Arg<BlockHeight> = arg("block-height");
+    pub const BLOCK_HEIGHT_FROM: ArgDefault<BlockHeight> =
+        arg_default("from-height", DefaultFn(|| BlockHeight(0)));
     pub const BLOCK_HEIGHT_OPT: ArgOpt<BlockHeight> = arg_opt("height");
     pub const BLOCK_HEIGHT_TO_OPT: ArgOpt<BlockHeight> = arg_opt("to-height");
     pub const BRIDGE_POOL_GAS_AMOUNT: ArgDefault<token::DenominatedAmount> =
@@ -3444,11 +3931,14 @@ pub mod args {
         DefaultFn(|| "".parse().unwrap()),
     );
     pub const BRIDGE_POOL_TARGET: Arg<EthAddress> = arg("target");
+    pub const BRIDGE_POOL_TRANSFER_HASH: Arg<KeccakHash> = arg("transfer-hash");
     pub const BROADCAST_ONLY: ArgFlag = flag("broadcast-only");
+    pub const BYPASS_THRESHOLD: ArgOpt<u8> = arg_opt("bypass-threshold");
     pub const CHAIN_ID: Arg<ChainId> = arg("chain-id");
     pub const CHAIN_ID_OPT: ArgOpt<ChainId> = CHAIN_ID.opt();
     pub const CHAIN_ID_PREFIX: Arg<ChainIdPrefix> = arg("chain-prefix");
     pub const CHANNEL_ID: Arg<ChannelId> = arg("channel-id");
+    pub const CLASS_ID_OPT: ArgOpt<PrefixedClassId> = arg_opt("class-id");
     pub const CODE_PATH: Arg<PathBuf> = arg("code-path");
     pub const CODE_PATH_OPT: ArgOpt<PathBuf> = CODE_PATH.opt();
     pub const COMMISSION_RATE: Arg<Dec> = arg("commission-rate");
@@ -3458,6 +3948,11 @@ pub mod args {
         DefaultFn(|| Timeout::from_str("1s").unwrap()),
     );
     pub const CONVERSION_TABLE: Arg<PathBuf> = arg("conversion-table");
+    pub const CONVERSION_TABLE_OPT: ArgOpt<PathBuf> = CONVERSION_TABLE.opt();
+    pub const COUNTERPARTY_CHAIN_ID: Arg<ChainId> =
+        arg("counterparty-chain-id");
+    pub const COUNTERPARTY_LEDGER_ADDRESS: ArgDefaultFromCtx<ConfigRpcAddress> =
+        arg_default_from_ctx("counterparty-node", DefaultFn(|| "".to_string()));
     pub const DAEMON_MODE: ArgFlag = flag("daemon");
     pub const DAEMON_MODE_RETRY_DUR: ArgOpt<Duration> = arg_opt("retry-sleep");
     pub const DAEMON_MODE_SUCCESS_DUR: ArgOpt<Duration> =
@@ -3505,6 +4000,8 @@ pub mod args {
     pub const FEE_PAYER_OPT: ArgOpt<WalletPublicKey> = arg_opt("gas-payer");
     pub const FILE_PATH: Arg<String> = arg("file");
     pub const FORCE: ArgFlag = flag("force");
+    pub const GAP_LIMIT: ArgDefault<u32> =
+        arg_default("gap-limit", DefaultFn(|| DEFAULT_GAP_LIMIT));
     pub const GAS_LIMIT: ArgDefault<GasLimit> = arg_default(
         "gas-limit",
         DefaultFn(|| GasLimit::from(DEFAULT_GAS_LIMIT)),
@@ -3613,6 +4110,8 @@ pub mod args {
     pub const REFUND_TARGET: ArgOpt<WalletTransferTarget> =
         arg_opt("refund-target");
     pub const RELAYER: Arg<Address> = arg("relayer");
+    pub const REMOTE_SIGNER: ArgOpt<RemoteSignerAddr> = arg_opt("signer");
+    pub const REMOTE_SIGNER_ADDRESS: Arg<RemoteSignerAddr> = arg("address");
     pub const RETRIES: ArgOpt<u64> = arg_opt("retries");
     pub const SCHEME: ArgDefault<SchemeType> =
         arg_default("scheme", DefaultFn(|| SchemeType::Ed25519));
@@ -3620,9 +4119,9 @@ pub mod args {
     pub const SELF_BOND_AMOUNT: Arg<token::DenominatedAmount> =
         arg("self-bond-amount");
     pub const SENDER: Arg<String> = arg("sender");
+    pub const SESSION_PATH: Arg<PathBuf> = arg("session");
     pub const SHIELDED: ArgFlag = flag("shielded");
     pub const SHOW_IBC_TOKENS: ArgFlag = flag("show-ibc-tokens");
-    pub const SIGNER: ArgOpt<WalletAddress> = arg_opt("signer");
     pub const SIGNING_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
         arg_multi("signing-keys");
     pub const SIGNATURES: ArgMulti<PathBuf, GlobStar> = arg_multi("signatures");
@@ -3630,6 +4129,10 @@ pub mod args {
     pub const SOURCE_OPT: ArgOpt<WalletAddress> = SOURCE.opt();
     pub const SOURCE_VALIDATOR: Arg<WalletAddress> = arg("source-validator");
     pub const SPENDING_KEY_SOURCE: Arg<WalletSpendingKey> = arg("source");
+    pub const SPENDING_LIMIT: ArgOpt<token::DenominatedAmount> =
+        arg_opt("spending-limit");
+    pub const SPENDING_LIMIT_WINDOW: ArgOpt<Duration> =
+        arg_opt("spending-limit-window");
     pub const SPENDING_KEYS: ArgMulti<WalletSpendingKey, GlobStar> =
         arg_multi("spending-keys");
     pub const STEWARD: Arg<WalletAddress> = arg("steward");
@@ -3648,6 +4151,8 @@ pub mod args {
     pub const TRANSFER_TARGET: Arg<WalletTransferTarget> = arg("target");
     pub const TRANSPARENT: ArgFlag = flag("transparent");
     pub const TX_HASH: Arg<String> = arg("tx-hash");
+    pub const TX_INDEX_FROM: ArgDefault<u32> =
+        arg_default("from-tx-index", DefaultFn(|| 0));
     pub const THRESHOLD: ArgOpt<u8> = arg_opt("threshold");
     pub const UNSAFE_DONT_ENCRYPT: ArgFlag = flag("unsafe-dont-encrypt");
     pub const UNSAFE_SHOW_SECRET: ArgFlag = flag("unsafe-show-secret");
@@ -3668,6 +4173,11 @@ pub mod args {
     pub const VALIDATOR_ETH_HOT_KEY: ArgOpt<WalletPublicKey> =
         arg_opt("eth-hot-key");
     pub const VALUE: Arg<String> = arg("value");
+    pub const VESTING_AMOUNT: ArgOpt<token::DenominatedAmount> =
+        arg_opt("vesting-amount");
+    pub const VESTING_CLIFF: ArgOpt<DateTimeUtc> = arg_opt("vesting-cliff");
+    pub const VESTING_END: ArgOpt<DateTimeUtc> = arg_opt("vesting-end");
+    pub const VESTING_START: ArgOpt<DateTimeUtc> = arg_opt("vesting-start");
     pub const VOTER_OPT: ArgOpt<WalletAddress> = arg_opt("voter");
     pub const VIEWING_KEY: Arg<WalletViewingKey> = arg("key");
     pub const VIEWING_KEYS: ArgMulti<WalletViewingKey, GlobStar> =
@@ -4102,11 +4612,42 @@ pub mod args {
                     .fee_payer
                     .map(|fee_payer| chain_ctx.get(&fee_payer)),
                 fee_token: chain_ctx.get(&self.fee_token).into(),
+                conversion_table: self
+                    .conversion_table
+                    .map(|path| read_bp_conversion_table(chain_ctx, path)),
                 code_path: self.code_path,
             })
         }
     }
 
+    /// Read a JSON object mapping token aliases (or addresses) to
+    /// their conversion rates in gwei.
+    fn read_bp_conversion_table(
+        chain_ctx: &ChainContext,
+        path: PathBuf,
+    ) -> HashMap<Address, BpConversionTableEntry> {
+        let file = std::io::BufReader::new(std::fs::File::open(path).expect(
+            "Failed to open the provided file to the conversion table",
+        ));
+        let table: HashMap<String, f64> = serde_json::from_reader(file)
+            .expect("Failed to parse conversion table");
+        table
+            .into_iter()
+            .map(|(token, conversion_rate)| {
+                let token_from_ctx = FromContext::<Address>::new(token);
+                let address = chain_ctx.get(&token_from_ctx);
+                let alias = token_from_ctx.raw;
+                (
+                    address,
+                    BpConversionTableEntry {
+                        alias,
+                        conversion_rate,
+                    },
+                )
+            })
+            .collect()
+    }
+
     impl Args for EthereumBridgePool<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let tx = Tx::parse(matches);
@@ -4118,6 +4659,7 @@ pub mod args {
                 InputAmount::Unvalidated(BRIDGE_POOL_GAS_AMOUNT.parse(matches));
             let fee_payer = BRIDGE_POOL_GAS_PAYER.parse(matches);
             let fee_token = BRIDGE_POOL_GAS_TOKEN.parse(matches);
+            let conversion_table = CONVERSION_TABLE_OPT.parse(matches);
             let code_path = PathBuf::from(TX_BRIDGE_POOL_WASM);
             let nut = NUT.parse(matches);
             Self {
@@ -4129,6 +4671,7 @@ pub mod args {
                 fee_amount,
                 fee_payer,
                 fee_token,
+                conversion_table,
                 code_path,
                 nut,
             }
@@ -4166,6 +4709,12 @@ pub mod args {
                     "The token for paying the Bridge pool gas fees. Defaults \
                      to NAM."
                 )))
+                .arg(CONVERSION_TABLE_OPT.def().help(wrap!(
+                    "Path to a JSON object containing a mapping between token \
+                     aliases (or addresses) and their conversion rates in \
+                     gwei. If provided, warn when the gas fee is below the \
+                     cost of relaying recent transfers."
+                )))
                 .arg(NUT.def().help(wrap!(
                     "Add Non Usable Tokens (NUTs) to the Bridge pool. These \
                      are usually obtained from invalid transfers to Namada."
@@ -4173,6 +4722,46 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<CancelEthereumBridgePool<SdkTypes>>
+        for CancelEthereumBridgePool<CliTypes>
+    {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<CancelEthereumBridgePool<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            Ok(CancelEthereumBridgePool::<SdkTypes> {
+                tx,
+                transfer: self.transfer,
+                code_path: self.code_path,
+            })
+        }
+    }
+
+    impl Args for CancelEthereumBridgePool<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let transfer = BRIDGE_POOL_TRANSFER_HASH.parse(matches);
+            let code_path = PathBuf::from(TX_CANCEL_BRIDGE_POOL_WASM);
+            Self {
+                tx,
+                transfer,
+                code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>().arg(
+                BRIDGE_POOL_TRANSFER_HASH.def().help(wrap!(
+                    "The keccak hash of the pending transfer to cancel, as \
+                     shown when querying the contents of the Bridge pool."
+                )),
+            )
+        }
+    }
+
     impl CliToSdk<RecommendBatch<SdkTypes>> for RecommendBatch<CliTypes> {
         type Error = std::convert::Infallible;
 
@@ -4187,33 +4776,10 @@ pub mod args {
                 query,
                 max_gas: self.max_gas,
                 gas: self.gas,
-                conversion_table: {
-                    let file = std::io::BufReader::new(
-                        std::fs::File::open(self.conversion_table).expect(
-                            "Failed to open the provided file to the \
-                             conversion table",
-                        ),
-                    );
-                    let table: HashMap<String, f64> =
-                        serde_json::from_reader(file)
-                            .expect("Failed to parse conversion table");
-                    table
-                        .into_iter()
-                        .map(|(token, conversion_rate)| {
-                            let token_from_ctx =
-                                FromContext::<Address>::new(token);
-                            let address = chain_ctx.get(&token_from_ctx);
-                            let alias = token_from_ctx.raw;
-                            (
-                                address,
-                                BpConversionTableEntry {
-                                    alias,
-                                    conversion_rate,
-                                },
-                            )
-                        })
-                        .collect()
-                },
+                conversion_table: read_bp_conversion_table(
+                    chain_ctx,
+                    self.conversion_table,
+                ),
             })
         }
     }
@@ -4563,6 +5129,91 @@ pub mod args {
         }
     }
 
+    impl IbcRelay<CliTypes> {
+        /// Convert the arguments to their SDK counterpart. Unlike other
+        /// commands, the relayer needs a context for each of the two chains.
+        pub fn to_sdk(
+            self,
+            ctx: &mut Context,
+            counterparty_ctx: &mut Context,
+        ) -> Result<IbcRelay<SdkTypes>, std::io::Error> {
+            Ok(IbcRelay::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx)?,
+                counterparty_tx: self
+                    .counterparty_tx
+                    .to_sdk(counterparty_ctx)?,
+                port_id: self.port_id,
+                channel_id: self.channel_id,
+                daemon: self.daemon,
+                retry_dur: self.retry_dur,
+                success_dur: self.success_dur,
+                tx_code_path: self.tx_code_path,
+            })
+        }
+    }
+
+    impl Args for IbcRelay<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let counterparty_tx = Tx {
+                ledger_address: COUNTERPARTY_LEDGER_ADDRESS.parse(matches),
+                chain_id: Some(COUNTERPARTY_CHAIN_ID.parse(matches)),
+                ..tx.clone()
+            };
+            let port_id = PORT_ID.parse(matches);
+            let channel_id = CHANNEL_ID.parse(matches);
+            let daemon = DAEMON_MODE.parse(matches);
+            let retry_dur =
+                DAEMON_MODE_RETRY_DUR.parse(matches).map(|dur| dur.0);
+            let success_dur =
+                DAEMON_MODE_SUCCESS_DUR.parse(matches).map(|dur| dur.0);
+            let tx_code_path = PathBuf::from(TX_IBC_WASM);
+            Self {
+                tx,
+                counterparty_tx,
+                port_id,
+                channel_id,
+                daemon,
+                retry_dur,
+                success_dur,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(COUNTERPARTY_CHAIN_ID.def().help(wrap!(
+                    "The chain ID of the counterparty Namada chain. Its \
+                     wallet and config must exist in the base directory."
+                )))
+                .arg(COUNTERPARTY_LEDGER_ADDRESS.def().help(wrap!(
+                    "Address of a ledger node of the counterparty chain as \
+                     \"{scheme}://{host}:{port}\". If unset, the RPC \
+                     address from the counterparty chain's config is used."
+                )))
+                .arg(
+                    PORT_ID.def().help(wrap!(
+                        "The port ID of the channel on this chain."
+                    )),
+                )
+                .arg(CHANNEL_ID.def().help(wrap!(
+                    "The channel ID on this chain to relay packets over."
+                )))
+                .arg(DAEMON_MODE.def().help(wrap!(
+                    "Run in daemon mode, which will continuously relay \
+                     packets."
+                )))
+                .arg(DAEMON_MODE_RETRY_DUR.def().help(wrap!(
+                    "The amount of time to sleep between failed daemon mode \
+                     relays."
+                )))
+                .arg(DAEMON_MODE_SUCCESS_DUR.def().help(wrap!(
+                    "The amount of time to sleep between successful daemon \
+                     mode relays."
+                )))
+        }
+    }
+
     impl CliToSdk<TxCustom<SdkTypes>> for TxCustom<CliTypes> {
         type Error = std::io::Error;
 
@@ -5128,6 +5779,13 @@ pub mod args {
                     .map(|pk| chain_ctx.get(pk))
                     .collect(),
                 threshold: self.threshold,
+                vesting: self.vesting.map(|vesting| Vesting {
+                    token: chain_ctx.get(&vesting.token),
+                    amount: vesting.amount,
+                    start: vesting.start,
+                    cliff: vesting.cliff,
+                    end: vesting.end,
+                }),
             })
         }
     }
@@ -5138,15 +5796,30 @@ pub mod args {
             let vp_code_path = CODE_PATH_OPT
                 .parse(matches)
                 .unwrap_or_else(|| PathBuf::from(VP_USER_WASM));
-            let tx_code_path = PathBuf::from(TX_INIT_ACCOUNT_WASM);
             let public_keys = PUBLIC_KEYS.parse(matches);
             let threshold = THRESHOLD.parse(matches);
+            let vesting = VESTING_AMOUNT.parse(matches).map(|amount| {
+                let start = VESTING_START.parse(matches).unwrap();
+                Vesting {
+                    token: TOKEN.parse(matches),
+                    amount: InputAmount::Unvalidated(amount),
+                    start,
+                    cliff: VESTING_CLIFF.parse(matches).unwrap_or(start),
+                    end: VESTING_END.parse(matches).unwrap(),
+                }
+            });
+            let tx_code_path = PathBuf::from(if vesting.is_some() {
+                TX_INIT_VESTING_ACCOUNT_WASM
+            } else {
+                TX_INIT_ACCOUNT_WASM
+            });
             Self {
                 tx,
                 vp_code_path,
                 public_keys,
                 threshold,
                 tx_code_path,
+                vesting,
             }
         }
 
@@ -5166,6 +5839,52 @@ pub mod args {
                      authorization. Must be less then the maximum number of \
                      public keys provided."
                 )))
+                .arg(
+                    VESTING_AMOUNT
+                        .def()
+                        .help(wrap!(
+                            "Make the new account a vesting account, with the \
+                             given amount of the token given by `--token` \
+                             released from `--vesting-start` to \
+                             `--vesting-end`. A vesting account uses the \
+                             default user VP."
+                        ))
+                        .conflicts_with(CODE_PATH_OPT.name)
+                        .requires(TOKEN.name)
+                        .requires(VESTING_START.name)
+                        .requires(VESTING_END.name),
+                )
+                .arg(
+                    TOKEN_OPT
+                        .def()
+                        .help(wrap!("The token vested by `--vesting-amount`.")),
+                )
+                .arg(
+                    VESTING_START
+                        .def()
+                        .help(wrap!(
+                            "The time from which the vesting tokens are \
+                             released linearly."
+                        ))
+                        .requires(VESTING_AMOUNT.name),
+                )
+                .arg(
+                    VESTING_CLIFF
+                        .def()
+                        .help(wrap!(
+                            "The time before which no tokens are vested. \
+                             Defaults to the start of the vesting."
+                        ))
+                        .requires(VESTING_AMOUNT.name),
+                )
+                .arg(
+                    VESTING_END
+                        .def()
+                        .help(wrap!(
+                            "The time at which all the tokens are vested."
+                        ))
+                        .requires(VESTING_AMOUNT.name),
+                )
         }
     }
 
@@ -5544,6 +6263,98 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<TxUpdateVpParams<SdkTypes>> for TxUpdateVpParams<CliTypes> {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<TxUpdateVpParams<SdkTypes>, Self::Error> {
+            let tx = self.tx.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+
+            Ok(TxUpdateVpParams::<SdkTypes> {
+                tx,
+                tx_code_path: self.tx_code_path,
+                addr: chain_ctx.get(&self.addr),
+                vp_params: self.vp_params,
+                spending_limit: self.spending_limit.map(|limit| {
+                    SpendingLimit {
+                        token: chain_ctx.get(&limit.token),
+                        amount: limit.amount,
+                        window: limit.window,
+                    }
+                }),
+                bypass_threshold: self.bypass_threshold,
+            })
+        }
+    }
+
+    /// The default window of the spending limits set with `update-vp-params`
+    const DEFAULT_SPENDING_LIMIT_WINDOW: DurationSecs = DurationSecs(86_400);
+
+    impl Args for TxUpdateVpParams<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let addr = ADDRESS.parse(matches);
+            let tx_code_path = PathBuf::from(TX_UPDATE_VP_PARAMS_WASM);
+            let spending_limit =
+                SPENDING_LIMIT.parse(matches).map(|amount| SpendingLimit {
+                    token: TOKEN.parse(matches),
+                    amount: InputAmount::Unvalidated(amount),
+                    window: SPENDING_LIMIT_WINDOW
+                        .parse(matches)
+                        .map_or(DEFAULT_SPENDING_LIMIT_WINDOW, |window| {
+                            window.0.into()
+                        }),
+                });
+            let bypass_threshold = BYPASS_THRESHOLD.parse(matches);
+            Self {
+                tx,
+                addr,
+                tx_code_path,
+                vp_params: None,
+                spending_limit,
+                bypass_threshold,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(ADDRESS.def().help(wrap!(
+                    "The account's address. It's key is used to produce the \
+                     signature."
+                )))
+                .arg(
+                    SPENDING_LIMIT
+                        .def()
+                        .help(wrap!(
+                            "Set a limit on the amount of the token given by \
+                             `--token` that can be debited from the account \
+                             in a rolling window of time."
+                        ))
+                        .requires(TOKEN.name),
+                )
+                .arg(TOKEN_OPT.def().help(wrap!(
+                    "The token whose debits are limited by `--spending-limit`."
+                )))
+                .arg(
+                    SPENDING_LIMIT_WINDOW
+                        .def()
+                        .help(wrap!(
+                            "The duration of the window of the spending \
+                             limit, e.g. `12h`. Defaults to one day."
+                        ))
+                        .requires(SPENDING_LIMIT.name),
+                )
+                .arg(BYPASS_THRESHOLD.def().help(wrap!(
+                    "The number of signatures that allows a transaction to \
+                     exceed the account's spending limits. Must be higher \
+                     than the account's threshold."
+                )))
+        }
+    }
+
     impl CliToSdk<Bond<SdkTypes>> for Bond<CliTypes> {
         type Error = std::io::Error;
 
@@ -6268,34 +7079,83 @@ pub mod args {
         }
     }
 
-    impl CliToSdk<QueryAccount<SdkTypes>> for QueryAccount<CliTypes> {
+    impl CliToSdk<QueryAccount<SdkTypes>> for QueryAccount<CliTypes> {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<QueryAccount<SdkTypes>, Self::Error> {
+            Ok(QueryAccount::<SdkTypes> {
+                query: self.query.to_sdk(ctx)?,
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            })
+        }
+    }
+
+    impl Args for QueryAccount<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = OWNER.parse(matches);
+            Self { query, owner }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                OWNER
+                    .def()
+                    .help(wrap!("The substorage space address to query."))
+                    .required(true),
+            )
+        }
+    }
+
+    impl CliToSdk<QueryTxHistory<SdkTypes>> for QueryTxHistory<CliTypes> {
         type Error = std::convert::Infallible;
 
         fn to_sdk(
             self,
             ctx: &mut Context,
-        ) -> Result<QueryAccount<SdkTypes>, Self::Error> {
-            Ok(QueryAccount::<SdkTypes> {
+        ) -> Result<QueryTxHistory<SdkTypes>, Self::Error> {
+            Ok(QueryTxHistory::<SdkTypes> {
                 query: self.query.to_sdk(ctx)?,
                 owner: ctx.borrow_chain_or_exit().get(&self.owner),
+                from_height: self.from_height,
+                from_tx_index: self.from_tx_index,
             })
         }
     }
 
-    impl Args for QueryAccount<CliTypes> {
+    impl Args for QueryTxHistory<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
             let owner = OWNER.parse(matches);
-            Self { query, owner }
+            let from_height = BLOCK_HEIGHT_FROM.parse(matches);
+            let from_tx_index = TX_INDEX_FROM.parse(matches);
+            Self {
+                query,
+                owner,
+                from_height,
+                from_tx_index,
+            }
         }
 
         fn def(app: App) -> App {
-            app.add_args::<Query<CliTypes>>().arg(
-                OWNER
-                    .def()
-                    .help(wrap!("The substorage space address to query."))
-                    .required(true),
-            )
+            app.add_args::<Query<CliTypes>>()
+                .arg(
+                    OWNER
+                        .def()
+                        .help(wrap!("The address of the account to query."))
+                        .required(true),
+                )
+                .arg(BLOCK_HEIGHT_FROM.def().help(wrap!(
+                    "The block height from which to query the history. \
+                     Defaults to the oldest transactions."
+                )))
+                .arg(TX_INDEX_FROM.def().help(wrap!(
+                    "The index of the transaction in the block at the \
+                     `--from-height` from which to query the history."
+                )))
         }
     }
 
@@ -6861,6 +7721,78 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<SignSessionAdd<SdkTypes>> for SignSessionAdd<CliTypes> {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<SignSessionAdd<SdkTypes>, Self::Error> {
+            Ok(SignSessionAdd::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx)?,
+                session: self.session,
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            })
+        }
+    }
+
+    impl Args for SignSessionAdd<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let session = SESSION_PATH.parse(matches);
+            let owner = OWNER.parse(matches);
+            Self { tx, session, owner }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(
+                    SESSION_PATH
+                        .def()
+                        .help(wrap!("The path to the signing session file.")),
+                )
+                .arg(
+                    OWNER.def().help(wrap!("The address of the account owner")),
+                )
+        }
+    }
+
+    impl CliToSdk<SignSessionFinalize<SdkTypes>> for SignSessionFinalize<CliTypes> {
+        type Error = std::io::Error;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<SignSessionFinalize<SdkTypes>, Self::Error> {
+            Ok(SignSessionFinalize::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx)?,
+                session: self.session,
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            })
+        }
+    }
+
+    impl Args for SignSessionFinalize<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let session = SESSION_PATH.parse(matches);
+            let owner = OWNER.parse(matches);
+            Self { tx, session, owner }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(
+                    SESSION_PATH
+                        .def()
+                        .help(wrap!("The path to the signing session file.")),
+                )
+                .arg(
+                    OWNER.def().help(wrap!("The address of the account owner")),
+                )
+        }
+    }
+
     impl Args for ShieldedSync<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let ledger_address = CONFIG_RPC_LEDGER_ADDRESS.parse(matches);
@@ -6961,6 +7893,111 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<DiscoverKeys<SdkTypes>> for DiscoverKeys<CliTypes> {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<DiscoverKeys<SdkTypes>, Self::Error> {
+            Ok(DiscoverKeys::<SdkTypes> {
+                query: self.query.to_sdk(ctx)?,
+                scheme: self.scheme,
+                shielded: self.shielded,
+                alias: self.alias,
+                alias_force: self.alias_force,
+                gap_limit: self.gap_limit,
+                unsafe_dont_encrypt: self.unsafe_dont_encrypt,
+                prompt_bip39_passphrase: self.prompt_bip39_passphrase,
+                birthday: self.birthday,
+                with_indexer: self.with_indexer,
+                max_concurrent_fetches: self.max_concurrent_fetches,
+                block_batch_size: self.block_batch_size,
+            })
+        }
+    }
+
+    impl Args for DiscoverKeys<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let scheme = SCHEME.parse(matches);
+            let shielded = SHIELDED.parse(matches);
+            let alias = ALIAS.parse(matches);
+            let alias_force = ALIAS_FORCE.parse(matches);
+            let gap_limit = GAP_LIMIT.parse(matches);
+            let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
+            let prompt_bip39_passphrase =
+                HD_PROMPT_BIP39_PASSPHRASE.parse(matches);
+            let birthday = BIRTHDAY.parse(matches);
+            let with_indexer = WITH_INDEXER.parse(matches);
+            let max_concurrent_fetches = MAX_CONCURRENT_FETCHES.parse(matches);
+            let block_batch_size = BLOCK_BATCH.parse(matches);
+            Self {
+                query,
+                scheme,
+                shielded,
+                alias,
+                alias_force,
+                gap_limit,
+                unsafe_dont_encrypt,
+                prompt_bip39_passphrase,
+                birthday,
+                with_indexer,
+                max_concurrent_fetches,
+                block_batch_size,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(SCHEME.def().conflicts_with(SHIELDED.name).help(wrap!(
+                    "For the transparent pool, the type of the keys to \
+                     discover. Argument must be either ed25519 or secp256k1. \
+                     If none provided, the default key scheme is ed25519."
+                )))
+                .arg(SHIELDED.def().help(wrap!(
+                    "Discover the spending keys of the shielded pool that own \
+                     notes instead of the transparent keys."
+                )))
+                .arg(ALIAS.def().help(wrap!(
+                    "The alias prefix of the discovered keys and addresses. \
+                     Each key is stored under the alias followed by its \
+                     derivation index."
+                )))
+                .arg(ALIAS_FORCE.def().help(wrap!(
+                    "Force overwrite the aliases if they already exist."
+                )))
+                .arg(GAP_LIMIT.def().help(wrap!(
+                    "The number of consecutive unused derivation indices after \
+                     which the discovery stops. The default is 20."
+                )))
+                .arg(UNSAFE_DONT_ENCRYPT.def().help(wrap!(
+                    "UNSAFE: Do not encrypt the discovered keys. Do not use \
+                     this for keys used in a live network."
+                )))
+                .arg(HD_PROMPT_BIP39_PASSPHRASE.def().help(wrap!(
+                    "Use an additional passphrase for HD-key derivation."
+                )))
+                .arg(BIRTHDAY.def().requires(SHIELDED.name).help(wrap!(
+                    "A block height before which the shielded keys were not \
+                     used. The notes are only scanned from this height."
+                )))
+                .arg(WITH_INDEXER.def().requires(SHIELDED.name).help(wrap!(
+                    "Address of a `namada-masp-indexer` live instance. If \
+                     present, the notes will be scanned using data retrieved \
+                     from the given indexer."
+                )))
+                .arg(MAX_CONCURRENT_FETCHES.def().help(wrap!(
+                    "Maximum number of fetch jobs that will ever execute \
+                     concurrently during the shielded sync."
+                )))
+                .arg(BLOCK_BATCH.def().help(wrap!(
+                    "Number of blocks fetched per concurrent fetch job. The \
+                     default is 10."
+                )))
+        }
+    }
+
     impl CliToSdk<GenIbcShieldingTransfer<SdkTypes>>
         for GenIbcShieldingTransfer<CliTypes>
     {
@@ -7256,6 +8293,84 @@ pub mod args {
         }
     }
 
+    impl Args for QueryNft<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = OWNER_OPT.parse(matches);
+            let token = TOKEN_OPT.parse(matches);
+            Self {
+                query,
+                owner,
+                token,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(
+                    OWNER_OPT
+                        .def()
+                        .help(wrap!("List the NFTs owned by this address.")),
+                )
+                .arg(TOKEN_OPT.def().help(wrap!(
+                    "The IBC token address of the NFT to show the metadata \
+                     of."
+                )))
+                .group(
+                    ArgGroup::new("nft_query")
+                        .args([OWNER_OPT.name, TOKEN_OPT.name])
+                        .required(true),
+                )
+        }
+    }
+
+    impl CliToSdk<QueryNft<SdkTypes>> for QueryNft<CliTypes> {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<QueryNft<SdkTypes>, Self::Error> {
+            let query = self.query.to_sdk(ctx)?;
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            Ok(QueryNft::<SdkTypes> {
+                query,
+                owner: self.owner.map(|owner| chain_ctx.get(&owner)),
+                token: self.token.map(|token| chain_ctx.get(&token)),
+            })
+        }
+    }
+
+    impl Args for QueryNftClass<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let class_id = CLASS_ID_OPT.parse(matches);
+            Self { query, class_id }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(CLASS_ID_OPT.def().help(wrap!(
+                    "The NFT class ID, including its IBC trace. All the \
+                     classes are listed if unset."
+                )))
+        }
+    }
+
+    impl CliToSdk<QueryNftClass<SdkTypes>> for QueryNftClass<CliTypes> {
+        type Error = std::convert::Infallible;
+
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> Result<QueryNftClass<SdkTypes>, Self::Error> {
+            Ok(QueryNftClass::<SdkTypes> {
+                query: self.query.to_sdk(ctx)?,
+                class_id: self.class_id,
+            })
+        }
+    }
+
     impl Args for QueryEffNativeSupply<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
@@ -7473,6 +8588,7 @@ pub mod args {
                 memo: self.memo,
                 use_device: self.use_device,
                 device_transport: self.device_transport,
+                remote_signer: self.remote_signer,
             })
         }
     }
@@ -7610,6 +8726,17 @@ pub mod args {
                     ))
                     .conflicts_with(DRY_RUN_TX.name),
             )
+            .arg(
+                REMOTE_SIGNER
+                    .def()
+                    .help(wrap!(
+                        "Sign the transaction with the keys that are not in \
+                         the wallet using the remote signer at the given \
+                         address, either \"remote://<host>:<port>\" or \
+                         \"remote:///<socket path>\"."
+                    ))
+                    .conflicts_with_all([DRY_RUN_TX.name, USE_DEVICE.name]),
+            )
             .arg(
                 MEMO_OPT
                     .def()
@@ -7652,6 +8779,7 @@ pub mod args {
                 }
             };
             let device_transport = DEVICE_TRANSPORT.parse(matches);
+            let remote_signer = REMOTE_SIGNER.parse(matches);
             Self {
                 dry_run,
                 dry_run_wrapper,
@@ -7677,6 +8805,7 @@ pub mod args {
                 memo,
                 use_device,
                 device_transport,
+                remote_signer,
             }
         }
     }
@@ -8210,6 +9339,64 @@ pub mod args {
         }
     }
 
+    impl Args for ServeRemoteSigner {
+        fn parse(matches: &ArgMatches) -> Self {
+            let address = REMOTE_SIGNER_ADDRESS.parse(matches);
+            let aliases = ALIAS_MANY.parse(matches);
+            Self { address, aliases }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(REMOTE_SIGNER_ADDRESS.def().help(wrap!(
+                "The address to serve the signing requests on, either \
+                 \"remote://<host>:<port>\" with a loopback host or \
+                 \"remote:///<socket path>\"."
+            )))
+            .arg(ALIAS_MANY.def().help(wrap!(
+                "The aliases of the keys to sign with, separated by commas."
+            )))
+        }
+    }
+
+    impl Args for WalletBackup {
+        fn parse(matches: &ArgMatches) -> Self {
+            let file_path = FILE_PATH.parse(matches);
+            Self { file_path }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                FILE_PATH
+                    .def()
+                    .help(wrap!("Path to the backup file to write.")),
+            )
+        }
+    }
+
+    impl Args for WalletRestore {
+        fn parse(matches: &ArgMatches) -> Self {
+            let file_path = FILE_PATH.parse(matches);
+            let alias_force = ALIAS_FORCE.parse(matches);
+            Self {
+                file_path,
+                alias_force,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                FILE_PATH
+                    .def()
+                    .help(wrap!("Path to the backup file to restore.")),
+            )
+            .arg(ALIAS_FORCE.def().help(wrap!(
+                "Replace the entries of the wallet whose aliases conflict \
+                 with the backup's, instead of asking how to resolve each \
+                 conflict."
+            )))
+        }
+    }
+
     impl Args for KeyImport {
         fn parse(matches: &ArgMatches) -> Self {
             let file_path = FILE_PATH.parse(matches);
@@ -8949,6 +10136,9 @@ pub enum NamadaRelayer {
     EthBridgePoolWithCtx(Box<(cmds::EthBridgePoolWithCtx, Context)>),
     EthBridgePoolWithoutCtx(cmds::EthBridgePoolWithoutCtx),
     ValidatorSet(cmds::ValidatorSet),
+    /// The relayed command along with the contexts of this chain and of the
+    /// counterparty chain
+    IbcRelay(Box<(cmds::IbcRelay, Context, Context)>),
 }
 
 pub fn namada_relayer_cli() -> Result<NamadaRelayer> {
@@ -8971,6 +10161,37 @@ pub fn namada_relayer_cli() -> Result<NamadaRelayer> {
             cmds::NamadaRelayer::ValidatorSet(sub_cmd) => {
                 Ok(NamadaRelayer::ValidatorSet(sub_cmd))
             }
+            cmds::NamadaRelayer::IbcRelay(sub_cmd) => {
+                let global_args = args::Global::parse(&matches);
+                let counterparty_chain_id =
+                    sub_cmd.0.counterparty_tx.chain_id.clone().expect(
+                        "The counterparty chain ID is a required argument",
+                    );
+                let context = Context::new::<StdIo>(global_args.clone())?;
+                let counterparty_context =
+                    Context::new::<StdIo>(args::Global {
+                        chain_id: Some(counterparty_chain_id.clone()),
+                        ..global_args
+                    })?;
+                // The chain ID from the environment takes precedence over the
+                // global argument, so make sure it hasn't overridden the
+                // counterparty's
+                let loaded_chain_id = counterparty_context
+                    .chain
+                    .as_ref()
+                    .map(|chain| &chain.config.ledger.chain_id);
+                if loaded_chain_id != Some(&counterparty_chain_id) {
+                    return Err(color_eyre::eyre::eyre!(
+                        "Failed to load the context of the counterparty chain \
+                         {counterparty_chain_id}"
+                    ));
+                }
+                Ok(NamadaRelayer::IbcRelay(Box::new((
+                    sub_cmd,
+                    context,
+                    counterparty_context,
+                ))))
+            }
         },
         None => {
             let mut app = app;
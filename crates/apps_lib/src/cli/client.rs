@@ -124,6 +124,18 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::submit_update_account(&namada, args).await?;
                     }
+                    Sub::TxUpdateVpParams(TxUpdateVpParams(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_update_vp_params(&namada, args).await?;
+                    }
                     Sub::TxInitAccount(TxInitAccount(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -361,6 +373,40 @@ impl CliApi {
                         )
                         .await?;
                     }
+                    Sub::DiscoverKeys(DiscoverKeys(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        if args.shielded {
+                            let chain_ctx = ctx.take_chain_or_exit();
+                            let mut client = Some(client);
+                            let make_client = || {
+                                client.take().unwrap_or_else(|| {
+                                    C::from_tendermint_address(&ledger_address)
+                                })
+                            };
+                            crate::client::masp::discover_spending_keys(
+                                chain_ctx,
+                                make_client,
+                                args,
+                                &io,
+                            )
+                            .await?;
+                        } else {
+                            let namada = ctx.to_sdk(client, io);
+                            rpc::discover_transparent_keys(&namada, args).await;
+                            namada
+                                .wallet()
+                                .await
+                                .save()
+                                .unwrap_or_else(|err| eprintln!("{}", err));
+                        }
+                    }
                     Sub::GenIbcShieldingTransfer(GenIbcShieldingTransfer(
                         args,
                     )) => {
@@ -396,6 +442,27 @@ impl CliApi {
                             "The Namada Ethereum bridge is disabled"
                         );
                     }
+                    #[cfg(feature = "namada-eth-bridge")]
+                    Sub::CancelEthBridgePoolTransfer(args) => {
+                        let args = args.0;
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_cancel_bridge_pool_tx(&namada, args).await?;
+                    }
+                    #[cfg(not(feature = "namada-eth-bridge"))]
+                    Sub::CancelEthBridgePoolTransfer(_) => {
+                        display_line!(
+                            &io,
+                            "The Namada Ethereum bridge is disabled"
+                        );
+                    }
                     Sub::TxUnjailValidator(TxUnjailValidator(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -662,6 +729,30 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_total_supply(&namada, args).await;
                     }
+                    Sub::QueryNft(QueryNft(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_nft(&namada, args).await;
+                    }
+                    Sub::QueryNftClass(QueryNftClass(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_nft_class(&namada, args).await;
+                    }
                     Sub::QueryEffNativeSupply(QueryEffNativeSupply(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -796,6 +887,18 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_account(&namada, args).await;
                     }
+                    Sub::QueryTxHistory(QueryTxHistory(args)) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.query.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_tx_history(&namada, args).await;
+                    }
                     Sub::SignTx(SignTx(args)) => {
                         let chain_ctx = ctx.borrow_mut_chain_or_exit();
                         let ledger_address =
@@ -808,6 +911,34 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::sign_tx(&namada, args).await?;
                     }
+                    Sub::SignSession(SignSession::Add(SignSessionAdd(
+                        args,
+                    ))) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        tx::sign_session_add(&namada, args).await?;
+                    }
+                    Sub::SignSession(SignSession::Finalize(
+                        SignSessionFinalize(args),
+                    )) => {
+                        let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                        let ledger_address =
+                            chain_ctx.get(&args.tx.ledger_address);
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(&ledger_address)
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx)?;
+                        let namada = ctx.to_sdk(client, io);
+                        tx::sign_session_finalize(&namada, args).await?;
+                    }
                 }
             }
             cli::NamadaClient::WithoutContext(cmd_box) => {
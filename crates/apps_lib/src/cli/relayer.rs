@@ -1,12 +1,50 @@
 use color_eyre::eyre::Result;
+use namada_sdk::ibc_relayer;
 use namada_sdk::io::Io;
 
 use crate::cli;
 use crate::cli::api::{CliApi, CliClient};
 
 impl CliApi {
-    #[cfg(not(feature = "namada-eth-bridge"))]
     pub async fn handle_relayer_command<C>(
+        client: Option<C>,
+        cmd: cli::NamadaRelayer,
+        io: impl Io + Clone,
+    ) -> Result<()>
+    where
+        C: CliClient,
+    {
+        match cmd {
+            cli::NamadaRelayer::IbcRelay(boxed) => {
+                let (cli::cmds::IbcRelay(args), mut ctx, mut counterparty_ctx) =
+                    *boxed;
+                let chain_ctx = ctx.borrow_mut_chain_or_exit();
+                let ledger_address = chain_ctx.get(&args.tx.ledger_address);
+                let client = client.unwrap_or_else(|| {
+                    C::from_tendermint_address(&ledger_address)
+                });
+                client.wait_until_node_is_synced(&io).await?;
+                let counterparty_chain_ctx =
+                    counterparty_ctx.borrow_mut_chain_or_exit();
+                let counterparty_ledger_address = counterparty_chain_ctx
+                    .get(&args.counterparty_tx.ledger_address);
+                let counterparty_client =
+                    C::from_tendermint_address(&counterparty_ledger_address);
+                counterparty_client.wait_until_node_is_synced(&io).await?;
+                let args = args.to_sdk(&mut ctx, &mut counterparty_ctx)?;
+                let namada = ctx.to_sdk(client, io.clone());
+                let counterparty =
+                    counterparty_ctx.to_sdk(counterparty_client, io);
+                ibc_relayer::relay_ibc_packets(&namada, &counterparty, args)
+                    .await?;
+                Ok(())
+            }
+            cmd => Self::handle_eth_relayer_command(client, cmd, io).await,
+        }
+    }
+
+    #[cfg(not(feature = "namada-eth-bridge"))]
+    async fn handle_eth_relayer_command<C>(
         _client: Option<C>,
         _cmd: cli::NamadaRelayer,
         io: impl Io,
@@ -21,7 +59,7 @@ impl CliApi {
     }
 
     #[cfg(feature = "namada-eth-bridge")]
-    pub async fn handle_relayer_command<C>(
+    async fn handle_eth_relayer_command<C>(
         client: Option<C>,
         cmd: cli::NamadaRelayer,
         io: impl Io,
@@ -106,6 +144,15 @@ impl CliApi {
                     client.wait_until_node_is_synced(&io).await?;
                     bridge_pool::query_relay_progress(&client, &io).await?;
                 }
+                EthBridgePoolWithoutCtx::QueryFees(QueryBridgePoolFees(
+                    query,
+                )) => {
+                    let client = client.unwrap_or_else(|| {
+                        C::from_tendermint_address(&query.ledger_address)
+                    });
+                    client.wait_until_node_is_synced(&io).await?;
+                    bridge_pool::query_bridge_pool_fees(&client, &io).await?;
+                }
             },
             cli::NamadaRelayer::ValidatorSet(sub) => match sub {
                 ValidatorSet::BridgeValidatorSet(BridgeValidatorSet(args)) => {
@@ -159,6 +206,11 @@ impl CliApi {
                     .await?;
                 }
             },
+            cli::NamadaRelayer::IbcRelay(_) => {
+                return Err(color_eyre::eyre::eyre!(
+                    "The IBC relay command is not an Ethereum bridge command"
+                ));
+            }
         }
         Ok(())
     }
@@ -2,6 +2,7 @@
 
 use std::fs::File;
 use std::io::{self, Write};
+use std::path::PathBuf;
 use std::str::FromStr;
 
 use borsh::BorshDeserialize;
@@ -15,8 +16,10 @@ use namada_sdk::address::{Address, DecodeError};
 use namada_sdk::io::{display_line, edisplay_line, Io};
 use namada_sdk::key::*;
 use namada_sdk::masp::find_valid_diversifier;
+use namada_sdk::wallet::alias::Alias;
 use namada_sdk::wallet::{
-    DecryptionError, DerivationPath, DerivationPathError, FindKeyError, Wallet,
+    ConfirmationResponse, DecryptionError, DerivationPath, DerivationPathError,
+    FindKeyError, Wallet, WalletIo,
 };
 use rand_core::OsRng;
 
@@ -26,8 +29,10 @@ use crate::cli::args::CliToSdk;
 use crate::cli::{args, cmds, Context};
 use crate::client::utils::PRE_GENESIS_DIR;
 use crate::tendermint_node::validator_key_to_json;
+use crate::wallet::backup::{restore_files, Backup, WALLET_FILE};
 use crate::wallet::{
-    self, read_and_confirm_encryption_password, CliWalletUtils, WalletTransport,
+    self, read_and_confirm_encryption_password, CliWalletUtils, LocalSigner,
+    WalletTransport,
 };
 
 impl CliApi {
@@ -64,6 +69,21 @@ impl CliApi {
             cmds::NamadaWallet::KeyAddrRemove(
                 cmds::WalletRemoveKeyAddress(args),
             ) => key_address_remove(ctx, io, args),
+            cmds::NamadaWallet::ServeSigner(cmds::WalletServeSigner(args)) => {
+                serve_signer(ctx, io, args).await
+            }
+            cmds::NamadaWallet::Encrypt(cmds::WalletEncrypt) => {
+                store_encrypt(ctx, io)
+            }
+            cmds::NamadaWallet::Decrypt(cmds::WalletDecrypt) => {
+                store_decrypt(ctx, io)
+            }
+            cmds::NamadaWallet::Backup(cmds::WalletBackup(args)) => {
+                wallet_backup(ctx, io, args)
+            }
+            cmds::NamadaWallet::Restore(cmds::WalletRestore(args)) => {
+                wallet_restore(ctx, io, args)
+            }
             cmds::NamadaWallet::PayAddrGen(cmds::WalletGenPaymentAddress(
                 args,
             )) => {
@@ -1268,6 +1288,216 @@ fn key_export(
         })
 }
 
+/// Serve the signing requests of the remote signer protocol with the given
+/// keys
+async fn serve_signer(
+    ctx: Context,
+    io: &impl Io,
+    args::ServeRemoteSigner { address, aliases }: args::ServeRemoteSigner,
+) {
+    let mut wallet = load_wallet(ctx);
+    let keys = aliases
+        .iter()
+        .map(|alias| {
+            wallet
+                .find_secret_key(alias.to_lowercase(), None)
+                .unwrap_or_else(|err| {
+                    edisplay_line!(io, "{}", err);
+                    cli::safe_exit(1)
+                })
+        })
+        .collect::<Vec<_>>();
+    for key in &keys {
+        display_line!(io, "Signing with public key {}", key.ref_to());
+    }
+    display_line!(io, "Serving signing requests on {}", address);
+    if let Err(err) = LocalSigner::new(keys).serve(&address).await {
+        edisplay_line!(io, "The remote signer failed: {}", err);
+        cli::safe_exit(1)
+    }
+}
+
+/// Encrypt the whole wallet store at rest
+fn store_encrypt(ctx: Context, io: &impl Io) {
+    let mut wallet = load_wallet(ctx);
+    if wallet.is_store_encrypted() {
+        edisplay_line!(io, "The wallet is already encrypted.");
+        cli::safe_exit(1)
+    }
+    let password = CliWalletUtils::read_password(true, None);
+    wallet.set_store_password(Some(password));
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+    display_line!(io, "The wallet is now encrypted.");
+}
+
+/// Decrypt the whole wallet store at rest
+fn store_decrypt(ctx: Context, io: &impl Io) {
+    let mut wallet = load_wallet(ctx);
+    if !wallet.is_store_encrypted() {
+        edisplay_line!(io, "The wallet is not encrypted.");
+        cli::safe_exit(1)
+    }
+    wallet.set_store_password(None);
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+    display_line!(io, "The wallet is now stored in plaintext.");
+}
+
+/// Write an encrypted backup of the wallets and the shielded context
+fn wallet_backup(
+    ctx: Context,
+    io: &impl Io,
+    args::WalletBackup { file_path }: args::WalletBackup,
+) {
+    let chain_id = ctx
+        .chain
+        .as_ref()
+        .map(|chain| chain.config.ledger.chain_id.clone());
+    let backup = Backup::collect(&ctx.global_args.base_dir, chain_id.as_ref())
+        .unwrap_or_else(|err| {
+            edisplay_line!(io, "{}", err);
+            cli::safe_exit(1)
+        });
+    let password = CliWalletUtils::read_password(true, Some("the backup"));
+    std::fs::write(&file_path, backup.encrypt(&password)).unwrap_or_else(
+        |err| {
+            edisplay_line!(io, "Failed to write the backup: {}", err);
+            cli::safe_exit(1)
+        },
+    );
+    display_line!(
+        io,
+        "Backed up {} file(s) of the chain {} and {} pre-genesis file(s) to \
+         {}.",
+        backup.chain_files.len(),
+        chain_id.map(|id| id.to_string()).unwrap_or_default(),
+        backup.pre_genesis_files.len(),
+        file_path
+    );
+}
+
+/// Restore a backup of the wallets and the shielded context
+fn wallet_restore(
+    mut ctx: Context,
+    io: &impl Io,
+    args::WalletRestore {
+        file_path,
+        alias_force,
+    }: args::WalletRestore,
+) {
+    let data =
+        std::fs::read(&file_path).unwrap_or_else(|err| restore_failed(io, err));
+    let password = CliWalletUtils::read_password(false, Some("the backup"));
+    let mut backup = Backup::decrypt(&data, &password)
+        .unwrap_or_else(|err| restore_failed(io, err));
+
+    let base_dir = ctx.global_args.base_dir.clone();
+    let restored_files = restore_files(
+        &base_dir.join(PRE_GENESIS_DIR),
+        &backup.pre_genesis_files,
+    )
+    .unwrap_or_else(|err| restore_failed(io, err));
+    display_restored_files(io, restored_files);
+
+    let Some(backup_chain_id) = backup.chain_id.take() else {
+        return;
+    };
+    let Some(chain_ctx) = ctx.chain.as_mut() else {
+        edisplay_line!(
+            io,
+            "No chain is configured, the wallet of the chain {} is not \
+             restored. Join a network first.",
+            backup_chain_id
+        );
+        cli::safe_exit(1)
+    };
+    let chain_id = chain_ctx.config.ledger.chain_id.clone();
+
+    // Load the backed up wallet from a temporary directory, decrypting it if
+    // it was encrypted at rest
+    let mut backup_store = match backup.chain_files.remove(WALLET_FILE) {
+        Some(wallet_file) => {
+            let tmp_dir = tempfile::tempdir()
+                .unwrap_or_else(|err| restore_failed(io, err));
+            std::fs::write(tmp_dir.path().join(WALLET_FILE), wallet_file.data)
+                .unwrap_or_else(|err| restore_failed(io, err));
+            let mut backup_wallet = wallet::load(tmp_dir.path())
+                .unwrap_or_else(|err| restore_failed(io, err));
+            std::mem::take(backup_wallet.store_mut())
+        }
+        None => Default::default(),
+    };
+
+    // The shielded context and the validator data only hold for the chain
+    // they were backed up from
+    let validator_data = backup_store.take_validator_data();
+    if backup_chain_id == chain_id {
+        let restored_files = restore_files(
+            &base_dir.join(chain_id.as_str()),
+            &backup.chain_files,
+        )
+        .unwrap_or_else(|err| restore_failed(io, err));
+        display_restored_files(io, restored_files);
+        let store = chain_ctx.wallet.store_mut();
+        if let Some(data) = validator_data {
+            if store.get_validator_data().is_none() {
+                store.add_validator_data(data.address, data.keys);
+                display_line!(io, "Restored the validator data");
+            }
+        }
+    } else {
+        display_line!(
+            io,
+            "The backup is of the chain {}, not of {}. Only its keys and \
+             addresses are restored.",
+            backup_chain_id,
+            chain_id
+        );
+    }
+
+    let resolve_conflict = |alias: &Alias| {
+        if alias_force {
+            ConfirmationResponse::Replace
+        } else {
+            CliWalletUtils::show_overwrite_confirmation(
+                alias,
+                "an entry of the backup",
+            )
+        }
+    };
+    chain_ctx
+        .wallet
+        .store_mut()
+        .extend_resolving_conflicts(backup_store, resolve_conflict);
+    chain_ctx
+        .wallet
+        .save()
+        .unwrap_or_else(|err| restore_failed(io, err));
+    display_line!(io, "Restored the keys and addresses of the backup.");
+}
+
+/// Display the files written and skipped by a restore
+fn display_restored_files(
+    io: &impl Io,
+    (restored, skipped): (Vec<PathBuf>, Vec<PathBuf>),
+) {
+    for path in restored {
+        display_line!(io, "Restored {}", path.display());
+    }
+    for path in skipped {
+        display_line!(io, "Skipped the existing file {}", path.display());
+    }
+}
+
+/// Exit after a failure to restore a backup
+fn restore_failed(io: &impl Io, err: impl std::fmt::Display) -> ! {
+    edisplay_line!(io, "Failed to restore the backup: {}", err);
+    cli::safe_exit(1)
+}
+
 /// Convert a consensus key to tendermint validator key in json format
 fn key_convert(
     ctx: Context,
@@ -1,16 +1,22 @@
 use std::time::Duration;
 
 use color_eyre::owo_colors::OwoColorize;
-use namada_sdk::args::ShieldedSync;
+use namada_sdk::args::{DiscoverKeys, ShieldedSync};
 use namada_sdk::control_flow::install_shutdown_signal;
 use namada_sdk::error::Error;
 #[cfg(any(test, feature = "testing"))]
 use namada_sdk::io::DevNullProgressBar;
 use namada_sdk::io::{display, display_line, Client, Io, MaybeSend, MaybeSync};
+use namada_sdk::masp::utils::RetryStrategy;
 use namada_sdk::masp::{
     IndexerMaspClient, LedgerMaspClient, MaspLocalTaskEnv, ShieldedContext,
     ShieldedSyncConfig, ShieldedUtils,
 };
+use namada_sdk::wallet::discovery::{GapLimitScan, HdKeyDeriver};
+use namada_sdk::wallet::DatedKeypair;
+
+use crate::cli::context::ChainContext;
+use crate::wallet::{read_and_confirm_encryption_password, CliWalletUtils};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn syncing<
@@ -148,3 +154,100 @@ pub async fn syncing<
 
     Ok(shielded)
 }
+
+/// Discover the spending keys derived from a mnemonic code that own notes,
+/// syncing the shielded context with the viewing keys of the accounts until
+/// the gap limit, and add them to the wallet. A client is made for every
+/// shielded sync.
+pub async fn discover_spending_keys<C, IO>(
+    mut chain_ctx: ChainContext,
+    mut make_client: impl FnMut() -> C,
+    args: DiscoverKeys,
+    io: &IO,
+) -> Result<(), Error>
+where
+    C: Client + Send + Sync + 'static,
+    IO: Io + Send + Sync,
+{
+    let deriver = HdKeyDeriver::read_mnemonic_code::<CliWalletUtils>(
+        args.prompt_bip39_passphrase,
+    )
+    .ok_or_else(|| {
+        Error::Other("Failed to read the mnemonic code".to_string())
+    })?;
+    let password =
+        read_and_confirm_encryption_password(args.unsafe_dont_encrypt);
+    let mut shielded = ShieldedContext::new(chain_ctx.shielded);
+    let mut scan = GapLimitScan::new(args.gap_limit);
+    loop {
+        let accounts = scan.next_batch();
+        if accounts.is_empty() {
+            break;
+        }
+        let keys: Vec<_> = accounts
+            .map(|account| (account, deriver.spending_key(account)))
+            .collect();
+        let sync_args = ShieldedSync {
+            ledger_address: args.query.ledger_address.clone(),
+            last_query_height: None,
+            spending_keys: vec![],
+            viewing_keys: keys
+                .iter()
+                .map(|(_, (_, spend_key))| {
+                    DatedKeypair::new(spend_key.to_viewing_key(), args.birthday)
+                })
+                .collect(),
+            with_indexer: args.with_indexer.clone(),
+            wait_for_last_query_height: false,
+            max_concurrent_fetches: args.max_concurrent_fetches,
+            block_batch_size: args.block_batch_size,
+            retry_strategy: RetryStrategy::Forever,
+        };
+        shielded = syncing(shielded, make_client(), sync_args, io).await?;
+
+        for (account, (path, spend_key)) in keys {
+            let vk = spend_key.to_viewing_key().as_viewing_key();
+            let owns_notes = shielded
+                .pos_map
+                .get(&vk)
+                .is_some_and(|notes| !notes.is_empty());
+            if !owns_notes {
+                continue;
+            }
+            scan.mark_used(account);
+            let alias = chain_ctx.wallet.insert_spending_key(
+                format!("{}-{}", args.alias, account),
+                args.alias_force,
+                spend_key,
+                args.birthday,
+                password.clone(),
+                Some(path.clone()),
+            );
+            match alias {
+                Some(alias) => display_line!(
+                    io,
+                    "Discovered a spending key at {} with alias \"{}\"",
+                    path,
+                    alias
+                ),
+                None => display_line!(
+                    io,
+                    "Discovered a spending key at {}, but it was not added to \
+                     the wallet",
+                    path
+                ),
+            }
+        }
+    }
+    chain_ctx
+        .wallet
+        .save()
+        .map_err(|err| Error::Other(err.to_string()))?;
+    display_line!(
+        io,
+        "Discovered {} spending key(s) within a gap limit of {}.",
+        scan.used().len(),
+        args.gap_limit
+    );
+    Ok(())
+}
@@ -14,6 +14,7 @@ use masp_primitives::transaction::components::I128Sum;
 use masp_primitives::zip32::ExtendedFullViewingKey;
 use namada_core::masp::{BalanceOwner, MaspEpoch};
 use namada_core::token::Amount;
+use namada_sdk::account::{VestingSchedule, VpParams};
 use namada_sdk::address::{Address, InternalAddress, MASP};
 use namada_sdk::chain::{BlockHeight, Epoch};
 use namada_sdk::collections::{HashMap, HashSet};
@@ -29,6 +30,8 @@ use namada_sdk::governance::storage::proposal::{
 use namada_sdk::governance::utils::{ProposalVotes, VotePower};
 use namada_sdk::governance::ProposalVote;
 use namada_sdk::hash::Hash;
+use namada_sdk::ibc::trace::{ibc_token, ibc_token_for_nft};
+use namada_sdk::ibc::{NftClass, NftMetadata};
 use namada_sdk::io::{display, display_line, edisplay_line, Client, Io};
 use namada_sdk::key::*;
 use namada_sdk::masp::shielded_wallet::ShieldedApi;
@@ -42,22 +45,25 @@ use namada_sdk::proof_of_stake::types::{
     ValidatorStateInfo, WeightedValidator,
 };
 use namada_sdk::proof_of_stake::{OwnedPosParams, PosParams};
-use namada_sdk::queries::RPC;
+use namada_sdk::queries::{RPC, TX_HISTORY_PAGE_SIZE};
 use namada_sdk::rpc::{
     self, enriched_bonds_and_unbonds, format_denominated_amount, query_epoch,
     TxResponse,
 };
 use namada_sdk::storage::BlockResults;
 use namada_sdk::tendermint_rpc::endpoint::status;
+use namada_sdk::time::DateTimeUtc;
 use namada_sdk::token::{
     DenominatedAmount, MaspDigitPos, NATIVE_MAX_DECIMAL_PLACES,
 };
 use namada_sdk::tx::display_batch_resp;
+use namada_sdk::wallet::discovery::{GapLimitScan, HdKeyDeriver};
 use namada_sdk::wallet::AddressVpType;
 use namada_sdk::{error, state as storage, token, Namada};
 
 use crate::cli::{self, args};
 use crate::tendermint::merkle::proof::ProofOps;
+use crate::wallet::read_and_confirm_encryption_password;
 
 /// Query the status of a given transaction.
 ///
@@ -602,11 +608,200 @@ pub async fn query_account(context: &impl Namada, args: args::QueryAccount) {
         for (public_key, _) in account.public_keys_map.pk_to_idx {
             display_line!(context.io(), "- {}", public_key);
         }
+        if let Some(params) = account.vp_params {
+            display_vp_params(context, &params);
+        }
+        if let Some(schedule) = account.vesting {
+            display_vesting_schedule(context, &schedule);
+        }
     } else {
         display_line!(context.io(), "No account exists for {}", args.owner);
     }
 }
 
+pub async fn query_tx_history(
+    context: &impl Namada,
+    args: args::QueryTxHistory,
+) {
+    let entries = rpc::query_tx_history(
+        context.client(),
+        &args.owner,
+        args.from_height,
+        args.from_tx_index,
+    )
+    .await
+    .unwrap();
+    if entries.is_empty() {
+        display_line!(
+            context.io(),
+            "No transactions found for {} from height {}. The node may not \
+             maintain the transaction history index.",
+            args.owner,
+            args.from_height
+        );
+    }
+    for (height, tx_index, hash) in &entries {
+        display_line!(context.io(), "{height} {tx_index} {hash}");
+    }
+    if entries.len() == TX_HISTORY_PAGE_SIZE {
+        if let Some((height, tx_index, _)) = entries.last() {
+            let (next_height, next_tx_index) = match tx_index.checked_add(1) {
+                Some(next_tx_index) => (*height, next_tx_index),
+                None => (height.next_height(), 0),
+            };
+            display_line!(
+                context.io(),
+                "More transactions may follow from `--from-height {} \
+                 --from-tx-index {}`.",
+                next_height,
+                next_tx_index
+            );
+        }
+    }
+}
+
+/// Display the parameters of an account's VP
+fn display_vp_params(context: &impl Namada, params: &VpParams) {
+    let io = context.io();
+    for (token, limit) in &params.spending_limits {
+        display_line!(
+            io,
+            "Spending limit per transaction of {}: {}",
+            token,
+            limit.to_string_native()
+        );
+    }
+    for (token, cap) in &params.daily_caps {
+        display_line!(io, "Daily cap of {}: {}", token, cap.to_string_native());
+    }
+    for (token, limit) in &params.withdrawal_limits {
+        display_line!(
+            io,
+            "Spending limit of {}: {} per {} seconds",
+            token,
+            limit.amount.to_string_native(),
+            limit.window
+        );
+    }
+    if let Some(bypass_threshold) = params.bypass_threshold {
+        display_line!(
+            io,
+            "Spending limits bypass threshold: {}",
+            bypass_threshold
+        );
+    }
+    if let Some(time_lock) = params.time_lock {
+        display_line!(io, "Time locked until: {}", time_lock);
+    }
+    if !params.allowed_counterparties.is_empty() {
+        display_line!(io, "Allowed counterparties:");
+        for counterparty in &params.allowed_counterparties {
+            display_line!(io, "- {}", counterparty);
+        }
+    }
+}
+
+/// Display the vesting schedule of a vesting account, with its vested and
+/// locked amounts at the current time
+fn display_vesting_schedule(context: &impl Namada, schedule: &VestingSchedule) {
+    let io = context.io();
+    let now = DateTimeUtc::now();
+    display_line!(io, "Vesting schedule of {}:", schedule.token);
+    display_line!(io, "{:2}Amount: {}", "", schedule.amount.to_string_native());
+    display_line!(io, "{:2}Start: {}", "", schedule.start);
+    display_line!(io, "{:2}Cliff: {}", "", schedule.cliff);
+    display_line!(io, "{:2}End: {}", "", schedule.end);
+    display_line!(
+        io,
+        "{:2}Vested at {}: {}",
+        "",
+        now,
+        schedule.vested(now).to_string_native()
+    );
+    display_line!(
+        io,
+        "{:2}Locked at {}: {}",
+        "",
+        now,
+        schedule.locked(now).to_string_native()
+    );
+}
+
+/// Discover the transparent keys derived from a mnemonic code whose implicit
+/// accounts were used on chain, scanning the address indices until the gap
+/// limit, and add them to the wallet
+pub async fn discover_transparent_keys<N: Namada>(
+    context: &N,
+    args: args::DiscoverKeys,
+) {
+    let io = context.io();
+    let deriver = HdKeyDeriver::read_mnemonic_code::<N::WalletUtils>(
+        args.prompt_bip39_passphrase,
+    )
+    .unwrap_or_else(|| {
+        edisplay_line!(io, "Failed to read the mnemonic code.");
+        cli::safe_exit(1)
+    });
+    let password =
+        read_and_confirm_encryption_password(args.unsafe_dont_encrypt);
+    let native_token = context.native_token();
+    let mut scan = GapLimitScan::new(args.gap_limit);
+    loop {
+        let indices = scan.next_batch();
+        if indices.is_empty() {
+            break;
+        }
+        for index in indices {
+            let (path, sk) = deriver.transparent_key(args.scheme, index);
+            let address = Address::from(&sk.ref_to());
+            let is_used = rpc::is_implicit_account_used(
+                context.client(),
+                &address,
+                &native_token,
+            )
+            .await
+            .unwrap_or_else(|err| {
+                edisplay_line!(io, "Failed to query {}: {}", address, err);
+                cli::safe_exit(1)
+            });
+            if !is_used {
+                continue;
+            }
+            scan.mark_used(index);
+            let alias = context.wallet_mut().await.insert_keypair(
+                format!("{}-{}", args.alias, index),
+                args.alias_force,
+                sk,
+                password.clone(),
+                None,
+                Some(path.clone()),
+            );
+            match alias {
+                Some(alias) => display_line!(
+                    io,
+                    "Discovered the address {} at {} with alias \"{}\"",
+                    address,
+                    path,
+                    alias
+                ),
+                None => display_line!(
+                    io,
+                    "Discovered the address {} at {}, but it was not added \
+                     to the wallet",
+                    address,
+                    path
+                ),
+            }
+        }
+    }
+    display_line!(
+        io,
+        "Discovered {} used address(es) within a gap limit of {}.",
+        scan.used().len(),
+        args.gap_limit
+    );
+}
+
 pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
     let stewards = query_pgf_stewards(context.client()).await;
     let fundings = query_pgf_fundings(context.client()).await;
@@ -1453,6 +1648,92 @@ pub async fn query_total_supply<N: Namada>(
     );
 }
 
+/// Query IBC NFTs, either those owned by an address or a single one.
+pub async fn query_nft<N: Namada>(context: &N, args: args::QueryNft) {
+    if let Some(owner) = args.owner {
+        let tokens = unwrap_client_response::<N::Client, Vec<NftMetadata>>(
+            RPC.vp().nft().tokens(context.client(), &owner).await,
+        );
+        if tokens.is_empty() {
+            display_line!(context.io(), "No NFTs found for {owner}");
+        }
+        for metadata in tokens {
+            display_nft_metadata(context.io(), &metadata);
+        }
+    }
+    if let Some(token) = args.token {
+        let metadata = unwrap_client_response::<N::Client, Option<NftMetadata>>(
+            RPC.vp().nft().metadata(context.client(), &token).await,
+        );
+        match metadata {
+            Some(metadata) => display_nft_metadata(context.io(), &metadata),
+            None => {
+                display_line!(context.io(), "No NFT metadata found for {token}")
+            }
+        }
+    }
+}
+
+/// Query an IBC NFT class, or all the known ones.
+pub async fn query_nft_class<N: Namada>(
+    context: &N,
+    args: args::QueryNftClass,
+) {
+    let classes = match args.class_id {
+        Some(class_id) => {
+            let class = ibc_token(class_id.to_string());
+            let class = unwrap_client_response::<N::Client, Option<NftClass>>(
+                RPC.vp().nft().class(context.client(), &class).await,
+            );
+            match class {
+                Some(class) => vec![class],
+                None => {
+                    display_line!(
+                        context.io(),
+                        "No NFT class found for {class_id}"
+                    );
+                    return;
+                }
+            }
+        }
+        None => unwrap_client_response::<N::Client, Vec<NftClass>>(
+            RPC.vp().nft().classes(context.client()).await,
+        ),
+    };
+    if classes.is_empty() {
+        display_line!(context.io(), "No NFT classes found");
+    }
+    for class in classes {
+        display_line!(context.io(), "Class ID: {}", class.class_id);
+        if let Some(uri) = &class.class_uri {
+            display_line!(context.io(), "  URI: {uri}");
+        }
+        if let Some(data) = &class.class_data {
+            display_line!(context.io(), "  Data: {data}");
+        }
+    }
+}
+
+fn display_nft_metadata(io: &impl Io, metadata: &NftMetadata) {
+    display_line!(
+        io,
+        "Class ID: {}, Token ID: {}",
+        metadata.class_id,
+        metadata.token_id
+    );
+    display_line!(
+        io,
+        "  IBC token: {}",
+        ibc_token_for_nft(&metadata.class_id, &metadata.token_id)
+    );
+    if let Some(uri) = &metadata.token_uri {
+        display_line!(io, "  URI: {uri}");
+    }
+    if let Some(data) = &metadata.token_data {
+        display_line!(io, "  Data: {data}");
+    }
+}
+
 /// Query the effective total supply of the native token
 pub async fn query_effective_native_supply<N: Namada>(context: &N) {
     let native_supply = unwrap_client_response::<N::Client, token::Amount>(
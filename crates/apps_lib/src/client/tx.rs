@@ -15,6 +15,7 @@ use namada_sdk::governance::cli::onchain::{
 use namada_sdk::ibc::convert_masp_tx_to_ibc_memo;
 use namada_sdk::io::{display_line, edisplay_line, Io};
 use namada_sdk::key::*;
+use namada_sdk::remote_signer::{sign_with_external_signer, RemoteSigner};
 use namada_sdk::rpc::{InnerTxResult, TxBroadcastData, TxResponse};
 use namada_sdk::state::EPOCH_SWITCH_BLOCKS_DELAY;
 use namada_sdk::tx::data::compute_inner_tx_hash;
@@ -163,7 +164,8 @@ where
     Ok(tx)
 }
 
-// Sign the given transaction using a hardware wallet as a backup
+// Sign the given transaction using a hardware wallet or a remote signer as a
+// backup
 pub async fn sign<N: Namada>(
     context: &N,
     tx: &mut Tx,
@@ -185,6 +187,19 @@ pub async fn sign<N: Namada>(
                 with_hw_data,
             )
             .await?;
+    } else if let Some(addr) = &args.remote_signer {
+        // Sign with the keys that are not in the wallet using the remote
+        // signer as backup
+        let signer = RemoteSigner::new(addr.clone());
+        context
+            .sign(
+                tx,
+                args,
+                signing_data,
+                sign_with_external_signer::<RemoteSigner>,
+                &signer,
+            )
+            .await?;
     } else {
         // Otherwise sign without a backup procedure
         context
@@ -276,6 +291,32 @@ pub async fn submit_bridge_pool_tx<N: Namada>(
     Ok(())
 }
 
+pub async fn submit_cancel_bridge_pool_tx<N: Namada>(
+    namada: &N,
+    args: args::CancelEthereumBridgePool,
+) -> Result<(), error::Error> {
+    let (tx, signing_data) = args.clone().build(namada).await?;
+
+    if args.tx.dump_tx || args.tx.dump_wrapper_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx)?;
+    } else {
+        let owners = signing_data
+            .owner
+            .clone()
+            .map_or_else(Default::default, |owner| vec![owner]);
+        let refs: Vec<&Address> = owners.iter().collect();
+        batch_opt_reveal_pk_and_submit(
+            namada,
+            &args.tx,
+            &refs,
+            (tx, signing_data),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_custom<N: Namada>(
     namada: &N,
     args: args::TxCustom,
@@ -329,6 +370,26 @@ where
     Ok(())
 }
 
+pub async fn submit_update_vp_params<N: Namada>(
+    namada: &N,
+    args: args::TxUpdateVpParams,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada_sdk::io::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx || args.tx.dump_wrapper_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx)?;
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_init_account<N: Namada>(
     namada: &N,
     args: args::TxInitAccount,
@@ -749,6 +810,7 @@ pub async fn submit_init_validator(
             tx_code_path: tx_init_account_code_path,
             public_keys: account_keys,
             threshold,
+            vesting: None,
         },
     )
     .await?;
@@ -1196,6 +1258,197 @@ where
     Ok(())
 }
 
+/// Load a signing session from the given file
+fn load_signing_session(
+    path: &std::path::Path,
+) -> Result<signing::SigningSession, error::Error> {
+    let bytes = std::fs::read(path).map_err(|err| {
+        error::Error::Other(format!(
+            "Failed to read the signing session {}: {}",
+            path.display(),
+            err
+        ))
+    })?;
+    signing::SigningSession::try_from_json_bytes(&bytes).map_err(|err| {
+        error::Error::Other(format!(
+            "Couldn't decode the signing session {}: {}",
+            path.display(),
+            err
+        ))
+    })
+}
+
+/// Query the account of the owner of a signing session
+async fn signing_session_account<N: Namada>(
+    namada: &N,
+    owner: &Address,
+) -> Result<namada_sdk::account::Account, error::Error> {
+    namada_sdk::rpc::get_account_info(namada.client(), owner)
+        .await?
+        .ok_or_else(|| {
+            error::Error::from(error::TxSubmitError::InvalidAccount(
+                owner.encode(),
+            ))
+        })
+}
+
+pub async fn sign_session_add<N: Namada>(
+    namada: &N,
+    args::SignSessionAdd {
+        tx: tx_args,
+        session: session_path,
+        owner,
+    }: args::SignSessionAdd,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada_sdk::io::Client>::Error: std::fmt::Display,
+{
+    let mut session = load_signing_session(&session_path)?;
+
+    // Check the transaction before signing it
+    if let Some(chain_id) = &tx_args.chain_id {
+        if session.tx.header.chain_id != *chain_id {
+            return Err(error::Error::Other(format!(
+                "The transaction is for the chain {}, not for {}.",
+                session.tx.header.chain_id, chain_id
+            )));
+        }
+    }
+    if let Some(expiration) = session.tx.header.expiration {
+        if expiration < namada_sdk::time::DateTimeUtc::now() {
+            return Err(error::Error::Other(format!(
+                "The transaction expired at {}.",
+                expiration
+            )));
+        }
+    }
+    for signature in &session.signatures {
+        session.verify_signature(signature)?;
+    }
+
+    let summary =
+        signing::to_ledger_vector(&*namada.wallet().await, &session.tx).await?;
+    display_line!(namada.io(), "Transaction {}:", session.tx.raw_header_hash());
+    for line in &summary.output {
+        display_line!(namada.io(), "  {}", line);
+    }
+    display_line!(
+        namada.io(),
+        "Signed so far by {} key(s).",
+        session.signatures.len()
+    );
+
+    // Sign with the keys of the account that are held by the wallet
+    let account = signing_session_account(namada, &owner).await?;
+    let public_keys = if tx_args.signing_keys.is_empty() {
+        account.get_all_public_keys()
+    } else {
+        tx_args.signing_keys.clone()
+    };
+    let mut secret_keys = vec![];
+    {
+        let mut wallet = namada.wallet_mut().await;
+        for public_key in &public_keys {
+            if let Ok(secret_key) =
+                signing::find_key_by_pk(&mut wallet, &tx_args, public_key)
+            {
+                secret_keys.push(secret_key);
+            }
+        }
+    }
+    if secret_keys.is_empty() {
+        return Err(error::Error::Other(format!(
+            "None of the keys of the account {} is in the wallet.",
+            owner
+        )));
+    }
+    let signatures = session.tx.compute_section_signature(
+        &secret_keys,
+        &account.public_keys_map,
+        Some(owner.clone()),
+    );
+    for signature in &signatures {
+        display_line!(namada.io(), "Signed with {}", signature.pubkey);
+    }
+    session.add_signatures(signatures)?;
+
+    let out = File::create(&session_path)
+        .expect("Should be able to create the signing session file.");
+    session
+        .to_writer_json(out)
+        .expect("Session should be serializable and the file writeable.");
+    display_line!(
+        namada.io(),
+        "Signing session updated at {}. It now holds {} of the {} required \
+         signature(s).",
+        session_path.display(),
+        session.valid_signatures(&owner, &account.public_keys_map),
+        account.threshold
+    );
+
+    Ok(())
+}
+
+pub async fn sign_session_finalize<N: Namada>(
+    namada: &N,
+    args::SignSessionFinalize {
+        tx: tx_args,
+        session: session_path,
+        owner,
+    }: args::SignSessionFinalize,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada_sdk::io::Client>::Error: std::fmt::Display,
+{
+    let session = load_signing_session(&session_path)?;
+
+    // Check the threshold against the keys currently set on chain
+    let account = signing_session_account(namada, &owner).await?;
+    let valid_signatures =
+        session.valid_signatures(&owner, &account.public_keys_map);
+    if valid_signatures < account.threshold {
+        return Err(error::Error::from(
+            error::TxSubmitError::MissingSigningKeys(
+                account.threshold,
+                valid_signatures,
+            ),
+        ));
+    }
+    if tx_args.wrapper_fee_payer.is_none() {
+        return Err(error::Error::Other(
+            "The fee payer of the transaction must be given with \
+             `--gas-payer`."
+                .to_string(),
+        ));
+    }
+
+    let signatures = session
+        .signatures
+        .iter()
+        .map(serde_json::to_vec)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| {
+            error::Error::Encode(error::EncodingError::Serde(err.to_string()))
+        })?;
+    let serialized_tx = serde_json::to_vec(&session.tx).map_err(|err| {
+        error::Error::Encode(error::EncodingError::Serde(err.to_string()))
+    })?;
+    submit_custom(
+        namada,
+        args::TxCustom {
+            tx: args::Tx {
+                signatures,
+                ..tx_args
+            },
+            code_path: None,
+            data_path: None,
+            serialized_tx: Some(serialized_tx),
+            owner: Some(owner),
+        },
+    )
+    .await
+}
+
 pub async fn submit_reveal_pk<N: Namada>(
     namada: &N,
     args: args::RevealPk,
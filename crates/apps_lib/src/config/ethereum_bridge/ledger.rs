@@ -1,8 +1,12 @@
 //! Runtime configuration for a validator node.
+use std::num::NonZeroUsize;
+
 #[allow(unused_imports)]
 use namada_sdk::ethereum_events::EthereumEvent;
 use serde::{Deserialize, Serialize};
 
+use crate::config::Error;
+
 /// Default [Ethereum JSON-RPC](https://ethereum.org/en/developers/docs/apis/json-rpc/) endpoint used by the oracle
 pub const DEFAULT_ORACLE_RPC_ENDPOINT: &str = "http://127.0.0.1:8545";
 
@@ -13,8 +17,9 @@ pub const ORACLE_CHANNEL_BUFFER_SIZE: usize = 1000;
 /// The mode in which to run the Ethereum bridge.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Mode {
-    /// The oracle will listen to the Ethereum JSON-RPC endpoint as
-    /// specified in the `oracle_rpc_endpoint` setting.
+    /// The oracle will listen to the Ethereum JSON-RPC endpoints as
+    /// specified in the `oracle_rpc_endpoint` and
+    /// `oracle_rpc_extra_endpoints` settings.
     RemoteEndpoint,
     /// Instead of the oracle listening for events using an Ethereum
     /// JSON-RPC endpoint, an endpoint will be exposed by the ledger
@@ -33,6 +38,16 @@ pub struct Config {
     /// The Ethereum JSON-RPC endpoint that the Ethereum event oracle will use
     /// to listen for events from the Ethereum bridge smart contracts
     pub oracle_rpc_endpoint: String,
+    /// Additional Ethereum JSON-RPC endpoints the oracle will listen to.
+    /// Events are only forwarded to the ledger once enough endpoints
+    /// have agreed on them, as dictated by `oracle_rpc_quorum`.
+    #[serde(default)]
+    pub oracle_rpc_extra_endpoints: Vec<String>,
+    /// The number of Ethereum JSON-RPC endpoints that must agree on an
+    /// event before it is forwarded to the ledger. Defaults to a simple
+    /// majority of the configured endpoints.
+    #[serde(default)]
+    pub oracle_rpc_quorum: Option<NonZeroUsize>,
     /// The size of bounded channel between the Ethereum oracle and main
     /// ledger subprocesses. This is the number of Ethereum events that
     /// can be held in the channel. The default is 1000.
@@ -44,7 +59,34 @@ impl Default for Config {
         Self {
             mode: Mode::RemoteEndpoint,
             oracle_rpc_endpoint: DEFAULT_ORACLE_RPC_ENDPOINT.to_owned(),
+            oracle_rpc_extra_endpoints: vec![],
+            oracle_rpc_quorum: None,
             channel_buffer_size: ORACLE_CHANNEL_BUFFER_SIZE,
         }
     }
 }
+
+impl Config {
+    /// All the Ethereum JSON-RPC endpoints the oracle will listen to.
+    pub fn oracle_rpc_endpoints(&self) -> Vec<String> {
+        std::iter::once(&self.oracle_rpc_endpoint)
+            .chain(&self.oracle_rpc_extra_endpoints)
+            .cloned()
+            .collect()
+    }
+
+    /// Check that the configured quorum of Ethereum JSON-RPC endpoints can be
+    /// reached, i.e. that it does not exceed the number of endpoints.
+    pub fn validate(&self) -> Result<(), Error> {
+        let endpoints = self.oracle_rpc_extra_endpoints.len().saturating_add(1);
+        match self.oracle_rpc_quorum {
+            Some(quorum) if quorum.get() > endpoints => {
+                Err(Error::BadOracleRpcQuorum {
+                    quorum: quorum.get(),
+                    endpoints,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
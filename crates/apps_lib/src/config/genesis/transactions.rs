@@ -103,6 +103,7 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         memo: None,
         use_device,
         device_transport: DeviceTransport::default(),
+        remote_signer: None,
     }
 }
 
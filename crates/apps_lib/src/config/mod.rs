@@ -127,6 +127,12 @@ pub struct Shell {
     pub blocks_between_snapshots: Option<NonZeroU64>,
     /// Number of snapshots to keep
     pub snapshots_to_keep: Option<NonZeroU64>,
+    /// When set, the node maintains an index of the transactions that moved
+    /// the tokens of every transparent account, which can be queried for the
+    /// history of an account. Only the blocks processed while this is set are
+    /// indexed.
+    #[serde(default)]
+    pub tx_history_index: bool,
 }
 
 impl Ledger {
@@ -157,6 +163,7 @@ impl Ledger {
                 tendermint_mode: mode,
                 blocks_between_snapshots: None,
                 snapshots_to_keep: None,
+                tx_history_index: false,
             },
             cometbft: tendermint_config,
             ethereum_bridge: ethereum_bridge::ledger::Config::default(),
@@ -210,6 +217,11 @@ pub enum Error {
          {{protocol}}/{{ip}}/tcp/{{port}}/p2p/{{peerid}}"
     )]
     BadBootstrapPeerFormat(String),
+    #[error(
+        "The Ethereum oracle RPC quorum ({quorum}) exceeds the number of \
+         configured endpoints ({endpoints})"
+    )]
+    BadOracleRpcQuorum { quorum: usize, endpoints: usize },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -292,10 +304,13 @@ impl Config {
                     .separator("__"),
             );
 
-        let config = builder.build().map_err(Error::ReadError)?;
-        config
+        let config: Self = builder
+            .build()
+            .map_err(Error::ReadError)?
             .try_deserialize()
-            .map_err(Error::DeserializationError)
+            .map_err(Error::DeserializationError)?;
+        config.ledger.ethereum_bridge.validate()?;
+        Ok(config)
     }
 
     /// Generate configuration and write it to a file.
@@ -857,11 +872,12 @@ namespace = "cometbft"
 #[cfg(test)]
 mod tests {
     use std::env;
+    use std::num::NonZeroUsize;
 
     use namada_sdk::chain::ChainId;
     use tempfile::tempdir;
 
-    use super::{Config, DEFAULT_COMETBFT_CONFIG};
+    use super::{Config, Error, DEFAULT_COMETBFT_CONFIG};
     use crate::config::TendermintMode;
     use crate::tendermint_config::TendermintConfig;
 
@@ -890,4 +906,34 @@ mod tests {
 
         assert_eq!(config.ledger.cometbft.moniker.as_ref(), moniker);
     }
+    /// Check that a config with an unreachable Ethereum oracle RPC quorum is
+    /// rejected when it is read
+    #[test]
+    fn test_config_oracle_rpc_quorum() {
+        let base_dir = tempdir().unwrap();
+        let chain_id = ChainId("ChainyMcChainFace".to_owned());
+
+        let mut config = Config::generate(
+            base_dir.path(),
+            &chain_id,
+            TendermintMode::Validator,
+            true,
+        )
+        .unwrap();
+        config.ledger.ethereum_bridge.oracle_rpc_extra_endpoints =
+            vec!["http://127.0.0.1:8546".to_owned()];
+        config.ledger.ethereum_bridge.oracle_rpc_quorum = NonZeroUsize::new(2);
+        config.write(base_dir.path(), &chain_id, true).unwrap();
+        assert!(Config::read(base_dir.path(), &chain_id, None).is_ok());
+
+        config.ledger.ethereum_bridge.oracle_rpc_quorum = NonZeroUsize::new(3);
+        config.write(base_dir.path(), &chain_id, true).unwrap();
+        assert!(matches!(
+            Config::read(base_dir.path(), &chain_id, None),
+            Err(Error::BadOracleRpcQuorum {
+                quorum: 3,
+                endpoints: 2
+            })
+        ));
+    }
 }
@@ -0,0 +1,261 @@
+//! Versioned and encrypted backups of the wallet, the shielded context and
+//! the pre-genesis wallets of a base directory
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use namada_sdk::chain::ChainId;
+use namada_sdk::hash::Hash;
+use namada_sdk::wallet::{envelope, DecryptionError};
+use thiserror::Error;
+
+use crate::client::utils::PRE_GENESIS_DIR;
+
+/// The current version of the backup format
+pub const BACKUP_VERSION: u8 = 1;
+
+/// The prefix of the first line of a backup file, followed by its version
+const BACKUP_HEADER: &str = "namada-backup-v";
+
+/// The wallet file name in a chain directory
+pub const WALLET_FILE: &str = "wallet.toml";
+
+/// The files of a chain directory that are backed up
+const CHAIN_FILES: [&str; 3] =
+    [WALLET_FILE, "shielded.dat", "speculative_shielded.dat"];
+
+/// Errors of the backups
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum BackupError {
+    #[error("Failed to read or write a file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The file is not a Namada backup")]
+    NotABackup,
+    #[error(
+        "Unsupported backup version {0}, the latest supported version is \
+         {BACKUP_VERSION}"
+    )]
+    UnsupportedVersion(u8),
+    #[error("Failed to decrypt the backup: {0}")]
+    Decryption(DecryptionError),
+    #[error("Failed to decode the backup: {0}")]
+    Decoding(std::io::Error),
+    #[error("The backed up file {0} is corrupted")]
+    Corrupted(String),
+    #[error("The backed up file path {0} is invalid")]
+    InvalidPath(String),
+}
+
+/// A backed up file with the hash of its contents
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
+pub struct BackupFile {
+    /// The hash of the contents
+    pub hash: Hash,
+    /// The contents
+    pub data: Vec<u8>,
+}
+
+impl BackupFile {
+    /// Back up the given file contents
+    pub fn new(data: Vec<u8>) -> Self {
+        Self {
+            hash: Hash::sha256(&data),
+            data,
+        }
+    }
+}
+
+/// A backup of the files of a base directory
+#[derive(Debug, Default, BorshSerialize, BorshDeserialize)]
+pub struct Backup {
+    /// The chain of the backed up chain directory files, if any
+    pub chain_id: Option<ChainId>,
+    /// The files of the chain directory by their name
+    pub chain_files: BTreeMap<String, BackupFile>,
+    /// The files of the pre-genesis directory by their path relative to it
+    pub pre_genesis_files: BTreeMap<String, BackupFile>,
+}
+
+impl Backup {
+    /// Collect the wallet and the shielded context of the given chain, if
+    /// any, and the pre-genesis wallets of the base directory
+    pub fn collect(
+        base_dir: &Path,
+        chain_id: Option<&ChainId>,
+    ) -> Result<Self, BackupError> {
+        let mut backup = Self::default();
+        if let Some(chain_id) = chain_id {
+            let chain_dir = base_dir.join(chain_id.as_str());
+            for name in CHAIN_FILES {
+                let path = chain_dir.join(name);
+                if path.exists() {
+                    backup.chain_files.insert(
+                        name.to_string(),
+                        BackupFile::new(fs::read(path)?),
+                    );
+                }
+            }
+            backup.chain_id = Some(chain_id.clone());
+        }
+        let pre_genesis_dir = base_dir.join(PRE_GENESIS_DIR);
+        if pre_genesis_dir.exists() {
+            collect_toml_files(
+                &pre_genesis_dir,
+                &pre_genesis_dir,
+                &mut backup.pre_genesis_files,
+            )?;
+        }
+        Ok(backup)
+    }
+
+    /// Encrypt the backup with the given password
+    pub fn encrypt(&self, password: &str) -> Vec<u8> {
+        let mut data =
+            format!("{BACKUP_HEADER}{BACKUP_VERSION}\n").into_bytes();
+        data.extend(envelope::seal(&self.serialize_to_vec(), password));
+        data
+    }
+
+    /// Decrypt a backup with the given password and check the integrity of
+    /// its files
+    pub fn decrypt(data: &[u8], password: &str) -> Result<Self, BackupError> {
+        let header_len = data
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .ok_or(BackupError::NotABackup)?;
+        let (header, sealed) = data.split_at(header_len);
+        let version = std::str::from_utf8(header)
+            .ok()
+            .and_then(|header| header.strip_prefix(BACKUP_HEADER))
+            .and_then(|version| version.parse::<u8>().ok())
+            .ok_or(BackupError::NotABackup)?;
+        if version != BACKUP_VERSION {
+            return Err(BackupError::UnsupportedVersion(version));
+        }
+        let data = envelope::open(&sealed[1..], password)
+            .map_err(BackupError::Decryption)?;
+        let backup =
+            Self::try_from_slice(&data).map_err(BackupError::Decoding)?;
+        for (name, file) in
+            backup.chain_files.iter().chain(&backup.pre_genesis_files)
+        {
+            if Hash::sha256(&file.data) != file.hash {
+                return Err(BackupError::Corrupted(name.clone()));
+            }
+        }
+        Ok(backup)
+    }
+}
+
+/// Collect the TOML files in the given directory and its subdirectories by
+/// their path relative to the root directory
+fn collect_toml_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut BTreeMap<String, BackupFile>,
+) -> Result<(), BackupError> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_toml_files(root, &path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "toml") {
+            let name = path
+                .strip_prefix(root)
+                .expect("The path must be in the root directory")
+                .to_string_lossy()
+                .into_owned();
+            files.insert(name, BackupFile::new(fs::read(&path)?));
+        }
+    }
+    Ok(())
+}
+
+/// Write the given backed up files to the directory, unless they already
+/// exist. Returns the paths of the restored files and of the skipped ones.
+pub fn restore_files<'a>(
+    dir: &Path,
+    files: impl IntoIterator<Item = (&'a String, &'a BackupFile)>,
+) -> Result<(Vec<PathBuf>, Vec<PathBuf>), BackupError> {
+    let mut restored = vec![];
+    let mut skipped = vec![];
+    for (name, file) in files {
+        // Only restore files inside of the directory
+        let is_relative = Path::new(name)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if !is_relative {
+            return Err(BackupError::InvalidPath(name.clone()));
+        }
+        let path = dir.join(name);
+        if path.exists() {
+            skipped.push(path);
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &file.data)?;
+        restored.push(path);
+    }
+    Ok((restored, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_roundtrip() {
+        let base_dir = tempfile::tempdir().unwrap();
+        let chain_id = ChainId("test-chain".to_string());
+        let chain_dir = base_dir.path().join(chain_id.as_str());
+        let validator_dir = base_dir.path().join(PRE_GENESIS_DIR).join("val");
+        fs::create_dir_all(&chain_dir).unwrap();
+        fs::create_dir_all(&validator_dir).unwrap();
+        fs::write(chain_dir.join(WALLET_FILE), b"wallet").unwrap();
+        fs::write(chain_dir.join("shielded.dat"), b"shielded").unwrap();
+        fs::write(validator_dir.join("validator-wallet.toml"), b"val").unwrap();
+
+        let backup = Backup::collect(base_dir.path(), Some(&chain_id)).unwrap();
+        assert_eq!(backup.chain_files.len(), 2);
+        assert_eq!(backup.pre_genesis_files.len(), 1);
+        let data = backup.encrypt("password");
+
+        assert!(matches!(
+            Backup::decrypt(&data, "wrong password"),
+            Err(BackupError::Decryption(_))
+        ));
+        let restored = Backup::decrypt(&data, "password").unwrap();
+        assert_eq!(restored.chain_id, Some(chain_id));
+        assert_eq!(restored.chain_files[WALLET_FILE].data, b"wallet");
+
+        // Existing files are not overwritten
+        let restore_dir = tempfile::tempdir().unwrap();
+        fs::write(restore_dir.path().join(WALLET_FILE), b"existing").unwrap();
+        let (restored_files, skipped) =
+            restore_files(restore_dir.path(), &restored.chain_files).unwrap();
+        assert_eq!(
+            restored_files,
+            vec![restore_dir.path().join("shielded.dat")]
+        );
+        assert_eq!(skipped, vec![restore_dir.path().join(WALLET_FILE)]);
+        assert_eq!(
+            fs::read(restore_dir.path().join(WALLET_FILE)).unwrap(),
+            b"existing"
+        );
+    }
+
+    #[test]
+    fn test_backup_unsupported_version() {
+        let data = Backup::default().encrypt("password");
+        let data = [b"namada-backup-v9".as_slice(), &data[16..]].concat();
+        assert!(matches!(
+            Backup::decrypt(&data, "password"),
+            Err(BackupError::UnsupportedVersion(9))
+        ));
+    }
+}
@@ -1,5 +1,7 @@
+pub mod backup;
 pub mod defaults;
 pub mod pre_genesis;
+mod remote_signer;
 mod store;
 mod transport;
 
@@ -18,6 +20,7 @@ use namada_sdk::wallet::{
 pub use namada_sdk::wallet::{ValidatorData, ValidatorKeys};
 use namada_wallet::fs::FsWalletStorage;
 use rand_core::OsRng;
+pub use remote_signer::LocalSigner;
 pub use store::wallet_file;
 pub use transport::{TransportTcp, WalletTransport};
 use zeroize::Zeroizing;
@@ -0,0 +1,212 @@
+//! Reference local signer daemon serving the requests of the remote signer
+//! protocol with keys loaded from the wallet
+//!
+//! The daemon signs blindly: it signs any section hashes requested for its
+//! keys, without seeing the transactions they belong to, and the protocol has
+//! no authentication. Anyone who can connect to the daemon can therefore sign
+//! arbitrary transactions with its keys, so it only serves on loopback TCP
+//! addresses and on Unix sockets accessible to the user running it.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use namada_sdk::key::*;
+use namada_sdk::remote_signer::{
+    write_message, RemoteSignerAddr, SignRequest, SignResponse,
+};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, BufStream};
+
+/// A signer of the requests for its keys
+#[derive(Debug, Default)]
+pub struct LocalSigner {
+    keys: HashMap<common::PublicKey, common::SecretKey>,
+}
+
+impl LocalSigner {
+    /// Make a signer holding the given keys
+    pub fn new(keys: impl IntoIterator<Item = common::SecretKey>) -> Self {
+        Self {
+            keys: keys.into_iter().map(|sk| (sk.ref_to(), sk)).collect(),
+        }
+    }
+
+    /// Sign the commitment of the given request, if the requested key is held
+    /// by this signer
+    pub fn sign(&self, request: &SignRequest) -> SignResponse {
+        match self.keys.get(&request.public_key) {
+            Some(secret_key) => SignResponse::Signature(
+                common::SigScheme::sign(secret_key, request.commitment()),
+            ),
+            None => SignResponse::Rejected(format!(
+                "The public key {} is not held by the signer",
+                request.public_key
+            )),
+        }
+    }
+
+    /// Serve the signing requests on the given address until an error occurs
+    /// while accepting a connection. Every request for the keys of the signer
+    /// is signed, so a TCP address must be a loopback address and a Unix
+    /// socket is only made accessible to its owner.
+    pub async fn serve(self, addr: &RemoteSignerAddr) -> std::io::Result<()> {
+        let signer = Arc::new(self);
+        match addr {
+            RemoteSignerAddr::Tcp(addr) => {
+                let addrs: Vec<SocketAddr> =
+                    tokio::net::lookup_host(addr).await?.collect();
+                if let Some(addr) =
+                    addrs.iter().find(|addr| !addr.ip().is_loopback())
+                {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!(
+                            "Refusing to serve on the non-loopback address \
+                             {addr}, as the signer has no authentication"
+                        ),
+                    ));
+                }
+                let listener =
+                    tokio::net::TcpListener::bind(&addrs[..]).await?;
+                loop {
+                    let (stream, _) = listener.accept().await?;
+                    tokio::spawn(handle_connection(signer.clone(), stream));
+                }
+            }
+            #[cfg(unix)]
+            RemoteSignerAddr::Unix(path) => {
+                use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+                let listener = tokio::net::UnixListener::bind(path)?;
+                std::fs::set_permissions(
+                    path,
+                    std::fs::Permissions::from_mode(0o600),
+                )?;
+                let owner = std::fs::metadata(path)?.uid();
+                loop {
+                    let (stream, _) = listener.accept().await?;
+                    // Reject the connections of other users made before the
+                    // permissions of the socket were set
+                    match stream.peer_cred() {
+                        Ok(cred) if cred.uid() == owner => {
+                            tokio::spawn(handle_connection(
+                                signer.clone(),
+                                stream,
+                            ));
+                        }
+                        _ => tracing::warn!(
+                            "Rejected a connection from another user"
+                        ),
+                    }
+                }
+            }
+            #[cfg(not(unix))]
+            RemoteSignerAddr::Unix(_) => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "Unix sockets are not supported on this platform",
+            )),
+        }
+    }
+}
+
+/// Reply to the requests received on a connection until it's closed
+async fn handle_connection<S>(signer: Arc<LocalSigner>, stream: S)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut stream = BufStream::new(stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match stream.read_line(&mut line).await {
+            Ok(0) => return,
+            Ok(_) => {}
+            Err(err) => {
+                tracing::warn!("Failed to read a signing request: {err}");
+                return;
+            }
+        }
+        let response = match serde_json::from_str::<SignRequest>(&line) {
+            Ok(request) => {
+                tracing::info!(
+                    "Signing {} section hashes with {}",
+                    request.targets.len(),
+                    request.public_key
+                );
+                signer.sign(&request)
+            }
+            Err(err) => SignResponse::Rejected(format!(
+                "Invalid signing request: {err}"
+            )),
+        };
+        if let Err(err) = write_message(&mut stream, &response).await {
+            tracing::warn!("Failed to write a signing response: {err}");
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_sdk::hash::Hash;
+    use namada_sdk::key::testing::{keypair_1, keypair_2};
+    use namada_sdk::remote_signer::{ExternalSigner, RemoteSigner};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_remote_signer_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let addr = RemoteSignerAddr::Unix(dir.path().join("signer.sock"));
+        let keypair = keypair_1();
+        let signer = LocalSigner::new([keypair.clone()]);
+        let server_addr = addr.clone();
+        tokio::spawn(async move { signer.serve(&server_addr).await });
+        // Wait for the socket to be bound
+        while !dir.path().join("signer.sock").exists() {
+            tokio::task::yield_now().await;
+        }
+
+        let client = RemoteSigner::new(addr);
+        let request = SignRequest {
+            public_key: keypair.ref_to(),
+            targets: vec![Hash::sha256(b"section")],
+        };
+        let signature = match client.sign(request.clone()).await.unwrap() {
+            SignResponse::Signature(signature) => signature,
+            response => panic!("Unexpected response {response:?}"),
+        };
+        common::SigScheme::verify_signature(
+            &keypair.ref_to(),
+            &request.commitment(),
+            &signature,
+        )
+        .unwrap();
+
+        // Requests for unknown keys are rejected
+        let response = client
+            .sign(SignRequest {
+                public_key: keypair_2().ref_to(),
+                ..request
+            })
+            .await
+            .unwrap();
+        assert!(matches!(response, SignResponse::Rejected(_)));
+
+        // The socket is only accessible to its owner
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let metadata =
+                std::fs::metadata(dir.path().join("signer.sock")).unwrap();
+            assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_refuses_non_loopback_address() {
+        let addr = RemoteSignerAddr::Tcp("0.0.0.0:0".to_string());
+        let err = LocalSigner::default().serve(&addr).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
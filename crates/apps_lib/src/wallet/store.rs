@@ -94,4 +94,34 @@ mod test_wallet {
         let data = store.encode();
         let _ = Store::decode(data).expect("Test failed");
     }
+
+    #[test]
+    fn test_encrypted_store_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let password = zeroize::Zeroizing::new("password".to_string());
+        let mut wallet = CliWalletUtils::new(dir.path().to_path_buf());
+        wallet
+            .insert_address(
+                "alias",
+                Address::decode(
+                    "tnam1q99c37u38grkdcc2qze0hz4zjjd8zr3yucd3mzgz",
+                )
+                .unwrap(),
+                false,
+            )
+            .unwrap();
+        wallet.set_store_password(Some(password.clone()));
+        wallet.save().unwrap();
+
+        // Nothing is readable from the file
+        let data = std::fs::read(wallet_file(dir.path())).unwrap();
+        assert!(namada_sdk::wallet::envelope::is_sealed(&data));
+        assert!(!String::from_utf8(data).unwrap().contains("alias"));
+
+        let mut loaded = CliWalletUtils::new(dir.path().to_path_buf());
+        loaded.set_store_password(Some(password));
+        loaded.load().unwrap();
+        assert!(loaded.is_store_encrypted());
+        assert!(loaded.find_address("alias").is_some());
+    }
 }
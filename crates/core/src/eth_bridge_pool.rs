@@ -5,6 +5,7 @@ use std::borrow::Cow;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
+use ethabi::ethereum_types::U256 as ethUint;
 use ethabi::token::Token;
 use namada_macros::{BorshDeserializer, StorageKeys};
 #[cfg(feature = "migrations")]
@@ -18,7 +19,7 @@ use crate as namada_core; // This is needed for `StorageKeys` macro
 use crate::address::Address;
 use crate::eth_abi::Encode;
 use crate::ethereum_events::{
-    EthAddress, TransferToEthereum as TransferToEthereumEvent,
+    EthAddress, TransferToEthereum as TransferToEthereumEvent, Uint,
 };
 use crate::hash::Hash as HashDigest;
 use crate::storage::{DbKeySeg, Key};
@@ -367,6 +368,97 @@ pub struct GasFee {
     pub token: Address,
 }
 
+/// The maximum number of batches kept in the history of
+/// [`RelayCosts`].
+pub const RELAY_COSTS_HISTORY_LEN: usize = 64;
+
+/// The cost of relaying a batch of Bridge pool transfers
+/// to Ethereum.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub struct RelayCost {
+    /// The number of transfers in the batch.
+    pub transfers: u64,
+    /// The cost paid by the relayer on Ethereum to relay
+    /// the batch, in wei.
+    pub cost: Uint,
+}
+
+impl RelayCost {
+    /// The cost of relaying a single transfer of the batch, in
+    /// wei, rounded up. Returns [`None`] if the batch is empty.
+    pub fn per_transfer(&self) -> Option<Uint> {
+        let cost = ethUint::from(self.cost);
+        let transfers = ethUint::from(self.transfers);
+        let per_transfer = cost.checked_div(transfers)?;
+        if cost.checked_rem(transfers)?.is_zero() {
+            Some(per_transfer.into())
+        } else {
+            per_transfer.checked_add(1.into()).map(Uint::from)
+        }
+    }
+}
+
+/// History of the costs paid by relayers on Ethereum to
+/// relay batches of Bridge pool transfers.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSchema,
+)]
+pub struct RelayCosts {
+    /// The total number of relayed batches.
+    pub relayed_batches: u64,
+    /// The costs of the most recently relayed batches, from
+    /// oldest to newest. At most [`RELAY_COSTS_HISTORY_LEN`]
+    /// batches are kept.
+    pub recent: Vec<RelayCost>,
+}
+
+impl RelayCosts {
+    /// Record the cost of a newly relayed batch.
+    pub fn record(&mut self, cost: RelayCost) {
+        self.relayed_batches = self.relayed_batches.saturating_add(1);
+        if self.recent.len() >= RELAY_COSTS_HISTORY_LEN {
+            self.recent.remove(0);
+        }
+        self.recent.push(cost);
+    }
+
+    /// The median cost of relaying a single transfer, in wei,
+    /// over the most recently relayed batches. Returns [`None`]
+    /// if no batches have been relayed yet.
+    pub fn cost_per_transfer(&self) -> Option<Uint> {
+        let mut costs: Vec<_> = self
+            .recent
+            .iter()
+            .filter_map(RelayCost::per_transfer)
+            .collect();
+        costs.sort_unstable();
+        costs.get(costs.len() / 2).copied()
+    }
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Testing helpers and strategies for the Ethereum bridge pool
 pub mod testing {
@@ -467,4 +559,44 @@ mod test_eth_bridge_pool_types {
         let event: TransferToEthereumEvent = (&pending).into();
         assert_eq!(pending.keccak256(), event.keccak256());
     }
+
+    /// Test that the cost of relaying a single transfer of a
+    /// batch is rounded up.
+    #[test]
+    fn test_relay_cost_per_transfer() {
+        let cost = |transfers, cost: u64| RelayCost {
+            transfers,
+            cost: cost.into(),
+        };
+        assert_eq!(cost(0, 10).per_transfer(), None);
+        assert_eq!(cost(2, 10).per_transfer(), Some(5u64.into()));
+        assert_eq!(cost(3, 10).per_transfer(), Some(4u64.into()));
+    }
+
+    /// Test that the history of relay costs is bounded, and that
+    /// the cost per transfer is the median of the history.
+    #[test]
+    fn test_relay_costs_history() {
+        let mut costs = RelayCosts::default();
+        assert_eq!(costs.cost_per_transfer(), None);
+
+        for (transfers, cost) in [(1, 5u64), (2, 2), (3, 9)] {
+            costs.record(RelayCost {
+                transfers,
+                cost: cost.into(),
+            });
+        }
+        assert_eq!(costs.cost_per_transfer(), Some(3u64.into()));
+
+        for _ in 0..RELAY_COSTS_HISTORY_LEN {
+            costs.record(RelayCost {
+                transfers: 2,
+                cost: 20u64.into(),
+            });
+        }
+        let batches = RELAY_COSTS_HISTORY_LEN as u64;
+        assert_eq!(costs.recent.len(), RELAY_COSTS_HISTORY_LEN);
+        assert_eq!(costs.relayed_batches, 3 + batches);
+        assert_eq!(costs.cost_per_transfer(), Some(10u64.into()));
+    }
 }
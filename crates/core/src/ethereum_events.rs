@@ -279,6 +279,10 @@ pub enum EthereumEvent {
         /// The Namada address that receives the gas fees
         /// for relaying a batch of transfers
         relayer: Address,
+        /// The cost paid by the relayer on Ethereum to relay the
+        /// batch of transfers, in wei. This is the gas used by the
+        /// relay transaction times its effective gas price.
+        relay_cost: Uint,
     },
     /// Event indication that the validator set has been updated
     /// in the governance contract
@@ -92,6 +92,8 @@ pub enum DbColFam {
     ROLLBACK,
     /// Replay protection
     REPLAYPROT,
+    /// Transaction history index
+    TXHISTORY,
 }
 
 /// Subspace column family name
@@ -106,6 +108,8 @@ pub const STATE_CF: &str = "state";
 pub const BLOCK_CF: &str = "block";
 /// Replay protection column family name
 pub const REPLAY_PROTECTION_CF: &str = "replay_protection";
+/// Transaction history index column family name
+pub const TX_HISTORY_CF: &str = "tx_history";
 
 impl DbColFam {
     /// Get the name of the column family
@@ -117,11 +121,12 @@ impl DbColFam {
             DbColFam::DIFFS => DIFFS_CF,
             DbColFam::ROLLBACK => ROLLBACK_CF,
             DbColFam::REPLAYPROT => REPLAY_PROTECTION_CF,
+            DbColFam::TXHISTORY => TX_HISTORY_CF,
         }
     }
 
     /// Return an array of all column families
-    pub fn all() -> [&'static str; 6] {
+    pub fn all() -> [&'static str; 7] {
         [
             SUBSPACE_CF,
             BLOCK_CF,
@@ -129,6 +134,7 @@ impl DbColFam {
             DIFFS_CF,
             ROLLBACK_CF,
             REPLAY_PROTECTION_CF,
+            TX_HISTORY_CF,
         ]
     }
 }
@@ -144,6 +150,7 @@ impl FromStr for DbColFam {
             STATE_CF => Ok(Self::STATE),
             REPLAY_PROTECTION_CF => Ok(Self::REPLAYPROT),
             BLOCK_CF => Ok(Self::BLOCK),
+            TX_HISTORY_CF => Ok(Self::TXHISTORY),
             _ => Err(Error::DbColFamily(s.to_string())),
         }
     }
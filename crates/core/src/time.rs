@@ -25,6 +25,7 @@ pub fn duration_passed(
 }
 
 /// A duration in seconds precision.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[derive(
     Clone,
     Copy,
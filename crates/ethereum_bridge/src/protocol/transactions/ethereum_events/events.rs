@@ -10,12 +10,12 @@ use namada_core::chain::BlockHeight;
 use namada_core::collections::HashSet;
 use namada_core::eth_abi::Encode;
 use namada_core::eth_bridge_pool::{
-    erc20_nut_address, erc20_token_address, PendingTransfer,
-    TransferToEthereumKind,
+    erc20_nut_address, erc20_token_address, PendingTransfer, RelayCost,
+    RelayCosts, TransferToEthereumKind,
 };
 use namada_core::ethereum_events::{
     EthAddress, EthereumEvent, TransferToEthereum, TransferToNamada,
-    TransfersToNamada,
+    TransfersToNamada, Uint as EthUint,
 };
 use namada_core::hints;
 use namada_core::storage::{Key, KeySeg};
@@ -29,7 +29,8 @@ use token::{burn_tokens, decrement_total_supply, increment_total_supply};
 
 use crate::event::EthBridgeEvent;
 use crate::storage::bridge_pool::{
-    get_nonce_key, is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
+    get_nonce_key, get_relay_costs_key, is_pending_transfer_key,
+    BRIDGE_POOL_ADDRESS,
 };
 use crate::storage::eth_bridge_queries::{EthAssetMint, EthBridgeQueries};
 use crate::storage::parameters::read_native_erc20_address;
@@ -57,8 +58,9 @@ where
         EthereumEvent::TransfersToEthereum {
             ref transfers,
             ref relayer,
+            relay_cost,
             ..
-        } => act_on_transfers_to_eth(state, transfers, relayer),
+        } => act_on_transfers_to_eth(state, transfers, relayer, relay_cost),
         _ => {
             tracing::debug!(?event, "No actions taken for Ethereum event");
             Ok(Default::default())
@@ -240,6 +242,7 @@ fn act_on_transfers_to_eth<D, H>(
     state: &mut WlState<D, H>,
     transfers: &[TransferToEthereum],
     relayer: &Address,
+    relay_cost: EthUint,
 ) -> Result<(BTreeSet<Key>, BTreeSet<EthBridgeEvent>)>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
@@ -257,6 +260,20 @@ where
     increment_bp_nonce(&nonce_key, state)?;
     changed_keys.insert(nonce_key);
 
+    // keep track of the costs paid by relayers on Ethereum,
+    // to recommend fees for new transfers
+    if !transfers.is_empty() {
+        let relay_costs_key = get_relay_costs_key();
+        let mut relay_costs: RelayCosts =
+            state.read(&relay_costs_key)?.unwrap_or_default();
+        relay_costs.record(RelayCost {
+            transfers: transfers.len() as u64,
+            cost: relay_cost,
+        });
+        state.write(&relay_costs_key, relay_costs)?;
+        changed_keys.insert(relay_costs_key);
+    }
+
     // all keys of pending transfers
     let prefix = BRIDGE_POOL_ADDRESS.to_db_key().into();
     let mut pending_keys: HashSet<Key> = state
@@ -947,10 +964,12 @@ mod tests {
             .iter()
             .map(TransferToEthereum::from)
             .collect();
+        let n_transfers = transfers.len() as u64;
         let event = EthereumEvent::TransfersToEthereum {
             nonce: arbitrary_nonce(),
             transfers,
             relayer: relayer.clone(),
+            relay_cost: (n_transfers * 10).into(),
         };
         let payer_nam_balance_key = balance_key(&nam(), &relayer);
         let payer_erc_balance_key =
@@ -994,6 +1013,7 @@ mod tests {
         assert!(changed_keys.remove(&pool_nam_balance_key));
         assert!(changed_keys.remove(&pool_erc_balance_key));
         assert!(changed_keys.remove(&get_nonce_key()));
+        assert!(changed_keys.remove(&get_relay_costs_key()));
         assert!(changed_keys.iter().all(|k| pending_keys.contains(k)));
 
         let prefix = BRIDGE_POOL_ADDRESS.to_db_key().into();
@@ -1013,6 +1033,20 @@ mod tests {
             .expect("Test failed: no value in storage");
         assert_eq!(relayer_erc_balance, Amount::from(2));
 
+        let relay_costs: RelayCosts = state
+            .read(&get_relay_costs_key())
+            .expect("Test failed: read error")
+            .expect("Test failed: no value in storage");
+        assert_eq!(relay_costs.relayed_batches, 1);
+        assert_eq!(
+            relay_costs.recent,
+            vec![RelayCost {
+                transfers: n_transfers,
+                cost: (n_transfers * 10).into(),
+            }]
+        );
+        assert_eq!(relay_costs.cost_per_transfer(), Some(10u64.into()));
+
         let bp_nam_balance_post = state
             .read(&pool_nam_balance_key)
             .expect("Test failed: read error")
@@ -1070,6 +1104,7 @@ mod tests {
             nonce: arbitrary_nonce(),
             transfers: vec![],
             relayer: gen_implicit_address(),
+            relay_cost: Default::default(),
         };
         let _ = act_on(&mut state, event).unwrap();
 
@@ -1266,6 +1301,7 @@ mod tests {
             nonce: arbitrary_nonce(),
             transfers,
             relayer,
+            relay_cost: Default::default(),
         };
         f(&mut state, event)
     }
@@ -9,6 +9,12 @@ pub use namada_core::eth_bridge_pool::{
 use namada_core::storage::{DbKeySeg, Key};
 pub use namada_state::merkle_tree::eth_bridge_pool::BridgePoolTree;
 
+use crate::ADDRESS as BRIDGE_ADDRESS;
+
+/// Sub-key of the Ethereum bridge storage under which the
+/// history of the costs of relaying Bridge pool batches is kept.
+const RELAY_COSTS_SEGMENT: &str = "relay_costs";
+
 /// Get the storage key for the root of the Merkle tree
 /// containing the transfers in the pool
 pub fn get_signed_root_key() -> Key {
@@ -31,6 +37,21 @@ pub fn get_nonce_key() -> Key {
     }
 }
 
+/// Get the storage key of the history of the costs paid by
+/// relayers on Ethereum to relay batches of Bridge pool transfers.
+///
+/// This key is kept in the Ethereum bridge's subspace, such that
+/// the Bridge pool's subspace only holds pending transfers and
+/// their signed root.
+pub fn get_relay_costs_key() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_ADDRESS),
+            DbKeySeg::StringSeg(RELAY_COSTS_SEGMENT.into()),
+        ],
+    }
+}
+
 /// Check if a key belongs to the bridge pools sub-storage
 pub fn is_bridge_pool_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &BRIDGE_POOL_ADDRESS)
@@ -10,6 +10,11 @@
 //! correctly. This means that the appropriate data is
 //! added to the pool and gas fees are submitted appropriately
 //! and that tokens to be transferred are escrowed.
+//!
+//! Transfers may also be cancelled by their sender, as long
+//! as no Bridge pool root including them can be relayed to
+//! Ethereum. In that case, the VP checks that the escrowed
+//! tokens and gas fees are refunded in full.
 
 use std::borrow::Cow;
 use std::collections::BTreeSet;
@@ -22,17 +27,18 @@ use namada_core::booleans::BoolResultUnitExt;
 use namada_core::eth_bridge_pool::{
     erc20_token_address, PendingTransfer, TransferToEthereumKind,
 };
-use namada_core::ethereum_events::EthAddress;
+use namada_core::ethereum_events::{EthAddress, Uint};
 use namada_core::hints;
 use namada_core::storage::Key;
 use namada_core::uint::I320;
-use namada_state::ResultExt;
+use namada_state::{ReadBridgePoolTree, ResultExt};
 use namada_systems::trans_token::{self as token, Amount};
+use namada_tx::action::{Action, BridgePoolAction, Read as _};
 use namada_tx::BatchedTxRef;
 use namada_vp_env::{Error, Result, StorageRead, VpEnv};
 
 use crate::storage::bridge_pool::{
-    get_pending_key, is_bridge_pool_key, BRIDGE_POOL_ADDRESS,
+    get_nonce_key, get_pending_key, is_bridge_pool_key, BRIDGE_POOL_ADDRESS,
 };
 use crate::storage::eth_bridge_queries::is_bridge_active_at;
 use crate::storage::parameters::read_native_erc20_address;
@@ -64,7 +70,8 @@ pub struct BridgePool<'ctx, CTX, TokenKeys> {
 
 impl<'ctx, CTX, TokenKeys> BridgePool<'ctx, CTX, TokenKeys>
 where
-    CTX: VpEnv<'ctx> + namada_tx::action::Read<Err = Error>,
+    CTX:
+        VpEnv<'ctx> + namada_tx::action::Read<Err = Error> + ReadBridgePoolTree,
     TokenKeys: token::Keys,
 {
     /// Run the validity predicate
@@ -72,11 +79,11 @@ where
         ctx: &'ctx CTX,
         batched_tx: &BatchedTxRef<'_>,
         keys_changed: &BTreeSet<Key>,
-        _verifiers: &BTreeSet<Address>,
+        verifiers: &BTreeSet<Address>,
     ) -> Result<()> {
         tracing::debug!(
             keys_changed_len = keys_changed.len(),
-            verifiers_len = _verifiers.len(),
+            verifiers_len = verifiers.len(),
             "Ethereum Bridge Pool VP triggered",
         );
         if !is_bridge_active_at(&ctx.pre(), ctx.get_block_epoch()?)? {
@@ -95,21 +102,6 @@ where
                 .into_storage_result()?;
 
         let pending_key = get_pending_key(&transfer);
-        // check that transfer is not already in the pool
-        match ctx.pre().read::<PendingTransfer>(&pending_key) {
-            Ok(Some(_)) => {
-                let error = Error::new_const(
-                    "Rejecting transaction as the transfer is already in the \
-                     Ethereum bridge pool.",
-                );
-                tracing::debug!("{error}");
-                return Err(error);
-            }
-            // NOTE: make sure we don't erase storage errors returned by the
-            // ctx, as these may contain gas errors!
-            Err(e) => return Err(e),
-            _ => {}
-        }
         for key in keys_changed.iter().filter(|k| is_bridge_pool_key(k)) {
             if *key != pending_key {
                 let error = Error::new_alloc(format!(
@@ -121,6 +113,23 @@ where
                 return Err(error);
             }
         }
+        // if the transfer is already in the pool, the only valid
+        // change is its cancellation
+        match ctx.pre().read::<PendingTransfer>(&pending_key) {
+            Ok(Some(_)) => {
+                return Self::validate_cancellation(
+                    ctx,
+                    &transfer,
+                    &pending_key,
+                    keys_changed,
+                    verifiers,
+                );
+            }
+            // NOTE: make sure we don't erase storage errors returned by the
+            // ctx, as these may contain gas errors!
+            Err(e) => return Err(e),
+            _ => {}
+        }
         let pending: PendingTransfer =
             ctx.post().read(&pending_key)?.ok_or_else(|| {
                 Error::new_const(
@@ -198,6 +207,109 @@ where
         })
     }
 
+    /// Validate the cancellation of a transfer that is
+    /// still pending in the Bridge pool.
+    ///
+    /// Only the sender of a transfer may cancel it, and only
+    /// while no Bridge pool root including it can be relayed.
+    /// The escrowed tokens and gas fees must be refunded in full.
+    fn validate_cancellation(
+        ctx: &'ctx CTX,
+        transfer: &PendingTransfer,
+        pending_key: &Key,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<()> {
+        if ctx.post().has_key(pending_key)? {
+            let error = Error::new_const(
+                "Rejecting transaction as the transfer is already in the \
+                 Ethereum bridge pool.",
+            );
+            tracing::debug!("{error}");
+            return Err(error);
+        }
+        let sender = &transfer.transfer.sender;
+        let authorized_by_sender = ctx.read_actions()?.iter().any(|action| {
+            matches!(
+                action,
+                Action::BridgePool(BridgePoolAction::CancelTransfer(source))
+                    if source == sender
+            )
+        });
+        if !authorized_by_sender || !verifiers.contains(sender) {
+            let error = Error::new_alloc(format!(
+                "Rejecting transaction as the cancellation of the transfer \
+                 was not authorized by its sender {sender}",
+            ));
+            tracing::debug!("{error}");
+            return Err(error);
+        }
+        if Self::may_be_relayed(ctx, pending_key)? {
+            let error = Error::new_const(
+                "Rejecting transaction as the transfer is included in Bridge \
+                 pool roots that may still be signed and relayed",
+            );
+            tracing::debug!("{error}");
+            return Err(error);
+        }
+        // The deltas in the refunded amounts we must check.
+        let wnam_address = read_native_erc20_address(&ctx.pre())?;
+        let refund_checks =
+            Self::determine_escrow_checks(ctx, &wnam_address, transfer)?
+                .reversed();
+        if !refund_checks.validate::<TokenKeys>(keys_changed) {
+            let error = Error::new_const(
+                "Invalid storage modifications in the Bridge pool",
+            );
+            tracing::debug!("{error}");
+            return Err(error);
+        }
+        if !Self::check_escrowed_toks(ctx, refund_checks.gas_check)? {
+            return Err(Error::new_const(
+                "Gas was not correctly refunded from the Bridge pool storage",
+            ));
+        }
+        if !Self::check_escrowed_toks(ctx, refund_checks.token_check)? {
+            return Err(Error::new_alloc(format!(
+                "The {} tokens were not refunded properly",
+                transfer.transfer.asset
+            )));
+        }
+        tracing::info!(
+            "The Ethereum bridge pool VP accepted the cancellation of the \
+             transfer {:?}.",
+            transfer
+        );
+        Ok(())
+    }
+
+    /// Check if a pending transfer may be relayed to Ethereum
+    /// as part of a signed Bridge pool root.
+    ///
+    /// A transfer committed to the Bridge pool tree is included
+    /// in the root of every block since, and any of these roots
+    /// may still be signed, even after a more recent root was.
+    /// Each root is signed along with the nonce of its block, and
+    /// can only be relayed while the nonce has not advanced past
+    /// it. The most recent of these roots is the one of the last
+    /// committed block, thus the transfer is safe to cancel only
+    /// once the nonce has advanced past the last committed one.
+    fn may_be_relayed(ctx: &'ctx CTX, pending_key: &Key) -> Result<bool> {
+        if ctx.bridge_pool_insertion_height(pending_key)?.is_none() {
+            // the transfer was added in the current block
+            return Ok(false);
+        }
+        let nonce_key = get_nonce_key();
+        let committed_nonce = ctx
+            .read_last_committed_bytes(&nonce_key)?
+            .map(|bytes| Uint::try_from_slice(&bytes))
+            .transpose()
+            .into_storage_result()?
+            .unwrap_or_default();
+        let nonce: Uint = ctx.pre().read(&nonce_key)?.unwrap_or_default();
+        Ok(nonce <= committed_nonce)
+    }
+
     /// Get the change in the balance of an account
     /// associated with an address
     fn account_balance_delta(
@@ -514,6 +626,18 @@ struct EscrowDelta<'ctx, KIND> {
 }
 
 impl<KIND> EscrowDelta<'_, KIND> {
+    /// Reverse the direction of an [`EscrowDelta`], such that
+    /// it checks for a refund out of the escrow account.
+    fn reversed(self) -> Self {
+        Self {
+            payer_account: self.escrow_account,
+            escrow_account: self.payer_account,
+            expected_debit: self.expected_credit,
+            expected_credit: self.expected_debit,
+            ..self
+        }
+    }
+
     /// Validate an [`EscrowDelta`].
     ///
     /// # Conditions for validation
@@ -601,6 +725,16 @@ impl EscrowCheck<'_> {
         self.gas_check.validate::<TokenKeys>(changed_keys)
             && self.token_check.validate::<TokenKeys>(changed_keys)
     }
+
+    /// Reverse the direction of the escrow checks, in order
+    /// to check refunds of cancelled transfers.
+    #[inline]
+    fn reversed(self) -> Self {
+        Self {
+            gas_check: self.gas_check.reversed(),
+            token_check: self.token_check.reversed(),
+        }
+    }
 }
 
 /// Perform a gas check.
@@ -631,13 +765,16 @@ mod test_bridge_pool_vp {
 
     use namada_core::address::testing::{nam, wnam};
     use namada_core::borsh::BorshSerializeExt;
+    use namada_core::chain::BlockHeight;
     use namada_core::eth_bridge_pool::{GasFee, TransferToEthereum};
     use namada_core::hash::Hash;
+    use namada_core::keccak::KeccakHash;
     use namada_gas::{TxGasMeter, VpGasMeter};
     use namada_state::testing::TestState;
     use namada_state::write_log::WriteLog;
     use namada_state::{StateRead, StorageWrite, TxIndex};
     use namada_trans_token::storage_key::balance_key;
+    use namada_tx::action::Write;
     use namada_tx::data::TxType;
     use namada_tx::Tx;
     use namada_vm::wasm::run::VpEvalWasm;
@@ -650,6 +787,7 @@ mod test_bridge_pool_vp {
     use crate::storage::parameters::{
         Contracts, EthereumBridgeParams, UpgradeableContract,
     };
+    use crate::storage::proof::BridgePoolRootProof;
     use crate::storage::wrapped_erc20s;
 
     type CA = WasmCacheRwAccess;
@@ -1369,6 +1507,261 @@ mod test_bridge_pool_vp {
         assert!(res.is_err());
     }
 
+    /// The height at which the transfer to be cancelled was
+    /// committed to the Bridge pool tree.
+    const PENDING_TRANSFER_HEIGHT: BlockHeight = BlockHeight(2);
+
+    /// Helper function that tests the cancellation of a transfer
+    /// pending in the Bridge pool, given the height of the latest
+    /// signed root, whether the Bridge pool nonce was advanced in
+    /// the current block and the refunded amounts.
+    fn assert_cancel_transfer(
+        signed_root_height: Option<BlockHeight>,
+        nonce_advanced: bool,
+        authorized: bool,
+        gas_refund: I320,
+        token_refund: I320,
+        expect: Expect,
+    ) {
+        // setup
+        let mut state = setup_storage();
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.push_default_inner_tx();
+
+        // the transfer to be cancelled
+        let transfer = PendingTransfer {
+            transfer: TransferToEthereum {
+                kind: TransferToEthereumKind::Erc20,
+                asset: ASSET,
+                sender: bertha_address(),
+                recipient: EthAddress([1; 20]),
+                amount: TOKENS.into(),
+            },
+            gas_fee: GasFee {
+                token: nam(),
+                amount: GAS_FEE.into(),
+                payer: bertha_address(),
+            },
+        };
+        let pending_key = get_pending_key(&transfer);
+
+        // add the transfer to the pool in some previous block
+        let _ = state
+            .write_log_mut()
+            .write(&pending_key, transfer.serialize_to_vec())
+            .expect("Test failed");
+        state
+            .in_mem_mut()
+            .block
+            .tree
+            .update(&pending_key, PENDING_TRANSFER_HEIGHT.serialize_to_vec())
+            .expect("Test failed");
+        match signed_root_height {
+            Some(height) => {
+                let signed_root = (
+                    BridgePoolRootProof::new((
+                        KeccakHash::default(),
+                        Uint::default(),
+                    )),
+                    height,
+                );
+                let _ = state
+                    .write_log_mut()
+                    .write(
+                        &get_signed_root_key(),
+                        signed_root.serialize_to_vec(),
+                    )
+                    .expect("Test failed");
+            }
+            None => {
+                let _ = state
+                    .write_log_mut()
+                    .delete(&get_signed_root_key())
+                    .expect("Test failed");
+            }
+        }
+        if nonce_advanced {
+            // a batch was relayed earlier in the current block
+            let _ = state
+                .write_log_mut()
+                .write(&get_nonce_key(), Uint::from(1).serialize_to_vec())
+                .expect("Test failed");
+        }
+        state.write_log_mut().commit_tx_to_batch();
+
+        // cancel the transfer
+        let _ = state
+            .write_log_mut()
+            .delete(&pending_key)
+            .expect("Test failed");
+        if authorized {
+            state
+                .push_action(Action::BridgePool(
+                    BridgePoolAction::CancelTransfer(bertha_address()),
+                ))
+                .expect("Test failed");
+        }
+        let mut keys_changed = BTreeSet::from([pending_key]);
+
+        // refund Bertha
+        let mut new_keys_changed = update_balances(
+            state.write_log_mut(),
+            Balance {
+                asset: ASSET,
+                kind: TransferToEthereumKind::Erc20,
+                owner: bertha_address(),
+                gas: BERTHA_WEALTH.into(),
+                token: BERTHA_TOKENS.into(),
+            },
+            gas_refund,
+            token_refund,
+        );
+        keys_changed.append(&mut new_keys_changed);
+
+        // draw the refund from the bridge pool balances
+        let mut new_keys_changed = update_balances(
+            state.write_log_mut(),
+            Balance {
+                asset: ASSET,
+                kind: TransferToEthereumKind::Erc20,
+                owner: BRIDGE_POOL_ADDRESS,
+                gas: ESCROWED_AMOUNT.into(),
+                token: ESCROWED_TOKENS.into(),
+            },
+            -gas_refund,
+            -token_refund,
+        );
+        keys_changed.append(&mut new_keys_changed);
+        let verifiers = BTreeSet::from([bertha_address()]);
+
+        // create the data to be given to the vp
+        let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new(u64::MAX),
+        ));
+        let ctx = setup_ctx(&tx, &state, &gas_meter, &keys_changed, &verifiers);
+
+        let mut tx = Tx::new(state.in_mem().chain_id.clone(), None);
+        tx.add_data(transfer);
+
+        let tx = tx.batch_ref_first_tx().unwrap();
+        let res = BridgePool::validate_tx(&ctx, &tx, &keys_changed, &verifiers);
+        match (expect, res) {
+            (Expect::Accepted, Ok(())) => (),
+            (Expect::Accepted, Err(err)) => {
+                panic!("Expected VP success, but got: {err}")
+            }
+            (Expect::Rejected, Err(_)) => (),
+            (Expect::Rejected, Ok(())) => {
+                panic!("Expected VP failure, but the tx was accepted")
+            }
+        }
+    }
+
+    /// Test that cancelling a transfer once no root including
+    /// it can be relayed anymore, and refunding its escrow, passes
+    /// the vp.
+    #[test]
+    fn test_cancel_transfer_happy_flow() {
+        assert_cancel_transfer(
+            Some(BlockHeight(1)),
+            true,
+            true,
+            I320::from(GAS_FEE),
+            I320::from(TOKENS),
+            Expect::Accepted,
+        );
+        assert_cancel_transfer(
+            None,
+            true,
+            true,
+            I320::from(GAS_FEE),
+            I320::from(TOKENS),
+            Expect::Accepted,
+        );
+        // the signed root including the transfer was
+        // signed with an outdated nonce
+        assert_cancel_transfer(
+            Some(PENDING_TRANSFER_HEIGHT),
+            true,
+            true,
+            I320::from(GAS_FEE),
+            I320::from(TOKENS),
+            Expect::Accepted,
+        );
+    }
+
+    /// Test that transfers included in a signed root cannot
+    /// be cancelled.
+    #[test]
+    fn test_cancel_signed_transfer_rejected() {
+        assert_cancel_transfer(
+            Some(PENDING_TRANSFER_HEIGHT),
+            false,
+            true,
+            I320::from(GAS_FEE),
+            I320::from(TOKENS),
+            Expect::Rejected,
+        );
+    }
+
+    /// Test that transfers cannot be cancelled while the root
+    /// at the height they were added at is not signed, but is
+    /// still being voted on.
+    #[test]
+    fn test_cancel_transfer_in_voted_root_rejected() {
+        assert_cancel_transfer(
+            Some(BlockHeight(1)),
+            false,
+            true,
+            I320::from(GAS_FEE),
+            I320::from(TOKENS),
+            Expect::Rejected,
+        );
+        assert_cancel_transfer(
+            None,
+            false,
+            true,
+            I320::from(GAS_FEE),
+            I320::from(TOKENS),
+            Expect::Rejected,
+        );
+    }
+
+    /// Test that only the sender of a transfer may cancel it.
+    #[test]
+    fn test_cancel_transfer_unauthorized_rejected() {
+        assert_cancel_transfer(
+            Some(BlockHeight(1)),
+            true,
+            false,
+            I320::from(GAS_FEE),
+            I320::from(TOKENS),
+            Expect::Rejected,
+        );
+    }
+
+    /// Test that the escrow of a cancelled transfer must be
+    /// refunded in full.
+    #[test]
+    fn test_cancel_transfer_partial_refund_rejected() {
+        assert_cancel_transfer(
+            Some(BlockHeight(1)),
+            true,
+            true,
+            I320::from(GAS_FEE - 1),
+            I320::from(TOKENS),
+            Expect::Rejected,
+        );
+        assert_cancel_transfer(
+            Some(BlockHeight(1)),
+            true,
+            true,
+            I320::from(GAS_FEE),
+            I320::from(0),
+            Expect::Rejected,
+        );
+    }
+
     /// Test that a transfer added to the pool with zero gas fees
     /// is rejected.
     #[test]
@@ -70,10 +70,13 @@ where
             .read(&escrow_key)?
             .ok_or_else(|| Error::new_const("Escrow must be present"))?;
 
-        // The amount escrowed should increase.
-        if escrow_pre < escrow_post {
+        // The amount escrowed should increase, or decrease when the
+        // escrow of a cancelled Bridge pool transfer is refunded.
+        if escrow_pre != escrow_post {
             // NB: normally, we only escrow NAM under the Ethereum bridge
-            // address in the context of a Bridge pool transfer
+            // address in the context of a Bridge pool transfer, and only
+            // refund it in the context of a Bridge pool cancellation,
+            // whose refunds are checked by the Bridge pool VP
             let bridge_pool_is_verifier =
                 verifiers.contains(&storage::bridge_pool::BRIDGE_POOL_ADDRESS);
 
@@ -85,8 +88,8 @@ where
             })
         } else {
             Err(Error::new_const(
-                "User tx attempted to decrease the amount of native tokens \
-                 escrowed in the Ethereum Bridge's account",
+                "User tx did not change the amount of native tokens escrowed \
+                 in the Ethereum Bridge's account",
             ))
         }
     }
@@ -458,6 +461,82 @@ mod tests {
         assert!(res.is_err());
     }
 
+    /// Test that refunding escrowed Nam is only accepted if
+    /// the Bridge pool VP is triggered.
+    #[test]
+    fn test_refund_nam_requires_bridge_pool_vp() {
+        let mut state = setup_storage();
+        // escrow some nam in a previous tx
+        let escrow_key = balance_key(&nam(), &crate::ADDRESS);
+        let _ = state
+            .write_log_mut()
+            .write(
+                &escrow_key,
+                Amount::from(
+                    BRIDGE_POOL_ESCROW_INITIAL_BALANCE + ESCROW_AMOUNT,
+                )
+                .serialize_to_vec(),
+            )
+            .expect("Test failed");
+        state.write_log_mut().commit_tx_to_batch();
+
+        // credit the user's balance
+        let account_key = balance_key(
+            &nam(),
+            &Address::decode(ARBITRARY_OWNER_A_ADDRESS).expect("Test failed"),
+        );
+        let _ = state
+            .write_log_mut()
+            .write(
+                &account_key,
+                Amount::from(ARBITRARY_OWNER_A_INITIAL_BALANCE + ESCROW_AMOUNT)
+                    .serialize_to_vec(),
+            )
+            .expect("Test failed");
+
+        // debit the balance from the escrow
+        let _ = state
+            .write_log_mut()
+            .write(
+                &escrow_key,
+                Amount::from(BRIDGE_POOL_ESCROW_INITIAL_BALANCE)
+                    .serialize_to_vec(),
+            )
+            .expect("Test failed");
+
+        let keys_changed = BTreeSet::from([account_key, escrow_key]);
+
+        // set up the VP
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.push_default_inner_tx();
+        let batched_tx = tx.batch_ref_first_tx().unwrap();
+
+        for (verifiers, accepted) in [
+            (BTreeSet::from([BRIDGE_POOL_ADDRESS]), true),
+            (BTreeSet::from([]), false),
+        ] {
+            let gas_meter = RefCell::new(VpGasMeter::new_from_tx_meter(
+                &TxGasMeter::new(u64::MAX),
+            ));
+            let ctx = setup_ctx(
+                batched_tx.tx,
+                batched_tx.cmt,
+                &state,
+                &gas_meter,
+                &keys_changed,
+                &verifiers,
+            );
+
+            let res = EthBridge::validate_tx(
+                &ctx,
+                &batched_tx,
+                &keys_changed,
+                &verifiers,
+            );
+            assert_eq!(res.is_ok(), accepted);
+        }
+    }
+
     /// Test that the VP checks that the bridge pool vp will
     /// be triggered if escrowing occurs.
     #[test]
@@ -149,6 +149,20 @@ impl Display for EventLevel {
     }
 }
 
+impl FromStr for EventLevel {
+    type Err = EventError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "block" => Ok(EventLevel::Block),
+            "tx" => Ok(EventLevel::Tx),
+            _ => Err(EventError::AttributeEncoding(format!(
+                "Unknown event level {s:?}"
+            ))),
+        }
+    }
+}
+
 /// ABCI event type.
 ///
 /// It is comprised of an event domain and sub-domain, plus any other
@@ -529,3 +543,38 @@ impl From<Event> for namada_core::tendermint::abci::Event {
         }
     }
 }
+
+impl TryFrom<namada_core::tendermint::abci::Event> for Event {
+    type Error = EventError;
+
+    fn try_from(
+        event: namada_core::tendermint::abci::Event,
+    ) -> Result<Self, Self::Error> {
+        use extend::{AttributesMap, Domain, EventAttributeEntry};
+
+        let mut attributes: BTreeMap<String, String> = event
+            .attributes
+            .iter_attributes()
+            .map(|(key, value)| (key.to_owned(), value.to_owned()))
+            .collect();
+        let level = attributes
+            .remove("event-level")
+            .ok_or(EventError::MissingAttribute("event-level"))?
+            .parse()?;
+        // NB: the type of events with an encoded domain only holds their
+        // sub-domain, see the conversion to ABCI events
+        let event_type = match attributes
+            .get(<Domain<Event> as EventAttributeEntry<'static>>::KEY)
+        {
+            Some(domain) => format!("{domain}/{}", event.kind),
+            None => event.kind,
+        }
+        .parse()?;
+
+        Ok(Self {
+            level,
+            event_type,
+            attributes,
+        })
+    }
+}
@@ -17,11 +17,14 @@
 
 pub mod event;
 pub mod storage;
+pub mod trace;
 
 use std::fmt::Display;
 use std::num::ParseIntError;
 use std::str::FromStr;
+use std::sync::Mutex;
 
+use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::hints;
 use namada_macros::BorshDeserializer;
@@ -29,6 +32,7 @@ use namada_macros::BorshDeserializer;
 use namada_migrations::*;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use trace::{ExecutionTrace, GasTrace, MemoryAccess, VpTrace};
 
 #[allow(missing_docs)]
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
@@ -50,6 +54,25 @@ pub enum GasParseError {
     Overflow,
 }
 
+#[allow(missing_docs)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum GasScheduleError {
+    #[error(
+        "Unsupported gas schedule version {0}, expected \
+         {GAS_SCHEDULE_VERSION}"
+    )]
+    UnsupportedVersion(u8),
+    #[error(
+        "Gas cost {name} of {value} is out of the allowed range [{min}, {max}]"
+    )]
+    OutOfBounds {
+        name: &'static str,
+        value: u64,
+        min: u64,
+        max: u64,
+    },
+}
+
 // RAW GAS COSTS
 // =============================================================================
 // The raw gas costs exctracted from the benchmarks.
@@ -209,9 +232,202 @@ pub const MASP_FINAL_CHECK_GAS: u64 =
     MASP_FINAL_CHECK_GAS_RAW * GAS_COST_CORRECTION;
 // =============================================================================
 
+/// The current version of the [`GasSchedule`] layout
+pub const GAS_SCHEDULE_VERSION: u8 = 1;
+
+/// The maximum factor by which a cost of the [`GasSchedule`] can deviate from
+/// its default value, in either direction
+pub const GAS_SCHEDULE_MAX_ADJUSTMENT_FACTOR: u64 = 100;
+
 /// Gas module result for functions that may fail
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The gas costs charged by the protocol for the operations that are not
+/// metered by the instrumentation of the wasm code.
+///
+/// The schedule is stored in the parameters subspace and can be updated by
+/// governance. The default schedule matches the costs exposed as constants by
+/// this crate. The costs of wasm opcodes and of memory growth are injected in
+/// the instrumented wasm modules (which are cached) and are therefore not part
+/// of the schedule.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshDeserializer,
+    BorshSerialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct GasSchedule {
+    /// The version of the schedule layout
+    pub version: u8,
+    /// The cost of compiling wasm code, per byte
+    pub compile_gas_per_byte: u64,
+    /// The cost of validating untrusted wasm code, per byte
+    pub wasm_code_validation_gas_per_byte: u64,
+    /// The fixed cost of validating a wrapper transaction
+    pub wrapper_tx_validation_gas: u64,
+    /// The cost of the space occupied by a transaction in a block, per byte
+    pub storage_occupation_gas_per_byte: u64,
+    /// The cost of transmitting a transaction over the network, per byte
+    pub network_transmission_gas_per_byte: u64,
+    /// The cost of accessing data from memory (both read and write mode), per
+    /// byte
+    pub memory_access_gas_per_byte: u64,
+    /// The cost of accessing data from storage, per byte
+    pub storage_access_gas_per_byte: u64,
+    /// The cost of writing data to storage, per byte
+    pub storage_write_gas_per_byte: u64,
+    /// The cost of removing data from storage, per byte
+    pub storage_delete_gas_per_byte: u64,
+    /// The cost of verifying a single signature of a transaction
+    pub verify_tx_sig_gas: u64,
+    /// The cost to validate an Ibc action
+    pub ibc_action_validate_gas: u64,
+    /// The cost to execute an Ibc action
+    pub ibc_action_execute_gas: u64,
+    /// The cost of masp sig verification
+    pub masp_verify_sig_gas: u64,
+    /// The fixed cost of spend note verification
+    pub masp_fixed_spend_gas: u64,
+    /// The variable cost of spend note verification
+    pub masp_variable_spend_gas: u64,
+    /// The fixed cost of convert note verification
+    pub masp_fixed_convert_gas: u64,
+    /// The variable cost of convert note verification
+    pub masp_variable_convert_gas: u64,
+    /// The fixed cost of output note verification
+    pub masp_fixed_output_gas: u64,
+    /// The variable cost of output note verification
+    pub masp_variable_output_gas: u64,
+    /// The cost to process a masp spend note in the bundle
+    pub masp_spend_check_gas: u64,
+    /// The cost to process a masp convert note in the bundle
+    pub masp_convert_check_gas: u64,
+    /// The cost to process a masp output note in the bundle
+    pub masp_output_check_gas: u64,
+    /// The cost to run the final masp check in the bundle
+    pub masp_final_check_gas: u64,
+}
+
+impl Default for GasSchedule {
+    fn default() -> Self {
+        Self {
+            version: GAS_SCHEDULE_VERSION,
+            compile_gas_per_byte: COMPILE_GAS_PER_BYTE,
+            wasm_code_validation_gas_per_byte:
+                WASM_CODE_VALIDATION_GAS_PER_BYTE,
+            wrapper_tx_validation_gas: WRAPPER_TX_VALIDATION_GAS,
+            storage_occupation_gas_per_byte: STORAGE_OCCUPATION_GAS_PER_BYTE,
+            network_transmission_gas_per_byte:
+                NETWORK_TRANSMISSION_GAS_PER_BYTE,
+            memory_access_gas_per_byte: MEMORY_ACCESS_GAS_PER_BYTE,
+            storage_access_gas_per_byte: STORAGE_ACCESS_GAS_PER_BYTE,
+            storage_write_gas_per_byte: STORAGE_WRITE_GAS_PER_BYTE,
+            storage_delete_gas_per_byte: STORAGE_DELETE_GAS_PER_BYTE,
+            verify_tx_sig_gas: VERIFY_TX_SIG_GAS,
+            ibc_action_validate_gas: IBC_ACTION_VALIDATE_GAS,
+            ibc_action_execute_gas: IBC_ACTION_EXECUTE_GAS,
+            masp_verify_sig_gas: MASP_VERIFY_SIG_GAS,
+            masp_fixed_spend_gas: MASP_FIXED_SPEND_GAS,
+            masp_variable_spend_gas: MASP_VARIABLE_SPEND_GAS,
+            masp_fixed_convert_gas: MASP_FIXED_CONVERT_GAS,
+            masp_variable_convert_gas: MASP_VARIABLE_CONVERT_GAS,
+            masp_fixed_output_gas: MASP_FIXED_OUTPUT_GAS,
+            masp_variable_output_gas: MASP_VARIABLE_OUTPUT_GAS,
+            masp_spend_check_gas: MASP_SPEND_CHECK_GAS,
+            masp_convert_check_gas: MASP_CONVERT_CHECK_GAS,
+            masp_output_check_gas: MASP_OUTPUT_CHECK_GAS,
+            masp_final_check_gas: MASP_FINAL_CHECK_GAS,
+        }
+    }
+}
+
+impl GasSchedule {
+    /// Check that the schedule has a supported version and that every cost is
+    /// within [`GAS_SCHEDULE_MAX_ADJUSTMENT_FACTOR`] of its default value
+    /// (and never zero).
+    pub fn validate(&self) -> std::result::Result<(), GasScheduleError> {
+        if self.version != GAS_SCHEDULE_VERSION {
+            return Err(GasScheduleError::UnsupportedVersion(self.version));
+        }
+        let defaults = Self::default().costs();
+        for ((name, value), (_, default)) in
+            self.costs().into_iter().zip(defaults)
+        {
+            let min = default
+                .checked_div(GAS_SCHEDULE_MAX_ADJUSTMENT_FACTOR)
+                .unwrap_or_default()
+                .max(1);
+            let max =
+                default.saturating_mul(GAS_SCHEDULE_MAX_ADJUSTMENT_FACTOR);
+            if value < min || value > max {
+                return Err(GasScheduleError::OutOfBounds {
+                    name,
+                    value,
+                    min,
+                    max,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The named costs of the schedule
+    fn costs(&self) -> [(&'static str, u64); 23] {
+        [
+            ("compile_gas_per_byte", self.compile_gas_per_byte),
+            (
+                "wasm_code_validation_gas_per_byte",
+                self.wasm_code_validation_gas_per_byte,
+            ),
+            ("wrapper_tx_validation_gas", self.wrapper_tx_validation_gas),
+            (
+                "storage_occupation_gas_per_byte",
+                self.storage_occupation_gas_per_byte,
+            ),
+            (
+                "network_transmission_gas_per_byte",
+                self.network_transmission_gas_per_byte,
+            ),
+            (
+                "memory_access_gas_per_byte",
+                self.memory_access_gas_per_byte,
+            ),
+            (
+                "storage_access_gas_per_byte",
+                self.storage_access_gas_per_byte,
+            ),
+            (
+                "storage_write_gas_per_byte",
+                self.storage_write_gas_per_byte,
+            ),
+            (
+                "storage_delete_gas_per_byte",
+                self.storage_delete_gas_per_byte,
+            ),
+            ("verify_tx_sig_gas", self.verify_tx_sig_gas),
+            ("ibc_action_validate_gas", self.ibc_action_validate_gas),
+            ("ibc_action_execute_gas", self.ibc_action_execute_gas),
+            ("masp_verify_sig_gas", self.masp_verify_sig_gas),
+            ("masp_fixed_spend_gas", self.masp_fixed_spend_gas),
+            ("masp_variable_spend_gas", self.masp_variable_spend_gas),
+            ("masp_fixed_convert_gas", self.masp_fixed_convert_gas),
+            ("masp_variable_convert_gas", self.masp_variable_convert_gas),
+            ("masp_fixed_output_gas", self.masp_fixed_output_gas),
+            ("masp_variable_output_gas", self.masp_variable_output_gas),
+            ("masp_spend_check_gas", self.masp_spend_check_gas),
+            ("masp_convert_check_gas", self.masp_convert_check_gas),
+            ("masp_output_check_gas", self.masp_output_check_gas),
+            ("masp_final_check_gas", self.masp_final_check_gas),
+        ]
+    }
+}
+
 /// Representation of tracking gas in sub-units.
 ///
 /// This effectively decouples gas metering from fee payment, allowing higher
@@ -343,11 +559,15 @@ pub trait GasMetering {
     /// will still be updated
     fn consume(&mut self, gas: Gas) -> Result<()>;
 
+    /// Get the gas schedule used to price the operations
+    fn gas_schedule(&self) -> &GasSchedule;
+
     /// Add the compiling cost proportionate to the code length
     fn add_compiling_gas(&mut self, bytes_len: u64) -> Result<()> {
+        let cost = self.gas_schedule().compile_gas_per_byte;
         self.consume(
             bytes_len
-                .checked_mul(COMPILE_GAS_PER_BYTE)
+                .checked_mul(cost)
                 .ok_or(Error::GasOverflow)?
                 .into(),
         )
@@ -355,9 +575,10 @@ pub trait GasMetering {
 
     /// Add the gas for loading the wasm code from storage
     fn add_wasm_load_from_storage_gas(&mut self, bytes_len: u64) -> Result<()> {
+        let cost = self.gas_schedule().storage_access_gas_per_byte;
         self.consume(
             bytes_len
-                .checked_mul(STORAGE_ACCESS_GAS_PER_BYTE)
+                .checked_mul(cost)
                 .ok_or(Error::GasOverflow)?
                 .into(),
         )
@@ -365,9 +586,10 @@ pub trait GasMetering {
 
     /// Add the gas for validating untrusted wasm code
     fn add_wasm_validation_gas(&mut self, bytes_len: u64) -> Result<()> {
+        let cost = self.gas_schedule().wasm_code_validation_gas_per_byte;
         self.consume(
             bytes_len
-                .checked_mul(WASM_CODE_VALIDATION_GAS_PER_BYTE)
+                .checked_mul(cost)
                 .ok_or(Error::GasOverflow)?
                 .into(),
         )
@@ -379,6 +601,16 @@ pub trait GasMetering {
     /// Get the gas limit
     fn get_gas_limit(&self) -> Gas;
 
+    /// Get the amount of gas still available before reaching the limit
+    fn get_available_gas(&self) -> Gas;
+
+    /// Check if gas tracing is enabled
+    fn is_tracing(&self) -> bool;
+
+    /// Record the gas charged by a call to a host function. This is a no-op
+    /// unless tracing is enabled.
+    fn trace_host_call(&mut self, name: &str, memory: MemoryAccess, gas: Gas);
+
     /// Check if the vps went out of gas. Starts with the gas consumed by the
     /// transaction.
     fn check_vps_limit(&self, vps_gas: Gas) -> Result<()> {
@@ -403,6 +635,11 @@ pub struct TxGasMeter {
     /// The gas limit for a transaction
     pub tx_gas_limit: Gas,
     transaction_gas: Gas,
+    /// The gas schedule used to price the operations
+    gas_schedule: GasSchedule,
+    /// The gas trace, only present when tracing is enabled. Behind a lock,
+    /// because the VPs are traced in parallel.
+    trace: Option<Mutex<GasTrace>>,
 }
 
 /// Gas metering in a validity predicate
@@ -416,6 +653,10 @@ pub struct VpGasMeter {
     initial_gas: Gas,
     /// The current gas usage in the VP
     current_gas: Gas,
+    /// The gas schedule used to price the operations
+    gas_schedule: GasSchedule,
+    /// The trace of the VP execution, only present when tracing is enabled
+    trace: Option<ExecutionTrace>,
 }
 
 impl GasMetering for TxGasMeter {
@@ -454,16 +695,86 @@ impl GasMetering for TxGasMeter {
     fn get_gas_limit(&self) -> Gas {
         self.tx_gas_limit.clone()
     }
+
+    fn get_available_gas(&self) -> Gas {
+        self.tx_gas_limit
+            .checked_sub(self.transaction_gas.clone())
+            .unwrap_or_default()
+    }
+
+    fn gas_schedule(&self) -> &GasSchedule {
+        &self.gas_schedule
+    }
+
+    fn is_tracing(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    fn trace_host_call(&mut self, name: &str, memory: MemoryAccess, gas: Gas) {
+        if let Some(trace) = self.trace.as_mut() {
+            trace
+                .get_mut()
+                .expect("Gas trace lock should not be poisoned")
+                .tx
+                .record(name, memory, gas);
+        }
+    }
 }
 
 impl TxGasMeter {
-    /// Initialize a new Tx gas meter. Requires a gas limit for the specific
-    /// wrapper transaction
+    /// Initialize a new Tx gas meter with the default gas schedule. Requires a
+    /// gas limit for the specific wrapper transaction
     pub fn new(tx_gas_limit: impl Into<Gas>) -> Self {
+        Self::new_with_schedule(tx_gas_limit, GasSchedule::default())
+    }
+
+    /// Initialize a new Tx gas meter pricing operations with the given gas
+    /// schedule. Requires a gas limit for the specific wrapper transaction
+    pub fn new_with_schedule(
+        tx_gas_limit: impl Into<Gas>,
+        gas_schedule: GasSchedule,
+    ) -> Self {
         Self {
             gas_overflow: false,
             tx_gas_limit: tx_gas_limit.into(),
             transaction_gas: Gas::default(),
+            gas_schedule,
+            trace: None,
+        }
+    }
+
+    /// Enable tracing of the gas consumption. Only meant to be used for
+    /// dry-runs and tests.
+    pub fn enable_tracing(&mut self) {
+        self.trace = Some(Mutex::new(GasTrace::default()));
+    }
+
+    /// Get the recorded gas trace, if tracing is enabled
+    pub fn gas_trace(&self) -> Option<GasTrace> {
+        self.trace.as_ref().map(|trace| {
+            let mut trace = trace
+                .lock()
+                .expect("Gas trace lock should not be poisoned")
+                .clone();
+            // VPs are traced in parallel, sort them for a stable output
+            trace.vps.sort_by(|a, b| a.address.cmp(&b.address));
+            trace
+        })
+    }
+
+    /// Record the gas consumed by the VP of the given address. This is a
+    /// no-op unless tracing is enabled.
+    pub fn trace_vp(&self, address: &Address, vp_gas_meter: &VpGasMeter) {
+        if let Some(trace) = self.trace.as_ref() {
+            trace
+                .lock()
+                .expect("Gas trace lock should not be poisoned")
+                .vps
+                .push(VpTrace {
+                    address: address.clone(),
+                    gas: vp_gas_meter.get_vp_consumed_gas(),
+                    execution: vp_gas_meter.trace.clone().unwrap_or_default(),
+                });
         }
     }
 
@@ -473,26 +784,21 @@ impl TxGasMeter {
     ///  - cost of downloading (as part of the block) the transaction bytes over
     ///    the network
     pub fn add_wrapper_gas(&mut self, tx_bytes: &[u8]) -> Result<()> {
-        self.consume(WRAPPER_TX_VALIDATION_GAS.into())?;
+        self.consume(self.gas_schedule.wrapper_tx_validation_gas.into())?;
 
         let bytes_len = tx_bytes.len() as u64;
+        let cost_per_byte = self
+            .gas_schedule
+            .storage_occupation_gas_per_byte
+            .checked_add(self.gas_schedule.network_transmission_gas_per_byte)
+            .ok_or(Error::GasOverflow)?;
         self.consume(
             bytes_len
-                .checked_mul(
-                    STORAGE_OCCUPATION_GAS_PER_BYTE
-                        + NETWORK_TRANSMISSION_GAS_PER_BYTE,
-                )
+                .checked_mul(cost_per_byte)
                 .ok_or(Error::GasOverflow)?
                 .into(),
         )
     }
-
-    /// Get the amount of gas still available to the transaction
-    pub fn get_available_gas(&self) -> Gas {
-        self.tx_gas_limit
-            .checked_sub(self.transaction_gas.clone())
-            .unwrap_or_default()
-    }
 }
 
 impl GasMetering for VpGasMeter {
@@ -536,6 +842,27 @@ impl GasMetering for VpGasMeter {
     fn get_gas_limit(&self) -> Gas {
         self.tx_gas_limit.clone()
     }
+
+    fn get_available_gas(&self) -> Gas {
+        self.initial_gas
+            .checked_add(self.current_gas.clone())
+            .and_then(|consumed| self.tx_gas_limit.checked_sub(consumed))
+            .unwrap_or_default()
+    }
+
+    fn gas_schedule(&self) -> &GasSchedule {
+        &self.gas_schedule
+    }
+
+    fn is_tracing(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    fn trace_host_call(&mut self, name: &str, memory: MemoryAccess, gas: Gas) {
+        if let Some(trace) = self.trace.as_mut() {
+            trace.record(name, memory, gas);
+        }
+    }
 }
 
 impl VpGasMeter {
@@ -546,6 +873,8 @@ impl VpGasMeter {
             tx_gas_limit: tx_gas_meter.tx_gas_limit.clone(),
             initial_gas: tx_gas_meter.transaction_gas.clone(),
             current_gas: Gas::default(),
+            gas_schedule: tx_gas_meter.gas_schedule.clone(),
+            trace: tx_gas_meter.is_tracing().then(ExecutionTrace::default),
         }
     }
 
@@ -571,6 +900,8 @@ mod tests {
                 gas_overflow: false,
                 tx_gas_limit: BLOCK_GAS_LIMIT.into(),
                 transaction_gas: Gas::default(),
+                gas_schedule: GasSchedule::default(),
+                trace: None,
             };
             let mut meter = VpGasMeter::new_from_tx_meter(&tx_gas_meter);
             meter.consume(gas.into()).expect("cannot add the gas");
@@ -584,6 +915,8 @@ mod tests {
             gas_overflow: false,
             tx_gas_limit: BLOCK_GAS_LIMIT.into(),
             transaction_gas: (TX_GAS_LIMIT - 1).into(),
+            gas_schedule: GasSchedule::default(),
+            trace: None,
         };
         let mut meter = VpGasMeter::new_from_tx_meter(&tx_gas_meter);
         assert_matches!(
@@ -600,6 +933,8 @@ mod tests {
             gas_overflow: false,
             tx_gas_limit: TX_GAS_LIMIT.into(),
             transaction_gas: (TX_GAS_LIMIT - 1).into(),
+            gas_schedule: GasSchedule::default(),
+            trace: None,
         };
         let mut meter = VpGasMeter::new_from_tx_meter(&tx_gas_meter);
         assert_matches!(
@@ -632,4 +967,106 @@ mod tests {
             Error::TransactionGasExceededError(_)
         );
     }
+
+    #[test]
+    fn test_gas_schedule_validation() {
+        GasSchedule::default()
+            .validate()
+            .expect("the default gas schedule must be valid");
+
+        let schedule = GasSchedule {
+            version: GAS_SCHEDULE_VERSION + 1,
+            ..Default::default()
+        };
+        assert_matches!(
+            schedule.validate(),
+            Err(GasScheduleError::UnsupportedVersion(_))
+        );
+
+        let schedule = GasSchedule {
+            verify_tx_sig_gas: 0,
+            ..Default::default()
+        };
+        assert_matches!(
+            schedule.validate(),
+            Err(GasScheduleError::OutOfBounds {
+                name: "verify_tx_sig_gas",
+                ..
+            })
+        );
+
+        let schedule = GasSchedule {
+            storage_write_gas_per_byte: STORAGE_WRITE_GAS_PER_BYTE
+                * GAS_SCHEDULE_MAX_ADJUSTMENT_FACTOR
+                + 1,
+            ..Default::default()
+        };
+        assert_matches!(
+            schedule.validate(),
+            Err(GasScheduleError::OutOfBounds {
+                name: "storage_write_gas_per_byte",
+                ..
+            })
+        );
+
+        let schedule = GasSchedule {
+            storage_write_gas_per_byte: STORAGE_WRITE_GAS_PER_BYTE * 2,
+            masp_final_check_gas: MASP_FINAL_CHECK_GAS / 2,
+            ..Default::default()
+        };
+        schedule
+            .validate()
+            .expect("the gas schedule should be valid");
+    }
+
+    #[test]
+    fn test_gas_meters_use_schedule() {
+        let schedule = GasSchedule {
+            wrapper_tx_validation_gas: 1,
+            storage_occupation_gas_per_byte: 2,
+            network_transmission_gas_per_byte: 3,
+            compile_gas_per_byte: 4,
+            ..Default::default()
+        };
+        let mut meter =
+            TxGasMeter::new_with_schedule(TX_GAS_LIMIT, schedule.clone());
+        meter.add_wrapper_gas(&[0; 10]).unwrap();
+        assert_eq!(meter.get_tx_consumed_gas(), Gas::from(1 + 10 * (2 + 3)));
+
+        let mut vp_meter = VpGasMeter::new_from_tx_meter(&meter);
+        assert_eq!(vp_meter.gas_schedule(), &schedule);
+        vp_meter.add_compiling_gas(10).unwrap();
+        assert_eq!(vp_meter.get_vp_consumed_gas(), Gas::from(40));
+    }
+
+    #[test]
+    fn test_gas_meters_tracing() {
+        let memory = MemoryAccess {
+            read_bytes: 8,
+            written_bytes: 0,
+        };
+        let mut tx_meter = TxGasMeter::new(TX_GAS_LIMIT);
+        assert!(!tx_meter.is_tracing());
+        tx_meter.trace_host_call("namada_tx_read", memory, 10.into());
+        assert!(tx_meter.gas_trace().is_none());
+
+        tx_meter.enable_tracing();
+        tx_meter.trace_host_call("namada_tx_read", memory, 10.into());
+
+        let mut vp_meter = VpGasMeter::new_from_tx_meter(&tx_meter);
+        assert!(vp_meter.is_tracing());
+        vp_meter.consume(30.into()).unwrap();
+        vp_meter.trace_host_call("namada_vp_read_pre", memory, 20.into());
+        let address = Address::Internal(
+            namada_core::address::InternalAddress::Parameters,
+        );
+        tx_meter.trace_vp(&address, &vp_meter);
+
+        let trace = tx_meter.gas_trace().unwrap();
+        assert_eq!(trace.tx.host_calls.len(), 1);
+        assert_eq!(trace.vps.len(), 1);
+        assert_eq!(trace.vps[0].address, address);
+        assert_eq!(trace.vps[0].gas, Gas::from(30));
+        assert_eq!(trace.vps[0].execution.host_calls.len(), 1);
+    }
 }
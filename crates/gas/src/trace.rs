@@ -0,0 +1,251 @@
+//! Opt-in tracing of the gas consumed by transactions and validity
+//! predicates. The tracing is only meant to be enabled for dry-runs and tests,
+//! to help with profiling the gas usage of wasm code.
+
+use std::collections::BTreeMap;
+
+use namada_core::address::Address;
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::Gas;
+
+/// The name of the host function injected into wasm code to charge gas for
+/// the executed instructions
+pub const WASM_GAS_HOOK: &str = "gas";
+
+/// The sizes of the data passed between the wasm code and the host
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct MemoryAccess {
+    /// The number of bytes read from the wasm memory, e.g. the storage keys
+    /// and values passed to the host
+    pub read_bytes: u64,
+    /// The number of bytes written to the wasm memory, e.g. the values read
+    /// from storage returned to the wasm code
+    pub written_bytes: u64,
+}
+
+impl MemoryAccess {
+    /// The bytes accessed since the given earlier total
+    pub fn since(self, earlier: Self) -> Self {
+        Self {
+            read_bytes: self.read_bytes.saturating_sub(earlier.read_bytes),
+            written_bytes: self
+                .written_bytes
+                .saturating_sub(earlier.written_bytes),
+        }
+    }
+}
+
+/// A call to a host function
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct HostCallTrace {
+    /// The name of the host function
+    pub name: String,
+    /// The data passed between the wasm code and the host during the call
+    pub memory: MemoryAccess,
+    /// The gas charged during the call
+    pub gas: Gas,
+}
+
+/// The gas consumed by the execution of some wasm code
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct ExecutionTrace {
+    /// The gas charged for the executed wasm instructions
+    pub wasm_gas: Gas,
+    /// The calls to host functions, in order of execution
+    pub host_calls: Vec<HostCallTrace>,
+}
+
+impl ExecutionTrace {
+    /// Record a call to a host function. The calls to the [`WASM_GAS_HOOK`]
+    /// are accumulated in `wasm_gas` instead.
+    pub fn record(&mut self, name: &str, memory: MemoryAccess, gas: Gas) {
+        if name == WASM_GAS_HOOK {
+            self.wasm_gas = self
+                .wasm_gas
+                .checked_add(gas)
+                .unwrap_or_else(|| u64::MAX.into());
+        } else {
+            self.host_calls.push(HostCallTrace {
+                name: name.to_string(),
+                memory,
+                gas,
+            });
+        }
+    }
+
+    /// The total gas recorded in this trace
+    pub fn total_gas(&self) -> Gas {
+        self.host_calls
+            .iter()
+            .try_fold(self.wasm_gas.clone(), |acc, call| {
+                acc.checked_add(call.gas.clone())
+            })
+            .unwrap_or_else(|| u64::MAX.into())
+    }
+
+    fn fold_stacks(&self, prefix: &str, stacks: &mut BTreeMap<String, u64>) {
+        add_stack(stacks, format!("{prefix};wasm"), &self.wasm_gas);
+        for call in &self.host_calls {
+            add_stack(stacks, format!("{prefix};{}", call.name), &call.gas);
+        }
+    }
+}
+
+/// The gas consumed by a validity predicate
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct VpTrace {
+    /// The address of the account whose VP was triggered
+    pub address: Address,
+    /// The total gas consumed by the VP
+    pub gas: Gas,
+    /// The trace of the VP's wasm execution, empty for native VPs
+    pub execution: ExecutionTrace,
+}
+
+/// A trace of the gas consumed by a transaction, including all the inner txs
+/// of a batch, and the validity predicates it triggered
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct GasTrace {
+    /// The trace of the transaction's wasm execution
+    pub tx: ExecutionTrace,
+    /// The traces of the validity predicates, ordered by address
+    pub vps: Vec<VpTrace>,
+}
+
+impl GasTrace {
+    /// Render the trace in the folded stacks format consumed by flamegraph
+    /// tools, e.g. `inferno-flamegraph`. The gas is given in sub-units.
+    pub fn to_folded_stacks(&self) -> String {
+        let mut stacks = BTreeMap::new();
+        self.tx.fold_stacks("tx", &mut stacks);
+        for vp in &self.vps {
+            let prefix = format!("vp:{}", vp.address);
+            vp.execution.fold_stacks(&prefix, &mut stacks);
+            // Gas charged outside of the wasm execution, e.g. for loading and
+            // compiling the code or by native VPs
+            let other = vp
+                .gas
+                .checked_sub(vp.execution.total_gas())
+                .unwrap_or_default();
+            add_stack(&mut stacks, format!("{prefix};other"), &other);
+        }
+        stacks
+            .into_iter()
+            .filter(|(_, gas)| *gas != 0)
+            .map(|(stack, gas)| format!("{stack} {gas}\n"))
+            .collect()
+    }
+}
+
+fn add_stack(stacks: &mut BTreeMap<String, u64>, stack: String, gas: &Gas) {
+    let total = stacks.entry(stack).or_default();
+    *total = total.saturating_add(gas.clone().into());
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::address::InternalAddress;
+
+    use super::*;
+
+    #[test]
+    fn test_execution_trace_record() {
+        let mut trace = ExecutionTrace::default();
+        let memory = MemoryAccess {
+            read_bytes: 32,
+            written_bytes: 0,
+        };
+        trace.record(WASM_GAS_HOOK, MemoryAccess::default(), 10.into());
+        trace.record("namada_tx_read", memory, 100.into());
+        trace.record(WASM_GAS_HOOK, MemoryAccess::default(), 5.into());
+
+        assert_eq!(trace.wasm_gas, 15.into());
+        assert_eq!(
+            trace.host_calls,
+            vec![HostCallTrace {
+                name: "namada_tx_read".to_string(),
+                memory,
+                gas: 100.into(),
+            }]
+        );
+        assert_eq!(trace.total_gas(), 115.into());
+    }
+
+    #[test]
+    fn test_folded_stacks() {
+        let mut tx = ExecutionTrace::default();
+        let memory = MemoryAccess::default();
+        tx.record(WASM_GAS_HOOK, memory, 7.into());
+        tx.record("namada_tx_read", memory, 10.into());
+        tx.record("namada_tx_read", memory, 10.into());
+        tx.record("namada_tx_write", memory, 30.into());
+        let mut vp = ExecutionTrace::default();
+        vp.record("namada_vp_read_pre", memory, 20.into());
+        let address = Address::Internal(InternalAddress::Ibc);
+        let trace = GasTrace {
+            tx,
+            vps: vec![VpTrace {
+                address: address.clone(),
+                gas: 50.into(),
+                execution: vp,
+            }],
+        };
+
+        let expected = format!(
+            "tx;namada_tx_read 20\ntx;namada_tx_write 30\ntx;wasm 7\n\
+             vp:{address};namada_vp_read_pre 20\nvp:{address};other 30\n"
+        );
+        assert_eq!(trace.to_folded_stacks(), expected);
+    }
+}
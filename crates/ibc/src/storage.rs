@@ -305,18 +305,37 @@ pub fn client_update_height_key(client_id: &ClientId) -> Key {
 
 /// Returns a key for the NFT class
 pub fn nft_class_key(class_id: &PrefixedClassId) -> Key {
-    let ibc_token = ibc_token(class_id.to_string());
+    nft_class_key_by_token(&ibc_token(class_id.to_string()))
+}
+
+/// Returns a key for the NFT class with the IBC token of the class ID
+pub fn nft_class_key_by_token(ibc_token: &Address) -> Key {
     let path = format!("{NFT_CLASS}/{ibc_token}");
     ibc_key(path).expect("Creating a key for the NFT class shouldn't fail")
 }
 
+/// Returns a prefix of all the NFT classes
+pub fn nft_class_prefix() -> Key {
+    ibc_key(NFT_CLASS).expect("Creating a key for the NFT class shouldn't fail")
+}
+
 /// Returns a key for the NFT metadata
 pub fn nft_metadata_key(class_id: &PrefixedClassId, token_id: &TokenId) -> Key {
-    let ibc_token = ibc_token_for_nft(class_id, token_id);
+    nft_metadata_key_by_token(&ibc_token_for_nft(class_id, token_id))
+}
+
+/// Returns a key for the NFT metadata with the IBC token of the NFT
+pub fn nft_metadata_key_by_token(ibc_token: &Address) -> Key {
     let path = format!("{NFT_METADATA}/{ibc_token}");
     ibc_key(path).expect("Creating a key for the NFT metadata shouldn't fail")
 }
 
+/// Returns a prefix of all the NFT metadata
+pub fn nft_metadata_prefix() -> Key {
+    ibc_key(NFT_METADATA)
+        .expect("Creating a key for the NFT metadata shouldn't fail")
+}
+
 /// Returns a client ID from the given client key `#IBC/clients/<client_id>`
 pub fn client_id(key: &Key) -> Result<ClientId> {
     match &key.segments[..] {
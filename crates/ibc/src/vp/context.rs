@@ -9,7 +9,6 @@ use namada_core::chain::{BlockHeader, BlockHeight, ChainId, Epoch, Epochs};
 use namada_core::collections::{HashMap, HashSet};
 use namada_core::storage::{Key, TxIndex};
 use namada_events::Event;
-use namada_gas::MEMORY_ACCESS_GAS_PER_BYTE;
 use namada_state::write_log::StorageModification;
 pub use namada_state::Result;
 use namada_state::{Error, PrefixIter, StateRead, StorageRead, StorageWrite};
@@ -83,6 +82,16 @@ where
     }
 }
 
+impl<'view, 'a, S, CA, EVAL> PseudoExecutionStorage<'view, 'a, S, CA, EVAL>
+where
+    S: 'static + StateRead,
+{
+    /// The cost of accessing the temporary store, per byte
+    fn memory_access_gas_per_byte(&self) -> u64 {
+        self.ctx.ctx.state.gas_schedule().memory_access_gas_per_byte
+    }
+}
+
 impl<'view, 'a, S, CA, EVAL> StorageRead
     for PseudoExecutionStorage<'view, 'a, S, CA, EVAL>
 where
@@ -94,19 +103,16 @@ where
 Self: 'iter;
 
     fn read_bytes(&self, key: &Key) -> Result<Option<Vec<u8>>> {
+        let cost = self.memory_access_gas_per_byte();
         match self.store.get(key) {
             Some(StorageModification::Write { ref value }) => {
                 let gas = checked!(key.len() + value.len())? as u64;
-                self.ctx.ctx.charge_gas(
-                    checked!(gas * MEMORY_ACCESS_GAS_PER_BYTE)?.into(),
-                )?;
+                self.ctx.ctx.charge_gas(checked!(gas * cost)?.into())?;
                 Ok(Some(value.clone()))
             }
             Some(StorageModification::Delete) => {
                 let len = key.len() as u64;
-                self.ctx.ctx.charge_gas(
-                    checked!(len * MEMORY_ACCESS_GAS_PER_BYTE)?.into(),
-                )?;
+                self.ctx.ctx.charge_gas(checked!(len * cost)?.into())?;
                 Ok(None)
             }
             Some(StorageModification::InitAccount { .. }) => {
@@ -114,9 +120,7 @@ Self: 'iter;
             }
             None => {
                 let len = key.len() as u64;
-                self.ctx.ctx.charge_gas(
-                    checked!(len * MEMORY_ACCESS_GAS_PER_BYTE)?.into(),
-                )?;
+                self.ctx.ctx.charge_gas(checked!(len * cost)?.into())?;
                 self.ctx.read_bytes(key)
             }
         }
@@ -190,17 +194,15 @@ where
         let gas = checked!(key.len() + value.len())? as u64;
         self.store
             .insert(key.clone(), StorageModification::Write { value });
-        self.ctx
-            .ctx
-            .charge_gas(checked!(gas * MEMORY_ACCESS_GAS_PER_BYTE)?.into())
+        let cost = self.memory_access_gas_per_byte();
+        self.ctx.ctx.charge_gas(checked!(gas * cost)?.into())
     }
 
     fn delete(&mut self, key: &Key) -> Result<()> {
         self.store.insert(key.clone(), StorageModification::Delete);
         let len = key.len() as u64;
-        self.ctx
-            .ctx
-            .charge_gas(checked!(len * MEMORY_ACCESS_GAS_PER_BYTE)?.into())
+        let cost = self.memory_access_gas_per_byte();
+        self.ctx.ctx.charge_gas(checked!(len * cost)?.into())
     }
 }
 
@@ -17,7 +17,6 @@ use namada_core::address::Address;
 use namada_core::arith::checked;
 use namada_core::collections::HashSet;
 use namada_core::storage::Key;
-use namada_gas::{IBC_ACTION_EXECUTE_GAS, IBC_ACTION_VALIDATE_GAS};
 use namada_state::write_log::StorageModification;
 use namada_state::{Error, Result, StateRead};
 use namada_systems::trans_token::{self as token, Amount};
@@ -251,7 +250,8 @@ where
         let module = NftTransferModule::<_, Token>::new(ctx.clone());
         actions.add_transfer_module(module);
         // Charge gas for the expensive execution
-        self.ctx.charge_gas(IBC_ACTION_EXECUTE_GAS.into())?;
+        let cost = self.ctx.state.gas_schedule().ibc_action_execute_gas;
+        self.ctx.charge_gas(cost.into())?;
         actions.execute::<Transfer>(tx_data)?;
 
         let changed_ibc_keys: HashSet<&Key> =
@@ -306,7 +306,8 @@ where
         let module = NftTransferModule::<_, Token>::new(ctx);
         actions.add_transfer_module(module);
         // Charge gas for the expensive validation
-        self.ctx.charge_gas(IBC_ACTION_VALIDATE_GAS.into())?;
+        let cost = self.ctx.state.gas_schedule().ibc_action_validate_gas;
+        self.ctx.charge_gas(cost.into())?;
         Ok(actions.validate::<Transfer>(tx_data)?)
     }
 
@@ -0,0 +1,84 @@
+/*
+ * C bindings of the Namada light SDK, built with the `ffi` feature of the
+ * `namada_light_sdk` crate. See the `ffi` module of the crate for the
+ * documentation of the functions.
+ */
+
+#ifndef NAMADA_LIGHT_SDK_H
+#define NAMADA_LIGHT_SDK_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum NamadaStatus {
+    NAMADA_STATUS_OK = 0,
+    NAMADA_STATUS_NULL_POINTER = 1,
+    NAMADA_STATUS_INVALID_ARGUMENT = 2,
+    NAMADA_STATUS_INVALID_TX = 3,
+    NAMADA_STATUS_PANIC = 4,
+} NamadaStatus;
+
+typedef enum NamadaResponseKind {
+    NAMADA_RESPONSE_AMOUNT = 0,
+    NAMADA_RESPONSE_DENOMINATED_AMOUNT = 1,
+    NAMADA_RESPONSE_EPOCH = 2,
+    NAMADA_RESPONSE_BLOCK_HEIGHT = 3,
+    NAMADA_RESPONSE_ADDRESS = 4,
+    NAMADA_RESPONSE_DRY_RUN_RESULT = 5,
+} NamadaResponseKind;
+
+typedef struct NamadaBuffer {
+    uint8_t *data;
+    size_t len;
+} NamadaBuffer;
+
+typedef struct NamadaTx NamadaTx;
+
+const char *namada_last_error(void);
+
+NamadaStatus namada_tx_new(const char *chain_id, const char *expiration,
+                           const uint8_t *code_hash, const char *code_tag,
+                           const uint8_t *data, size_t data_len,
+                           NamadaTx **out);
+
+void namada_tx_free(NamadaTx *tx);
+
+NamadaStatus namada_tx_sign_bytes(const NamadaTx *tx, NamadaBuffer *out);
+
+NamadaStatus namada_tx_attach_signature(NamadaTx *tx, const char *public_key,
+                                        const char *signature);
+
+NamadaStatus namada_tx_attach_fee(NamadaTx *tx, const char *fee,
+                                  const char *token, const char *fee_payer,
+                                  uint64_t gas_limit);
+
+NamadaStatus namada_tx_fee_sign_bytes(const NamadaTx *tx, uint8_t *out);
+
+NamadaStatus namada_tx_attach_fee_signature(NamadaTx *tx,
+                                            const char *public_key,
+                                            const char *signature);
+
+NamadaStatus namada_tx_validate(const NamadaTx *tx);
+
+NamadaStatus namada_tx_to_bytes(const NamadaTx *tx, NamadaBuffer *out);
+
+NamadaStatus namada_sign_hash(const char *secret_key, const uint8_t *hash,
+                              char **out);
+
+NamadaStatus namada_decode_response(NamadaResponseKind kind,
+                                    const uint8_t *data, size_t data_len,
+                                    char **out);
+
+void namada_buffer_free(NamadaBuffer buffer);
+
+void namada_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* NAMADA_LIGHT_SDK_H */
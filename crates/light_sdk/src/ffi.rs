@@ -0,0 +1,933 @@
+//! C bindings of the light SDK, to build and sign transactions and to decode
+//! the responses of queries from other languages.
+//!
+//! A transaction is built with [`namada_tx_new`] and then handled through an
+//! opaque [`NamadaTx`] pointer, mirroring the builders of the
+//! [`transaction`](crate::transaction) module. Keys, signatures, addresses
+//! and amounts are passed in their string encodings. The data of the
+//! transaction is passed already borsh encoded.
+//!
+//! All the functions return a [`NamadaStatus`]. On failure, a description of
+//! the error can be read with [`namada_last_error`]. The memory allocated by
+//! the bindings must be released with the matching `namada_*_free` function.
+//!
+//! The C declarations of the bindings are in `include/namada_light_sdk.h`.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+
+use borsh::BorshDeserialize;
+use namada_sdk::address::Address;
+use namada_sdk::chain::{BlockHeight, ChainId, Epoch};
+use namada_sdk::hash::Hash;
+use namada_sdk::key::{common, SigScheme};
+use namada_sdk::time::DateTimeUtc;
+use namada_sdk::token::{self, DenominatedAmount};
+use namada_sdk::tx::data::{DryRunResult, GasLimit};
+use namada_sdk::tx::Tx;
+use serde::Serialize;
+
+use crate::transaction::{self, GlobalArgs};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// The status returned by the bindings
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamadaStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// An argument could not be parsed or decoded
+    InvalidArgument = 2,
+    /// The transaction is invalid
+    InvalidTx = 3,
+    /// The call panicked
+    Panic = 4,
+}
+
+/// The types of the query responses that can be decoded
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamadaResponseKind {
+    /// A raw token amount
+    Amount = 0,
+    /// A token amount with its denomination
+    DenominatedAmount = 1,
+    /// An epoch
+    Epoch = 2,
+    /// A block height
+    BlockHeight = 3,
+    /// An address
+    Address = 4,
+    /// The result of a dry run of a transaction
+    DryRunResult = 5,
+}
+
+/// A byte buffer allocated by the bindings, to be released with
+/// [`namada_buffer_free`]
+#[repr(C)]
+#[derive(Debug)]
+pub struct NamadaBuffer {
+    /// The bytes of the buffer
+    pub data: *mut u8,
+    /// The number of bytes of the buffer
+    pub len: usize,
+}
+
+impl NamadaBuffer {
+    fn new(bytes: Vec<u8>) -> Self {
+        let bytes = bytes.into_boxed_slice();
+        let len = bytes.len();
+        Self {
+            data: Box::into_raw(bytes).cast(),
+            len,
+        }
+    }
+}
+
+/// A transaction under construction, to be released with [`namada_tx_free`]
+#[derive(Debug)]
+pub struct NamadaTx(Tx);
+
+/// An error of a call, with its status and description
+struct FfiError(NamadaStatus, String);
+
+impl FfiError {
+    fn invalid_argument(name: &str, err: impl Display) -> Self {
+        Self(
+            NamadaStatus::InvalidArgument,
+            format!("Invalid argument `{name}`: {err}"),
+        )
+    }
+
+    fn null_pointer(name: &str) -> Self {
+        Self(
+            NamadaStatus::NullPointer,
+            format!("The argument `{name}` is a null pointer"),
+        )
+    }
+}
+
+/// Run the body of a binding, recording its error if any
+fn run(body: impl FnOnce() -> Result<(), FfiError>) -> NamadaStatus {
+    let result =
+        panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| {
+            Err(FfiError(
+                NamadaStatus::Panic,
+                "The call panicked".to_string(),
+            ))
+        });
+    match result {
+        Ok(()) => NamadaStatus::Ok,
+        Err(FfiError(status, msg)) => {
+            // Error messages never contain nul bytes
+            let msg = CString::new(msg).unwrap_or_default();
+            LAST_ERROR.with(|last| *last.borrow_mut() = Some(msg));
+            status
+        }
+    }
+}
+
+/// Read a nul-terminated UTF-8 string argument
+unsafe fn str_arg<'a>(
+    ptr: *const c_char,
+    name: &str,
+) -> Result<&'a str, FfiError> {
+    if ptr.is_null() {
+        return Err(FfiError::null_pointer(name));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|err| FfiError::invalid_argument(name, err))
+}
+
+/// Parse a nul-terminated string argument
+unsafe fn parse_arg<T>(ptr: *const c_char, name: &str) -> Result<T, FfiError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    str_arg(ptr, name)?
+        .parse()
+        .map_err(|err| FfiError::invalid_argument(name, err))
+}
+
+/// Read a byte buffer argument, which may only be null if it is empty
+unsafe fn bytes_arg<'a>(
+    ptr: *const u8,
+    len: usize,
+    name: &str,
+) -> Result<&'a [u8], FfiError> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if ptr.is_null() {
+        return Err(FfiError::null_pointer(name));
+    }
+    Ok(std::slice::from_raw_parts(ptr, len))
+}
+
+/// Read a 32 bytes hash argument
+unsafe fn hash_arg(ptr: *const u8, name: &str) -> Result<Hash, FfiError> {
+    let bytes = bytes_arg(ptr, namada_sdk::hash::HASH_LENGTH, name)?;
+    Hash::try_from(bytes).map_err(|err| FfiError::invalid_argument(name, err))
+}
+
+/// Get a mutable reference to the pointee of an argument
+unsafe fn mut_arg<'a, T>(
+    ptr: *mut T,
+    name: &str,
+) -> Result<&'a mut T, FfiError> {
+    ptr.as_mut().ok_or_else(|| FfiError::null_pointer(name))
+}
+
+/// Get a reference to the pointee of an argument
+unsafe fn ref_arg<'a, T>(ptr: *const T, name: &str) -> Result<&'a T, FfiError> {
+    ptr.as_ref().ok_or_else(|| FfiError::null_pointer(name))
+}
+
+/// Decode a borsh encoded value and encode it to JSON
+fn decode_to_json<T>(bytes: &[u8]) -> Result<String, FfiError>
+where
+    T: BorshDeserialize + Serialize,
+{
+    let value = T::try_from_slice(bytes)
+        .map_err(|err| FfiError::invalid_argument("data", err))?;
+    serde_json::to_string(&value)
+        .map_err(|err| FfiError::invalid_argument("data", err))
+}
+
+/// Get the description of the last error of the calling thread, or null if
+/// no call failed yet. The string is owned by the bindings and is valid until
+/// the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn namada_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |msg| msg.as_ptr())
+    })
+}
+
+/// Build a new transaction that executes the wasm code with the given hash
+/// and tag on the given borsh encoded data. The expiration is an RFC 3339
+/// date-time, or null for a transaction that never expires.
+///
+/// # Safety
+///
+/// The strings must be nul-terminated, `code_hash` must point to 32 bytes,
+/// `data` must point to `data_len` bytes and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_new(
+    chain_id: *const c_char,
+    expiration: *const c_char,
+    code_hash: *const u8,
+    code_tag: *const c_char,
+    data: *const u8,
+    data_len: usize,
+    out: *mut *mut NamadaTx,
+) -> NamadaStatus {
+    run(|| {
+        let out = mut_arg(out, "out")?;
+        let chain_id: ChainId = parse_arg(chain_id, "chain_id")?;
+        let expiration: Option<DateTimeUtc> = if expiration.is_null() {
+            None
+        } else {
+            Some(parse_arg(expiration, "expiration")?)
+        };
+        let args = GlobalArgs {
+            expiration,
+            code_hash: hash_arg(code_hash, "code_hash")?,
+            chain_id,
+        };
+        let code_tag = str_arg(code_tag, "code_tag")?;
+        let data = bytes_arg(data, data_len, "data")?;
+        let tx = transaction::build_tx_from_serialized(
+            args,
+            data.to_vec(),
+            code_tag.to_string(),
+        );
+        *out = Box::into_raw(Box::new(NamadaTx(tx)));
+        Ok(())
+    })
+}
+
+/// Release a transaction
+///
+/// # Safety
+///
+/// The transaction must have been created by [`namada_tx_new`] and must not
+/// be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_free(tx: *mut NamadaTx) {
+    if !tx.is_null() {
+        drop(Box::from_raw(tx));
+    }
+}
+
+/// Get the hashes of the transaction to sign, concatenated in a buffer of
+/// 32 bytes hashes
+///
+/// # Safety
+///
+/// `tx` must be a valid transaction and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_sign_bytes(
+    tx: *const NamadaTx,
+    out: *mut NamadaBuffer,
+) -> NamadaStatus {
+    run(|| {
+        let out = mut_arg(out, "out")?;
+        let tx = ref_arg(tx, "tx")?;
+        let hashes = transaction::get_sign_bytes(&tx.0);
+        *out =
+            NamadaBuffer::new(hashes.iter().flat_map(|hash| hash.0).collect());
+        Ok(())
+    })
+}
+
+/// Attach the signature of the given public key to the transaction
+///
+/// # Safety
+///
+/// `tx` must be a valid transaction and the strings nul-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_attach_signature(
+    tx: *mut NamadaTx,
+    public_key: *const c_char,
+    signature: *const c_char,
+) -> NamadaStatus {
+    run(|| {
+        let tx = mut_arg(tx, "tx")?;
+        let public_key: common::PublicKey =
+            parse_arg(public_key, "public_key")?;
+        let signature: common::Signature = parse_arg(signature, "signature")?;
+        tx.0 = transaction::attach_raw_signatures(
+            std::mem::take(&mut tx.0),
+            public_key,
+            signature,
+        );
+        Ok(())
+    })
+}
+
+/// Attach the fee data to the transaction. The fee is an amount of the token
+/// per unit of gas, e.g. `"0.01"`.
+///
+/// # Safety
+///
+/// `tx` must be a valid transaction and the strings nul-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_attach_fee(
+    tx: *mut NamadaTx,
+    fee: *const c_char,
+    token: *const c_char,
+    fee_payer: *const c_char,
+    gas_limit: u64,
+) -> NamadaStatus {
+    run(|| {
+        let tx = mut_arg(tx, "tx")?;
+        let fee: DenominatedAmount = parse_arg(fee, "fee")?;
+        let token: Address = parse_arg(token, "token")?;
+        let fee_payer: common::PublicKey = parse_arg(fee_payer, "fee_payer")?;
+        tx.0 = transaction::attach_fee(
+            std::mem::take(&mut tx.0),
+            fee,
+            token,
+            fee_payer,
+            GasLimit::from(gas_limit),
+        );
+        Ok(())
+    })
+}
+
+/// Write the 32 bytes hash of the fee data to sign to `out`
+///
+/// # Safety
+///
+/// `tx` must be a valid transaction and `out` must point to 32 writable
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_fee_sign_bytes(
+    tx: *const NamadaTx,
+    out: *mut u8,
+) -> NamadaStatus {
+    run(|| {
+        let tx = ref_arg(tx, "tx")?;
+        if out.is_null() {
+            return Err(FfiError::null_pointer("out"));
+        }
+        let hash = transaction::get_wrapper_sign_bytes(&tx.0);
+        std::ptr::copy_nonoverlapping(hash.0.as_ptr(), out, hash.0.len());
+        Ok(())
+    })
+}
+
+/// Attach the signature of the fee payer to the transaction
+///
+/// # Safety
+///
+/// `tx` must be a valid transaction and the strings nul-terminated.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_attach_fee_signature(
+    tx: *mut NamadaTx,
+    public_key: *const c_char,
+    signature: *const c_char,
+) -> NamadaStatus {
+    run(|| {
+        let tx = mut_arg(tx, "tx")?;
+        let public_key: common::PublicKey =
+            parse_arg(public_key, "public_key")?;
+        let signature: common::Signature = parse_arg(signature, "signature")?;
+        tx.0 = transaction::attach_fee_signature(
+            std::mem::take(&mut tx.0),
+            public_key,
+            signature,
+        );
+        Ok(())
+    })
+}
+
+/// Validate the signatures of the transaction
+///
+/// # Safety
+///
+/// `tx` must be a valid transaction.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_validate(
+    tx: *const NamadaTx,
+) -> NamadaStatus {
+    run(|| {
+        let tx = ref_arg(tx, "tx")?;
+        tx.0.validate_tx()
+            .map(|_| ())
+            .map_err(|err| FfiError(NamadaStatus::InvalidTx, err.to_string()))
+    })
+}
+
+/// Get the protobuf encoding of the transaction, to be broadcast to a node
+///
+/// # Safety
+///
+/// `tx` must be a valid transaction and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_to_bytes(
+    tx: *const NamadaTx,
+    out: *mut NamadaBuffer,
+) -> NamadaStatus {
+    run(|| {
+        let out = mut_arg(out, "out")?;
+        let tx = ref_arg(tx, "tx")?;
+        *out = NamadaBuffer::new(tx.0.to_bytes());
+        Ok(())
+    })
+}
+
+/// Sign a 32 bytes hash with the given secret key, writing the string
+/// encoding of the signature to `out`. The signature must be released with
+/// [`namada_string_free`].
+///
+/// # Safety
+///
+/// The secret key must be nul-terminated, `hash` must point to 32 bytes and
+/// `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn namada_sign_hash(
+    secret_key: *const c_char,
+    hash: *const u8,
+    out: *mut *mut c_char,
+) -> NamadaStatus {
+    run(|| {
+        let out = mut_arg(out, "out")?;
+        let secret_key: common::SecretKey =
+            parse_arg(secret_key, "secret_key")?;
+        let hash = hash_arg(hash, "hash")?;
+        let signature = common::SigScheme::sign(&secret_key, hash);
+        // The string encoding of a signature never contains nul bytes
+        *out = CString::new(signature.to_string())
+            .unwrap_or_default()
+            .into_raw();
+        Ok(())
+    })
+}
+
+/// Decode the borsh encoded response of a query to JSON, writing it to
+/// `out`. The JSON string must be released with [`namada_string_free`].
+///
+/// # Safety
+///
+/// `data` must point to `data_len` bytes and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn namada_decode_response(
+    kind: NamadaResponseKind,
+    data: *const u8,
+    data_len: usize,
+    out: *mut *mut c_char,
+) -> NamadaStatus {
+    run(|| {
+        let out = mut_arg(out, "out")?;
+        let data = bytes_arg(data, data_len, "data")?;
+        let json = match kind {
+            NamadaResponseKind::Amount => decode_to_json::<token::Amount>(data),
+            NamadaResponseKind::DenominatedAmount => {
+                decode_to_json::<DenominatedAmount>(data)
+            }
+            NamadaResponseKind::Epoch => decode_to_json::<Epoch>(data),
+            NamadaResponseKind::BlockHeight => {
+                decode_to_json::<BlockHeight>(data)
+            }
+            NamadaResponseKind::Address => decode_to_json::<Address>(data),
+            NamadaResponseKind::DryRunResult => {
+                decode_to_json::<DryRunResult>(data)
+            }
+        }?;
+        // JSON strings escape nul bytes
+        *out = CString::new(json).unwrap_or_default().into_raw();
+        Ok(())
+    })
+}
+
+/// Release a buffer
+///
+/// # Safety
+///
+/// The buffer must have been allocated by the bindings and must not be used
+/// after this call.
+#[no_mangle]
+pub unsafe extern "C" fn namada_buffer_free(buffer: NamadaBuffer) {
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            buffer.data,
+            buffer.len,
+        )));
+    }
+}
+
+/// Release a string
+///
+/// # Safety
+///
+/// The string must have been allocated by the bindings and must not be used
+/// after this call.
+#[no_mangle]
+pub unsafe extern "C" fn namada_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use borsh_ext::BorshSerializeExt;
+    use namada_sdk::address::testing::nam;
+    use namada_sdk::hash::HASH_LENGTH;
+    use namada_sdk::key::testing::{keypair_1, keypair_2};
+    use namada_sdk::key::RefTo;
+
+    use super::*;
+
+    fn c_string(string: impl ToString) -> CString {
+        CString::new(string.to_string()).unwrap()
+    }
+
+    fn empty_buffer() -> NamadaBuffer {
+        NamadaBuffer {
+            data: ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    /// Read a string allocated by the bindings and release it
+    unsafe fn take_string(string: *mut c_char) -> String {
+        assert!(!string.is_null());
+        let owned = CStr::from_ptr(string).to_str().unwrap().to_string();
+        namada_string_free(string);
+        owned
+    }
+
+    /// Read the bytes of a buffer allocated by the bindings and release it
+    unsafe fn take_buffer(buffer: NamadaBuffer) -> Vec<u8> {
+        assert!(!buffer.data.is_null());
+        let bytes =
+            std::slice::from_raw_parts(buffer.data, buffer.len).to_vec();
+        namada_buffer_free(buffer);
+        bytes
+    }
+
+    fn last_error() -> String {
+        let error = namada_last_error();
+        assert!(!error.is_null());
+        unsafe { CStr::from_ptr(error) }
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    /// Build a new transaction with the bindings
+    unsafe fn new_tx() -> *mut NamadaTx {
+        let chain_id = c_string(ChainId::default());
+        let code_tag = c_string("tx_transfer.wasm");
+        let code_hash = [1_u8; HASH_LENGTH];
+        let data = [1_u8, 2, 3];
+        let mut tx = ptr::null_mut();
+        let status = namada_tx_new(
+            chain_id.as_ptr(),
+            ptr::null(),
+            code_hash.as_ptr(),
+            code_tag.as_ptr(),
+            data.as_ptr(),
+            data.len(),
+            &mut tx,
+        );
+        assert_eq!(status, NamadaStatus::Ok);
+        assert!(!tx.is_null());
+        tx
+    }
+
+    /// Sign a hash with the bindings
+    unsafe fn sign(secret_key: &common::SecretKey, hash: *const u8) -> CString {
+        let secret_key = c_string(secret_key);
+        let mut signature = ptr::null_mut();
+        let status =
+            namada_sign_hash(secret_key.as_ptr(), hash, &mut signature);
+        assert_eq!(status, NamadaStatus::Ok);
+        c_string(take_string(signature))
+    }
+
+    /// Decode a response with the bindings
+    unsafe fn decode(
+        kind: NamadaResponseKind,
+        data: &[u8],
+    ) -> Result<String, NamadaStatus> {
+        let mut json = ptr::null_mut();
+        match namada_decode_response(kind, data.as_ptr(), data.len(), &mut json)
+        {
+            NamadaStatus::Ok => Ok(take_string(json)),
+            status => Err(status),
+        }
+    }
+
+    /// Test that a transaction built, signed and encoded with the bindings is
+    /// valid and decodes to the same transaction
+    #[test]
+    fn test_tx_round_trip() {
+        unsafe {
+            let tx = new_tx();
+
+            let mut sign_bytes = empty_buffer();
+            assert_eq!(
+                namada_tx_sign_bytes(tx, &mut sign_bytes),
+                NamadaStatus::Ok
+            );
+            let sign_bytes = take_buffer(sign_bytes);
+            assert_eq!(sign_bytes, (*tx).0.raw_header_hash().0.to_vec());
+            let signer = keypair_1();
+            let signature = sign(&signer, sign_bytes.as_ptr());
+            let public_key = c_string(signer.ref_to());
+            assert_eq!(
+                namada_tx_attach_signature(
+                    tx,
+                    public_key.as_ptr(),
+                    signature.as_ptr()
+                ),
+                NamadaStatus::Ok
+            );
+
+            let fee_payer = keypair_2();
+            let fee_payer_pk = c_string(fee_payer.ref_to());
+            let fee = c_string("0.01");
+            let token = c_string(nam());
+            assert_eq!(
+                namada_tx_attach_fee(
+                    tx,
+                    fee.as_ptr(),
+                    token.as_ptr(),
+                    fee_payer_pk.as_ptr(),
+                    100_000,
+                ),
+                NamadaStatus::Ok
+            );
+            // The fee payer didn't sign the transaction yet
+            assert_eq!(namada_tx_validate(tx), NamadaStatus::InvalidTx);
+            assert!(last_error().contains("signature"));
+
+            let mut fee_hash = [0_u8; HASH_LENGTH];
+            assert_eq!(
+                namada_tx_fee_sign_bytes(tx, fee_hash.as_mut_ptr()),
+                NamadaStatus::Ok
+            );
+            let fee_signature = sign(&fee_payer, fee_hash.as_ptr());
+            assert_eq!(
+                namada_tx_attach_fee_signature(
+                    tx,
+                    fee_payer_pk.as_ptr(),
+                    fee_signature.as_ptr()
+                ),
+                NamadaStatus::Ok
+            );
+            assert_eq!(namada_tx_validate(tx), NamadaStatus::Ok);
+
+            let mut bytes = empty_buffer();
+            assert_eq!(namada_tx_to_bytes(tx, &mut bytes), NamadaStatus::Ok);
+            let decoded = Tx::try_from_bytes(&take_buffer(bytes)).unwrap();
+            assert_eq!(decoded, (*tx).0);
+            assert!(decoded.validate_tx().unwrap().is_some());
+
+            namada_tx_free(tx);
+        }
+    }
+
+    /// Test that null and invalid arguments are rejected with their status
+    /// and error description
+    #[test]
+    fn test_invalid_arguments() {
+        unsafe {
+            let chain_id = c_string(ChainId::default());
+            let code_tag = c_string("tx_transfer.wasm");
+            let code_hash = [1_u8; HASH_LENGTH];
+            let new_tx_with =
+                |chain_id: *const c_char,
+                 expiration: *const c_char,
+                 data_len: usize,
+                 out: *mut *mut NamadaTx| {
+                    namada_tx_new(
+                        chain_id,
+                        expiration,
+                        code_hash.as_ptr(),
+                        code_tag.as_ptr(),
+                        ptr::null(),
+                        data_len,
+                        out,
+                    )
+                };
+            let mut tx = ptr::null_mut();
+
+            assert_eq!(
+                new_tx_with(ptr::null(), ptr::null(), 0, &mut tx),
+                NamadaStatus::NullPointer
+            );
+            assert_eq!(
+                last_error(),
+                "The argument `chain_id` is a null pointer"
+            );
+            let invalid = c_string("invalid");
+            assert_eq!(
+                new_tx_with(invalid.as_ptr(), ptr::null(), 0, &mut tx),
+                NamadaStatus::InvalidArgument
+            );
+            assert!(last_error().starts_with("Invalid argument `chain_id`"));
+            assert_eq!(
+                new_tx_with(chain_id.as_ptr(), invalid.as_ptr(), 0, &mut tx),
+                NamadaStatus::InvalidArgument
+            );
+            assert!(last_error().starts_with("Invalid argument `expiration`"));
+            assert_eq!(
+                new_tx_with(chain_id.as_ptr(), ptr::null(), 1, &mut tx),
+                NamadaStatus::NullPointer
+            );
+            assert_eq!(last_error(), "The argument `data` is a null pointer");
+            assert_eq!(
+                new_tx_with(chain_id.as_ptr(), ptr::null(), 0, ptr::null_mut()),
+                NamadaStatus::NullPointer
+            );
+            assert_eq!(last_error(), "The argument `out` is a null pointer");
+            assert!(tx.is_null());
+
+            let tx = new_tx();
+            let mut buffer = empty_buffer();
+            assert_eq!(
+                namada_tx_sign_bytes(ptr::null(), &mut buffer),
+                NamadaStatus::NullPointer
+            );
+            assert_eq!(
+                namada_tx_sign_bytes(tx, ptr::null_mut()),
+                NamadaStatus::NullPointer
+            );
+            assert_eq!(
+                namada_tx_attach_signature(
+                    tx,
+                    invalid.as_ptr(),
+                    invalid.as_ptr()
+                ),
+                NamadaStatus::InvalidArgument
+            );
+            assert!(last_error().starts_with("Invalid argument `public_key`"));
+            let public_key = c_string(keypair_1().ref_to());
+            assert_eq!(
+                namada_tx_attach_fee(
+                    tx,
+                    invalid.as_ptr(),
+                    invalid.as_ptr(),
+                    public_key.as_ptr(),
+                    100_000,
+                ),
+                NamadaStatus::InvalidArgument
+            );
+            assert!(last_error().starts_with("Invalid argument `fee`"));
+            assert_eq!(
+                namada_tx_fee_sign_bytes(tx, ptr::null_mut()),
+                NamadaStatus::NullPointer
+            );
+            assert_eq!(
+                namada_tx_attach_fee_signature(
+                    ptr::null_mut(),
+                    public_key.as_ptr(),
+                    invalid.as_ptr()
+                ),
+                NamadaStatus::NullPointer
+            );
+            assert_eq!(last_error(), "The argument `tx` is a null pointer");
+            assert_eq!(
+                namada_tx_attach_fee_signature(
+                    tx,
+                    public_key.as_ptr(),
+                    invalid.as_ptr()
+                ),
+                NamadaStatus::InvalidArgument
+            );
+            assert!(last_error().starts_with("Invalid argument `signature`"));
+            assert_eq!(
+                namada_tx_validate(ptr::null()),
+                NamadaStatus::NullPointer
+            );
+            assert_eq!(
+                namada_tx_to_bytes(tx, ptr::null_mut()),
+                NamadaStatus::NullPointer
+            );
+            // The failed calls left the transaction unchanged
+            assert_eq!(namada_tx_validate(tx), NamadaStatus::Ok);
+            namada_tx_free(tx);
+
+            let mut signature = ptr::null_mut();
+            assert_eq!(
+                namada_sign_hash(
+                    invalid.as_ptr(),
+                    code_hash.as_ptr(),
+                    &mut signature
+                ),
+                NamadaStatus::InvalidArgument
+            );
+            assert!(last_error().starts_with("Invalid argument `secret_key`"));
+            let secret_key = c_string(keypair_1());
+            assert_eq!(
+                namada_sign_hash(
+                    secret_key.as_ptr(),
+                    ptr::null(),
+                    &mut signature
+                ),
+                NamadaStatus::NullPointer
+            );
+            assert_eq!(last_error(), "The argument `hash` is a null pointer");
+            assert!(signature.is_null());
+        }
+    }
+
+    /// Test that the last error is recorded per thread and kept until the
+    /// next failing call
+    #[test]
+    fn test_last_error() {
+        std::thread::spawn(|| {
+            assert!(namada_last_error().is_null());
+            assert_eq!(
+                unsafe { namada_tx_validate(ptr::null()) },
+                NamadaStatus::NullPointer
+            );
+            assert_eq!(last_error(), "The argument `tx` is a null pointer");
+
+            let tx = unsafe { new_tx() };
+            assert_eq!(unsafe { namada_tx_validate(tx) }, NamadaStatus::Ok);
+            assert_eq!(last_error(), "The argument `tx` is a null pointer");
+            unsafe { namada_tx_free(tx) };
+        })
+        .join()
+        .unwrap();
+    }
+
+    /// Test the decoding of the query responses to JSON
+    #[test]
+    fn test_decode_response() {
+        unsafe {
+            let amount = token::Amount::native_whole(5);
+            let denominated = DenominatedAmount::native(amount);
+            for (kind, data, expected) in [
+                (
+                    NamadaResponseKind::Amount,
+                    amount.serialize_to_vec(),
+                    serde_json::to_string(&amount),
+                ),
+                (
+                    NamadaResponseKind::DenominatedAmount,
+                    denominated.serialize_to_vec(),
+                    serde_json::to_string(&denominated),
+                ),
+                (
+                    NamadaResponseKind::Epoch,
+                    Epoch(42).serialize_to_vec(),
+                    serde_json::to_string(&Epoch(42)),
+                ),
+                (
+                    NamadaResponseKind::BlockHeight,
+                    BlockHeight(7).serialize_to_vec(),
+                    serde_json::to_string(&BlockHeight(7)),
+                ),
+                (
+                    NamadaResponseKind::Address,
+                    nam().serialize_to_vec(),
+                    serde_json::to_string(&nam()),
+                ),
+            ] {
+                assert_eq!(decode(kind, &data), Ok(expected.unwrap()));
+            }
+
+            assert_eq!(
+                decode(NamadaResponseKind::Epoch, &[]),
+                Err(NamadaStatus::InvalidArgument)
+            );
+            assert_eq!(
+                decode(NamadaResponseKind::Address, &[0xff]),
+                Err(NamadaStatus::InvalidArgument)
+            );
+            assert!(last_error().starts_with("Invalid argument `data`"));
+            let epoch = Epoch(42).serialize_to_vec();
+            let mut json = ptr::null_mut();
+            assert_eq!(
+                namada_decode_response(
+                    NamadaResponseKind::Epoch,
+                    ptr::null(),
+                    8,
+                    &mut json
+                ),
+                NamadaStatus::NullPointer
+            );
+            assert_eq!(
+                namada_decode_response(
+                    NamadaResponseKind::Epoch,
+                    epoch.as_ptr(),
+                    epoch.len(),
+                    ptr::null_mut()
+                ),
+                NamadaStatus::NullPointer
+            );
+            assert!(json.is_null());
+        }
+    }
+
+    /// Test that the free functions accept null pointers and empty buffers
+    #[test]
+    fn test_free_null() {
+        unsafe {
+            namada_tx_free(ptr::null_mut());
+            namada_string_free(ptr::null_mut());
+            namada_buffer_free(empty_buffer());
+        }
+    }
+}
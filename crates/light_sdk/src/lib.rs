@@ -17,6 +17,13 @@
 //!
 //! Both the [`reading`] and [`writing`] modules are further divided into a
 //! blocking and asynchronous submodules.
+//!
+//! The C bindings of the transaction builders are in the `ffi` module, behind
+//! the `ffi` feature. To build them as a C library, run e.g.
+//! `cargo rustc -p namada_light_sdk --features ffi --crate-type cdylib`.
+//!
+//! The crate can also be built for `wasm32` targets with the default features
+//! disabled, in which case only the [`transaction`] module is available.
 
 #![doc(html_favicon_url = "https://dev.namada.net/master/favicon.png")]
 #![doc(html_logo_url = "https://dev.namada.net/master/rustdoc-logo.png")]
@@ -29,7 +36,11 @@
     clippy::print_stderr
 )]
 
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(not(target_family = "wasm"))]
 pub mod reading;
 pub mod transaction;
+#[cfg(not(target_family = "wasm"))]
 pub mod writing;
 pub use namada_sdk;
@@ -1,3 +1,4 @@
+use namada_sdk::account::{VestingSchedule, VpParams};
 use namada_sdk::address::Address;
 use namada_sdk::hash::Hash;
 use namada_sdk::key::common;
@@ -9,8 +10,10 @@ use super::{attach_fee, attach_fee_signature, GlobalArgs};
 use crate::transaction;
 
 const TX_INIT_ACCOUNT_WASM: &str = "tx_init_account.wasm";
+const TX_INIT_VESTING_ACCOUNT_WASM: &str = "tx_init_vesting_account.wasm";
 const TX_REVEAL_PK_WASM: &str = "tx_reveal_pk.wasm";
 const TX_UPDATE_ACCOUNT_WASM: &str = "tx_update_account.wasm";
+const TX_UPDATE_VP_PARAMS_WASM: &str = "tx_update_vp_params.wasm";
 
 /// Transaction to initialize an established account
 #[derive(Debug, Clone)]
@@ -94,6 +97,93 @@ impl InitAccount {
     }
 }
 
+/// Transaction to initialize an established vesting account, whose VP must be
+/// the user VP
+#[derive(Debug, Clone)]
+pub struct InitVestingAccount(Tx);
+
+impl InitVestingAccount {
+    /// Build a raw InitVestingAccount transaction from the given parameters
+    pub fn new(
+        public_keys: Vec<common::PublicKey>,
+        vp_code_hash: Hash,
+        threshold: u8,
+        vesting: VestingSchedule,
+        args: GlobalArgs,
+    ) -> Self {
+        let init_vesting_account = namada_sdk::account::InitVestingAccount {
+            account: namada_sdk::account::InitAccount {
+                public_keys,
+                vp_code_hash,
+                threshold,
+            },
+            vesting,
+        };
+
+        Self(transaction::build_tx(
+            args,
+            init_vesting_account,
+            TX_INIT_VESTING_ACCOUNT_WASM.to_string(),
+        ))
+    }
+
+    /// Get the bytes to sign for the given transaction
+    pub fn get_sign_bytes(&self) -> Vec<Hash> {
+        transaction::get_sign_bytes(&self.0)
+    }
+
+    /// Attach the provided signatures to the tx
+    pub fn attach_signatures(
+        self,
+        signer: common::PublicKey,
+        signature: common::Signature,
+    ) -> Self {
+        Self(transaction::attach_raw_signatures(
+            self.0, signer, signature,
+        ))
+    }
+
+    /// Attach the fee data to the tx
+    pub fn attach_fee(
+        self,
+        fee: DenominatedAmount,
+        token: Address,
+        fee_payer: common::PublicKey,
+        gas_limit: GasLimit,
+    ) -> Self {
+        Self(attach_fee(self.0, fee, token, fee_payer, gas_limit))
+    }
+
+    /// Get the bytes of the fee data to sign
+    pub fn get_fee_sig_bytes(&self) -> Hash {
+        transaction::get_wrapper_sign_bytes(&self.0)
+    }
+
+    /// Attach a signature of the fee to the tx
+    pub fn attach_fee_signature(
+        self,
+        signer: common::PublicKey,
+        signature: common::Signature,
+    ) -> Self {
+        Self(attach_fee_signature(self.0, signer, signature))
+    }
+
+    /// Generates the protobuf encoding of this transaction
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    /// Gets the inner transaction without the domain wrapper
+    pub fn payload(self) -> Tx {
+        self.0
+    }
+
+    /// Validate this wrapper transaction
+    pub fn validate_tx(&self) -> Result<Option<&Authorization>, TxError> {
+        self.0.validate_tx()
+    }
+}
+
 /// Transaction to reveal a public key to the ledger to validate signatures of
 /// an implicit account
 pub struct RevealPk(Tx);
@@ -247,3 +337,76 @@ impl UpdateAccount {
         self.0.validate_tx()
     }
 }
+
+/// Transaction to update the parameters of the VP of an established account
+pub struct UpdateVpParams(Tx);
+
+impl UpdateVpParams {
+    /// Build a raw UpdateVpParams transaction from the given parameters
+    pub fn new(addr: Address, vp_params: VpParams, args: GlobalArgs) -> Self {
+        let update_vp_params =
+            namada_sdk::account::UpdateVpParams { addr, vp_params };
+
+        Self(transaction::build_tx(
+            args,
+            update_vp_params,
+            TX_UPDATE_VP_PARAMS_WASM.to_string(),
+        ))
+    }
+
+    /// Get the bytes to sign for the given transaction
+    pub fn get_sign_bytes(&self) -> Vec<Hash> {
+        transaction::get_sign_bytes(&self.0)
+    }
+
+    /// Attach the provided signatures to the tx
+    pub fn attach_signatures(
+        self,
+        signer: common::PublicKey,
+        signature: common::Signature,
+    ) -> Self {
+        Self(transaction::attach_raw_signatures(
+            self.0, signer, signature,
+        ))
+    }
+
+    /// Attach the fee data to the tx
+    pub fn attach_fee(
+        self,
+        fee: DenominatedAmount,
+        token: Address,
+        fee_payer: common::PublicKey,
+        gas_limit: GasLimit,
+    ) -> Self {
+        Self(attach_fee(self.0, fee, token, fee_payer, gas_limit))
+    }
+
+    /// Get the bytes of the fee data to sign
+    pub fn get_fee_sig_bytes(&self) -> Hash {
+        transaction::get_wrapper_sign_bytes(&self.0)
+    }
+
+    /// Attach a signature of the fee to the tx
+    pub fn attach_fee_signature(
+        self,
+        signer: common::PublicKey,
+        signature: common::Signature,
+    ) -> Self {
+        Self(attach_fee_signature(self.0, signer, signature))
+    }
+
+    /// Generates the protobuf encoding of this transaction
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    /// Gets the inner transaction without the domain wrapper
+    pub fn payload(self) -> Tx {
+        self.0
+    }
+
+    /// Validate this wrapper transaction
+    pub fn validate_tx(&self) -> Result<Option<&Authorization>, TxError> {
+        self.0.validate_tx()
+    }
+}
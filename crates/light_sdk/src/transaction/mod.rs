@@ -2,6 +2,7 @@ use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use borsh::BorshSerialize;
+use borsh_ext::BorshSerializeExt;
 use namada_sdk::address::Address;
 use namada_sdk::chain::ChainId;
 use namada_sdk::hash::Hash;
@@ -28,12 +29,21 @@ pub struct GlobalArgs {
 }
 
 pub(in crate::transaction) fn build_tx(
+    args: GlobalArgs,
+    data: impl BorshSerialize,
+    code_tag: String,
+) -> Tx {
+    build_tx_from_serialized(args, data.serialize_to_vec(), code_tag)
+}
+
+/// Same as [`build_tx`], but with the data of the tx already serialized
+pub(crate) fn build_tx_from_serialized(
     GlobalArgs {
         expiration,
         code_hash,
         chain_id,
     }: GlobalArgs,
-    data: impl BorshSerialize,
+    data: Vec<u8>,
     code_tag: String,
 ) -> Tx {
     let mut inner_tx = Tx::new(chain_id, expiration);
@@ -41,16 +51,16 @@ pub(in crate::transaction) fn build_tx(
     inner_tx.header.timestamp =
         DateTimeUtc::from_str("2000-01-01T00:00:00Z").unwrap();
     inner_tx.add_code_from_hash(code_hash, Some(code_tag));
-    inner_tx.add_data(data);
+    inner_tx.add_serialized_data(data);
 
     inner_tx
 }
 
-pub(in crate::transaction) fn get_sign_bytes(tx: &Tx) -> Vec<Hash> {
+pub(crate) fn get_sign_bytes(tx: &Tx) -> Vec<Hash> {
     vec![tx.raw_header_hash()]
 }
 
-pub(in crate::transaction) fn get_wrapper_sign_bytes(tx: &Tx) -> Hash {
+pub(crate) fn get_wrapper_sign_bytes(tx: &Tx) -> Hash {
     let targets = tx.sechashes();
     // Commit to the given targets
     let partial = Authorization {
@@ -61,7 +71,7 @@ pub(in crate::transaction) fn get_wrapper_sign_bytes(tx: &Tx) -> Hash {
     partial.get_raw_hash()
 }
 
-pub(in crate::transaction) fn attach_raw_signatures(
+pub(crate) fn attach_raw_signatures(
     mut tx: Tx,
     signer: common::PublicKey,
     signature: common::Signature,
@@ -75,7 +85,7 @@ pub(in crate::transaction) fn attach_raw_signatures(
     tx
 }
 
-pub(in crate::transaction) fn attach_fee(
+pub(crate) fn attach_fee(
     mut tx: Tx,
     fee: DenominatedAmount,
     token: Address,
@@ -93,7 +103,7 @@ pub(in crate::transaction) fn attach_fee(
     tx
 }
 
-pub(in crate::transaction) fn attach_fee_signature(
+pub(crate) fn attach_fee_signature(
     mut tx: Tx,
     signer: common::PublicKey,
     signature: common::Signature,
@@ -80,6 +80,18 @@ impl BridgePoolTree {
         })
     }
 
+    /// Get the height at which the key was inserted, if
+    /// it is present in the tree.
+    ///
+    /// Will return an error if the key is malformed.
+    pub fn get_insertion_height(
+        &self,
+        key: &storage::Key,
+    ) -> Result<Option<BlockHeight>, Error> {
+        let hash = Self::parse_key(key)?;
+        Ok(self.leaves.get(&hash).cloned())
+    }
+
     /// Update the tree with a new value.
     ///
     /// Returns the new root if successful. Will
@@ -588,6 +600,11 @@ mod test_bridge_pool_tree {
         );
         assert_eq!(tree.root(), expected);
         assert_matches!(tree.get(&deleted_key), Err(_));
+        assert_matches!(tree.get_insertion_height(&deleted_key), Ok(None));
+        assert_matches!(
+            tree.get_insertion_height(&Key::from(&transfers[0])),
+            Ok(Some(BlockHeight(1)))
+        );
     }
 
     /// Test that parse key works correctly
@@ -613,6 +613,24 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
         self.tree(&store_type).subtree_get(&sub_key)
     }
 
+    /// Get the height at which a pending transfer was committed to the
+    /// Ethereum bridge pool tree, if it is present in the tree
+    pub fn get_bridge_pool_insertion_height(
+        &self,
+        key: &Key,
+    ) -> Result<Option<BlockHeight>> {
+        let (store_type, sub_key) = StoreType::sub_key(key)?;
+        if store_type != StoreType::BridgePool {
+            return Err(Error::StoreType(format!(
+                "Expected a Bridge pool key, got a key of the {store_type} \
+                 store: {key}"
+            )));
+        }
+        self.bridge_pool
+            .get_insertion_height(&sub_key)
+            .map_err(|err| Error::MerkleTree(err.to_string()))
+    }
+
     /// Update the tree with the given key and value
     pub fn update(&mut self, key: &Key, value: impl AsRef<[u8]>) -> Result<()> {
         let (store_type, sub_key) = StoreType::sub_key(key)?;
@@ -3,12 +3,16 @@
 use std::cell::RefCell;
 
 use namada_sdk::borsh::BorshSerializeExt;
-use namada_sdk::gas::{GasMetering, TxGasMeter};
+use namada_sdk::chain::BlockHeight;
+use namada_sdk::gas::trace::GasTrace;
+use namada_sdk::gas::{Gas, GasMetering, TxGasMeter};
 use namada_sdk::parameters;
 use namada_sdk::queries::{EncodedResponseQuery, RequestQuery};
 use namada_sdk::state::{
-    DBIter, Result, ResultExt, StorageHasher, TxIndex, DB,
+    self, DBIter, ReadConversionState, Result, ResultExt, State, StorageHasher,
+    TxIndex, TxWrites, DB,
 };
+use namada_sdk::tx::action::Read;
 use namada_sdk::tx::data::{DryRunResult, GasLimit, TxResult, TxType};
 use namada_sdk::tx::Tx;
 use namada_vm::wasm::{TxCache, VpCache};
@@ -29,10 +33,87 @@ where
     H: 'static + StorageHasher + Sync,
     CA: 'static + WasmCacheAccess + Sync,
 {
-    let tx = Tx::try_from_bytes(&request.data[..]).into_storage_result()?;
+    let (dry_run_result, _gas_trace, height) = execute_tx(
+        &mut state,
+        &mut vp_wasm_cache,
+        &mut tx_wasm_cache,
+        &request.data,
+        false,
+    )?;
+
+    Ok(EncodedResponseQuery {
+        data: dry_run_result.serialize_to_vec(),
+        proof: None,
+        info: Default::default(),
+        height,
+    })
+}
+
+/// Dry run a transaction with gas tracing enabled. Responds with the
+/// [`DryRunResult`] together with the [`GasTrace`] of the transaction's host
+/// function calls and of the triggered VPs.
+pub fn dry_run_tx_trace<D, H, CA>(
+    mut state: namada_sdk::state::TempWlState<'static, D, H>,
+    mut vp_wasm_cache: VpCache<CA>,
+    mut tx_wasm_cache: TxCache<CA>,
+    request: &RequestQuery,
+) -> Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+    CA: 'static + WasmCacheAccess + Sync,
+{
+    let (dry_run_result, gas_trace, height) = execute_tx(
+        &mut state,
+        &mut vp_wasm_cache,
+        &mut tx_wasm_cache,
+        &request.data,
+        true,
+    )?;
+
+    Ok(EncodedResponseQuery {
+        data: (dry_run_result, gas_trace.unwrap_or_default())
+            .serialize_to_vec(),
+        proof: None,
+        info: Default::default(),
+        height,
+    })
+}
+
+/// Execute the encoded transaction on the given state, optionally tracing its
+/// gas consumption. The changes of the transaction are left in the batch write
+/// log of the state.
+pub(crate) fn execute_tx<S, D, H, CA>(
+    state: &mut S,
+    vp_wasm_cache: &mut VpCache<CA>,
+    tx_wasm_cache: &mut TxCache<CA>,
+    tx_bytes: &[u8],
+    trace: bool,
+) -> Result<(DryRunResult, Option<GasTrace>, BlockHeight)>
+where
+    S: 'static
+        + State<D = D, H = H>
+        + Read<Err = state::Error>
+        + TxWrites
+        + ReadConversionState
+        + Sync,
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+    CA: 'static + WasmCacheAccess + Sync,
+{
+    let tx = Tx::try_from_bytes(tx_bytes).into_storage_result()?;
     tx.validate_tx().into_storage_result()?;
 
-    let gas_scale = parameters::get_gas_scale(&state)?;
+    let gas_scale = parameters::get_gas_scale(state)?;
+    let gas_schedule = state.gas_schedule().clone();
+    let new_gas_meter = |gas_limit: Gas| {
+        let mut tx_gas_meter =
+            TxGasMeter::new_with_schedule(gas_limit, gas_schedule);
+        if trace {
+            tx_gas_meter.enable_tracing();
+        }
+        RefCell::new(tx_gas_meter)
+    };
 
     // Wrapper dry run to allow estimating the entire gas cost of a transaction
     let (wrapper_hash, extended_tx_result, tx_gas_meter) =
@@ -42,17 +123,17 @@ where
                     .gas_limit
                     .as_scaled_gas(gas_scale)
                     .into_storage_result()?;
-                let tx_gas_meter = RefCell::new(TxGasMeter::new(gas_limit));
+                let tx_gas_meter = new_gas_meter(gas_limit);
                 let mut shell_params = ShellParams::new(
                     &tx_gas_meter,
-                    &mut state,
-                    &mut vp_wasm_cache,
-                    &mut tx_wasm_cache,
+                    state,
+                    vp_wasm_cache,
+                    tx_wasm_cache,
                 );
                 let tx_result = protocol::apply_wrapper_tx(
                     &tx,
                     &wrapper,
-                    &request.data,
+                    tx_bytes,
                     &TxIndex::default(),
                     &tx_gas_meter,
                     &mut shell_params,
@@ -66,14 +147,14 @@ where
             _ => {
                 // When dry running only the inner tx(s), use the max block gas
                 // as the gas limit
-                let max_block_gas = parameters::get_max_block_gas(&state)?;
+                let max_block_gas = parameters::get_max_block_gas(state)?;
                 let gas_limit = GasLimit::from(max_block_gas)
                     .as_scaled_gas(gas_scale)
                     .into_storage_result()?;
                 (
                     None,
                     TxResult::default().to_extended_result(None),
-                    RefCell::new(TxGasMeter::new(gas_limit)),
+                    new_gas_meter(gas_limit),
                 )
             }
         };
@@ -84,27 +165,26 @@ where
         extended_tx_result,
         TxIndex(0),
         &tx_gas_meter,
-        &mut state,
-        &mut vp_wasm_cache,
-        &mut tx_wasm_cache,
+        state,
+        vp_wasm_cache,
+        tx_wasm_cache,
     )
     .map_err(|err| err.error)
     .into_storage_result()?;
     let tx_result_string = extended_tx_result.tx_result.to_result_string();
+    let tx_gas_meter = tx_gas_meter.into_inner();
     let dry_run_result = DryRunResult(
         tx_result_string,
         tx_gas_meter
-            .borrow()
             .get_tx_consumed_gas()
             .get_whole_gas_units(gas_scale),
     );
 
-    Ok(EncodedResponseQuery {
-        data: dry_run_result.serialize_to_vec(),
-        proof: None,
-        info: Default::default(),
-        height: state.in_mem().get_last_block_height(),
-    })
+    Ok((
+        dry_run_result,
+        tx_gas_meter.gas_trace(),
+        state.in_mem().get_last_block_height(),
+    ))
 }
 
 #[cfg(test)]
@@ -235,6 +315,16 @@ mod test {
                     self.tx_wasm_cache.clone(),
                     &request,
                 )
+            } else if request.path == RPC.shell().dry_run_tx_trace_path() {
+                dry_run_tx_trace(
+                    // Safe for the same reasons as the dry-run above
+                    unsafe {
+                        self.state.read_only().with_static_temp_write_log()
+                    },
+                    self.vp_wasm_cache.clone(),
+                    self.tx_wasm_cache.clone(),
+                    &request,
+                )
             } else {
                 let ctx = RequestCtx {
                     state: self.state.read_only(),
@@ -293,7 +383,7 @@ mod test {
         let tx_bytes = outer_tx.to_bytes();
         let result = RPC
             .shell()
-            .dry_run_tx(&client, Some(tx_bytes), None, false)
+            .dry_run_tx(&client, Some(tx_bytes.clone()), None, false)
             .await
             .unwrap();
         assert!(
@@ -307,6 +397,17 @@ mod test {
                 .is_accepted()
         );
 
+        // Request dry run tx with gas tracing
+        let (traced_result, trace) = RPC
+            .shell()
+            .dry_run_tx_trace(&client, Some(tx_bytes), None, false)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(traced_result.1, result.data.1);
+        assert!(trace.tx.wasm_gas > Gas::default());
+        assert!(trace.vps.is_empty());
+
         // Request storage value for a balance key ...
         let token_addr = address::testing::established_address_1();
         let owner = address::testing::established_address_2();
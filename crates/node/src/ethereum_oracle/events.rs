@@ -73,6 +73,10 @@ pub mod eth_events {
                     nonce: nonce.parse_uint256()?,
                     transfers: transfers.parse_transfer_to_eth_array()?,
                     relayer: relayer_address.parse_address()?,
+                    // NB: the cost of the relay transaction is not
+                    // part of the log. it is filled in by the oracle,
+                    // from the receipt of the transaction
+                    relay_cost: Uint::default(),
                 },
                 RawEvents::Bridge(BridgeEvents::TransferToChainFilter(
                     TransferToChainFilter {
@@ -1,7 +1,9 @@
 pub mod control;
 pub mod events;
+pub mod quorum;
 pub mod test_tools;
 
+use std::num::NonZeroUsize;
 use std::ops::ControlFlow;
 
 use async_trait::async_trait;
@@ -12,7 +14,7 @@ use namada_sdk::control_flow::time::{Constant, Duration, Instant, Sleep};
 use namada_sdk::eth_bridge::ethers::providers::{Http, Middleware, Provider};
 use namada_sdk::eth_bridge::oracle::config::Config;
 use namada_sdk::eth_bridge::{eth_syncing_status_timeout, ethers, SyncStatus};
-use namada_sdk::ethereum_events::EthereumEvent;
+use namada_sdk::ethereum_events::{EthereumEvent, Uint};
 use namada_sdk::{ethereum_structs, hints};
 use num256::Uint256;
 use thiserror::Error;
@@ -21,6 +23,7 @@ use tokio::sync::mpsc::Sender as BoundedSender;
 use tokio::task::LocalSet;
 
 use self::events::PendingEvent;
+use self::quorum::QuorumClient;
 use super::abortable::AbortableSpawner;
 use crate::oracle::control::Command;
 
@@ -44,6 +47,13 @@ pub enum Error {
     MoreConfirmations,
     #[error("The Ethereum oracle timed out")]
     Timeout,
+    #[error(
+        "Only {0} out of {1} Ethereum RPC endpoints answered, which is less \
+         than the required quorum of {2}"
+    )]
+    NoQuorum(usize, usize, usize),
+    #[error("Couldn't get the cost of an Ethereum transaction: {0}")]
+    TransactionCost(String),
 }
 
 /// Convert values to [`ethabi`] Ethereum event logs.
@@ -72,7 +82,7 @@ impl IntoEthAbiLog for ethabi::RawLog {
 #[async_trait(?Send)]
 pub trait RpcClient {
     /// Ethereum event log.
-    type Log: IntoEthAbiLog;
+    type Log: IntoEthAbiLog + Clone;
 
     /// Instantiate a new client, pointing to the
     /// given RPC url.
@@ -89,6 +99,11 @@ pub trait RpcClient {
         abi_signature: &str,
     ) -> Result<Vec<Self::Log>, Error>;
 
+    /// Get the cost of the Ethereum transaction that emitted the
+    /// given event log, in wei. This is the gas used by the
+    /// transaction times its effective gas price.
+    async fn transaction_cost(&self, log: &Self::Log) -> Result<Uint, Error>;
+
     /// Check if the fullnode we are connected to is syncing or is up
     /// to date with the Ethereum (an return the block height).
     ///
@@ -147,6 +162,38 @@ impl RpcClient for Provider<Http> {
         })
     }
 
+    async fn transaction_cost(&self, log: &Self::Log) -> Result<Uint, Error> {
+        let tx_hash = log.transaction_hash.ok_or_else(|| {
+            Error::TransactionCost(
+                "The event log has no transaction hash".into(),
+            )
+        })?;
+        let receipt = self
+            .get_transaction_receipt(tx_hash)
+            .await
+            .map_err(|error| Error::TransactionCost(error.to_string()))?
+            .ok_or_else(|| {
+                Error::TransactionCost(format!(
+                    "No receipt found for transaction {tx_hash:?}"
+                ))
+            })?;
+        let (Some(gas_used), Some(gas_price)) =
+            (receipt.gas_used, receipt.effective_gas_price)
+        else {
+            return Err(Error::TransactionCost(format!(
+                "The receipt of transaction {tx_hash:?} has no gas usage"
+            )));
+        };
+        gas_used
+            .checked_mul(gas_price)
+            .map(Uint::from)
+            .ok_or_else(|| {
+                Error::TransactionCost(format!(
+                    "The cost of transaction {tx_hash:?} overflows"
+                ))
+            })
+    }
+
     async fn syncing(
         &self,
         last_processed_block: Option<&ethereum_structs::BlockHeight>,
@@ -276,28 +323,36 @@ async fn await_initial_configuration(
 }
 
 /// Set up an Oracle and run the process where the Oracle
-/// processes and forwards Ethereum events to the ledger
+/// processes and forwards Ethereum events to the ledger.
+///
+/// The Oracle listens to all of the given RPC `urls`, and only
+/// forwards the events that at least `quorum` of them agree on.
+/// If no quorum is given, a simple majority of the endpoints
+/// is required.
 pub fn run_oracle<C: RpcClient>(
-    url: impl AsRef<str>,
+    urls: Vec<String>,
+    quorum: Option<NonZeroUsize>,
     sender: BoundedSender<EthereumEvent>,
     control: control::Receiver,
     last_processed_block: last_processed_block::Sender,
     spawner: &mut AbortableSpawner,
 ) {
-    let url = url.as_ref().to_owned();
     spawner
         .abortable("Ethereum Oracle", move |aborter| {
             let rt = tokio::runtime::Handle::current();
             rt.block_on(async move {
                 LocalSet::new()
                     .run_until(async move {
+                        let client =
+                            QuorumClient::<C>::from_urls(&urls, quorum);
                         tracing::info!(
-                            ?url,
+                            ?urls,
+                            quorum = client.quorum(),
                             "Ethereum event oracle is starting"
                         );
 
-                        let oracle = Oracle::<C>::new(
-                            Either::Right(&url),
+                        let oracle = Oracle::new(
+                            Either::Left(client),
                             sender,
                             last_processed_block,
                             DEFAULT_BACKOFF,
@@ -307,7 +362,7 @@ pub fn run_oracle<C: RpcClient>(
                         run_oracle_aux(oracle).await;
 
                         tracing::info!(
-                            ?url,
+                            ?urls,
                             "Ethereum event oracle is no longer running"
                         );
                     })
@@ -512,45 +567,49 @@ async fn process_events_in_block<C: RpcClient>(
             "Checking for bridge events"
         );
         // fetch the events for matching the given signature
-        let mut events = {
-            let logs = oracle
-                .client
-                .check_events_in_block(block_to_process.clone(), addr, &sig)
-                .await?;
-            if !logs.is_empty() {
-                tracing::info!(
-                    ?block_to_process,
-                    ?addr,
-                    ?sig,
-                    n_events = logs.len(),
-                    "Found bridge events in Ethereum block"
-                )
+        let logs = oracle
+            .client
+            .check_events_in_block(block_to_process.clone(), addr, &sig)
+            .await?;
+        if !logs.is_empty() {
+            tracing::info!(
+                ?block_to_process,
+                ?addr,
+                ?sig,
+                n_events = logs.len(),
+                "Found bridge events in Ethereum block"
+            )
+        }
+        let mut events = vec![];
+        for log in logs {
+            let raw_log = log.clone().into_ethabi_log();
+            let mut event = match PendingEvent::decode(
+                codec,
+                block_to_process.clone().into(),
+                &raw_log,
+                u64::from(config.min_confirmations).into(),
+            ) {
+                Ok(event) => event,
+                Err(error) => {
+                    tracing::error!(
+                        ?error,
+                        ?block_to_process,
+                        ?addr,
+                        ?sig,
+                        "Couldn't decode event: {:#?}",
+                        raw_log
+                    );
+                    continue;
+                }
+            };
+            // record how much the relayer paid to relay a batch
+            if let EthereumEvent::TransfersToEthereum { relay_cost, .. } =
+                &mut event.event
+            {
+                *relay_cost = oracle.client.transaction_cost(&log).await?;
             }
-            logs.into_iter()
-                .map(IntoEthAbiLog::into_ethabi_log)
-                .filter_map(|log| {
-                    match PendingEvent::decode(
-                        codec,
-                        block_to_process.clone().into(),
-                        &log,
-                        u64::from(config.min_confirmations).into(),
-                    ) {
-                        Ok(event) => Some(event),
-                        Err(error) => {
-                            tracing::error!(
-                                ?error,
-                                ?block_to_process,
-                                ?addr,
-                                ?sig,
-                                "Couldn't decode event: {:#?}",
-                                log
-                            );
-                            None
-                        }
-                    }
-                })
-                .collect()
-        };
+            events.push(event);
+        }
         pending.append(&mut events);
         if !pending.is_empty() {
             tracing::info!(
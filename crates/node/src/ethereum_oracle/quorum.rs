@@ -0,0 +1,643 @@
+//! An [`RpcClient`] that spreads its queries across several Ethereum
+//! JSON-RPC endpoints, and only reports data that a quorum of them
+//! agree upon.
+
+use std::cell::Cell;
+use std::num::NonZeroUsize;
+
+use async_trait::async_trait;
+use ethabi::Address;
+use futures::future::join_all;
+use namada_sdk::control_flow::time::{Duration, Instant};
+use namada_sdk::eth_bridge::SyncStatus;
+use namada_sdk::ethereum_events::Uint;
+use namada_sdk::ethereum_structs;
+use num256::Uint256;
+
+use super::{Error, IntoEthAbiLog, RpcClient};
+
+/// Health metrics of a single Ethereum RPC endpoint.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct EndpointHealth {
+    /// Number of queries the endpoint answered successfully.
+    pub successes: u64,
+    /// Number of queries that failed with an error.
+    pub failures: u64,
+    /// Number of successful queries whose answer did not match the
+    /// one agreed upon by the quorum.
+    pub disagreements: u64,
+}
+
+/// An Ethereum RPC endpoint, along with its health metrics.
+struct Endpoint<C> {
+    url: String,
+    client: C,
+    health: Cell<EndpointHealth>,
+}
+
+impl<C> Endpoint<C> {
+    /// Update the health metrics of this endpoint.
+    fn record(&self, update: impl FnOnce(&mut EndpointHealth)) {
+        let mut health = self.health.get();
+        update(&mut health);
+        self.health.set(health);
+    }
+
+    fn record_success(&self) {
+        self.record(|h| h.successes = h.successes.saturating_add(1));
+    }
+
+    fn record_failure(&self, error: &Error) {
+        self.record(|h| h.failures = h.failures.saturating_add(1));
+        let health = self.health.get();
+        tracing::warn!(
+            url = %self.url,
+            %error,
+            successes = health.successes,
+            failures = health.failures,
+            disagreements = health.disagreements,
+            "Ethereum RPC endpoint failed to answer a query"
+        );
+    }
+
+    fn record_disagreement(&self) {
+        self.record(|h| h.disagreements = h.disagreements.saturating_add(1));
+        let health = self.health.get();
+        tracing::warn!(
+            url = %self.url,
+            successes = health.successes,
+            failures = health.failures,
+            disagreements = health.disagreements,
+            "Ethereum RPC endpoint disagreed with the quorum"
+        );
+    }
+}
+
+/// An [`RpcClient`] backed by multiple Ethereum RPC endpoints.
+///
+/// Every query is sent to all endpoints. Endpoints that fail are skipped
+/// over, and the answer is only accepted if at least `quorum` of the
+/// endpoints agree on it.
+pub struct QuorumClient<C> {
+    endpoints: Vec<Endpoint<C>>,
+    quorum: usize,
+}
+
+impl<C: RpcClient> QuorumClient<C> {
+    /// Build a new [`QuorumClient`] from a list of RPC urls and their
+    /// respective clients. If no `quorum` is given, a simple majority
+    /// of the endpoints is required.
+    ///
+    /// Panics if no endpoints are given, or if the quorum exceeds the
+    /// number of endpoints. The quorum of the ledger's configuration is
+    /// checked against its endpoints when the configuration is loaded.
+    pub fn new(
+        endpoints: impl IntoIterator<Item = (String, C)>,
+        quorum: Option<NonZeroUsize>,
+    ) -> Self {
+        let endpoints: Vec<_> = endpoints
+            .into_iter()
+            .map(|(url, client)| Endpoint {
+                url,
+                client,
+                health: Cell::new(EndpointHealth::default()),
+            })
+            .collect();
+        assert!(
+            !endpoints.is_empty(),
+            "At least one Ethereum RPC endpoint must be configured"
+        );
+        #[allow(clippy::arithmetic_side_effects)]
+        let quorum = quorum
+            .map(NonZeroUsize::get)
+            .unwrap_or(endpoints.len() / 2 + 1);
+        assert!(
+            quorum <= endpoints.len(),
+            "The Ethereum RPC quorum ({quorum}) exceeds the number of \
+             configured endpoints ({})",
+            endpoints.len()
+        );
+        Self { endpoints, quorum }
+    }
+
+    /// Instantiate a client for each of the given RPC urls.
+    pub fn from_urls<S: AsRef<str>>(
+        urls: impl IntoIterator<Item = S>,
+        quorum: Option<NonZeroUsize>,
+    ) -> Self {
+        Self::new(
+            urls.into_iter().map(|url| {
+                let url = url.as_ref();
+                (url.to_owned(), C::new_client(url))
+            }),
+            quorum,
+        )
+    }
+
+    /// The number of endpoints that must agree on a query's answer.
+    #[inline]
+    pub fn quorum(&self) -> usize {
+        self.quorum
+    }
+
+    /// Return the health metrics of each configured endpoint.
+    pub fn health(&self) -> impl Iterator<Item = (&str, EndpointHealth)> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| (endpoint.url.as_str(), endpoint.health.get()))
+    }
+
+    /// Build the error returned when less than a quorum of the
+    /// endpoints answered a query.
+    ///
+    /// If no endpoint answered and none of them may recover from their
+    /// errors, the last error is propagated as is, which preserves the
+    /// behavior of a single endpoint setup.
+    fn quorum_error(
+        &self,
+        answered: usize,
+        errors: Vec<(usize, Error)>,
+    ) -> Error {
+        let unrecoverable = errors
+            .iter()
+            .all(|(i, error)| !self.endpoints[*i].client.may_recover(error));
+        if answered == 0 && unrecoverable {
+            if let Some((_, error)) = errors.into_iter().last() {
+                return error;
+            }
+        }
+        Error::NoQuorum(answered, self.endpoints.len(), self.quorum)
+    }
+}
+
+/// An event log that a quorum of the endpoints agree upon, along
+/// with the logs that each of these endpoints returned for it.
+#[derive(Clone)]
+pub struct QuorumLog<L> {
+    raw: ethabi::RawLog,
+    endpoint_logs: Vec<(usize, L)>,
+}
+
+impl<L> IntoEthAbiLog for QuorumLog<L> {
+    #[inline]
+    fn into_ethabi_log(self) -> ethabi::RawLog {
+        self.raw
+    }
+}
+
+/// Check if two logs are the same.
+fn same_log(a: &ethabi::RawLog, b: &ethabi::RawLog) -> bool {
+    a.topics == b.topics && a.data == b.data
+}
+
+#[async_trait(?Send)]
+impl<C: RpcClient> RpcClient for QuorumClient<C> {
+    type Log = QuorumLog<C::Log>;
+
+    #[inline]
+    fn new_client(rpc_url: &str) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_urls([rpc_url], None)
+    }
+
+    async fn check_events_in_block(
+        &self,
+        block: ethereum_structs::BlockHeight,
+        address: Address,
+        abi_signature: &str,
+    ) -> Result<Vec<Self::Log>, Error> {
+        let responses = join_all(self.endpoints.iter().map(|endpoint| {
+            endpoint.client.check_events_in_block(
+                block.clone(),
+                address,
+                abi_signature,
+            )
+        }))
+        .await;
+
+        let mut answers = vec![];
+        let mut errors = vec![];
+        for (i, response) in responses.into_iter().enumerate() {
+            match response {
+                Ok(logs) => {
+                    self.endpoints[i].record_success();
+                    let logs: Vec<_> = logs
+                        .into_iter()
+                        .map(|log| (log.clone().into_ethabi_log(), log))
+                        .collect();
+                    answers.push((i, logs));
+                }
+                Err(error) => {
+                    self.endpoints[i].record_failure(&error);
+                    errors.push((i, error));
+                }
+            }
+        }
+        if answers.len() < self.quorum {
+            return Err(self.quorum_error(answers.len(), errors));
+        }
+
+        // collect the endpoints that returned each log
+        let mut tally: Vec<QuorumLog<C::Log>> = vec![];
+        for (i, logs) in answers.iter() {
+            for (j, (raw, log)) in logs.iter().enumerate() {
+                if logs[..j].iter().any(|(other, _)| same_log(other, raw)) {
+                    continue;
+                }
+                let endpoint_log = (*i, log.clone());
+                match tally.iter_mut().find(|other| same_log(&other.raw, raw)) {
+                    Some(other) => other.endpoint_logs.push(endpoint_log),
+                    None => tally.push(QuorumLog {
+                        raw: raw.clone(),
+                        endpoint_logs: vec![endpoint_log],
+                    }),
+                }
+            }
+        }
+        let agreed: Vec<_> = tally
+            .into_iter()
+            .filter(|log| log.endpoint_logs.len() >= self.quorum)
+            .collect();
+
+        for (i, logs) in answers.iter() {
+            let matches_quorum = logs.len() == agreed.len()
+                && logs.iter().all(|(raw, _)| {
+                    agreed.iter().any(|a| same_log(&a.raw, raw))
+                });
+            if !matches_quorum {
+                self.endpoints[*i].record_disagreement();
+            }
+        }
+        Ok(agreed)
+    }
+
+    async fn transaction_cost(&self, log: &Self::Log) -> Result<Uint, Error> {
+        // only the endpoints that returned the log can find
+        // the transaction that emitted it
+        let responses =
+            join_all(log.endpoint_logs.iter().map(|(i, endpoint_log)| {
+                self.endpoints[*i].client.transaction_cost(endpoint_log)
+            }))
+            .await;
+
+        let mut answers = vec![];
+        let mut errors = vec![];
+        for ((i, _), response) in log.endpoint_logs.iter().zip(responses) {
+            match response {
+                Ok(cost) => {
+                    self.endpoints[*i].record_success();
+                    answers.push((*i, cost));
+                }
+                Err(error) => {
+                    self.endpoints[*i].record_failure(&error);
+                    errors.push((*i, error));
+                }
+            }
+        }
+        if answers.len() < self.quorum {
+            return Err(self.quorum_error(answers.len(), errors));
+        }
+
+        // the cost returned by the most endpoints
+        let (cost, agreeing) = answers
+            .iter()
+            .map(|(_, cost)| {
+                let agreeing =
+                    answers.iter().filter(|(_, other)| other == cost).count();
+                (*cost, agreeing)
+            })
+            .max_by_key(|(_, agreeing)| *agreeing)
+            .expect("A quorum of endpoints answered");
+        for (i, other) in answers.iter() {
+            if other != &cost {
+                self.endpoints[*i].record_disagreement();
+            }
+        }
+        if agreeing < self.quorum {
+            return Err(Error::NoQuorum(
+                agreeing,
+                self.endpoints.len(),
+                self.quorum,
+            ));
+        }
+        Ok(cost)
+    }
+
+    async fn syncing(
+        &self,
+        last_processed_block: Option<&ethereum_structs::BlockHeight>,
+        backoff: Duration,
+        deadline: Instant,
+    ) -> Result<SyncStatus, Error> {
+        let responses = join_all(self.endpoints.iter().map(|endpoint| {
+            endpoint
+                .client
+                .syncing(last_processed_block, backoff, deadline)
+        }))
+        .await;
+
+        let mut heights: Vec<Uint256> = vec![];
+        let mut syncing = 0usize;
+        let mut behind = 0usize;
+        let mut errors = vec![];
+        for (i, response) in responses.into_iter().enumerate() {
+            match response {
+                Ok(SyncStatus::AtHeight(height)) => {
+                    self.endpoints[i].record_success();
+                    heights.push(height);
+                }
+                Ok(SyncStatus::Syncing) => {
+                    self.endpoints[i].record_success();
+                    syncing = syncing.saturating_add(1);
+                }
+                // the endpoint has not seen a new block since the
+                // last one we processed, which is not a failure
+                Err(Error::FallenBehind) => {
+                    self.endpoints[i].record_success();
+                    behind = behind.saturating_add(1);
+                }
+                Err(error) => {
+                    self.endpoints[i].record_failure(&error);
+                    errors.push((i, error));
+                }
+            }
+        }
+        if heights.len() >= self.quorum {
+            // the highest block that at least a quorum of the
+            // endpoints have reached
+            heights.sort_unstable_by(|a, b| b.cmp(a));
+            #[allow(clippy::arithmetic_side_effects)]
+            let height = heights.swap_remove(self.quorum - 1);
+            return Ok(SyncStatus::AtHeight(height));
+        }
+        let answered =
+            heights.len().saturating_add(syncing).saturating_add(behind);
+        if answered < self.quorum {
+            Err(self.quorum_error(answered, errors))
+        } else if syncing > 0 {
+            Ok(SyncStatus::Syncing)
+        } else {
+            Err(Error::FallenBehind)
+        }
+    }
+
+    #[inline]
+    fn may_recover(&self, error: &Error) -> bool {
+        match error {
+            Error::NoQuorum(_, _, _) => true,
+            error => self
+                .endpoints
+                .iter()
+                .any(|endpoint| endpoint.client.may_recover(error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_quorum {
+    use ethbridge_bridge_events::TransferToChainFilter;
+    use namada_sdk::eth_bridge::ethers::providers::{Http, Provider};
+    use tokio::sync::oneshot::channel;
+
+    use super::*;
+    use crate::ethereum_oracle::control;
+    use crate::ethereum_oracle::test_tools::event_log::GetLog;
+    use crate::ethereum_oracle::test_tools::events_endpoint;
+    use crate::ethereum_oracle::test_tools::mock_web3_client::{
+        event_signature, TestCmd, Web3Client, Web3Controller,
+    };
+
+    /// Set up a [`QuorumClient`] backed by `n` mock web3 clients.
+    fn setup(
+        n: usize,
+        quorum: usize,
+    ) -> (QuorumClient<Web3Client>, Vec<Web3Controller>) {
+        let mut controllers = vec![];
+        let clients: Vec<_> = (0..n)
+            .map(|i| {
+                let (_, client) = Web3Client::setup();
+                controllers.push(client.controller());
+                (format!("http://endpoint-{i}"), client)
+            })
+            .collect();
+        let client = QuorumClient::new(clients, NonZeroUsize::new(quorum));
+        (client, controllers)
+    }
+
+    /// Make the given mock client return an event at height 1. The
+    /// returned receiver must be kept alive until the event is seen.
+    fn add_event(
+        controller: &Web3Controller,
+        nonce: u64,
+    ) -> tokio::sync::oneshot::Receiver<()> {
+        let log = TransferToChainFilter {
+            nonce: nonce.into(),
+            transfers: vec![],
+            confirmations: 1.into(),
+        }
+        .get_log();
+        let (seen, seen_recv) = channel();
+        controller.apply_cmd(TestCmd::NewEvent {
+            event_type: event_signature::<TransferToChainFilter>(),
+            log,
+            height: 1,
+            seen,
+        });
+        seen_recv
+    }
+
+    async fn check_block(
+        client: &QuorumClient<Web3Client>,
+    ) -> Result<Vec<QuorumLog<ethabi::RawLog>>, Error> {
+        client
+            .check_events_in_block(
+                ethereum_structs::BlockHeight::from(1u64),
+                Address::zero(),
+                &event_signature::<TransferToChainFilter>(),
+            )
+            .await
+    }
+
+    /// Test that the default quorum is a simple majority
+    #[test]
+    fn test_default_quorum() {
+        for (n, quorum) in [(1, 1), (2, 2), (3, 2), (4, 3), (5, 3)] {
+            let endpoints = (0..n).map(|i| {
+                (format!("http://endpoint-{i}"), Web3Client::setup().1)
+            });
+            assert_eq!(QuorumClient::new(endpoints, None).quorum(), quorum);
+        }
+    }
+
+    /// Test that events are forwarded if a quorum of endpoints
+    /// returns them, even if some endpoints are failing
+    #[tokio::test]
+    async fn test_fallback_on_errors() {
+        let (client, controllers) = setup(3, 2);
+        controllers[0].apply_cmd(TestCmd::Unresponsive);
+        let _seen =
+            [add_event(&controllers[1], 0), add_event(&controllers[2], 0)];
+
+        let logs = check_block(&client).await.expect("Test failed");
+        assert_eq!(logs.len(), 1);
+
+        let health: Vec<_> = client.health().map(|(_, h)| h).collect();
+        assert_eq!(health[0].failures, 1);
+        assert_eq!(health[0].successes, 0);
+        assert_eq!(health[1].successes, 1);
+        assert_eq!(health[2].successes, 1);
+        assert!(health.iter().all(|h| h.disagreements == 0));
+    }
+
+    /// Test that events only seen by less than a quorum of the
+    /// endpoints are dropped, and that the endpoints which
+    /// disagree get flagged
+    #[tokio::test]
+    async fn test_events_require_quorum() {
+        let (client, controllers) = setup(3, 2);
+        let _seen = [
+            add_event(&controllers[0], 0),
+            add_event(&controllers[1], 0),
+            add_event(&controllers[1], 1),
+        ];
+
+        let logs = check_block(&client).await.expect("Test failed");
+        assert_eq!(logs.len(), 1);
+        assert_eq!(
+            logs[0].raw.data,
+            TransferToChainFilter {
+                nonce: 0.into(),
+                transfers: vec![],
+                confirmations: 1.into(),
+            }
+            .get_log()
+            .data
+        );
+
+        let disagreements: Vec<_> =
+            client.health().map(|(_, h)| h.disagreements).collect();
+        assert_eq!(disagreements, vec![0, 1, 1]);
+    }
+
+    /// Test that a recoverable error is returned if
+    /// not enough endpoints answer a query
+    #[tokio::test]
+    async fn test_no_quorum() {
+        let (client, controllers) = setup(3, 2);
+        controllers[0].apply_cmd(TestCmd::Unresponsive);
+        controllers[1].apply_cmd(TestCmd::Unresponsive);
+
+        let error = check_block(&client).await.expect_err("Test failed");
+        assert!(matches!(error, Error::NoQuorum(1, 3, 2)));
+        assert!(client.may_recover(&error));
+    }
+
+    /// Test that the cost of the transaction that emitted a log is
+    /// only reported if a quorum of the endpoints that returned the
+    /// log agree on it
+    #[tokio::test]
+    async fn test_transaction_cost_requires_quorum() {
+        let (client, controllers) = setup(3, 2);
+        let _seen = [
+            add_event(&controllers[0], 0),
+            add_event(&controllers[1], 0),
+            add_event(&controllers[2], 0),
+        ];
+        controllers[0].apply_cmd(TestCmd::NewTransactionCost(5u64.into()));
+        controllers[1].apply_cmd(TestCmd::NewTransactionCost(5u64.into()));
+        controllers[2].apply_cmd(TestCmd::NewTransactionCost(7u64.into()));
+
+        let logs = check_block(&client).await.expect("Test failed");
+        assert_eq!(logs.len(), 1);
+        let cost = client
+            .transaction_cost(&logs[0])
+            .await
+            .expect("Test failed");
+        assert_eq!(cost, 5u64.into());
+        let disagreements: Vec<_> =
+            client.health().map(|(_, h)| h.disagreements).collect();
+        assert_eq!(disagreements, vec![0, 0, 1]);
+
+        controllers[1].apply_cmd(TestCmd::NewTransactionCost(6u64.into()));
+        let error = client
+            .transaction_cost(&logs[0])
+            .await
+            .expect_err("Test failed");
+        assert!(matches!(error, Error::NoQuorum(1, 3, 2)));
+    }
+
+    /// Test that the reported height is the highest one
+    /// reached by a quorum of the endpoints
+    #[tokio::test]
+    async fn test_syncing_height_agreement() {
+        let (client, controllers) = setup(3, 2);
+        controllers[0].apply_cmd(TestCmd::NewHeight(Uint256::from(10u32)));
+        controllers[1].apply_cmd(TestCmd::NewHeight(Uint256::from(7u32)));
+        controllers[2].apply_cmd(TestCmd::NewHeight(Uint256::from(5u32)));
+
+        let status = client
+            .syncing(None, Duration::from_millis(5), Instant::now())
+            .await
+            .expect("Test failed");
+        assert!(matches!(
+            status,
+            SyncStatus::AtHeight(height) if height == Uint256::from(7u32)
+        ));
+    }
+
+    /// Test that the queries sent over HTTP to live endpoints which do not
+    /// speak JSON-RPC, here the events endpoints of the test tools, are
+    /// recorded as failures of each of the endpoints
+    #[tokio::test]
+    async fn test_events_endpoints_failures() {
+        let mut aborts = vec![];
+        let mut urls = vec![];
+        for _ in 0..3 {
+            let addr = std::net::TcpListener::bind("127.0.0.1:0")
+                .and_then(|listener| listener.local_addr())
+                .expect("Test failed");
+            let (sender, _) = tokio::sync::mpsc::channel(1);
+            let (_, control_recv) = control::channel();
+            let (abort_send, abort_recv) = channel();
+            tokio::spawn(events_endpoint::serve(
+                addr.to_string(),
+                sender,
+                control_recv,
+                abort_recv,
+            ));
+            aborts.push(abort_send);
+            urls.push(format!("http://{addr}"));
+        }
+        // let the endpoints start listening
+        tokio::task::yield_now().await;
+
+        let client = QuorumClient::<Provider<Http>>::from_urls(
+            &urls,
+            NonZeroUsize::new(2),
+        );
+        let error = client
+            .check_events_in_block(
+                ethereum_structs::BlockHeight::from(1u64),
+                Address::zero(),
+                &event_signature::<TransferToChainFilter>(),
+            )
+            .await
+            .expect_err("Test failed");
+        // no endpoint may recover, so the last error is returned as is
+        assert!(matches!(error, Error::CheckEvents(_, _, _)));
+        assert!(!client.may_recover(&error));
+        for (url, health) in client.health() {
+            assert!(urls.iter().any(|u| u == url));
+            assert_eq!(
+                health,
+                EndpointHealth {
+                    successes: 0,
+                    failures: 1,
+                    disagreements: 0,
+                }
+            );
+        }
+    }
+}
@@ -68,6 +68,7 @@ pub mod mock_web3_client {
     use ethabi::Address;
     use ethbridge_events::EventCodec;
     use namada_sdk::control_flow::time::{Duration, Instant};
+    use namada_sdk::ethereum_events::Uint;
     use namada_sdk::ethereum_structs::BlockHeight;
     use num256::Uint256;
     use tokio::sync::mpsc::{
@@ -87,6 +88,7 @@ pub mod mock_web3_client {
         Normal,
         Unresponsive,
         NewHeight(Uint256),
+        NewTransactionCost(Uint),
         NewEvent {
             event_type: MockEventType,
             log: ethabi::RawLog,
@@ -115,6 +117,9 @@ pub mod mock_web3_client {
                 TestCmd::NewHeight(height) => {
                     oracle.latest_block_height = height
                 }
+                TestCmd::NewTransactionCost(cost) => {
+                    oracle.transaction_cost = cost
+                }
                 TestCmd::NewEvent {
                     event_type: ty,
                     log,
@@ -139,6 +144,7 @@ pub mod mock_web3_client {
     pub struct Web3ClientInner {
         active: bool,
         latest_block_height: Uint256,
+        transaction_cost: Uint,
         events: Vec<(MockEventType, ethabi::RawLog, u32, Sender<()>)>,
         blocks_processed: UnboundedSender<Uint256>,
         last_block_processed: Option<Uint256>,
@@ -199,6 +205,17 @@ pub mod mock_web3_client {
             }
         }
 
+        async fn transaction_cost(&self, _: &Self::Log) -> Result<Uint, Error> {
+            let client = self.0.lock().unwrap();
+            if client.active {
+                Ok(client.transaction_cost)
+            } else {
+                Err(Error::TransactionCost(
+                    "Test oracle is not responding".into(),
+                ))
+            }
+        }
+
         async fn syncing(
             &self,
             _: Option<&BlockHeight>,
@@ -226,6 +243,7 @@ pub mod mock_web3_client {
                 Self(Arc::new(Mutex::new(Web3ClientInner {
                     active: true,
                     latest_block_height: Default::default(),
+                    transaction_cost: Default::default(),
                     events: vec![],
                     blocks_processed: block_processed_send,
                     last_block_processed: None,
@@ -0,0 +1,377 @@
+//! Simulation of transactions and epoch transitions against a local fork of
+//! the ledger's state.
+//!
+//! The transactions are executed with the same protocol as the ledger, but
+//! their changes are only kept in the write log of the fork, which is never
+//! committed. See [`namada_sdk::fork`] to create a fork of a node's state.
+
+use std::cell::RefCell;
+
+use namada_sdk::chain::Epoch;
+use namada_sdk::events::extend::{ComposeEvent, Height};
+use namada_sdk::events::{EmitEvents, Event};
+use namada_sdk::fork::{ForkedState, StorageSource};
+use namada_sdk::gas::TxGasMeter;
+use namada_sdk::governance::pgf::inflation as pgf_inflation;
+use namada_sdk::hash::Hash;
+use namada_sdk::state::{LastBlock, Result, StateRead, TxIndex};
+use namada_sdk::storage::BlockHeader;
+use namada_sdk::tx::data::DryRunResult;
+use namada_sdk::tx::Tx;
+use namada_sdk::{governance, ibc, parameters, proof_of_stake, token};
+use namada_vm::wasm::{TxCache, VpCache};
+use namada_vm::WasmCacheAccess;
+
+use crate::dry_run_tx::execute_tx;
+use crate::protocol;
+
+/// A simulation of the ledger on top of a forked state. The transactions are
+/// all applied to the same block, until a new epoch is started with
+/// [`Simulation::advance_epoch`].
+pub struct Simulation<S, CA>
+where
+    CA: 'static + WasmCacheAccess + Sync,
+{
+    state: ForkedState<S>,
+    vp_wasm_cache: VpCache<CA>,
+    tx_wasm_cache: TxCache<CA>,
+    events: Vec<Event>,
+}
+
+impl<S, CA> Simulation<S, CA>
+where
+    S: 'static + StorageSource + Sync,
+    CA: 'static + WasmCacheAccess + Sync,
+{
+    /// Start a simulation on the given forked state
+    pub fn new(
+        mut state: ForkedState<S>,
+        vp_wasm_cache: VpCache<CA>,
+        tx_wasm_cache: TxCache<CA>,
+    ) -> Result<Self> {
+        let gas_schedule = parameters::read_gas_schedule(&state)?;
+        state.write_log_mut().set_gas_schedule(gas_schedule);
+        Ok(Self {
+            state,
+            vp_wasm_cache,
+            tx_wasm_cache,
+            events: vec![],
+        })
+    }
+
+    /// The state of the simulation, including the uncommitted changes
+    pub fn state(&self) -> &ForkedState<S> {
+        &self.state
+    }
+
+    /// Take the events emitted by the protocol when advancing the epochs
+    pub fn take_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Apply a transaction to the current block. The changes of the accepted
+    /// inner transactions are kept for the following transactions, unless
+    /// the transaction is an atomic batch with any failing inner transaction.
+    /// The changes of a transaction that fails altogether are dropped.
+    pub fn apply_tx(&mut self, tx: &Tx) -> Result<DryRunResult> {
+        let result = execute_tx(
+            self.state.restrict_writes_to_write_log(),
+            &mut self.vp_wasm_cache,
+            &mut self.tx_wasm_cache,
+            &tx.to_bytes(),
+            false,
+        );
+        match result {
+            Ok((dry_run_result, _gas_trace, _height)) => {
+                if tx.header().atomic
+                    && !dry_run_result.0.are_results_successfull()
+                {
+                    self.state.write_log_mut().drop_batch();
+                } else {
+                    self.state.write_log_mut().commit_batch_and_current_tx();
+                }
+                Ok(dry_run_result)
+            }
+            Err(err) => {
+                self.state.write_log_mut().drop_batch();
+                Err(err)
+            }
+        }
+    }
+
+    /// Finish the current block and begin a new epoch in the next one,
+    /// regardless of the minimum duration of the current epoch. The
+    /// sub-systems are finalized as on the first block of an epoch in the
+    /// ledger, except for the MASP conversions, which are not updated.
+    ///
+    /// Returns the new epoch.
+    pub fn advance_epoch(&mut self) -> Result<Epoch> {
+        let in_mem = self.state.in_mem_mut();
+        let last_height = in_mem.block.height;
+        let last_time = in_mem
+            .header
+            .as_ref()
+            .map_or(in_mem.next_epoch_min_start_time, |header| header.time);
+        in_mem.last_block = Some(LastBlock {
+            height: last_height,
+            time: last_time,
+        });
+        in_mem.last_epoch = in_mem.block.epoch;
+
+        let height = last_height.next_height();
+        in_mem.begin_block(height)?;
+        let time = std::cmp::max(last_time, in_mem.next_epoch_min_start_time);
+        in_mem.set_header(BlockHeader {
+            hash: Hash::default(),
+            time,
+            next_validators_hash: Hash::default(),
+        })?;
+        // Switch the epoch with the next call to `update_epoch`
+        in_mem.update_epoch_blocks_delay = Some(1);
+
+        let parameters = parameters::read(&self.state)?;
+        self.state.update_epoch(height, time, &parameters)?;
+        let current_epoch = self.state.in_mem().block.epoch;
+
+        self.finalize_sub_systems(current_epoch)?;
+        self.apply_inflation(current_epoch)?;
+        Ok(current_epoch)
+    }
+
+    /// Finalize the governance, PoS and IBC sub-systems on the first block of
+    /// a new epoch
+    fn finalize_sub_systems(&mut self, current_epoch: Epoch) -> Result<()> {
+        let vp_wasm_cache = &mut self.vp_wasm_cache;
+        let tx_wasm_cache = &mut self.tx_wasm_cache;
+        governance::finalize_block::<
+            _,
+            token::Store<_>,
+            proof_of_stake::Store<_>,
+            _,
+            _,
+        >(
+            &mut self.state,
+            &mut self.events,
+            current_epoch,
+            true,
+            |tx, state| {
+                let dispatch_result = protocol::dispatch_tx(
+                    tx,
+                    protocol::DispatchArgs::Raw {
+                        wrapper_hash: None,
+                        tx_index: TxIndex::default(),
+                        wrapper_tx_result: None,
+                        vp_wasm_cache,
+                        tx_wasm_cache,
+                    },
+                    // No gas limit for governance proposal
+                    &RefCell::new(TxGasMeter::new(u64::MAX)),
+                    state,
+                );
+                // Governance must construct the tx with data and code
+                // commitments
+                let cmt = tx.first_commitments().unwrap().to_owned();
+                let accepted = match dispatch_result {
+                    Ok(extended_tx_result) => extended_tx_result
+                        .tx_result
+                        .get_inner_tx_result(None, either::Right(&cmt))
+                        .expect("Proposal tx must have a result")
+                        .as_ref()
+                        .is_ok_and(|batched_result| {
+                            batched_result.is_accepted()
+                        }),
+                    Err(_) => false,
+                };
+                if accepted {
+                    state.write_log_mut().commit_batch_and_current_tx();
+                } else {
+                    tracing::warn!(
+                        "Governance proposal rejected in the simulation"
+                    );
+                    state.write_log_mut().drop_batch();
+                }
+                Ok(accepted)
+            },
+            |state, token, source, target| {
+                ibc::transfer_over_ibc::<
+                    _,
+                    parameters::Store<_>,
+                    token::Store<_>,
+                    token::Transfer,
+                >(state, token, source, target)
+            },
+        )?;
+
+        // The gas schedule may have been changed by the transactions or the
+        // governance proposals
+        let gas_schedule = parameters::read_gas_schedule(&self.state)?;
+        self.state.write_log_mut().set_gas_schedule(gas_schedule);
+
+        proof_of_stake::finalize_block::<_, governance::Store<_>>(
+            &mut self.state,
+            &mut self.events,
+            true,
+            current_epoch.next(),
+            vec![],
+            vec![],
+        )?;
+
+        ibc::finalize_block(&mut self.state, &mut self.events, true)
+    }
+
+    /// Apply the PoS and PGF inflation of the previous epoch
+    fn apply_inflation(&mut self, current_epoch: Epoch) -> Result<()> {
+        let last_epoch = current_epoch
+            .prev()
+            .expect("Must have a prev epoch when applying inflation");
+        let in_mem = self.state.in_mem();
+        let first_block_of_last_epoch = in_mem
+            .block
+            .pred_epochs
+            .first_block_heights[usize::try_from(last_epoch.0)
+            .expect("Last epoch shouldn't exceed `usize::MAX`")]
+        .0;
+        let num_blocks_in_last_epoch = in_mem
+            .block
+            .height
+            .0
+            .checked_sub(first_block_of_last_epoch)
+            .expect(
+                "First block of last epoch must always be lower than or equal \
+                 to current block height",
+            );
+
+        proof_of_stake::rewards::apply_inflation::<
+            _,
+            governance::Store<_>,
+            parameters::Store<_>,
+            token::Store<_>,
+        >(&mut self.state, last_epoch, num_blocks_in_last_epoch)?;
+
+        pgf_inflation::apply_inflation::<
+            _,
+            parameters::Store<_>,
+            token::Store<_>,
+            _,
+        >(
+            self.state.restrict_writes_to_write_log(),
+            |state, token, source, target| {
+                ibc::transfer_over_ibc::<
+                    _,
+                    parameters::Store<_>,
+                    token::Store<_>,
+                    token::Transfer,
+                >(state, token, source, target)
+            },
+        )?;
+
+        // Take events that may be emitted from PGF
+        let height = self.state.in_mem().get_last_block_height().next_height();
+        for event in self.state.write_log_mut().take_events() {
+            self.events.emit(event.with(Height(height)));
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::arithmetic_side_effects)]
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use namada_sdk::fork::ForkedDB;
+    use namada_sdk::governance;
+    use namada_sdk::proof_of_stake::storage::{
+        read_consensus_validator_set_addresses, read_pos_params,
+    };
+    use namada_sdk::state::{DBIter, DbResult, DB};
+    use namada_sdk::storage::{Key, PrefixValue};
+    use namada_vm::wasm;
+
+    use super::*;
+    use crate::shell::test_utils::{self, TestShell};
+
+    /// A storage source with a snapshot of the state of a test shell
+    struct SnapshotSource(BTreeMap<Key, Vec<u8>>);
+
+    impl StorageSource for SnapshotSource {
+        fn read(&self, key: &Key) -> DbResult<Option<Vec<u8>>> {
+            Ok(self.0.get(key).cloned())
+        }
+
+        fn read_prefix(&self, prefix: &Key) -> DbResult<Vec<PrefixValue>> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|(key, _)| key.split_prefix(prefix).is_some())
+                .map(|(key, value)| PrefixValue {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect())
+        }
+    }
+
+    /// Fork the committed state of the test shell
+    fn fork(shell: &TestShell) -> ForkedState<SnapshotSource> {
+        let values = shell
+            .state
+            .db()
+            .iter_prefix(None)
+            .map(|(key, value, _gas)| (Key::parse(key).unwrap(), value))
+            .collect();
+        let mut state = ForkedState::new(
+            ForkedDB::new(SnapshotSource(values)),
+            shell.state.in_mem().chain_id.clone(),
+            shell.state.in_mem().native_token.clone(),
+            None,
+            |_key| false,
+        );
+        let shell_in_mem = shell.state.in_mem();
+        let in_mem = state.in_mem_mut();
+        in_mem.block.height =
+            shell_in_mem.get_last_block_height().next_height();
+        in_mem.block.epoch = shell_in_mem.block.epoch;
+        in_mem.block.pred_epochs = shell_in_mem.block.pred_epochs.clone();
+        in_mem.header.clone_from(&shell_in_mem.header);
+        in_mem.last_block.clone_from(&shell_in_mem.last_block);
+        in_mem.last_epoch = shell_in_mem.last_epoch;
+        state
+    }
+
+    /// Test that advancing the epoch of a simulation updates the validator
+    /// sets of the fork and leaves the original state untouched
+    #[test]
+    fn test_simulation_advance_epoch() {
+        let (shell, _recv, _, _) = test_utils::setup();
+        let (vp_wasm_cache, _vp_cache_dir) =
+            wasm::compilation_cache::common::testing::cache();
+        let (tx_wasm_cache, _tx_cache_dir) =
+            wasm::compilation_cache::common::testing::cache();
+        let mut simulation =
+            Simulation::new(fork(&shell), vp_wasm_cache, tx_wasm_cache)
+                .unwrap();
+
+        let epoch = shell.state.in_mem().block.epoch;
+        let new_epoch = simulation.advance_epoch().unwrap();
+        assert_eq!(new_epoch, epoch.next());
+
+        let params =
+            read_pos_params::<_, governance::Store<_>>(simulation.state())
+                .unwrap();
+        let pipeline_epoch = new_epoch + params.pipeline_len;
+        assert!(
+            !read_consensus_validator_set_addresses(
+                simulation.state(),
+                pipeline_epoch
+            )
+            .unwrap()
+            .is_empty()
+        );
+        assert!(
+            read_consensus_validator_set_addresses(&shell.state, pipeline_epoch)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -19,6 +19,7 @@ pub mod bench_utils;
 mod broadcaster;
 mod dry_run_tx;
 pub mod ethereum_oracle;
+pub mod fork;
 pub mod protocol;
 pub mod shell;
 pub mod shims;
@@ -32,7 +33,7 @@ use std::path::PathBuf;
 
 use byte_unit::Byte;
 use data_encoding::HEXUPPER;
-pub use dry_run_tx::dry_run_tx;
+pub use dry_run_tx::{dry_run_tx, dry_run_tx_trace};
 use futures::future::TryFutureExt;
 use namada_apps_lib::cli::args;
 use namada_apps_lib::config::utils::{
@@ -420,6 +421,12 @@ pub fn rollback(config: config::Ledger) -> Result<(), shell::Error> {
     shell::rollback(config)
 }
 
+/// Pre-compile the wasm codes stored in the node's DB into the compilation
+/// caches
+pub fn warmup_wasm_caches(config: config::Ledger) -> Result<(), shell::Error> {
+    shell::warmup_wasm_caches(config)
+}
+
 /// Runs and monitors a few concurrent tasks.
 ///
 /// This includes:
@@ -825,7 +832,8 @@ async fn maybe_start_ethereum_oracle(
     match config.ethereum_bridge.mode {
         ethereum_bridge::ledger::Mode::RemoteEndpoint => {
             oracle::run_oracle::<Provider<Http>>(
-                ethereum_url,
+                config.ethereum_bridge.oracle_rpc_endpoints(),
+                config.ethereum_bridge.oracle_rpc_quorum,
                 eth_sender,
                 control_receiver,
                 last_processed_block_sender,
@@ -708,10 +708,11 @@ where
         .min(tx_gas_meter.borrow().get_available_gas().into());
     let gas_scale = get_gas_scale(&**state).map_err(Error::Error)?;
 
-    let masp_gas_meter = RefCell::new(TxGasMeter::new(
+    let masp_gas_meter = RefCell::new(TxGasMeter::new_with_schedule(
         Gas::from_whole_units(max_gas_limit.into(), gas_scale).ok_or_else(
             || Error::GasError("Overflow in gas expansion".to_string()),
         )?,
+        tx_gas_meter.borrow().gas_schedule().clone(),
     ));
 
     let valid_batched_tx_result = {
@@ -1345,6 +1346,7 @@ where
                 // all the other errors we keep evaluating the vps. This
                 // allows to display a consistent VpsResult across all
                 // nodes and find any invalid signatures
+                tx_gas_meter.trace_vp(addr, &gas_meter.borrow());
                 vps_gas = vps_gas
                     .checked_add(gas_meter.borrow().get_vp_consumed_gas())
                     .ok_or(Error::GasError(
@@ -5,6 +5,7 @@ use masp_primitives::merkle_tree::CommitmentTree;
 use masp_primitives::sapling::Node;
 use namada_sdk::events::extend::{
     ComposeEvent, Height, IndexedMaspData, Info, MaspDataRefs, TxHash,
+    UserAccount,
 };
 use namada_sdk::events::{EmitEvents, Event};
 use namada_sdk::gas::event::GasUsed;
@@ -20,6 +21,7 @@ use namada_sdk::state::{
     Result, ResultExt, StorageWrite, EPOCH_SWITCH_BLOCKS_DELAY,
 };
 use namada_sdk::storage::{BlockHeader, BlockResults, Epoch};
+use namada_sdk::token::event::{SourceAccounts, TargetAccount, TargetAccounts};
 use namada_sdk::tx::data::protocol::ProtocolTxType;
 use namada_sdk::tx::data::VpStatusFlags;
 use namada_sdk::tx::event::{Batch, Code};
@@ -98,6 +100,8 @@ where
         // - Governance - applied first in case a proposal changes any of the
         //   other syb-systems
         gov_finalize_block(self, emit_events, current_epoch, new_epoch)?;
+        // - Gas - reload the schedule in case governance has updated it
+        self.load_gas_schedule();
         // - Token
         token_finalize_block(&mut self.state, emit_events, is_masp_new_epoch)?;
         // - PoS
@@ -323,6 +327,28 @@ where
         }
     }
 
+    // Record the transaction in the history of the accounts whose tokens it
+    // moved, if the node maintains the index
+    fn index_tx_history(
+        &mut self,
+        temp_log: &TempTxLogs,
+        tx_data: &TxData<'_>,
+    ) {
+        if !self.tx_history_index {
+            return;
+        }
+        let tx_index = u32::try_from(tx_data.tx_index)
+            .expect("Transaction index should fit in u32");
+        let hash = tx_data.tx.header_hash();
+        for owner in &temp_log.history_owners {
+            self.state.write_log_mut().write_tx_history(
+                owner.clone(),
+                tx_index,
+                hash,
+            );
+        }
+    }
+
     // Evaluate the result of a transaction. Commit or drop the storage changes,
     // update stats and event, manage replay protection. For successful wrapper
     // transactions return the relevant data and delay the evaluation after the
@@ -473,6 +499,7 @@ where
                 .block
                 .results
                 .accept(tx_data.tx_index);
+            self.index_tx_history(&temp_log, &tx_data);
             temp_log.commit(tx_logs, response);
 
             // Atomic successful batches or non-atomic batches (even if the
@@ -540,6 +567,7 @@ where
                 .block
                 .results
                 .accept(tx_data.tx_index);
+            self.index_tx_history(&temp_log, &tx_data);
             temp_log.commit(tx_logs, response);
             // Commit the successful inner transactions before the error. Drop
             // the current tx write log which might be still populated with data
@@ -683,7 +711,10 @@ where
                                 continue;
                             }
                         };
-                    let tx_gas_meter = TxGasMeter::new(gas_limit);
+                    let tx_gas_meter = TxGasMeter::new_with_schedule(
+                        gas_limit,
+                        self.state.gas_schedule().clone(),
+                    );
                     for cmt in tx.commitments() {
                         if let Some(code_sec) = tx
                             .get_section(cmt.code_sechash())
@@ -925,6 +956,8 @@ struct TempTxLogs {
     stats: InternalStats,
     changed_keys: BTreeSet<Key>,
     response_events: Vec<Event>,
+    // The accounts whose tokens were moved by the accepted inner txs
+    history_owners: BTreeSet<Address>,
 }
 
 impl TempTxLogs {
@@ -937,6 +970,7 @@ impl TempTxLogs {
             stats: Default::default(),
             changed_keys: Default::default(),
             response_events: Default::default(),
+            history_owners: Default::default(),
         }
     }
 }
@@ -986,6 +1020,9 @@ impl<'finalize> TempTxLogs {
                             .extend(result.changed_keys.iter().cloned());
                         self.stats.increment_successful_txs();
                         flags.commit_batch_hash = true;
+                        for event in &result.events {
+                            self.collect_history_owners(event);
+                        }
 
                         // events from other sources
                         self.response_events.emit_many(
@@ -1043,6 +1080,27 @@ impl<'finalize> TempTxLogs {
 
         flags
     }
+
+    // Collect the internal accounts whose balances were changed by a token
+    // event
+    fn collect_history_owners(&mut self, event: &Event) {
+        let transfer_accounts = [
+            event.read_attribute_opt::<SourceAccounts>(),
+            event.read_attribute_opt::<TargetAccounts>(),
+        ]
+        .into_iter()
+        .filter_map(|accounts| accounts.ok().flatten())
+        .flat_map(|accounts| accounts.0.into_iter())
+        .map(|((account, _token), _amount)| account);
+        let target_account =
+            event.read_attribute_opt::<TargetAccount>().ok().flatten();
+
+        for account in transfer_accounts.chain(target_account) {
+            if let UserAccount::Internal(owner) = account {
+                self.history_owners.insert(owner);
+            }
+        }
+    }
 }
 
 struct ReplayProtectionHashes {
@@ -1826,6 +1884,7 @@ mod test_finalize_block {
                 nonce: 1u64.into(),
                 transfers: vec![transfer],
                 relayer: bertha,
+                relay_cost: Default::default(),
             };
             let (protocol_key, _) =
                 namada_apps_lib::wallet::defaults::validator_keys();
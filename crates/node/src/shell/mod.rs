@@ -23,6 +23,7 @@ mod stats;
 #[allow(dead_code)]
 pub mod testing;
 mod vote_extensions;
+mod wasm_warmup;
 
 use std::cell::RefCell;
 use std::collections::BTreeSet;
@@ -174,6 +175,31 @@ pub fn rollback(config: config::Ledger) -> ShellResult<()> {
         .map_err(|e| Error::Storage(namada_sdk::state::Error::new(e)))
 }
 
+/// Pre-compile the wasm codes stored in the node's DB into the wasm caches and
+/// wait for the compilation to finish. The node must not be running.
+pub fn warmup_wasm_caches(config: config::Ledger) -> ShellResult<()> {
+    let chain_id = config.chain_id;
+    let base_dir = config.shell.base_dir;
+    let chain_dir = base_dir.join(chain_id.as_str());
+    let native_token = genesis::chain::Finalized::read_native_token(&chain_dir)
+        .expect("Missing genesis files");
+    let state = storage::PersistentState::open(
+        config.shell.db_dir(&chain_id),
+        None,
+        chain_id.clone(),
+        native_token,
+        config.shell.storage_read_past_height_limit,
+        is_key_diff_storable,
+    );
+    // The in-memory caches are not used, the compiled modules are only
+    // written to files
+    let mut tx_wasm_cache = TxCache::new(chain_dir.join("tx_wasm_cache"), 1);
+    let mut vp_wasm_cache = VpCache::new(chain_dir.join("vp_wasm_cache"), 1);
+    let codes = wasm_warmup::read_codes(&state).map_err(Error::Storage)?;
+    wasm_warmup::warmup(codes, &mut tx_wasm_cache, &mut vp_wasm_cache);
+    Ok(())
+}
+
 #[derive(Debug)]
 #[allow(dead_code, clippy::large_enum_variant)]
 pub(super) enum ShellMode {
@@ -385,6 +411,9 @@ where
     /// Data for a node downloading and apply snapshots as part of
     /// the fast sync protocol.
     pub syncing: Option<SnapshotSync>,
+    /// Taken from config `tx_history_index`. When set, the transactions that
+    /// move the tokens of transparent accounts are indexed by their owners.
+    tx_history_index: bool,
 }
 
 /// Storage key filter to store the diffs into the storage. Return `false` for
@@ -460,6 +489,7 @@ impl Shell<crate::storage::PersistentDB, Sha256Hasher> {
 
         // rebuild the in-memory state
         self.state.load_last_state();
+        self.load_gas_schedule();
     }
 }
 
@@ -668,11 +698,33 @@ where
             scheduled_migration,
             blocks_between_snapshots: config.shell.blocks_between_snapshots,
             syncing: None,
+            tx_history_index: config.shell.tx_history_index,
         };
         shell.update_eth_oracle(&Default::default());
+        shell.load_gas_schedule();
+        #[cfg(not(any(test, fuzzing)))]
+        shell.warmup_wasm_caches();
         shell
     }
 
+    /// Pre-compile the wasm codes that are likely to be executed, in the
+    /// background.
+    #[cfg_attr(any(test, fuzzing), allow(dead_code))]
+    fn warmup_wasm_caches(&mut self) {
+        let codes = match wasm_warmup::read_codes(&self.state) {
+            Ok(codes) => codes,
+            Err(err) => {
+                tracing::error!("Failed to warm up the wasm caches: {err}");
+                return;
+            }
+        };
+        let mut tx_wasm_cache = self.tx_wasm_cache.clone();
+        let mut vp_wasm_cache = self.vp_wasm_cache.clone();
+        std::thread::spawn(move || {
+            wasm_warmup::warmup(codes, &mut tx_wasm_cache, &mut vp_wasm_cache)
+        });
+    }
+
     /// Return a reference to the [`EventLog`].
     #[inline]
     pub fn event_log(&self) -> &EventLog {
@@ -685,6 +737,14 @@ where
         &mut self.event_log
     }
 
+    /// Load the gas schedule parameter into the write log, from where it's
+    /// used by gas metering.
+    pub fn load_gas_schedule(&mut self) {
+        let gas_schedule = parameters::read_gas_schedule(&self.state)
+            .expect("Must be able to read the gas schedule");
+        self.state.write_log_mut().set_gas_schedule(gas_schedule);
+    }
+
     /// Load the Merkle root hash and the height of the last committed block, if
     /// any. This is returned when ABCI sends an `info` request.
     pub fn last_state(&self) -> response::Info {
@@ -785,6 +845,9 @@ where
         self.state
             .commit_block()
             .expect("Encountered a storage error while committing a block");
+        // A gas schedule updated by governance applies from the next block,
+        // so that all the txs of a block are priced the same
+        self.load_gas_schedule();
         let committed_height = self.state.in_mem().get_last_block_height();
         migrations::commit(
             self.state.db(),
@@ -1240,7 +1303,10 @@ where
                         return response;
                     }
                 };
-                let mut gas_meter = TxGasMeter::new(gas_limit);
+                let mut gas_meter = TxGasMeter::new_with_schedule(
+                    gas_limit,
+                    self.state.gas_schedule().clone(),
+                );
                 if gas_meter.add_wrapper_gas(tx_bytes).is_err() {
                     response.code = ResultCode::TxGasLimit.into();
                     response.log = "{INVALID_MSG}: Wrapper transaction \
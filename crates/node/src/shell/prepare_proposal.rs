@@ -7,7 +7,9 @@ use namada_sdk::gas::TxGasMeter;
 use namada_sdk::key::tm_raw_hash_to_string;
 use namada_sdk::parameters::get_gas_scale;
 use namada_sdk::proof_of_stake::storage::find_validator_by_raw_hash;
-use namada_sdk::state::{DBIter, StorageHasher, TempWlState, TxIndex, DB};
+use namada_sdk::state::{
+    DBIter, StateRead, StorageHasher, TempWlState, TxIndex, DB,
+};
 use namada_sdk::token::{Amount, DenominatedAmount};
 use namada_sdk::tx::data::WrapperTx;
 use namada_sdk::tx::Tx;
@@ -307,7 +309,10 @@ where
     let gas_scale = get_gas_scale(temp_state).map_err(|_| ())?;
     let gas_limit =
         wrapper.gas_limit.as_scaled_gas(gas_scale).map_err(|_| ())?;
-    let mut tx_gas_meter = TxGasMeter::new(gas_limit);
+    let mut tx_gas_meter = TxGasMeter::new_with_schedule(
+        gas_limit,
+        temp_state.gas_schedule().clone(),
+    );
     tx_gas_meter.add_wrapper_gas(tx_bytes).map_err(|_| ())?;
 
     super::replay_protection_checks(&tx, temp_state).map_err(|_| ())?;
@@ -437,7 +437,10 @@ where
                         };
                     }
                 };
-                let mut tx_gas_meter = TxGasMeter::new(gas_limit);
+                let mut tx_gas_meter = TxGasMeter::new_with_schedule(
+                    gas_limit,
+                    temp_state.gas_schedule().clone(),
+                );
                 if tx_gas_meter.add_wrapper_gas(tx_bytes).is_err() {
                     return TxResult {
                         code: ResultCode::TxGasLimit.into(),
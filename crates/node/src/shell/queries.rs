@@ -3,7 +3,7 @@
 use namada_sdk::queries::{RequestCtx, ResponseQuery, RPC};
 
 use super::*;
-use crate::dry_run_tx;
+use crate::{dry_run_tx, dry_run_tx_trace};
 
 impl<D, H> Shell<D, H>
 where
@@ -28,6 +28,14 @@ where
                 self.tx_wasm_cache.read_only(),
                 &query,
             )
+        } else if query.path == RPC.shell().dry_run_tx_trace_path() {
+            dry_run_tx_trace(
+                // Safe for the same reasons as the dry-run above
+                unsafe { self.state.read_only().with_static_temp_write_log() },
+                self.vp_wasm_cache.read_only(),
+                self.tx_wasm_cache.read_only(),
+                &query,
+            )
         } else {
             let ctx = RequestCtx {
                 state: self.state.read_only(),
@@ -61,7 +61,9 @@ use crate::tendermint_proto::abci::{
 use crate::tendermint_rpc::endpoint::block;
 use crate::tendermint_rpc::error::Error as RpcError;
 use crate::tendermint_rpc::SimpleRequest;
-use crate::{dry_run_tx, storage, tendermint, tendermint_rpc};
+use crate::{
+    dry_run_tx, dry_run_tx_trace, storage, tendermint, tendermint_rpc,
+};
 
 /// Mock Ethereum oracle used for testing purposes.
 struct MockEthOracle {
@@ -827,6 +829,16 @@ impl Client for MockNode {
                 borrowed.tx_wasm_cache.read_only(),
                 &request,
             )
+        } else if request.path == RPC.shell().dry_run_tx_trace_path() {
+            dry_run_tx_trace(
+                // Safe for the same reasons as the dry-run above
+                unsafe {
+                    borrowed.state.read_only().with_static_temp_write_log()
+                },
+                borrowed.vp_wasm_cache.read_only(),
+                borrowed.tx_wasm_cache.read_only(),
+                &request,
+            )
         } else {
             let ctx = RequestCtx {
                 state: &borrowed.state,
@@ -191,6 +191,7 @@ mod test_vote_extensions {
                 nonce,
                 transfers: vec![],
                 relayer: gen_established_address(),
+                relay_cost: Default::default(),
             })
             .then_some(())
             .ok_or(())
@@ -204,6 +205,7 @@ mod test_vote_extensions {
                 nonce: nonce + 1,
                 transfers: vec![],
                 relayer: gen_established_address(),
+                relay_cost: Default::default(),
             })
             .then_some(())
             .ok_or(())
@@ -215,6 +217,7 @@ mod test_vote_extensions {
                 nonce: nonce - 1,
                 transfers: vec![],
                 relayer: gen_established_address(),
+                relay_cost: Default::default(),
             })
             .then_some(())
             .ok_or(())
@@ -288,6 +291,7 @@ mod test_vote_extensions {
                 checksum: Hash::default(),
             }],
             relayer: gen_established_address(),
+            relay_cost: Default::default(),
         };
         let event_2 = EthereumEvent::TransfersToEthereum {
             nonce: 1.into(),
@@ -298,6 +302,7 @@ mod test_vote_extensions {
                 checksum: Hash::default(),
             }],
             relayer: gen_established_address(),
+            relay_cost: Default::default(),
         };
         let event_3 = EthereumEvent::TransfersToNamada {
             nonce: 0.into(),
@@ -371,6 +376,7 @@ mod test_vote_extensions {
                     checksum: Hash::default(),
                 }],
                 relayer: gen_established_address(),
+                relay_cost: Default::default(),
             }],
             block_height: shell.get_current_decision_height(),
             validator_addr: address.clone(),
@@ -416,6 +422,7 @@ mod test_vote_extensions {
                     checksum: Hash::default(),
                 }],
                 relayer: gen_established_address(),
+                relay_cost: Default::default(),
             }],
             block_height: signed_height,
             validator_addr: address,
@@ -539,6 +546,7 @@ mod test_vote_extensions {
                     checksum: Hash::default(),
                 }],
                 relayer: gen_established_address(),
+                relay_cost: Default::default(),
             }],
             block_height: shell.state.in_mem().get_last_block_height(),
             validator_addr: address.clone(),
@@ -580,6 +588,7 @@ mod test_vote_extensions {
                     checksum: Hash::default(),
                 }],
                 relayer: gen_established_address(),
+                relay_cost: Default::default(),
             }],
             block_height: shell.state.in_mem().get_last_block_height(),
             validator_addr: address.clone(),
@@ -0,0 +1,203 @@
+//! Warmup of the wasm compilation caches. The compiled modules are persisted
+//! to files, but after an upgrade of the node or a wipe of the caches, the
+//! first blocks would pay the full compilation cost of the wasm codes they
+//! execute. To avoid that, the codes that are likely to be executed are
+//! pre-compiled in the background when the node starts.
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use namada_sdk::hash::Hash;
+use namada_sdk::parameters;
+use namada_sdk::state::{self, StorageRead};
+use namada_sdk::storage::{DbKeySeg, Key};
+use namada_vm::wasm::{TxCache, VpCache};
+use namada_vm::WasmCacheRwAccess;
+
+/// How often the progress of the warmup is reported
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long to wait for the compilation to make progress before giving up on
+/// reporting it, e.g. when a compilation thread has crashed
+const STALLED_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// The hashes of the wasm codes to pre-compile
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct WarmupCodes {
+    /// The transactions' codes
+    pub txs: BTreeSet<Hash>,
+    /// The validity predicates' codes
+    pub vps: BTreeSet<Hash>,
+}
+
+/// The wasm codes to pre-compile, read from storage
+#[derive(Debug, Default)]
+pub struct CodesToCompile {
+    /// The transactions' codes
+    pub txs: Vec<Vec<u8>>,
+    /// The validity predicates' codes
+    pub vps: Vec<Vec<u8>>,
+}
+
+/// Find the wasm codes to pre-compile: the allowlisted txs and VPs, the
+/// implicit VP and all the named txs and VPs stored in the parameters. The
+/// custom VPs of accounts that are not allowlisted or named are not included,
+/// they are only compiled when first used.
+pub fn codes_to_warmup<S>(storage: &S) -> state::Result<WarmupCodes>
+where
+    S: StorageRead,
+{
+    let params = parameters::read(storage)?;
+    let parse_allowlist = |allowlist: &[String]| -> BTreeSet<Hash> {
+        allowlist
+            .iter()
+            .filter_map(|hash| Hash::from_str(hash).ok())
+            .collect()
+    };
+    let mut codes = WarmupCodes {
+        txs: parse_allowlist(&params.tx_allowlist),
+        vps: parse_allowlist(&params.vp_allowlist),
+    };
+    codes.vps.extend(params.implicit_vp_code_hash);
+
+    // The prefix of the keys of the code hashes by name
+    let mut prefix = Key::wasm_hash("");
+    prefix.segments.pop();
+    for entry in state::iter_prefix::<Hash>(storage, &prefix)? {
+        let (key, hash) = entry?;
+        match key.last() {
+            Some(DbKeySeg::StringSeg(name)) if name.starts_with("tx_") => {
+                codes.txs.insert(hash);
+            }
+            Some(DbKeySeg::StringSeg(name)) if name.starts_with("vp_") => {
+                codes.vps.insert(hash);
+            }
+            _ => {}
+        }
+    }
+    Ok(codes)
+}
+
+/// Read the codes to pre-compile from storage
+pub fn read_codes<S>(storage: &S) -> state::Result<CodesToCompile>
+where
+    S: StorageRead,
+{
+    let WarmupCodes { txs, vps } = codes_to_warmup(storage)?;
+    let read_all = |hashes: BTreeSet<Hash>| -> state::Result<Vec<Vec<u8>>> {
+        let mut codes = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            codes.extend(storage.read_bytes(&Key::wasm_code(&hash))?);
+        }
+        Ok(codes)
+    };
+    Ok(CodesToCompile {
+        txs: read_all(txs)?,
+        vps: read_all(vps)?,
+    })
+}
+
+/// Check the integrity of the caches, pre-compile the given codes and wait
+/// for the compilation to finish. This blocks for as long as the compilation
+/// takes, so the node should call it from a background thread.
+pub fn warmup(
+    CodesToCompile { txs, vps }: CodesToCompile,
+    tx_cache: &mut TxCache<WasmCacheRwAccess>,
+    vp_cache: &mut VpCache<WasmCacheRwAccess>,
+) {
+    let removed_txs = tx_cache.verify_integrity();
+    let removed_vps = vp_cache.verify_integrity();
+    if !removed_txs.is_empty() || !removed_vps.is_empty() {
+        tracing::warn!(
+            "Removed {} invalid txs and {} invalid VPs from the wasm caches.",
+            removed_txs.len(),
+            removed_vps.len()
+        );
+    }
+
+    tracing::info!(
+        "Warming up the wasm caches with {} txs and {} VPs.",
+        txs.len(),
+        vps.len()
+    );
+    for code in txs {
+        tx_cache.pre_compile(code);
+    }
+    for code in vps {
+        vp_cache.pre_compile(code);
+    }
+    report_progress(tx_cache, vp_cache);
+}
+
+/// Periodically log the progress of the compilation until all the modules
+/// known to the caches are compiled, or until the compilation has made no
+/// progress for [`STALLED_TIMEOUT`].
+fn report_progress(
+    tx_cache: &TxCache<WasmCacheRwAccess>,
+    vp_cache: &VpCache<WasmCacheRwAccess>,
+) {
+    let mut last_done = 0;
+    let mut last_progress = Instant::now();
+    loop {
+        let (txs_done, txs) = tx_cache.compilation_progress();
+        let (vps_done, vps) = vp_cache.compilation_progress();
+        if txs_done == txs && vps_done == vps {
+            tracing::info!(
+                "Finished warming up the wasm caches: {txs} txs and {vps} VPs \
+                 are compiled."
+            );
+            return;
+        }
+        let done = txs_done.saturating_add(vps_done);
+        if done != last_done {
+            last_done = done;
+            last_progress = Instant::now();
+        } else if last_progress.elapsed() >= STALLED_TIMEOUT {
+            tracing::warn!(
+                "The warmup of the wasm caches has stalled with \
+                 {txs_done}/{txs} txs and {vps_done}/{vps} VPs compiled. The \
+                 remaining codes will be compiled when first used."
+            );
+            return;
+        }
+        tracing::info!(
+            "Warming up the wasm caches: compiled {txs_done}/{txs} txs and \
+             {vps_done}/{vps} VPs."
+        );
+        std::thread::sleep(PROGRESS_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_sdk::state::testing::TestState;
+    use namada_sdk::state::StorageWrite;
+
+    use super::*;
+
+    #[test]
+    fn test_codes_to_warmup() {
+        let mut state = TestState::default();
+        parameters::init_test_storage(&mut state).unwrap();
+
+        let tx_hash = Hash::sha256(b"tx");
+        let vp_hash = Hash::sha256(b"vp");
+        let allowed_tx_hash = Hash::sha256(b"allowed tx");
+        state
+            .write(&Key::wasm_hash("tx_transfer.wasm"), tx_hash)
+            .unwrap();
+        state
+            .write(&Key::wasm_hash("vp_user.wasm"), vp_hash)
+            .unwrap();
+        parameters::update_tx_allowlist_parameter(
+            &mut state,
+            vec![allowed_tx_hash.to_string().to_lowercase()],
+        )
+        .unwrap();
+
+        let codes = codes_to_warmup(&state).unwrap();
+        assert_eq!(codes.txs, BTreeSet::from([tx_hash, allowed_tx_hash]));
+        assert!(codes.vps.contains(&vp_hash));
+    }
+}
@@ -56,6 +56,7 @@ use borsh_ext::BorshSerializeExt;
 use data_encoding::HEXLOWER;
 use itertools::Either;
 use namada_replay_protection as replay_protection;
+use namada_sdk::address::Address;
 use namada_sdk::arith::checked;
 use namada_sdk::collections::HashSet;
 use namada_sdk::eth_bridge::storage::bridge_pool;
@@ -67,13 +68,13 @@ use namada_sdk::state::merkle_tree::{
     tree_key_prefix_with_epoch, tree_key_prefix_with_height,
 };
 use namada_sdk::state::{
-    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, DbError as Error,
-    DbResult as Result, MerkleTreeStoresRead, PatternIterator, PrefixIterator,
-    StoreType, DB,
+    tx_history, BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch,
+    DbError as Error, DbResult as Result, MerkleTreeStoresRead,
+    PatternIterator, PrefixIterator, StoreType, DB,
 };
 use namada_sdk::storage::{
     BlockHeader, BlockHeight, DbColFam, Epoch, Key, KeySeg, BLOCK_CF, DIFFS_CF,
-    REPLAY_PROTECTION_CF, ROLLBACK_CF, STATE_CF, SUBSPACE_CF,
+    REPLAY_PROTECTION_CF, ROLLBACK_CF, STATE_CF, SUBSPACE_CF, TX_HISTORY_CF,
 };
 use namada_sdk::{decode, encode, ethereum_events};
 use rayon::prelude::*;
@@ -232,6 +233,25 @@ pub fn open(
         REPLAY_PROTECTION_CF,
         replay_protection_cf_opts,
     ));
+
+    // for the transaction history index (insert-intensive)
+    let mut tx_history_cf_opts = Options::default();
+    tx_history_cf_opts.set_compression_type(DBCompressionType::Zstd);
+    tx_history_cf_opts.set_compression_options(0, 0, 0, 1024 * 1024);
+    tx_history_cf_opts.set_compaction_style(DBCompactionStyle::Level);
+    tx_history_cf_opts.set_block_based_table_factory(&table_opts);
+    // The DBs created before the transaction history index lack its column
+    // family, which cannot be created in read-only mode
+    let has_tx_history_cf = !read_only
+        || rocksdb::DB::list_cf(&db_opts, path.as_ref()).is_ok_and(
+            |cf_names| cf_names.iter().any(|cf| cf == TX_HISTORY_CF),
+        );
+    if has_tx_history_cf {
+        cfs.push(ColumnFamilyDescriptor::new(
+            TX_HISTORY_CF,
+            tx_history_cf_opts,
+        ));
+    }
     Ok(if read_only {
         RocksDB {
             inner: ManuallyDrop::new(
@@ -632,6 +652,24 @@ impl RocksDB {
             batch.0.delete_cf(reprot_cf, current_key);
         }
 
+        // Delete the tx history entries of the last block
+        let tx_history_cf = self.get_column_family(TX_HISTORY_CF)?;
+        tracing::info!("Removing last block tx history entries");
+        let block_prefix = tx_history::block_prefix(last_block.height);
+        for (_, entry_key, _) in
+            iter_prefix(self, tx_history_cf, None, Some(&block_prefix))
+        {
+            batch.0.delete_cf(tx_history_cf, entry_key);
+        }
+        let block_prefix = format!("{block_prefix}/");
+        let mut block_upper_bound = block_prefix.clone().into_bytes();
+        if let Some(last) = block_upper_bound.last_mut() {
+            *last = last.checked_add(1).expect("cannot overflow");
+        }
+        batch
+            .0
+            .delete_range_cf(tx_history_cf, block_prefix, block_upper_bound);
+
         // Execute next step in parallel
         let batch = Mutex::new(batch);
 
@@ -734,7 +772,7 @@ impl RocksDB {
     }
 
     #[inline]
-    pub fn column_families(&self) -> [(&'static str, &ColumnFamily); 6] {
+    pub fn column_families(&self) -> [(&'static str, &ColumnFamily); 7] {
         DbColFam::all()
             .iter()
             .map(|cf| {
@@ -746,7 +784,7 @@ impl RocksDB {
             })
             .collect::<Vec<_>>()
             .try_into()
-            .map_err(|_| "There should be exactly six column families")
+            .map_err(|_| "There should be exactly seven column families")
             .unwrap()
     }
 
@@ -1718,6 +1756,29 @@ impl DB for RocksDB {
         Ok(())
     }
 
+    fn write_tx_history_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        owner: &Address,
+        height: BlockHeight,
+        tx_index: u32,
+        hash: &Hash,
+    ) -> Result<()> {
+        let tx_history_cf = self.get_column_family(TX_HISTORY_CF)?;
+
+        let key = tx_history::key(owner, height, tx_index).to_string();
+        let block_key = tx_history::block_key(owner, height, tx_index);
+        self.add_value_bytes_to_batch(
+            tx_history_cf,
+            block_key.to_string(),
+            key.clone().into_bytes(),
+            batch,
+        );
+        self.add_value_to_batch(tx_history_cf, key, hash, batch);
+
+        Ok(())
+    }
+
     fn move_current_replay_protection_entries(
         &mut self,
         batch: &mut Self::WriteBatch,
@@ -1991,6 +2052,27 @@ impl<'iter> DBIter<'iter> for RocksDB {
         let prefix = Some(replay_protection::current_prefix());
         iter_prefix(self, replay_protection_cf, None, prefix.as_ref())
     }
+
+    fn iter_tx_history(
+        &'iter self,
+        owner: &Address,
+        (from_height, from_tx_index): (BlockHeight, u32),
+    ) -> Self::PrefixIter {
+        let stripped_prefix = format!("{}/", tx_history::prefix(owner));
+        let read_opts = make_iter_read_opts(Some(stripped_prefix.clone()));
+        let from =
+            tx_history::key(owner, from_height, from_tx_index).to_string();
+        let mode = IteratorMode::From(from.as_bytes(), Direction::Forward);
+        let iter = match self.get_column_family(TX_HISTORY_CF) {
+            Ok(tx_history_cf) => {
+                self.inner.iterator_cf_opt(tx_history_cf, read_opts, mode)
+            }
+            // The DBs opened in read-only mode may lack the index, in which
+            // case the iterator runs over the empty default column family
+            Err(_) => self.inner.iterator_opt(mode, read_opts),
+        };
+        PersistentPrefixIterator(PrefixIterator::new(iter, stripped_prefix))
+    }
 }
 
 fn iter_subspace_prefix<'iter>(
@@ -2486,6 +2568,15 @@ mod test {
                 )
                 .unwrap();
             }
+            let owner = namada_sdk::address::testing::established_address_1();
+            db.write_tx_history_entry(
+                &mut batch,
+                &owner,
+                height_0,
+                0,
+                &Hash::sha256(b"tx1"),
+            )
+            .unwrap();
 
             add_block_to_batch(
                 &db,
@@ -2539,6 +2630,14 @@ mod test {
                 )
                 .unwrap();
             }
+            db.write_tx_history_entry(
+                &mut batch,
+                &owner,
+                height_1,
+                0,
+                &Hash::sha256(b"tx5"),
+            )
+            .unwrap();
 
             add_block_to_batch(
                 &db,
@@ -2564,6 +2663,14 @@ mod test {
                     db.has_replay_protection_entry(&Hash::sha256(tx)).unwrap()
                 );
             }
+            // The tx history can be read from the second block
+            let history: Vec<_> =
+                db.iter_tx_history(&owner, (height_1, 0)).collect();
+            assert_eq!(history.len(), 1);
+            assert_eq!(
+                decode::<Hash>(&history[0].1).unwrap(),
+                Hash::sha256(b"tx5")
+            );
 
             // Rollback to the first block height
             db.rollback(height_0).unwrap();
@@ -2594,9 +2701,48 @@ mod test {
                     !db.has_replay_protection_entry(&Hash::sha256(tx)).unwrap()
                 );
             }
+            // Only the tx history of the first block is kept
+            let history: Vec<_> =
+                db.iter_tx_history(&owner, (BlockHeight(0), 0)).collect();
+            assert_eq!(history.len(), 1);
+            assert_eq!(
+                decode::<Hash>(&history[0].1).unwrap(),
+                Hash::sha256(b"tx1")
+            );
+            let tx_history_cf = db.get_column_family(TX_HISTORY_CF).unwrap();
+            for (height, listed) in [(height_0, 1), (height_1, 0)] {
+                let block_prefix = tx_history::block_prefix(height);
+                let entries =
+                    iter_prefix(&db, tx_history_cf, None, Some(&block_prefix));
+                assert_eq!(entries.count(), listed);
+            }
         }
     }
 
+    /// Test that a DB without the tx history column family can be opened in
+    /// read-only mode, without creating the column family
+    #[test]
+    fn test_open_read_only_without_tx_history() {
+        let dir = tempdir().unwrap();
+        {
+            let mut db_opts = Options::default();
+            db_opts.create_if_missing(true);
+            db_opts.create_missing_column_families(true);
+            let cfs = DbColFam::all()
+                .into_iter()
+                .filter(|cf| *cf != TX_HISTORY_CF);
+            rocksdb::DB::open_cf(&db_opts, dir.path(), cfs).unwrap();
+        }
+        let db = open(dir.path(), true, None).unwrap();
+        assert!(db.get_column_family(TX_HISTORY_CF).is_err());
+        let owner = namada_sdk::address::testing::established_address_1();
+        assert_eq!(db.iter_tx_history(&owner, (BlockHeight(0), 0)).count(), 0);
+        drop(db);
+        let cf_names =
+            rocksdb::DB::list_cf(&Options::default(), dir.path()).unwrap();
+        assert!(!cf_names.iter().any(|cf| cf == TX_HISTORY_CF));
+    }
+
     #[test]
     fn test_diffs() {
         let dir = tempdir().unwrap();
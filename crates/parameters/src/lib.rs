@@ -29,6 +29,7 @@ use namada_core::chain::BlockHeight;
 pub use namada_core::parameters::ProposalBytes;
 use namada_core::time::DurationSecs;
 use namada_core::{hints, token};
+pub use namada_gas::GasSchedule;
 use namada_state::{Error, Key, ResultExt, StorageRead, StorageWrite};
 pub use namada_systems::parameters::*;
 pub use storage::{get_gas_scale, get_max_block_gas};
@@ -330,6 +331,30 @@ where
     Ok(gas_cost_table.get(token).map(|amount| amount.to_owned()))
 }
 
+/// Read the gas schedule parameter. Falls back to the default schedule if
+/// none has been written to storage.
+pub fn read_gas_schedule<S>(storage: &S) -> Result<GasSchedule>
+where
+    S: StorageRead,
+{
+    let key = storage::get_gas_schedule_key();
+    Ok(storage.read(&key)?.unwrap_or_default())
+}
+
+/// Update the gas schedule parameter in storage. The new schedule must be
+/// within the allowed bounds.
+pub fn update_gas_schedule_parameter<S>(
+    storage: &mut S,
+    value: &GasSchedule,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    value.validate().into_storage_result()?;
+    let key = storage::get_gas_schedule_key();
+    storage.write(&key, value)
+}
+
 /// Read the number of epochs per year parameter
 pub fn read_epochs_per_year<S>(storage: &S) -> Result<u64>
 where
@@ -819,4 +844,31 @@ mod tests {
 
         assert_eq!(max_block_time, Some(BLOCK_TIME_ESTIMATE_UPPER_BOUND));
     }
+
+    #[test]
+    fn test_read_and_update_gas_schedule() {
+        let mut storage = TestStorage::default();
+
+        // Without a stored schedule, the defaults apply
+        assert_eq!(
+            read_gas_schedule(&storage).unwrap(),
+            GasSchedule::default()
+        );
+
+        let schedule = GasSchedule {
+            storage_write_gas_per_byte: 2 * GasSchedule::default()
+                .storage_write_gas_per_byte,
+            ..Default::default()
+        };
+        update_gas_schedule_parameter(&mut storage, &schedule).unwrap();
+        assert_eq!(read_gas_schedule(&storage).unwrap(), schedule);
+
+        // Out of bounds schedules are rejected
+        let invalid = GasSchedule {
+            verify_tx_sig_gas: 0,
+            ..Default::default()
+        };
+        assert!(update_gas_schedule_parameter(&mut storage, &invalid).is_err());
+        assert_eq!(read_gas_schedule(&storage).unwrap(), schedule);
+    }
 }
@@ -41,6 +41,7 @@ struct Keys {
     masp_fee_payment_gas_limit: &'static str,
     gas_scale: &'static str,
     native_token_transferable: &'static str,
+    gas_schedule: &'static str,
 }
 
 /// Returns if the key is a parameter key.
@@ -96,6 +97,11 @@ pub fn is_max_tx_bytes_key(key: &Key) -> bool {
     is_max_tx_bytes_key_at_addr(key, &ADDRESS)
 }
 
+/// Returns if the key is the gas schedule key.
+pub fn is_gas_schedule_key(key: &Key) -> bool {
+    is_gas_schedule_key_at_addr(key, &ADDRESS)
+}
+
 /// Storage key used for epoch parameter.
 pub fn get_epoch_duration_storage_key() -> Key {
     get_epoch_duration_key_at_addr(ADDRESS)
@@ -121,6 +127,11 @@ pub fn get_gas_scale_key() -> Key {
     get_gas_scale_key_at_addr(ADDRESS)
 }
 
+/// Storage key used for the gas schedule
+pub fn get_gas_schedule_key() -> Key {
+    get_gas_schedule_key_at_addr(ADDRESS)
+}
+
 /// Storage key used for implicit VP parameter.
 pub fn get_implicit_vp_key() -> Key {
     get_implicit_vp_key_at_addr(ADDRESS)
@@ -5,6 +5,7 @@ use std::marker::PhantomData;
 
 use namada_core::address::Address;
 use namada_core::booleans::BoolResultUnitExt;
+use namada_gas::GasSchedule;
 use namada_systems::governance;
 use namada_tx::BatchedTxRef;
 use namada_vp_env::{Error, Key, Result, VpEnv};
@@ -49,12 +50,27 @@ where
                                  {key}",
                             ))
                         },
-                    )
+                    )?;
+                    if storage::is_gas_schedule_key(key) {
+                        Self::validate_gas_schedule(ctx, key)?;
+                    }
+                    Ok(())
                 }
                 KeyType::UNKNOWN => Ok(()),
             }
         })
     }
+
+    /// Check that an updated gas schedule is within the allowed bounds. The
+    /// schedule may be deleted to revert to the defaults.
+    fn validate_gas_schedule(ctx: &'ctx CTX, key: &Key) -> Result<()> {
+        let Some(schedule) = ctx.read_post::<GasSchedule>(key)? else {
+            return Ok(());
+        };
+        schedule.validate().map_err(|err| {
+            Error::new_alloc(format!("Invalid gas schedule: {err}"))
+        })
+    }
 }
 
 #[allow(clippy::upper_case_acronyms)]
@@ -5,6 +5,7 @@ use std::collections::BTreeMap;
 
 use borsh::BorshDeserialize;
 use namada_core::address::Address;
+use namada_core::arith::checked;
 use namada_core::chain::Epoch;
 use namada_core::collections::{HashMap, HashSet};
 use namada_core::dec::Dec;
@@ -194,6 +195,28 @@ where
         .collect()
 }
 
+/// Find the total amount of tokens of the given bond `owner` (or source)
+/// held by PoS, i.e. bonded or unbonded but not yet withdrawn, with all the
+/// validators. The raw amounts are summed, without deducting any slashes.
+pub fn find_bonded_and_unbonded_total<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<token::Amount>
+where
+    S: StorageRead,
+{
+    let mut total = token::Amount::zero();
+    for validator in delegation_targets_handle(owner).iter(storage)? {
+        let (validator, _) = validator?;
+        let bonds = find_bonds(storage, owner, &validator)?;
+        let unbonds = find_unbonds(storage, owner, &validator)?;
+        for amount in bonds.into_values().chain(unbonds.into_values()) {
+            total = checked!(total + amount)?;
+        }
+    }
+    Ok(total)
+}
+
 /// Collect the details of all bonds and unbonds that match the source and
 /// validator arguments.
 ///
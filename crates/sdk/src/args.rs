@@ -6,6 +6,7 @@ use std::str::FromStr;
 use std::time::Duration as StdDuration;
 
 use either::Either;
+use namada_account::VpParams;
 use namada_core::address::Address;
 use namada_core::chain::{BlockHeight, ChainId, Epoch};
 use namada_core::collections::HashMap;
@@ -14,7 +15,7 @@ use namada_core::ethereum_events::EthAddress;
 use namada_core::keccak::KeccakHash;
 use namada_core::key::{common, SchemeType};
 use namada_core::masp::{MaspEpoch, PaymentAddress};
-use namada_core::time::DateTimeUtc;
+use namada_core::time::{DateTimeUtc, DurationSecs};
 use namada_core::{storage, token};
 use namada_governance::cli::onchain::{
     DefaultProposal, PgfFundingProposal, PgfStewardProposal,
@@ -27,7 +28,9 @@ use serde::{Deserialize, Serialize};
 use zeroize::Zeroizing;
 
 use crate::eth_bridge::bridge_pool;
+use crate::ibc::apps::nft_transfer::types::PrefixedClassId;
 use crate::ibc::core::host::types::identifiers::{ChannelId, PortId};
+use crate::remote_signer::RemoteSignerAddr;
 use crate::signing::SigningTxData;
 use crate::wallet::{DatedSpendingKey, DatedViewingKey};
 use crate::{rpc, tx, Namada};
@@ -836,6 +839,23 @@ pub struct TxInitAccount<C: NamadaTypes = SdkTypes> {
     pub public_keys: Vec<C::PublicKey>,
     /// The account multisignature threshold
     pub threshold: Option<u8>,
+    /// The vesting schedule of the new account, if it's a vesting account
+    pub vesting: Option<Vesting<C>>,
+}
+
+/// A vesting schedule of a new account
+#[derive(Clone, Debug)]
+pub struct Vesting<C: NamadaTypes = SdkTypes> {
+    /// The vesting token
+    pub token: C::Address,
+    /// The total amount of the token subject to vesting
+    pub amount: InputAmount,
+    /// The start of the linear release
+    pub start: DateTimeUtc,
+    /// The time before which nothing is vested
+    pub cliff: DateTimeUtc,
+    /// The time at which everything is vested
+    pub end: DateTimeUtc,
 }
 
 impl<C: NamadaTypes> TxBuilder<C> for TxInitAccount<C> {
@@ -867,6 +887,16 @@ impl<C: NamadaTypes> TxInitAccount<C> {
         }
     }
 
+    /// A vesting schedule to make the new account a vesting account, which
+    /// is initialized by its own transaction
+    pub fn vesting(self, vesting: Vesting<C>) -> Self {
+        Self {
+            vesting: Some(vesting),
+            tx_code_path: PathBuf::from(tx::TX_INIT_VESTING_ACCOUNT_WASM),
+            ..self
+        }
+    }
+
     /// Path to the VP WASM code file
     pub fn vp_code_path(self, vp_code_path: PathBuf) -> Self {
         Self {
@@ -1117,6 +1147,96 @@ impl TxUpdateAccount {
     }
 }
 
+/// Transaction to update the parameters of an account's VP
+#[derive(Clone, Debug)]
+pub struct TxUpdateVpParams<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+    /// Address of the account whose VP parameters are to be updated
+    pub addr: C::Address,
+    /// The new parameters of the account's VP
+    pub vp_params: Option<VpParams>,
+    /// A rolling-window spending limit to add to the account's VP parameters
+    pub spending_limit: Option<SpendingLimit<C>>,
+    /// The number of signatures that can bypass the account's spending limits
+    pub bypass_threshold: Option<u8>,
+}
+
+/// A rolling-window spending limit of an account
+#[derive(Clone, Debug)]
+pub struct SpendingLimit<C: NamadaTypes = SdkTypes> {
+    /// The limited token
+    pub token: C::Address,
+    /// The maximum amount of the token that can be debited in any window
+    pub amount: InputAmount,
+    /// The duration of the window
+    pub window: DurationSecs,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxUpdateVpParams<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxUpdateVpParams {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxUpdateVpParams<C> {
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+
+    /// Address of the account whose VP parameters are to be updated
+    pub fn addr(self, addr: C::Address) -> Self {
+        Self { addr, ..self }
+    }
+
+    /// The new parameters of the account's VP, e.g. its spending limits.
+    /// Empty parameters remove the existing ones.
+    pub fn vp_params(self, vp_params: VpParams) -> Self {
+        Self {
+            vp_params: Some(vp_params),
+            ..self
+        }
+    }
+
+    /// A rolling-window spending limit to add to the account's VP parameters
+    pub fn spending_limit(self, spending_limit: SpendingLimit<C>) -> Self {
+        Self {
+            spending_limit: Some(spending_limit),
+            ..self
+        }
+    }
+
+    /// The number of signatures that can bypass the account's spending limits
+    pub fn bypass_threshold(self, bypass_threshold: u8) -> Self {
+        Self {
+            bypass_threshold: Some(bypass_threshold),
+            ..self
+        }
+    }
+}
+
+impl TxUpdateVpParams {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_update_vp_params(context, self).await
+    }
+}
+
 /// Bond arguments
 #[derive(Clone, Debug)]
 pub struct Bond<C: NamadaTypes = SdkTypes> {
@@ -1586,6 +1706,19 @@ pub struct QueryAccount<C: NamadaTypes = SdkTypes> {
     pub owner: C::Address,
 }
 
+/// Query the transaction history of an account
+#[derive(Clone, Debug)]
+pub struct QueryTxHistory<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Address of an owner
+    pub owner: C::Address,
+    /// The block height from which to query the history
+    pub from_height: BlockHeight,
+    /// The transaction index in the block from which to query the history
+    pub from_tx_index: u32,
+}
+
 /// Query token balance(s)
 #[derive(Clone, Debug)]
 pub struct QueryBalance<C: NamadaTypes = SdkTypes> {
@@ -2146,6 +2279,29 @@ pub struct SignTx<C: NamadaTypes = SdkTypes> {
     pub owner: C::Address,
 }
 
+#[derive(Clone, Debug)]
+/// Add signatures to an offline signing session
+pub struct SignSessionAdd<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Path to the signing session file
+    pub session: PathBuf,
+    /// The account address
+    pub owner: C::Address,
+}
+
+#[derive(Clone, Debug)]
+/// Submit the transaction of an offline signing session once the signatures
+/// meet the account threshold
+pub struct SignSessionFinalize<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Path to the signing session file
+    pub session: PathBuf,
+    /// The account address
+    pub owner: C::Address,
+}
+
 #[derive(Clone, Debug)]
 /// Sync notes from MASP owned by the provided spending /
 /// viewing keys. Syncing can be told to stop at a given
@@ -2176,6 +2332,35 @@ pub struct ShieldedSync<C: NamadaTypes = SdkTypes> {
     pub retry_strategy: RetryStrategy,
 }
 
+/// HD key discovery arguments
+#[derive(Clone, Debug)]
+pub struct DiscoverKeys<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Scheme type of the transparent keys
+    pub scheme: SchemeType,
+    /// Whether to discover MASP spending keys
+    pub shielded: bool,
+    /// Prefix of the aliases of the discovered keys, followed by their index
+    pub alias: String,
+    /// Whether to force overwrite the aliases
+    pub alias_force: bool,
+    /// Number of consecutive unused indices after which to stop
+    pub gap_limit: u32,
+    /// Don't encrypt the discovered keys
+    pub unsafe_dont_encrypt: bool,
+    /// Prompt for BIP39 passphrase
+    pub prompt_bip39_passphrase: bool,
+    /// Optional blockheight after which the MASP keys were created
+    pub birthday: Option<BlockHeight>,
+    /// Address of a `namada-masp-indexer` live instance to scan the notes
+    pub with_indexer: Option<C::MaspIndexerAddress>,
+    /// Maximum number of fetch jobs of the shielded sync
+    pub max_concurrent_fetches: usize,
+    /// Number of blocks fetched per fetch job of the shielded sync
+    pub block_batch_size: usize,
+}
+
 /// Query PoS commission rate
 #[derive(Clone, Debug)]
 pub struct QueryCommissionRate<C: NamadaTypes = SdkTypes> {
@@ -2234,6 +2419,26 @@ pub struct QueryTotalSupply<C: NamadaTypes = SdkTypes> {
     pub token: C::Address,
 }
 
+/// Query IBC NFTs
+#[derive(Clone, Debug)]
+pub struct QueryNft<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// List the NFTs owned by this address
+    pub owner: Option<C::Address>,
+    /// The IBC token of the NFT to get the metadata of
+    pub token: Option<C::Address>,
+}
+
+/// Query IBC NFT classes
+#[derive(Clone, Debug)]
+pub struct QueryNftClass<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The class to query. All the classes are listed if unset.
+    pub class_id: Option<PrefixedClassId>,
+}
+
 /// Query effective native supply
 #[derive(Clone, Debug)]
 pub struct QueryEffNativeSupply<C: NamadaTypes = SdkTypes> {
@@ -2347,6 +2552,9 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub use_device: bool,
     /// Hardware Wallet transport - HID (USB) or TCP
     pub device_transport: DeviceTransport,
+    /// Remote signer to sign the transaction with the keys that are not in
+    /// the wallet
+    pub remote_signer: Option<RemoteSignerAddr>,
 }
 
 /// Hardware Wallet transport - HID (USB) or TCP
@@ -2622,6 +2830,31 @@ pub struct KeyConvert {
     pub alias: String,
 }
 
+/// Remote signer daemon arguments
+#[derive(Clone, Debug)]
+pub struct ServeRemoteSigner {
+    /// The address to serve the signing requests on
+    pub address: RemoteSignerAddr,
+    /// The aliases of the keys to sign with
+    pub aliases: Vec<String>,
+}
+
+/// Wallet backup arguments
+#[derive(Clone, Debug)]
+pub struct WalletBackup {
+    /// The path of the backup file to write
+    pub file_path: String,
+}
+
+/// Wallet restore arguments
+#[derive(Clone, Debug)]
+pub struct WalletRestore {
+    /// The path of the backup file to restore
+    pub file_path: String,
+    /// Whether to replace the entries of the conflicting aliases
+    pub alias_force: bool,
+}
+
 /// Wallet key import arguments
 #[derive(Clone, Debug)]
 pub struct KeyImport {
@@ -2711,6 +2944,10 @@ pub struct EthereumBridgePool<C: NamadaTypes = SdkTypes> {
     pub fee_payer: Option<C::Address>,
     /// The token in which the gas is being paid
     pub fee_token: C::AddrOrNativeToken,
+    /// Conversion rates of the gas tokens to gwei. If provided,
+    /// the offered gas fee is checked against the cost of relaying
+    /// recent Bridge pool transfers.
+    pub conversion_table: Option<C::BpConversionTable>,
     /// Path to the tx WASM code file
     pub code_path: PathBuf,
 }
@@ -2779,6 +3016,17 @@ impl<C: NamadaTypes> EthereumBridgePool<C> {
         }
     }
 
+    /// Conversion rates of the gas tokens to gwei
+    pub fn conversion_table(
+        self,
+        conversion_table: C::BpConversionTable,
+    ) -> Self {
+        Self {
+            conversion_table: Some(conversion_table),
+            ..self
+        }
+    }
+
     /// Path to the tx WASM code file
     pub fn code_path(self, code_path: PathBuf) -> Self {
         Self { code_path, ..self }
@@ -2795,6 +3043,51 @@ impl EthereumBridgePool {
     }
 }
 
+/// A transfer to be cancelled from the Ethereum bridge pool.
+#[derive(Clone, Debug)]
+pub struct CancelEthereumBridgePool<C: NamadaTypes = SdkTypes> {
+    /// The args for building a tx to the bridge pool
+    pub tx: Tx<C>,
+    /// The keccak hash of the pending transfer to cancel
+    pub transfer: KeccakHash,
+    /// Path to the tx WASM code file
+    pub code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for CancelEthereumBridgePool<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        CancelEthereumBridgePool {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> CancelEthereumBridgePool<C> {
+    /// The keccak hash of the pending transfer to cancel
+    pub fn transfer(self, transfer: KeccakHash) -> Self {
+        Self { transfer, ..self }
+    }
+
+    /// Path to the tx WASM code file
+    pub fn code_path(self, code_path: PathBuf) -> Self {
+        Self { code_path, ..self }
+    }
+}
+
+impl CancelEthereumBridgePool {
+    /// Build a transaction from this builder
+    pub async fn build(
+        self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        bridge_pool::build_cancel_bridge_pool_tx(context, self).await
+    }
+}
+
 /// Bridge pool proof arguments.
 #[derive(Debug, Clone)]
 pub struct BridgePoolProof<C: NamadaTypes = SdkTypes> {
@@ -2900,6 +3193,29 @@ pub struct ValidatorSetUpdateRelay<C: NamadaTypes = SdkTypes> {
     pub success_dur: Option<StdDuration>,
 }
 
+/// IBC packet relayer arguments.
+#[derive(Debug, Clone)]
+pub struct IbcRelay<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments, for txs submitted to this chain
+    pub tx: Tx<C>,
+    /// Common tx arguments, for txs submitted to the counterparty chain
+    pub counterparty_tx: Tx<C>,
+    /// The port of the channel on this chain
+    pub port_id: PortId,
+    /// The channel to relay packets over, on this chain
+    pub channel_id: ChannelId,
+    /// Run in daemon mode, which will continuously relay packets.
+    pub daemon: bool,
+    /// The amount of time to sleep between failed
+    /// daemon mode relays.
+    pub retry_dur: Option<StdDuration>,
+    /// The amount of time to sleep between successful
+    /// daemon mode relays.
+    pub success_dur: Option<StdDuration>,
+    /// Path to the IBC tx WASM code file
+    pub tx_code_path: PathBuf,
+}
+
 /// IBC shielding transfer generation arguments
 #[derive(Clone, Debug)]
 pub struct GenIbcShieldingTransfer<C: NamadaTypes = SdkTypes> {
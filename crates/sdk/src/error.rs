@@ -4,6 +4,7 @@ use namada_core::address::Address;
 use namada_core::chain::Epoch;
 use namada_core::dec::Dec;
 use namada_core::ethereum_events::EthAddress;
+use namada_core::keccak::KeccakHash;
 use namada_core::{arith, storage};
 use namada_events::EventError;
 use namada_tx::Tx;
@@ -276,6 +277,12 @@ pub enum TxSubmitError {
     /// Account threshold is not set
     #[error("Account threshold is invalid.")]
     InvalidAccountThreshold,
+    /// Vesting schedule times are not ordered
+    #[error(
+        "The vesting schedule is invalid: the start must not be after the \
+         cliff and the cliff must not be after the end."
+    )]
+    InvalidVestingSchedule,
     /// Not enough signature
     #[error("Account threshold is {0} but the valid signatures are {1}.")]
     MissingSigningKeys(u8, u8),
@@ -336,6 +343,12 @@ pub enum EthereumBridgeError {
     /// Error reading the Bridge pool.
     #[error("Failed to read Bridge pool: {0}")]
     ReadBridgePool(String),
+    /// Error reading the costs of relaying Bridge pool batches.
+    #[error("Failed to read Bridge pool relay costs: {0}")]
+    ReadRelayCosts(String),
+    /// Error reading the stale transfers in the Bridge pool.
+    #[error("Failed to read stale Bridge pool transfers: {0}")]
+    ReadStaleTransfers(String),
     /// Error querying transfer to Ethereum progress.
     #[error("Failed to query transfer to Ethereum progress: {0}")]
     TransferToEthProgress(String),
@@ -369,4 +382,13 @@ pub enum EthereumBridgeError {
     /// Transfer already in pool error.
     #[error("An identical transfer is already present in the Bridge pool")]
     TransferAlreadyInPool,
+    /// Transfer not in pool error.
+    #[error("The transfer {0} is not pending in the Bridge pool")]
+    TransferNotInPool(KeccakHash),
+    /// Transfer already signed error.
+    #[error(
+        "The transfer {0} has already been included in a signed Bridge pool \
+         root, and can no longer be cancelled"
+    )]
+    TransferAlreadySigned(KeccakHash),
 }
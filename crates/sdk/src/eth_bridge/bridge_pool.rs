@@ -18,8 +18,9 @@ use namada_core::eth_bridge_pool::{
     erc20_token_address, GasFee, PendingTransfer, TransferToEthereum,
     TransferToEthereumKind,
 };
-use namada_core::ethereum_events::EthAddress;
+use namada_core::ethereum_events::{EthAddress, Uint as EthUint};
 use namada_core::keccak::KeccakHash;
+use namada_core::uint::Uint;
 use namada_core::voting_power::FractionalVotingPower;
 use namada_ethereum_bridge::storage::bridge_pool::get_pending_key;
 use namada_io::{display, display_line, edisplay_line, Client, Io};
@@ -37,8 +38,8 @@ use crate::error::{
 use crate::eth_bridge::ethers::abi::AbiDecode;
 use crate::internal_macros::echo_error;
 use crate::queries::{
-    GenBridgePoolProofReq, GenBridgePoolProofRsp, TransferToErcArgs,
-    TransferToEthereumStatus, RPC,
+    GenBridgePoolProofReq, GenBridgePoolProofRsp, StalePendingTransfer,
+    TransferToErcArgs, TransferToEthereumStatus, RPC,
 };
 use crate::rpc::{query_storage_value, query_wasm_code_hash, validate_amount};
 use crate::signing::{aux_signing_data, validate_transparent_fee};
@@ -58,6 +59,7 @@ pub async fn build_bridge_pool_tx(
         fee_amount,
         fee_payer,
         fee_token,
+        conversion_table,
         code_path,
     }: args::EthereumBridgePool,
 ) -> Result<(Tx, SigningTxData), Error> {
@@ -74,6 +76,7 @@ pub async fn build_bridge_pool_tx(
             fee_amount,
             fee_payer,
             fee_token,
+            conversion_table,
         ),
         query_wasm_code_hash(context, code_path.to_string_lossy()),
         aux_signing_data(
@@ -117,6 +120,111 @@ pub async fn build_bridge_pool_tx(
     Ok((tx, signing_data))
 }
 
+/// Craft a transaction that cancels a transfer pending in the Ethereum
+/// bridge pool, refunding its escrowed assets and gas fees.
+pub async fn build_cancel_bridge_pool_tx(
+    context: &impl Namada,
+    args::CancelEthereumBridgePool {
+        tx: tx_args,
+        transfer,
+        code_path,
+    }: args::CancelEthereumBridgePool,
+) -> Result<(Tx, SigningTxData), Error> {
+    let (transfer, tx_code_hash) = futures::try_join!(
+        validate_cancel_bridge_pool_tx(context, tx_args.force, &transfer),
+        query_wasm_code_hash(context, code_path.to_string_lossy()),
+    )?;
+    let sender = transfer.transfer.sender.clone();
+    let signing_data = aux_signing_data(
+        context,
+        &tx_args,
+        // token owner
+        Some(sender.clone()),
+        // tx signer
+        Some(sender),
+        vec![],
+        false,
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, &tx_args, &signing_data.fee_payer)
+            .await?;
+
+    let chain_id = tx_args
+        .chain_id
+        .clone()
+        .ok_or_else(|| Error::Other("No chain id available".into()))?;
+
+    let mut tx = Tx::new(chain_id, tx_args.expiration.to_datetime());
+    if let Some(memo) = &tx_args.memo {
+        tx.add_memo(memo);
+    }
+    tx.add_code_from_hash(
+        tx_code_hash,
+        Some(code_path.to_string_lossy().into_owned()),
+    )
+    .add_data(transfer);
+
+    prepare_tx(
+        &tx_args,
+        &mut tx,
+        fee_amount,
+        signing_data.fee_payer.clone(),
+    )
+    .await?;
+
+    Ok((tx, signing_data))
+}
+
+/// Look up a transfer pending in the Bridge pool, and check
+/// that it can still be cancelled.
+async fn validate_cancel_bridge_pool_tx(
+    context: &impl Namada,
+    force: bool,
+    transfer_hash: &KeccakHash,
+) -> Result<PendingTransfer, Error> {
+    let transfer = RPC
+        .shell()
+        .eth_bridge()
+        .read_ethereum_bridge_pool(context.client())
+        .await
+        .map_err(|e| {
+            Error::EthereumBridge(EthereumBridgeError::ReadBridgePool(
+                e.to_string(),
+            ))
+        })?
+        .into_iter()
+        .find(|transfer| &transfer.keccak256() == transfer_hash)
+        .ok_or_else(|| {
+            Error::EthereumBridge(EthereumBridgeError::TransferNotInPool(
+                transfer_hash.clone(),
+            ))
+        })?;
+
+    let transfer_is_signed = RPC
+        .shell()
+        .eth_bridge()
+        .read_signed_ethereum_bridge_pool(context.client())
+        .await
+        .map_err(|e| {
+            Error::EthereumBridge(EthereumBridgeError::ReadSignedBridgePool(
+                e.to_string(),
+            ))
+        })?
+        .contains(&transfer);
+    if transfer_is_signed {
+        let error = Error::EthereumBridge(
+            EthereumBridgeError::TransferAlreadySigned(transfer_hash.clone()),
+        );
+        if !force {
+            return Err(error);
+        }
+        edisplay_line!(context.io(), "{error}");
+    }
+
+    Ok(transfer)
+}
+
 /// Perform client validation checks on a Bridge pool transfer.
 #[allow(clippy::too_many_arguments)]
 async fn validate_bridge_pool_tx(
@@ -130,6 +238,7 @@ async fn validate_bridge_pool_tx(
     fee_amount: args::InputAmount,
     fee_payer: Option<Address>,
     fee_token: Address,
+    conversion_table: Option<HashMap<Address, args::BpConversionTableEntry>>,
 ) -> Result<PendingTransfer, Error> {
     let token_addr = erc20_token_address(&asset);
     let validate_token_amount =
@@ -179,6 +288,46 @@ async fn validate_bridge_pool_tx(
         return Ok(transfer);
     }
 
+    // warn if the offered fee does not cover what relaying
+    // recent transfers has cost on Ethereum
+    let conversion_rate = conversion_table.as_ref().and_then(|table| {
+        table
+            .get(&transfer.gas_fee.token)
+            .map(|entry| entry.conversion_rate)
+    });
+    if let Some(conversion_rate) = conversion_rate {
+        match query_relay_cost_per_transfer(context.client()).await {
+            Ok(Some(cost)) => {
+                if let Some(min_fee) = relay_cost_to_fee(cost, conversion_rate)
+                {
+                    if transfer.gas_fee.amount < min_fee {
+                        let warning = "Warning".on_yellow();
+                        let warning = warning.bold();
+                        let warning = warning.blink();
+                        display_line!(
+                            context.io(),
+                            "{warning}: The offered gas fee of {} is below \
+                             the recommended minimum of {} in {}, based on \
+                             the cost of relaying recent transfers. The \
+                             transfer may not be relayed before it expires.",
+                            transfer.gas_fee.amount,
+                            min_fee,
+                            transfer.gas_fee.token,
+                        );
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(error) => {
+                edisplay_line!(
+                    context.io(),
+                    "Failed to query the cost of relaying Bridge pool \
+                     transfers: {error}"
+                );
+            }
+        }
+    }
+
     //======================================================
     // XXX: the following validations should be kept in sync
     // with the validations performed by the Bridge pool VP!
@@ -391,6 +540,90 @@ pub async fn query_signed_bridge_pool(
     Ok(pool_contents)
 }
 
+/// Query the cost of relaying a single Bridge pool transfer to
+/// Ethereum, in wei. This is the median of the costs per transfer
+/// of the recently relayed batches, or [`None`] if no batches
+/// have been relayed yet.
+pub async fn query_relay_cost_per_transfer(
+    client: &(impl Client + Sync),
+) -> Result<Option<EthUint>, Error> {
+    let relay_costs = RPC
+        .shell()
+        .eth_bridge()
+        .read_relay_costs(client)
+        .await
+        .map_err(|e| {
+        Error::EthereumBridge(EthereumBridgeError::ReadRelayCosts(
+            e.to_string(),
+        ))
+    })?;
+    Ok(relay_costs.cost_per_transfer())
+}
+
+/// Convert a cost in wei to the minimum amount of a gas token
+/// that covers it, given the conversion rate of the token to gwei
+/// used by the relayer recommendations. Returns [`None`] if the
+/// conversion rate is not usable.
+fn relay_cost_to_fee(cost: EthUint, conversion_rate: f64) -> Option<Amount> {
+    if conversion_rate <= 0.0f64 || conversion_rate > 1e9 {
+        return None;
+    }
+    // This is the amount of gwei a single gas token is worth
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let gwei_per_gas_token =
+        Uint::from_u64((1e9 / conversion_rate).floor() as u64);
+    let gwei = Uint(cost.0)
+        .checked_add(Uint::from_u64(999_999_999))?
+        .checked_div(Uint::from_u64(1_000_000_000))?;
+    let fee = gwei
+        .checked_add(gwei_per_gas_token.checked_sub(Uint::one())?)?
+        .checked_div(gwei_per_gas_token)?;
+    Some(Amount::from(fee))
+}
+
+/// A json serializable representation of the Bridge
+/// pool fee market.
+#[derive(Serialize)]
+struct BridgePoolFeesResponse {
+    relay_cost_per_transfer: Option<EthUint>,
+    stale_transfers: HashMap<String, StalePendingTransfer>,
+}
+
+/// Query the cost, in wei, of relaying a single Bridge pool transfer,
+/// along with the transfers that have been pending for so long
+/// that they are at risk of expiring.
+/// Prints out a json payload.
+pub async fn query_bridge_pool_fees(
+    client: &(impl Client + Sync),
+    io: &impl Io,
+) -> Result<(), Error> {
+    let relay_cost_per_transfer = query_relay_cost_per_transfer(client).await?;
+    let stale_transfers = RPC
+        .shell()
+        .eth_bridge()
+        .read_stale_transfers(client)
+        .await
+        .map_err(|e| {
+            Error::EthereumBridge(EthereumBridgeError::ReadStaleTransfers(
+                e.to_string(),
+            ))
+        })?
+        .into_iter()
+        .map(|stale| (stale.transfer.keccak256().to_string(), stale))
+        .collect();
+    let response = BridgePoolFeesResponse {
+        relay_cost_per_transfer,
+        stale_transfers,
+    };
+    display_line!(
+        io,
+        "{}",
+        serde_json::to_string_pretty(&response)
+            .map_err(|e| EncodingError::Serde(e.to_string()))?
+    );
+    Ok(())
+}
+
 /// Iterates over all ethereum events
 /// and returns the amount of voting power
 /// backing each `TransferToEthereum` event.
@@ -1620,3 +1853,27 @@ mod recommendations {
 }
 
 pub use recommendations::recommend_batch;
+
+#[cfg(test)]
+mod test_relay_costs {
+    use super::*;
+
+    /// Test converting the cost of relaying a transfer to the
+    /// minimum fee in a gas token.
+    #[test]
+    fn test_relay_cost_to_fee() {
+        // 1.5 gwei is rounded up to 2 gwei
+        let cost = EthUint::from(1_500_000_000_u64);
+        // 1 gas token = 1 gwei
+        assert_eq!(relay_cost_to_fee(cost, 1e9), Some(Amount::from_u64(2)));
+        // 1 gas token = 2 gwei
+        assert_eq!(relay_cost_to_fee(cost, 5e8), Some(Amount::from_u64(1)));
+        // 1 gas token = 4 gwei
+        let cost = EthUint::from(7_000_000_000_u64);
+        assert_eq!(relay_cost_to_fee(cost, 2.5e8), Some(Amount::from_u64(2)));
+        // unusable conversion rates
+        assert_eq!(relay_cost_to_fee(cost, 0.0), None);
+        assert_eq!(relay_cost_to_fee(cost, -1.0), None);
+        assert_eq!(relay_cost_to_fee(cost, 2e9), None);
+    }
+}
@@ -8,6 +8,8 @@ use namada_ethereum_bridge::event::types::{
     BRIDGE_POOL_EXPIRED, BRIDGE_POOL_RELAYED,
 };
 use namada_ethereum_bridge::event::BridgePoolTxHash;
+use namada_governance::event::types::{PROPOSAL_PASSED, PROPOSAL_REJECTED};
+use namada_governance::event::ProposalId;
 use namada_ibc::event::types::UPDATE_CLIENT;
 use namada_ibc::event::{
     ClientId as ClientIdAttr, ConsensusHeights, IbcEvent, IbcEventType,
@@ -133,6 +135,18 @@ impl QueryMatcher {
         Self::with_event_type(APPLIED_TX).and_attribute(TxHashAttr(tx_hash))
     }
 
+    /// Returns a query matching the given passed governance proposal.
+    pub fn proposal_passed(proposal_id: u64) -> Self {
+        Self::with_event_type(PROPOSAL_PASSED)
+            .and_attribute(ProposalId(proposal_id))
+    }
+
+    /// Returns a query matching the given rejected governance proposal.
+    pub fn proposal_rejected(proposal_id: u64) -> Self {
+        Self::with_event_type(PROPOSAL_REJECTED)
+            .and_attribute(ProposalId(proposal_id))
+    }
+
     /// Returns a query matching the given IBC UpdateClient parameters
     pub fn ibc_update_client(
         client_id: ClientId,
@@ -1,5 +1,7 @@
 //! Logic to do with events emitted by the ledger.
 pub mod log;
+#[cfg(not(target_family = "wasm"))]
+pub mod subscription;
 
 use namada_core::collections::HashMap;
 pub use namada_events::*;
@@ -0,0 +1,389 @@
+//! Subscriptions to the events emitted by the ledger, over the WebSocket
+//! endpoint of CometBFT.
+//!
+//! A subscription is notified of every new block and reads the events of the
+//! block from its results, which are then filtered with [`QueryMatcher`]s.
+//! Should the connection to the node be lost, the subscription reconnects and
+//! backfills the events of the blocks committed in the meantime, such that no
+//! block is ever skipped.
+
+use std::collections::VecDeque;
+
+use futures::StreamExt;
+use namada_core::chain::BlockHeight;
+
+use super::log::dumb_queries::QueryMatcher;
+use super::Event;
+use crate::control_flow::time::{self, Duration};
+use crate::error::{Error, QueryError};
+use crate::tendermint::block::Height;
+use crate::tendermint_rpc::client::CompatMode;
+use crate::tendermint_rpc::query::EventType;
+use crate::tendermint_rpc::{
+    Client, Subscription, SubscriptionClient, Url, WebSocketClient,
+};
+
+/// The default delay before reconnecting to the node
+pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// A subscription to the events of the ledger that match any of a set of
+/// filters. An empty set of filters matches all the events.
+pub struct EventSubscription {
+    url: Url,
+    filters: Vec<QueryMatcher>,
+    next_height: Option<BlockHeight>,
+    retry_delay: Duration,
+    pending: VecDeque<Event>,
+    connection: Option<Connection>,
+}
+
+impl EventSubscription {
+    /// Subscribe to the events of the node at the given WebSocket URL (e.g.
+    /// `ws://127.0.0.1:26657/websocket`), starting from the last committed
+    /// block
+    pub fn new(url: Url, filters: Vec<QueryMatcher>) -> Self {
+        Self {
+            url,
+            filters,
+            next_height: None,
+            retry_delay: DEFAULT_RETRY_DELAY,
+            pending: VecDeque::new(),
+            connection: None,
+        }
+    }
+
+    /// Start the subscription from the given block height, backfilling the
+    /// events of the blocks that were already committed
+    pub fn from_height(mut self, height: BlockHeight) -> Self {
+        self.next_height = Some(height);
+        self
+    }
+
+    /// Set the delay before reconnecting to the node
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// The height of the next block whose events will be read, if known
+    pub fn next_height(&self) -> Option<BlockHeight> {
+        self.next_height
+    }
+
+    /// Wait for the next matching event. Connection failures are retried
+    /// indefinitely.
+    pub async fn next(&mut self) -> Event {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return event;
+            }
+            if let Err(err) = self.poll().await {
+                tracing::debug!(
+                    %err,
+                    retry_delay = ?self.retry_delay,
+                    "Lost the event subscription connection, reconnecting"
+                );
+                self.connection = None;
+                time::sleep(self.retry_delay).await;
+            }
+        }
+    }
+
+    /// Wait for a new block and read its events. On a new connection, the
+    /// events of the blocks committed since the last read one are read
+    /// instead.
+    async fn poll(&mut self) -> Result<(), Error> {
+        let connection = match self.connection.as_mut() {
+            Some(connection) => connection,
+            None => {
+                let connection = Connection::open(&self.url).await?;
+                let connection = self.connection.insert(connection);
+                return Self::backfill(
+                    connection,
+                    &self.filters,
+                    &mut self.next_height,
+                    &mut self.pending,
+                )
+                .await;
+            }
+        };
+        match connection.new_blocks.next().await {
+            Some(Ok(_new_block)) => {
+                Self::backfill(
+                    connection,
+                    &self.filters,
+                    &mut self.next_height,
+                    &mut self.pending,
+                )
+                .await
+            }
+            Some(Err(err)) => Err(rpc_error(err)),
+            None => Err(Error::from(QueryError::General(
+                "The new blocks subscription was closed".to_string(),
+            ))),
+        }
+    }
+
+    /// Read the matching events of the blocks from the next height up to the
+    /// last committed one
+    async fn backfill(
+        connection: &Connection,
+        filters: &[QueryMatcher],
+        next_height: &mut Option<BlockHeight>,
+        pending: &mut VecDeque<Event>,
+    ) -> Result<(), Error> {
+        let last_height = connection
+            .client
+            .latest_block_results()
+            .await
+            .map_err(rpc_error)?
+            .height
+            .value();
+        let first_height = next_height.map_or(last_height, |height| height.0);
+
+        for height in first_height..=last_height {
+            let results = connection
+                .client
+                .block_results(Height::try_from(height).map_err(|err| {
+                    Error::from(QueryError::General(err.to_string()))
+                })?)
+                .await
+                .map_err(rpc_error)?;
+            for event in results.end_block_events.unwrap_or_default() {
+                match Event::try_from(event) {
+                    Ok(event)
+                        if filters.is_empty()
+                            || filters
+                                .iter()
+                                .any(|filter| filter.matches(&event)) =>
+                    {
+                        pending.push_back(event)
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        tracing::debug!(
+                            %err,
+                            height,
+                            "Skipping a malformed event"
+                        );
+                    }
+                }
+            }
+            // Only move on once all the events of the block were read, so
+            // that they aren't read twice after a failure
+            *next_height = Some(BlockHeight(height).next_height());
+        }
+        Ok(())
+    }
+}
+
+/// A connection to the WebSocket endpoint of a node
+struct Connection {
+    client: WebSocketClient,
+    new_blocks: Subscription,
+    driver: tokio::task::JoinHandle<Result<(), crate::tendermint_rpc::Error>>,
+}
+
+impl Connection {
+    /// Connect to the node and subscribe to new blocks
+    async fn open(url: &Url) -> Result<Self, Error> {
+        let (client, driver) = WebSocketClient::builder(
+            url.clone().try_into().map_err(rpc_error)?,
+        )
+        .compat_mode(CompatMode::V0_37)
+        .build()
+        .await
+        .map_err(rpc_error)?;
+        let driver = tokio::spawn(driver.run());
+        let new_blocks = client
+            .subscribe(EventType::NewBlock.into())
+            .await
+            .map_err(rpc_error)?;
+        Ok(Self {
+            client,
+            new_blocks,
+            driver,
+        })
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.driver.abort();
+    }
+}
+
+fn rpc_error(err: crate::tendermint_rpc::Error) -> Error {
+    Error::from(QueryError::General(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    use futures::SinkExt;
+    use namada_core::hash::Hash;
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::Message;
+
+    use super::*;
+    use crate::events::extend::{ComposeEvent, TxHash};
+    use crate::events::EventLevel;
+    use crate::tx::event::types::APPLIED;
+
+    /// The hash of the tx applied at the given height
+    fn tx_hash(height: u64) -> Hash {
+        Hash::sha256(height.to_be_bytes())
+    }
+
+    /// The ABCI events of a block, with an applied tx and an unrelated event
+    fn block_events(height: u64) -> Value {
+        let applied: Event = Event::new(APPLIED, EventLevel::Tx)
+            .with(TxHash(tx_hash(height)))
+            .into();
+        let other = Event::new(
+            crate::events::EventType::new("test/other"),
+            EventLevel::Block,
+        );
+        let events: Vec<namada_core::tendermint::abci::Event> =
+            vec![applied.into(), other.into()];
+        serde_json::to_value(
+            events
+                .into_iter()
+                .map(|event| {
+                    json!({
+                        "type": event.kind,
+                        "attributes": event
+                            .attributes
+                            .iter()
+                            .map(|attr| json!({
+                                "key": attr.key_str().unwrap(),
+                                "value": attr.value_str().unwrap(),
+                                "index": true,
+                            }))
+                            .collect::<Vec<_>>(),
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )
+        .unwrap()
+    }
+
+    /// A mock node serving one connection. The last committed height is
+    /// advanced once the client has read the events of the block at the
+    /// height of `notify_at`, notifying the client of the new block, and
+    /// once more at `close_at`, after which the connection is closed
+    /// without a notification.
+    async fn serve_connection(
+        listener: &TcpListener,
+        last_height: &AtomicU64,
+        notify_at: Option<u64>,
+        close_at: Option<u64>,
+    ) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let mut subscription: Option<(Value, Value)> = None;
+
+        while let Some(Ok(msg)) = ws.next().await {
+            let Message::Text(text) = msg else {
+                continue;
+            };
+            let request: Value = serde_json::from_str(&text).unwrap();
+            let id = request["id"].clone();
+            // The height of the block whose events were explicitly requested
+            let read_height = request["params"]["height"]
+                .as_str()
+                .map(|height| height.parse::<u64>().unwrap());
+            let result = match request["method"].as_str().unwrap() {
+                "subscribe" => {
+                    subscription =
+                        Some((id.clone(), request["params"]["query"].clone()));
+                    json!({})
+                }
+                "block_results" => {
+                    let height = read_height
+                        .unwrap_or_else(|| last_height.load(Ordering::SeqCst));
+                    json!({
+                        "height": height.to_string(),
+                        "txs_results": null,
+                        "begin_block_events": null,
+                        "end_block_events": block_events(height),
+                        "validator_updates": [],
+                        "consensus_param_updates": null,
+                    })
+                }
+                _ => json!({}),
+            };
+            let response =
+                json!({"jsonrpc": "2.0", "id": id, "result": result});
+            ws.send(Message::Text(response.to_string())).await.unwrap();
+
+            if read_height.is_some() && read_height == notify_at {
+                last_height.fetch_add(1, Ordering::SeqCst);
+                let (id, query) = subscription.clone().unwrap();
+                let event = json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "result": {
+                        "query": query,
+                        "data": {
+                            "type": "tendermint/event/NewBlock",
+                            "value": {
+                                "block": null,
+                                "result_begin_block": null,
+                                "result_end_block": null,
+                            },
+                        },
+                        "events": {"tm.event": ["NewBlock"]},
+                    },
+                });
+                ws.send(Message::Text(event.to_string())).await.unwrap();
+            }
+            if read_height.is_some() && read_height == close_at {
+                last_height.fetch_add(1, Ordering::SeqCst);
+                ws.close(None).await.unwrap();
+                return;
+            }
+        }
+    }
+
+    /// Test that the subscription yields the matching events of new blocks
+    /// and backfills the blocks committed while it was disconnected
+    #[tokio::test]
+    async fn test_subscription_reconnects_and_backfills() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url: Url =
+            format!("ws://{}/websocket", listener.local_addr().unwrap())
+                .parse()
+                .unwrap();
+        let last_height = Arc::new(AtomicU64::new(1));
+
+        let node = {
+            let last_height = last_height.clone();
+            tokio::spawn(async move {
+                // Notify the client of block 2 and close the connection
+                // once block 3 is committed
+                serve_connection(&listener, &last_height, Some(1), Some(2))
+                    .await;
+                serve_connection(&listener, &last_height, None, None).await;
+            })
+        };
+
+        let mut subscription = EventSubscription::new(
+            url,
+            vec![QueryMatcher::with_event_type(APPLIED)],
+        )
+        .with_retry_delay(Duration::from_millis(10));
+        for height in 1..=3 {
+            let event = subscription.next().await;
+            assert_eq!(
+                event.read_attribute::<TxHash>().unwrap(),
+                tx_hash(height)
+            );
+        }
+        assert_eq!(subscription.next_height(), Some(BlockHeight(4)));
+        node.abort();
+    }
+}
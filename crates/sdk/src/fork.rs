@@ -0,0 +1,800 @@
+//! Local forks of the ledger's state, to simulate transactions against the
+//! state of a node without a running network.
+//!
+//! The storage values of a fork are read lazily from a [`StorageSource`],
+//! typically a node over RPC, and cached in memory. The values written in the
+//! fork are kept in an in-memory [`MockDB`] and they never reach the source.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::{Mutex, MutexGuard};
+
+use itertools::Either;
+use namada_core::chain::{BlockHeader, BlockHeight, ChainId, Epoch, Epochs};
+use namada_core::ethereum_events;
+use namada_core::hash::{Hash, Sha256Hasher};
+use namada_core::storage::{DbColFam, Key, PrefixValue};
+use namada_gas::Gas;
+use namada_state::mockdb::{MockDB, MockDBRestoreSource, MockDBWriteBatch};
+use namada_state::{
+    BlockStateRead, BlockStateWrite, DBIter, DbError, DbResult,
+    FullAccessState, MerkleTreeStoresRead, StoreType, DB,
+};
+use regex::Regex;
+use tokio::runtime::Handle;
+
+use crate::error::{Error, QueryError};
+use crate::rpc;
+
+/// The state of a fork
+pub type ForkedState<S> = FullAccessState<ForkedDB<S>, Sha256Hasher>;
+
+/// The prefix iterator of a [`ForkedDB`]
+pub type ForkedPrefixIter = std::vec::IntoIter<(String, Vec<u8>, Gas)>;
+
+/// A source of the storage values of a fork
+pub trait StorageSource {
+    /// Read the value of a storage key
+    fn read(&self, key: &Key) -> DbResult<Option<Vec<u8>>>;
+
+    /// Read the values of all the storage keys with the given prefix
+    fn read_prefix(&self, prefix: &Key) -> DbResult<Vec<PrefixValue>>;
+}
+
+/// Reads the storage values of a fork from a node over RPC, at the height of
+/// the fork. The node can only serve the values of a prefix at its last
+/// committed height, so the prefixes fail to be read once the node has
+/// committed a block on top of the fork.
+///
+/// The reads block the current thread until the node responds, hence the
+/// state of the fork must not be used from within an async task (use e.g.
+/// [`tokio::task::spawn_blocking`]).
+#[derive(Debug)]
+pub struct RpcStorageSource<C> {
+    client: C,
+    runtime: Handle,
+    height: BlockHeight,
+}
+
+impl<C> RpcStorageSource<C> {
+    /// Read the storage values at the given height, using the handle of the
+    /// runtime to drive the requests of the client
+    pub fn new(client: C, runtime: Handle, height: BlockHeight) -> Self {
+        Self {
+            client,
+            runtime,
+            height,
+        }
+    }
+}
+
+impl<C> StorageSource for RpcStorageSource<C>
+where
+    C: namada_io::Client + Sync,
+{
+    fn read(&self, key: &Key) -> DbResult<Option<Vec<u8>>> {
+        let (value, _proof) = self
+            .runtime
+            .block_on(rpc::query_storage_value_bytes(
+                &self.client,
+                key,
+                Some(self.height),
+                false,
+            ))
+            .map_err(|err| DbError::DBError(err.to_string()))?;
+        Ok(value)
+    }
+
+    fn read_prefix(&self, prefix: &Key) -> DbResult<Vec<PrefixValue>> {
+        self.runtime
+            .block_on(rpc::query_storage_prefix_bytes(
+                &self.client,
+                prefix,
+                Some(self.height),
+            ))
+            .map_err(|err| DbError::DBError(err.to_string()))
+    }
+}
+
+/// A DB whose values are read from a [`StorageSource`], unless they were
+/// written in the fork. The values read from the source are cached, such that
+/// every key is only read once.
+///
+/// The errors of the source are returned by [`DBIter::try_iter_prefix`]. The
+/// other prefix iterators of the DB are infallible, so they log the error and
+/// only iterate the values that were already read when the source fails.
+pub struct ForkedDB<S> {
+    /// The values written in the fork
+    local: MockDB,
+    /// The source of the values that weren't written in the fork, if any
+    source: Option<S>,
+    /// The keys written or deleted in the fork
+    written: Mutex<BTreeSet<Key>>,
+    /// The values read from the source
+    fetched: Mutex<Fetched>,
+}
+
+/// The values read from the source of a fork
+#[derive(Debug, Default)]
+struct Fetched {
+    /// The values of the keys, which are `None` for missing keys
+    values: BTreeMap<Key, Option<Vec<u8>>>,
+    /// The prefixes whose values were all read
+    prefixes: BTreeSet<Key>,
+}
+
+impl<S> ForkedDB<S> {
+    /// Create a fork of the storage of the given source
+    pub fn new(source: S) -> Self {
+        Self::with_source(Some(source))
+    }
+
+    fn with_source(source: Option<S>) -> Self {
+        Self {
+            local: MockDB::default(),
+            source,
+            written: Mutex::default(),
+            fetched: Mutex::default(),
+        }
+    }
+
+    fn written(&self) -> MutexGuard<'_, BTreeSet<Key>> {
+        self.written
+            .lock()
+            .expect("The written keys lock is poisoned")
+    }
+
+    fn fetched(&self) -> MutexGuard<'_, Fetched> {
+        self.fetched
+            .lock()
+            .expect("The fetched values lock is poisoned")
+    }
+
+    /// Check if the value of the key is written in the fork
+    fn is_written(&self, key: &Key) -> bool {
+        self.written().contains(key)
+    }
+
+    /// Record that the value of the key is written in the fork, such that it
+    /// is never read from the source again
+    fn mark_written(&self, key: &Key) {
+        self.written().insert(key.clone());
+    }
+}
+
+impl<S: StorageSource> ForkedDB<S> {
+    /// Read the value of a key from the source, unless it is already cached
+    fn read_source(&self, key: &Key) -> DbResult<Option<Vec<u8>>> {
+        if let Some(value) = self.fetched().values.get(key) {
+            return Ok(value.clone());
+        }
+        // The lock isn't held while reading the source, so that VPs running
+        // in parallel aren't blocked
+        let value = match &self.source {
+            Some(source) => source.read(key)?,
+            None => None,
+        };
+        Ok(self
+            .fetched()
+            .values
+            .entry(key.clone())
+            .or_insert(value)
+            .clone())
+    }
+
+    /// Read the values of a prefix from the source, unless they are already
+    /// cached. The values of the keys that were already read are kept.
+    fn read_source_prefix(&self, prefix: &Key) -> DbResult<()> {
+        if self.fetched().prefixes.contains(prefix) {
+            return Ok(());
+        }
+        let values = match &self.source {
+            Some(source) => source.read_prefix(prefix)?,
+            None => vec![],
+        };
+        let mut fetched = self.fetched();
+        for PrefixValue { key, value } in values {
+            fetched.values.entry(key).or_insert(Some(value));
+        }
+        fetched.prefixes.insert(prefix.clone());
+        Ok(())
+    }
+
+    /// Merge the values of a prefix read from the source with the values
+    /// written in the fork, ordered by the storage keys
+    fn try_merged_prefix(
+        &self,
+        prefix: Option<&Key>,
+    ) -> DbResult<ForkedPrefixIter> {
+        if let Some(prefix) = prefix {
+            self.read_source_prefix(prefix)?;
+        }
+        Ok(self.cached_prefix(prefix))
+    }
+
+    /// Same as [`ForkedDB::try_merged_prefix`], but if the source fails the
+    /// error is logged and only the values that were already read are merged
+    fn merged_prefix(&self, prefix: Option<&Key>) -> ForkedPrefixIter {
+        self.try_merged_prefix(prefix).unwrap_or_else(|err| {
+            tracing::error!(
+                "Failed to read the storage prefix {prefix:?} of the fork: \
+                 {err}"
+            );
+            self.cached_prefix(prefix)
+        })
+    }
+
+    /// Merge the values of a prefix that were already read from the source
+    /// with the values written in the fork, ordered by the storage keys
+    fn cached_prefix(&self, prefix: Option<&Key>) -> ForkedPrefixIter {
+        // Same matching of the prefix as in the `MockDB`
+        let key_prefix = match prefix {
+            Some(prefix) if prefix != &Key::default() => format!("{prefix}/"),
+            _ => String::new(),
+        };
+
+        let mut entries = BTreeMap::new();
+        {
+            let written = self.written();
+            let fetched = self.fetched();
+            for (key, value) in &fetched.values {
+                let Some(value) = value else { continue };
+                if written.contains(key) {
+                    continue;
+                }
+                let key = key.to_string();
+                if key.starts_with(&key_prefix) {
+                    entries.insert(key, value.clone());
+                }
+            }
+        }
+        for (key, value, _gas) in self.local.iter_prefix(prefix) {
+            entries.insert(key, value);
+        }
+        entries
+            .into_iter()
+            .map(|(key, value)| {
+                let gas = key.len() + value.len();
+                (key, value, (gas as u64).into())
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl<S> fmt::Debug for ForkedDB<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForkedDB")
+            .field("local", &self.local)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: StorageSource> DB for ForkedDB<S> {
+    type Cache = ();
+    type Migrator = ();
+    type RestoreSource<'a> = MockDBRestoreSource;
+    type WriteBatch = MockDBWriteBatch;
+
+    /// A forked DB isn't persisted, so same as the [`MockDB`], it is opened
+    /// empty and without a source. Use [`ForkedDB::new`] to fork a source.
+    fn open(
+        _db_path: impl AsRef<std::path::Path>,
+        _cache: Option<&Self::Cache>,
+    ) -> Self {
+        Self::with_source(None)
+    }
+
+    fn restore_from(&mut self, source: MockDBRestoreSource) -> DbResult<()> {
+        match source {}
+    }
+
+    fn flush(&self, _wait: bool) -> DbResult<()> {
+        Ok(())
+    }
+
+    fn read_last_block(&self) -> DbResult<Option<BlockStateRead>> {
+        self.local.read_last_block()
+    }
+
+    fn add_block_to_batch(
+        &self,
+        state: BlockStateWrite<'_>,
+        batch: &mut Self::WriteBatch,
+        is_full_commit: bool,
+    ) -> DbResult<()> {
+        self.local.add_block_to_batch(state, batch, is_full_commit)
+    }
+
+    fn read_block_header(
+        &self,
+        height: BlockHeight,
+    ) -> DbResult<Option<BlockHeader>> {
+        self.local.read_block_header(height)
+    }
+
+    fn read_merkle_tree_stores(
+        &self,
+        epoch: Epoch,
+        base_height: BlockHeight,
+        store_type: Option<StoreType>,
+    ) -> DbResult<Option<MerkleTreeStoresRead>> {
+        self.local
+            .read_merkle_tree_stores(epoch, base_height, store_type)
+    }
+
+    fn has_replay_protection_entry(&self, hash: &Hash) -> DbResult<bool> {
+        self.local.has_replay_protection_entry(hash)
+    }
+
+    fn read_subspace_val(&self, key: &Key) -> DbResult<Option<Vec<u8>>> {
+        if self.is_written(key) {
+            self.local.read_subspace_val(key)
+        } else {
+            self.read_source(key)
+        }
+    }
+
+    /// The values of past heights aren't available in a fork, so the values at
+    /// the height of the fork are read instead.
+    fn read_subspace_val_with_height(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+        last_height: BlockHeight,
+    ) -> DbResult<Option<Vec<u8>>> {
+        if self.is_written(key) {
+            self.local
+                .read_subspace_val_with_height(key, height, last_height)
+        } else {
+            self.read_source(key)
+        }
+    }
+
+    fn read_diffs_val(
+        &self,
+        key: &Key,
+        height: BlockHeight,
+        is_old: bool,
+    ) -> DbResult<Option<Vec<u8>>> {
+        self.local.read_diffs_val(key, height, is_old)
+    }
+
+    fn write_subspace_val(
+        &mut self,
+        height: BlockHeight,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+        persist_diffs: bool,
+    ) -> DbResult<i64> {
+        self.mark_written(key);
+        self.local
+            .write_subspace_val(height, key, value, persist_diffs)
+    }
+
+    fn delete_subspace_val(
+        &mut self,
+        height: BlockHeight,
+        key: &Key,
+        persist_diffs: bool,
+    ) -> DbResult<i64> {
+        self.mark_written(key);
+        self.local.delete_subspace_val(height, key, persist_diffs)
+    }
+
+    fn batch() -> Self::WriteBatch {
+        MockDB::batch()
+    }
+
+    fn exec_batch(&self, batch: Self::WriteBatch) -> DbResult<()> {
+        self.local.exec_batch(batch)
+    }
+
+    fn batch_write_subspace_val(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        key: &Key,
+        value: impl AsRef<[u8]>,
+        persist_diffs: bool,
+    ) -> DbResult<i64> {
+        self.mark_written(key);
+        self.local.batch_write_subspace_val(
+            batch,
+            height,
+            key,
+            value,
+            persist_diffs,
+        )
+    }
+
+    fn batch_delete_subspace_val(
+        &self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        key: &Key,
+        persist_diffs: bool,
+    ) -> DbResult<i64> {
+        self.mark_written(key);
+        self.local
+            .batch_delete_subspace_val(batch, height, key, persist_diffs)
+    }
+
+    fn prune_merkle_tree_store(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        store_type: &StoreType,
+        pruned_target: Either<BlockHeight, Epoch>,
+    ) -> DbResult<()> {
+        self.local
+            .prune_merkle_tree_store(batch, store_type, pruned_target)
+    }
+
+    fn read_bridge_pool_signed_nonce(
+        &self,
+        height: BlockHeight,
+        last_height: BlockHeight,
+    ) -> DbResult<Option<ethereum_events::Uint>> {
+        self.local
+            .read_bridge_pool_signed_nonce(height, last_height)
+    }
+
+    fn write_replay_protection_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        key: &Key,
+    ) -> DbResult<()> {
+        self.local.write_replay_protection_entry(batch, key)
+    }
+
+    fn move_current_replay_protection_entries(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+    ) -> DbResult<()> {
+        self.local.move_current_replay_protection_entries(batch)
+    }
+
+    fn write_tx_history_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        owner: &namada_core::address::Address,
+        height: BlockHeight,
+        tx_index: u32,
+        hash: &Hash,
+    ) -> DbResult<()> {
+        self.local
+            .write_tx_history_entry(batch, owner, height, tx_index, hash)
+    }
+
+    fn prune_non_persisted_diffs(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+    ) -> DbResult<()> {
+        self.local.prune_non_persisted_diffs(batch, height)
+    }
+
+    fn overwrite_entry(
+        &self,
+        batch: &mut Self::WriteBatch,
+        cf: &DbColFam,
+        key: &Key,
+        new_value: impl AsRef<[u8]>,
+    ) -> DbResult<()> {
+        self.local.overwrite_entry(batch, cf, key, new_value)
+    }
+}
+
+impl<'iter, S: StorageSource> DBIter<'iter> for ForkedDB<S> {
+    type PatternIter = ForkedPrefixIter;
+    type PrefixIter = ForkedPrefixIter;
+
+    fn iter_prefix(&'iter self, prefix: Option<&Key>) -> ForkedPrefixIter {
+        self.merged_prefix(prefix)
+    }
+
+    fn try_iter_prefix(
+        &'iter self,
+        prefix: Option<&Key>,
+    ) -> DbResult<ForkedPrefixIter> {
+        self.try_merged_prefix(prefix)
+    }
+
+    fn iter_pattern(
+        &'iter self,
+        prefix: Option<&Key>,
+        pattern: Regex,
+    ) -> ForkedPrefixIter {
+        // Same as the `MockDB`, the iteration stops at the first key that
+        // doesn't match the pattern
+        self.merged_prefix(prefix)
+            .take_while(|(key, _value, _gas)| pattern.is_match(key))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn iter_results(&'iter self) -> ForkedPrefixIter {
+        self.local.iter_results().collect::<Vec<_>>().into_iter()
+    }
+
+    fn iter_old_diffs(
+        &'iter self,
+        height: BlockHeight,
+        prefix: Option<&'iter Key>,
+    ) -> ForkedPrefixIter {
+        self.local
+            .iter_old_diffs(height, prefix)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn iter_new_diffs(
+        &'iter self,
+        height: BlockHeight,
+        prefix: Option<&'iter Key>,
+    ) -> ForkedPrefixIter {
+        self.local
+            .iter_new_diffs(height, prefix)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn iter_current_replay_protection(&'iter self) -> ForkedPrefixIter {
+        self.local
+            .iter_current_replay_protection()
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn iter_tx_history(
+        &'iter self,
+        owner: &namada_core::address::Address,
+        from: (BlockHeight, u32),
+    ) -> ForkedPrefixIter {
+        self.local
+            .iter_tx_history(owner, from)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+/// Fork the state of a node at its last committed block. The transactions
+/// applied to the fork are executed in a new block on top of it.
+///
+/// The start heights of the epochs before the current one aren't known, so
+/// they are all assumed to be the first block height.
+pub async fn fork<C>(
+    client: C,
+    runtime: Handle,
+) -> Result<ForkedState<RpcStorageSource<C>>, Error>
+where
+    C: namada_io::Client + Sync,
+{
+    let chain_id = client
+        .status()
+        .await
+        .map(|status| ChainId(status.node_info.network.to_string()))
+        .map_err(|err| Error::from(QueryError::General(err.to_string())))?;
+    let native_token = rpc::query_native_token(&client).await?;
+    let last_block = rpc::query_block(&client).await?.ok_or_else(|| {
+        Error::Other("The node hasn't committed any block yet".to_string())
+    })?;
+    let epoch = rpc::query_epoch_at_height(&client, last_block.height)
+        .await?
+        .ok_or_else(|| {
+            Error::from(QueryError::General(format!(
+                "The epoch of the block {} is unknown",
+                last_block.height
+            )))
+        })?;
+    let (epoch_start_height, epoch_duration) =
+        rpc::query_next_epoch_info(&client).await?;
+
+    let source = RpcStorageSource::new(client, runtime, last_block.height);
+    // The diffs are never persisted in a fork
+    let mut state = ForkedState::new(
+        ForkedDB::new(source),
+        chain_id,
+        native_token,
+        None,
+        |_key| false,
+    );
+    let in_mem = state.in_mem_mut();
+    in_mem.block.height = last_block.height.next_height();
+    in_mem.block.epoch = epoch;
+    in_mem.block.pred_epochs = Epochs {
+        first_block_heights: (0..epoch.0)
+            .map(|_| BlockHeight::first())
+            .chain(std::iter::once(epoch_start_height))
+            .collect(),
+    };
+    in_mem.header = Some(BlockHeader {
+        hash: Hash::default(),
+        time: last_block.time,
+        next_validators_hash: Hash::default(),
+    });
+    in_mem.last_epoch = epoch;
+    in_mem.next_epoch_min_start_height = epoch_start_height
+        .checked_add(epoch_duration.min_num_of_blocks)
+        .ok_or_else(|| Error::Other("Block height overflow".to_string()))?;
+    in_mem.next_epoch_min_start_time = last_block.time;
+    in_mem.last_block = Some(last_block);
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use namada_state::{StorageRead, StorageWrite};
+
+    use super::*;
+
+    /// A storage source that counts the reads of the source
+    #[derive(Default)]
+    struct TestSource {
+        values: BTreeMap<Key, Vec<u8>>,
+        reads: AtomicU64,
+    }
+
+    impl TestSource {
+        fn count_read(&self) {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    impl StorageSource for TestSource {
+        fn read(&self, key: &Key) -> DbResult<Option<Vec<u8>>> {
+            self.count_read();
+            Ok(self.values.get(key).cloned())
+        }
+
+        fn read_prefix(&self, prefix: &Key) -> DbResult<Vec<PrefixValue>> {
+            self.count_read();
+            let prefix = format!("{prefix}/");
+            Ok(self
+                .values
+                .iter()
+                .filter(|(key, _)| key.to_string().starts_with(&prefix))
+                .map(|(key, value)| PrefixValue {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect())
+        }
+    }
+
+    fn key(segments: &[&str]) -> Key {
+        segments.iter().fold(Key::default(), |key, segment| {
+            key.push(&segment.to_string()).unwrap()
+        })
+    }
+
+    fn test_state() -> ForkedState<TestSource> {
+        let mut source = TestSource::default();
+        for (segments, value) in [
+            (["a", "1"], 1_u64),
+            (["a", "2"], 2_u64),
+            (["a", "3"], 3_u64),
+            (["b", "1"], 4_u64),
+        ] {
+            source
+                .values
+                .insert(key(&segments), value.to_le_bytes().into());
+        }
+        ForkedState::new(
+            ForkedDB::new(source),
+            ChainId::default(),
+            namada_core::address::testing::nam(),
+            None,
+            |_key| false,
+        )
+    }
+
+    fn reads(state: &ForkedState<TestSource>) -> u64 {
+        let source = state.db().source.as_ref().unwrap();
+        source.reads.load(Ordering::SeqCst)
+    }
+
+    fn commit(state: &mut ForkedState<TestSource>) {
+        let mut batch = ForkedState::<TestSource>::batch();
+        state.commit_write_log_block(&mut batch).unwrap();
+        state.exec_batch(batch).unwrap();
+    }
+
+    /// Test that the values of the source are read once and shadowed by the
+    /// values written in the fork, also after they are committed
+    #[test]
+    fn test_forked_db_reads_and_writes() {
+        let mut state = test_state();
+        let a1 = key(&["a", "1"]);
+        let a2 = key(&["a", "2"]);
+
+        assert_eq!(state.read::<u64>(&a1).unwrap(), Some(1));
+        assert_eq!(state.read::<u64>(&a1).unwrap(), Some(1));
+        assert_eq!(state.read::<u64>(&key(&["c"])).unwrap(), None);
+        assert_eq!(reads(&state), 2);
+
+        state.write(&a1, 10_u64).unwrap();
+        state.delete(&a2).unwrap();
+        assert_eq!(state.read::<u64>(&a1).unwrap(), Some(10));
+        assert_eq!(state.read::<u64>(&a2).unwrap(), None);
+
+        commit(&mut state);
+        let reads_after_commit = reads(&state);
+        assert_eq!(state.read::<u64>(&a1).unwrap(), Some(10));
+        assert_eq!(state.read::<u64>(&a2).unwrap(), None);
+        assert_eq!(reads(&state), reads_after_commit);
+    }
+
+    /// Test that the prefix iterator merges the values of the source with the
+    /// values written in the fork
+    #[test]
+    fn test_forked_db_iter_prefix() {
+        let mut state = test_state();
+        state.write(&key(&["a", "1"]), 10_u64).unwrap();
+        state.delete(&key(&["a", "2"])).unwrap();
+        state.write(&key(&["a", "4"]), 40_u64).unwrap();
+        commit(&mut state);
+        let reads_after_commit = reads(&state);
+
+        let prefix = key(&["a"]);
+        let iterate = |state: &ForkedState<TestSource>| {
+            namada_state::iter_prefix::<u64>(state, &prefix)
+                .unwrap()
+                .map(|entry| {
+                    let (key, value) = entry.unwrap();
+                    (key.to_string(), value)
+                })
+                .collect::<Vec<_>>()
+        };
+        let expected = vec![
+            ("a/1".to_string(), 10),
+            ("a/3".to_string(), 3),
+            ("a/4".to_string(), 40),
+        ];
+        assert_eq!(iterate(&state), expected);
+        assert_eq!(iterate(&state), expected);
+        // The prefix is only read once from the source
+        assert_eq!(reads(&state), reads_after_commit + 1);
+    }
+
+    /// A storage source that fails to read prefixes
+    struct FailingSource;
+
+    impl StorageSource for FailingSource {
+        fn read(&self, _key: &Key) -> DbResult<Option<Vec<u8>>> {
+            Ok(None)
+        }
+
+        fn read_prefix(&self, _prefix: &Key) -> DbResult<Vec<PrefixValue>> {
+            Err(DbError::DBError("The source is unavailable".to_string()))
+        }
+    }
+
+    /// Test that the errors of the source are returned by the prefix
+    /// iterators of the state instead of panicking
+    #[test]
+    fn test_forked_db_iter_prefix_source_error() {
+        let state = ForkedState::new(
+            ForkedDB::new(FailingSource),
+            ChainId::default(),
+            namada_core::address::testing::nam(),
+            None,
+            |_key| false,
+        );
+        let prefix = key(&["a"]);
+        assert!(namada_state::iter_prefix_bytes(&state, &prefix).is_err());
+        assert!(state.db().try_iter_prefix(Some(&prefix)).is_err());
+        assert_eq!(state.db().iter_prefix(Some(&prefix)).count(), 0);
+    }
+
+    /// Test that a forked DB is opened empty, without a source
+    #[test]
+    fn test_forked_db_open() {
+        let db = ForkedDB::<TestSource>::open("", None);
+        assert!(db.source.is_none());
+        assert_eq!(db.read_subspace_val(&key(&["a", "1"])).unwrap(), None);
+        assert_eq!(db.iter_prefix(Some(&key(&["a"]))).count(), 0);
+    }
+}
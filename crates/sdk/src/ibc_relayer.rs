@@ -0,0 +1,932 @@
+//! A minimal IBC packet relayer between two Namada chains.
+//!
+//! The relayer works over an already established unordered channel. It
+//! delivers packets committed on one end of the channel which have not yet
+//! been received on the other end, relays the acknowledgements written by the
+//! receiving chain back to the sender and times out packets which can no
+//! longer be received. Client, connection and channel handshakes are out of
+//! scope and must be performed with a full-fledged relayer such as Hermes.
+//! Ordered channels aren't supported.
+
+use std::path::Path;
+
+use namada_core::chain::BlockHeight;
+use namada_core::storage::{Key, PrefixValue};
+use namada_ibc::event::{
+    packet_from_event_attributes, IbcEventType, PacketAck,
+};
+use namada_ibc::storage::{
+    ack_key, channel_key, client_state_key, commitment_key, connection_key,
+    consensus_state_key, ibc_key, next_sequence_recv_key,
+    port_channel_sequence_id, receipt_key,
+};
+use namada_io::{display_line, edisplay_line, Client, Io};
+use namada_tx::Tx;
+use prost::Message;
+
+use crate::control_flow::time::{self, Duration};
+use crate::error::{EncodingError, Error, QueryError, TxSubmitError};
+use crate::ibc::clients::tendermint::types::{
+    ClientState as TmClientState, Header as TmHeader,
+};
+use crate::ibc::core::channel::types::acknowledgement::Acknowledgement;
+use crate::ibc::core::channel::types::channel::{ChannelEnd, Order, State};
+use crate::ibc::core::channel::types::msgs::{
+    MsgAcknowledgement, MsgRecvPacket, MsgTimeout,
+};
+use crate::ibc::core::channel::types::packet::Packet;
+use crate::ibc::core::client::types::msgs::MsgUpdateClient;
+use crate::ibc::core::client::types::Height as IbcHeight;
+use crate::ibc::core::commitment_types::commitment::CommitmentProofBytes;
+use crate::ibc::core::commitment_types::proto::ics23::CommitmentProof;
+use crate::ibc::core::commitment_types::proto::v1::MerkleProof as RawMerkleProof;
+use crate::ibc::core::connection::types::ConnectionEnd;
+use crate::ibc::core::host::types::identifiers::{
+    ChannelId, ClientId, PortId, Sequence,
+};
+use crate::ibc::primitives::proto::{Any, Protobuf};
+use crate::ibc::primitives::{Signer, Timestamp, ToProto};
+use crate::queries::RPC;
+use crate::rpc::{
+    query_block, query_has_storage_key, query_storage_value_bytes,
+    query_wasm_code_hash,
+};
+use crate::signing::{aux_signing_data, default_sign, validate_fee};
+use crate::tendermint::validator::Set as ValidatorSet;
+use crate::tendermint_rpc::endpoint::validators;
+use crate::tx::{build_batch, prepare_tx, ProcessTxResponse};
+use crate::{args, Namada};
+
+/// IBC event type emitted when a packet is sent.
+const SEND_PACKET_EVENT: &str = "send_packet";
+
+/// IBC event type emitted when a packet acknowledgement is written.
+const WRITE_ACK_EVENT: &str = "write_acknowledgement";
+
+/// Number of validators fetched per page from CometBFT.
+const VALIDATORS_PER_PAGE: u8 = 100;
+
+/// The two ends of an IBC channel between two chains, as seen from one of
+/// them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelayPath {
+    /// The port on this chain
+    pub port_id: PortId,
+    /// The channel on this chain
+    pub channel_id: ChannelId,
+    /// The client tracking the counterparty chain on this chain
+    pub client_id: ClientId,
+    /// The port on the counterparty chain
+    pub counterparty_port_id: PortId,
+    /// The channel on the counterparty chain
+    pub counterparty_channel_id: ChannelId,
+    /// The client tracking this chain on the counterparty chain
+    pub counterparty_client_id: ClientId,
+}
+
+impl RelayPath {
+    /// Return the same path, as seen from the counterparty chain.
+    pub fn flip(&self) -> Self {
+        Self {
+            port_id: self.counterparty_port_id.clone(),
+            channel_id: self.counterparty_channel_id.clone(),
+            client_id: self.counterparty_client_id.clone(),
+            counterparty_port_id: self.port_id.clone(),
+            counterparty_channel_id: self.channel_id.clone(),
+            counterparty_client_id: self.client_id.clone(),
+        }
+    }
+}
+
+/// What should be done with a packet committed on the source chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketAction {
+    /// Deliver the packet to the destination chain
+    Receive,
+    /// Relay the acknowledgement back to the source chain
+    Acknowledge,
+    /// Time the packet out on the source chain
+    Timeout,
+}
+
+/// Decide what to do with a packet whose commitment is still present on the
+/// source chain.
+pub fn classify_packet(
+    packet: &Packet,
+    received: bool,
+    dst_height: IbcHeight,
+    dst_timestamp: &Timestamp,
+) -> PacketAction {
+    if received {
+        PacketAction::Acknowledge
+    } else if packet.timeout_height_on_b.has_expired(dst_height)
+        || packet.timeout_timestamp_on_b.has_expired(dst_timestamp)
+    {
+        PacketAction::Timeout
+    } else {
+        PacketAction::Receive
+    }
+}
+
+/// Packets pending to be relayed from a source to a destination chain.
+#[derive(Debug, Clone, Default)]
+pub struct PendingPackets {
+    /// Packets committed on the source chain, to be received on the
+    /// destination chain
+    pub to_receive: Vec<Packet>,
+    /// Received packets along with the acknowledgement written on the
+    /// destination chain, to be relayed back to the source chain
+    pub to_acknowledge: Vec<(Packet, Vec<u8>)>,
+    /// Packets which timed out on the destination chain
+    pub to_time_out: Vec<Packet>,
+}
+
+impl PendingPackets {
+    /// Check if there is nothing to relay.
+    pub fn is_empty(&self) -> bool {
+        self.to_receive.is_empty()
+            && self.to_acknowledge.is_empty()
+            && self.to_time_out.is_empty()
+    }
+}
+
+/// Look up the [`RelayPath`] of the given channel on a chain.
+pub async fn query_relay_path<C: Client + Sync>(
+    client: &C,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<RelayPath, Error> {
+    let bytes =
+        query_ibc_bytes(client, &channel_key(port_id, channel_id)).await?;
+    let channel = ChannelEnd::decode_vec(&bytes).map_err(|err| {
+        EncodingError::Decoding(format!(
+            "Failed to decode the channel end of {port_id}/{channel_id}: \
+             {err}"
+        ))
+    })?;
+    if !channel.state_matches(&State::Open) {
+        return Err(Error::Other(format!(
+            "The channel {port_id}/{channel_id} is not open"
+        )));
+    }
+    if channel.ordering() != &Order::Unordered {
+        return Err(Error::Other(format!(
+            "The channel {port_id}/{channel_id} is not unordered, which is \
+             required by the relayer"
+        )));
+    }
+    let counterparty_channel_id = channel
+        .counterparty()
+        .channel_id()
+        .cloned()
+        .ok_or_else(|| {
+            Error::Other(format!(
+                "The channel {port_id}/{channel_id} has no counterparty \
+                 channel"
+            ))
+        })?;
+    let conn_id = channel.connection_hops().first().ok_or_else(|| {
+        Error::Other(format!(
+            "The channel {port_id}/{channel_id} has no connection hops"
+        ))
+    })?;
+
+    let bytes = query_ibc_bytes(client, &connection_key(conn_id)).await?;
+    let connection = ConnectionEnd::decode_vec(&bytes).map_err(|err| {
+        EncodingError::Decoding(format!(
+            "Failed to decode the connection end of {conn_id}: {err}"
+        ))
+    })?;
+
+    Ok(RelayPath {
+        port_id: port_id.clone(),
+        channel_id: channel_id.clone(),
+        client_id: connection.client_id().clone(),
+        counterparty_port_id: channel.counterparty().port_id().clone(),
+        counterparty_channel_id,
+        counterparty_client_id: connection.counterparty().client_id().clone(),
+    })
+}
+
+/// Query the packets that have to be relayed from the source chain `src` to
+/// the destination chain `dst`, given a path as seen from `src`.
+pub async fn query_pending_packets<S, D>(
+    src: &S,
+    dst: &D,
+    path: &RelayPath,
+    dst_height: IbcHeight,
+    dst_timestamp: &Timestamp,
+) -> Result<PendingPackets, Error>
+where
+    S: Client + Sync,
+    D: Client + Sync,
+{
+    let prefix = ibc_key(format!(
+        "commitments/ports/{}/channels/{}/sequences",
+        path.port_id, path.channel_id
+    ))
+    .map_err(|err| Error::Other(err.to_string()))?;
+    let commitments: Vec<PrefixValue> = RPC
+        .shell()
+        .storage_prefix(src, None, None, false, &prefix)
+        .await
+        .map_err(|err| QueryError::NoResponse(err.to_string()))?
+        .data;
+
+    let mut pending = PendingPackets::default();
+    for PrefixValue { key, .. } in commitments {
+        let (_, _, sequence) = port_channel_sequence_id(&key)
+            .map_err(|err| Error::Other(err.to_string()))?;
+        let Some(send_event) = RPC
+            .shell()
+            .ibc_packet(
+                src,
+                &IbcEventType(SEND_PACKET_EVENT.to_string()),
+                &path.port_id,
+                &path.channel_id,
+                &path.counterparty_port_id,
+                &path.counterparty_channel_id,
+                &sequence,
+            )
+            .await
+            .map_err(|err| QueryError::NoResponse(err.to_string()))?
+        else {
+            tracing::warn!(
+                %sequence,
+                "The send packet event is no longer in the event log of the \
+                 source chain, skipping"
+            );
+            continue;
+        };
+        let packet = packet_from_event_attributes(send_event.attributes())?;
+
+        let received = query_has_storage_key(
+            dst,
+            &receipt_key(
+                &path.counterparty_port_id,
+                &path.counterparty_channel_id,
+                sequence,
+            ),
+        )
+        .await?;
+        match classify_packet(&packet, received, dst_height, dst_timestamp) {
+            PacketAction::Receive => pending.to_receive.push(packet),
+            PacketAction::Timeout => pending.to_time_out.push(packet),
+            PacketAction::Acknowledge => {
+                let Some(ack_event) = RPC
+                    .shell()
+                    .ibc_packet(
+                        dst,
+                        &IbcEventType(WRITE_ACK_EVENT.to_string()),
+                        &path.port_id,
+                        &path.channel_id,
+                        &path.counterparty_port_id,
+                        &path.counterparty_channel_id,
+                        &sequence,
+                    )
+                    .await
+                    .map_err(|err| QueryError::NoResponse(err.to_string()))?
+                else {
+                    tracing::warn!(
+                        %sequence,
+                        "The acknowledgement event is no longer in the event \
+                         log of the destination chain, skipping"
+                    );
+                    continue;
+                };
+                let ack = ack_event.read_attribute::<PacketAck<'_>>()?;
+                pending.to_acknowledge.push((packet, ack.into_bytes()));
+            }
+        }
+    }
+
+    Ok(pending)
+}
+
+/// Relay packets, acknowledgements and timeouts in both directions of the
+/// channel given in `args`, once or continuously in daemon mode.
+pub async fn relay_ibc_packets(
+    chain: &impl Namada,
+    counterparty: &impl Namada,
+    args: args::IbcRelay,
+) -> Result<(), Error> {
+    const DEFAULT_RETRY_DURATION: Duration = Duration::from_secs(1);
+    const DEFAULT_SUCCESS_DURATION: Duration = Duration::from_secs(5);
+
+    let path =
+        query_relay_path(chain.client(), &args.port_id, &args.channel_id)
+            .await?;
+    display_line!(
+        chain.io(),
+        "Relaying over {}/{} <-> {}/{}",
+        path.port_id,
+        path.channel_id,
+        path.counterparty_port_id,
+        path.counterparty_channel_id
+    );
+
+    if !args.daemon {
+        return relay_ibc_packets_once(chain, counterparty, &args, &path).await;
+    }
+
+    let retry_duration = args.retry_dur.unwrap_or(DEFAULT_RETRY_DURATION);
+    let success_duration = args.success_dur.unwrap_or(DEFAULT_SUCCESS_DURATION);
+
+    tracing::info!("The IBC packet relayer daemon has started");
+
+    loop {
+        let sleep_for =
+            match relay_ibc_packets_once(chain, counterparty, &args, &path)
+                .await
+            {
+                Ok(()) => success_duration,
+                Err(err) => {
+                    edisplay_line!(
+                        chain.io(),
+                        "Failed to relay packets: {err}"
+                    );
+                    retry_duration
+                }
+            };
+        tracing::debug!(?sleep_for, "Sleeping");
+        time::sleep(sleep_for).await;
+    }
+}
+
+/// Perform a single round of relaying in both directions of a path.
+async fn relay_ibc_packets_once(
+    chain: &impl Namada,
+    counterparty: &impl Namada,
+    args: &args::IbcRelay,
+    path: &RelayPath,
+) -> Result<(), Error> {
+    relay_one_direction(
+        chain,
+        &args.tx,
+        counterparty,
+        &args.counterparty_tx,
+        &args.tx_code_path,
+        path,
+    )
+    .await?;
+    relay_one_direction(
+        counterparty,
+        &args.counterparty_tx,
+        chain,
+        &args.tx,
+        &args.tx_code_path,
+        &path.flip(),
+    )
+    .await
+}
+
+/// Relay the packets sent from `src` to `dst` and the associated
+/// acknowledgements and timeouts back to `src`.
+async fn relay_one_direction(
+    src: &impl Namada,
+    src_tx_args: &args::Tx,
+    dst: &impl Namada,
+    dst_tx_args: &args::Tx,
+    tx_code_path: &Path,
+    path: &RelayPath,
+) -> Result<(), Error> {
+    let src_height = latest_height(src.client()).await?;
+    let dst_height = latest_height(dst.client()).await?;
+
+    // Proofs are queried one block behind the latest height, since they
+    // are verified against the app hash committed in the next header
+    let src_proof_height = src_height.prev_height().ok_or_else(|| {
+        Error::Other("The source chain has no committed blocks".to_string())
+    })?;
+    let dst_proof_height = dst_height.prev_height().ok_or_else(|| {
+        Error::Other(
+            "The destination chain has no committed blocks".to_string(),
+        )
+    })?;
+
+    let dst_timestamp = block_timestamp(dst.client(), dst_proof_height).await?;
+    let dst_ibc_height =
+        ibc_height(src.client(), &path.client_id, dst_proof_height).await?;
+    let pending = query_pending_packets(
+        src.client(),
+        dst.client(),
+        path,
+        dst_ibc_height,
+        &dst_timestamp,
+    )
+    .await?;
+    if pending.is_empty() {
+        tracing::debug!(
+            port_id = %path.port_id,
+            channel_id = %path.channel_id,
+            "No packets to relay"
+        );
+        return Ok(());
+    }
+
+    // Messages to the destination chain, proving the state of the source
+    // chain
+    if !pending.to_receive.is_empty() {
+        let signer = relayer_signer(dst, dst_tx_args).await?;
+        let (update, proof_height) = update_client_msg(
+            src.client(),
+            dst.client(),
+            &path.counterparty_client_id,
+            src_height,
+            &signer,
+        )
+        .await?;
+        let mut msgs: Vec<Any> = update.into_iter().collect();
+        for packet in &pending.to_receive {
+            let proof_commitment_on_a = query_proof(
+                src.client(),
+                &commitment_key(
+                    &packet.port_id_on_a,
+                    &packet.chan_id_on_a,
+                    packet.seq_on_a,
+                ),
+                src_proof_height,
+            )
+            .await?;
+            msgs.push(
+                MsgRecvPacket {
+                    packet: packet.clone(),
+                    proof_commitment_on_a,
+                    proof_height_on_a: proof_height,
+                    signer: signer.clone(),
+                }
+                .to_any(),
+            );
+        }
+        submit_ibc_msgs(dst, dst_tx_args, tx_code_path, msgs).await?;
+        display_line!(
+            dst.io(),
+            "Relayed {} packet(s) to {}/{}",
+            pending.to_receive.len(),
+            path.counterparty_port_id,
+            path.counterparty_channel_id
+        );
+    }
+
+    // Messages back to the source chain, proving the state of the
+    // destination chain
+    if !pending.to_acknowledge.is_empty() || !pending.to_time_out.is_empty() {
+        let signer = relayer_signer(src, src_tx_args).await?;
+        let (update, proof_height) = update_client_msg(
+            dst.client(),
+            src.client(),
+            &path.client_id,
+            dst_height,
+            &signer,
+        )
+        .await?;
+        let mut msgs: Vec<Any> = update.into_iter().collect();
+        for (packet, ack) in &pending.to_acknowledge {
+            let proof_acked_on_b = query_proof(
+                dst.client(),
+                &ack_key(
+                    &packet.port_id_on_b,
+                    &packet.chan_id_on_b,
+                    packet.seq_on_a,
+                ),
+                dst_proof_height,
+            )
+            .await?;
+            let acknowledgement = Acknowledgement::try_from(ack.clone())
+                .map_err(|err| EncodingError::Conversion(err.to_string()))?;
+            msgs.push(
+                MsgAcknowledgement {
+                    packet: packet.clone(),
+                    acknowledgement,
+                    proof_acked_on_b,
+                    proof_height_on_b: proof_height,
+                    signer: signer.clone(),
+                }
+                .to_any(),
+            );
+        }
+        for packet in &pending.to_time_out {
+            let next_seq_recv_on_b = query_next_seq_recv(
+                dst.client(),
+                &packet.port_id_on_b,
+                &packet.chan_id_on_b,
+                dst_proof_height,
+            )
+            .await?;
+            let proof_unreceived_on_b = query_proof(
+                dst.client(),
+                &receipt_key(
+                    &packet.port_id_on_b,
+                    &packet.chan_id_on_b,
+                    packet.seq_on_a,
+                ),
+                dst_proof_height,
+            )
+            .await?;
+            msgs.push(
+                MsgTimeout {
+                    packet: packet.clone(),
+                    next_seq_recv_on_b,
+                    proof_unreceived_on_b,
+                    proof_height_on_b: proof_height,
+                    signer: signer.clone(),
+                }
+                .to_any(),
+            );
+        }
+        submit_ibc_msgs(src, src_tx_args, tx_code_path, msgs).await?;
+        display_line!(
+            src.io(),
+            "Relayed {} acknowledgement(s) and {} timeout(s) to {}/{}",
+            pending.to_acknowledge.len(),
+            pending.to_time_out.len(),
+            path.port_id,
+            path.channel_id
+        );
+    }
+
+    Ok(())
+}
+
+/// Build a message updating the client of `src` on `dst` with the header at
+/// `target`, along with the IBC height at which proofs of the state of `src`
+/// at `target - 1` are verifiable. No message is returned if the client
+/// already has a consensus state at `target`.
+async fn update_client_msg<S, D>(
+    src: &S,
+    dst: &D,
+    client_id: &ClientId,
+    target: BlockHeight,
+    signer: &Signer,
+) -> Result<(Option<Any>, IbcHeight), Error>
+where
+    S: Client + Sync,
+    D: Client + Sync,
+{
+    let trusted_height = client_latest_height(dst, client_id).await?;
+    let target_height =
+        IbcHeight::new(trusted_height.revision_number(), target.0)
+            .map_err(|err| EncodingError::Conversion(err.to_string()))?;
+
+    if query_has_storage_key(
+        dst,
+        &consensus_state_key(client_id, target_height),
+    )
+    .await?
+    {
+        return Ok((None, target_height));
+    }
+    if target_height <= trusted_height {
+        return Err(Error::Other(format!(
+            "Cannot update the client {client_id} to height {target_height}, \
+             as it is already at height {trusted_height}"
+        )));
+    }
+
+    let signed_header = src
+        .commit(target.0)
+        .await
+        .map_err(|err| QueryError::General(err.to_string()))?
+        .signed_header;
+    let validator_set = query_validator_set(src, target).await?;
+    let trusted_next_validator_set = query_validator_set(
+        src,
+        BlockHeight(trusted_height.revision_height()).next_height(),
+    )
+    .await?;
+    let header = TmHeader {
+        signed_header,
+        validator_set,
+        trusted_height,
+        trusted_next_validator_set,
+    };
+    let msg = MsgUpdateClient {
+        client_id: client_id.clone(),
+        client_message: header.into(),
+        signer: signer.clone(),
+    };
+
+    Ok((Some(msg.to_any()), target_height))
+}
+
+/// Build and submit a batch of IBC messages to a chain. The messages are
+/// executed atomically.
+async fn submit_ibc_msgs(
+    context: &impl Namada,
+    tx_args: &args::Tx,
+    tx_code_path: &Path,
+    msgs: Vec<Any>,
+) -> Result<(), Error> {
+    let chain_id = tx_args.chain_id.clone().ok_or_else(|| {
+        Error::Other("Missing the chain ID of the IBC relay tx".to_string())
+    })?;
+    let tx_code_hash =
+        query_wasm_code_hash(context, tx_code_path.to_string_lossy())
+            .await
+            .map_err(|e| Error::from(QueryError::Wasm(e.to_string())))?;
+    let signing_data =
+        aux_signing_data(context, tx_args, None, None, vec![], false).await?;
+    let fee_amount = validate_fee(context, tx_args).await?;
+
+    let txs = msgs
+        .into_iter()
+        .map(|msg| {
+            let mut tx =
+                Tx::new(chain_id.clone(), tx_args.expiration.to_datetime());
+            if let Some(memo) = &tx_args.memo {
+                tx.add_memo(memo);
+            }
+            tx.add_code_from_hash(
+                tx_code_hash,
+                Some(tx_code_path.to_string_lossy().into_owned()),
+            )
+            .add_serialized_data(msg.encode_to_vec());
+            (tx, signing_data.clone())
+        })
+        .collect();
+    let (mut tx, _) = build_batch(txs)?;
+    tx.header.atomic = true;
+
+    prepare_tx(tx_args, &mut tx, fee_amount, signing_data.fee_payer.clone())
+        .await?;
+    context
+        .sign(&mut tx, tx_args, signing_data, default_sign, ())
+        .await?;
+
+    let wrapper_hash = tx.wrapper_hash();
+    let cmts = tx.commitments().clone();
+    let response = context.submit(tx, tx_args).await?;
+    if let ProcessTxResponse::Applied(_) = &response {
+        if cmts.iter().any(|cmt| {
+            response
+                .is_applied_and_valid(wrapper_hash.as_ref(), cmt)
+                .is_none()
+        }) {
+            return Err(TxSubmitError::Other(
+                "The IBC relay transaction was rejected".to_string(),
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// The relayer's address on a chain, used as the signer of IBC messages.
+async fn relayer_signer(
+    context: &impl Namada,
+    tx_args: &args::Tx,
+) -> Result<Signer, Error> {
+    let signing_data =
+        aux_signing_data(context, tx_args, None, None, vec![], false).await?;
+    let address = namada_core::address::Address::from(&signing_data.fee_payer);
+    Ok(Signer::from(address.to_string()))
+}
+
+/// Get the height of the last committed block of a chain.
+async fn latest_height<C: Client + Sync>(
+    client: &C,
+) -> Result<BlockHeight, Error> {
+    query_block(client)
+        .await?
+        .map(|block| block.height)
+        .ok_or_else(|| {
+            Error::from(QueryError::General(
+                "No block has been committed yet".to_string(),
+            ))
+        })
+}
+
+/// Get the timestamp of the block at the given height, as an IBC timestamp.
+async fn block_timestamp<C: Client + Sync>(
+    client: &C,
+    height: BlockHeight,
+) -> Result<Timestamp, Error> {
+    let time = client
+        .commit(height.0)
+        .await
+        .map_err(|err| QueryError::General(err.to_string()))?
+        .signed_header
+        .header
+        .time;
+    time.try_into().map_err(|_| {
+        EncodingError::Conversion(format!(
+            "Invalid timestamp of the block at height {height}"
+        ))
+        .into()
+    })
+}
+
+/// Convert a block height of the chain tracked by `client_id` to an IBC
+/// height, using the revision number of the client state.
+async fn ibc_height<C: Client + Sync>(
+    client: &C,
+    client_id: &ClientId,
+    height: BlockHeight,
+) -> Result<IbcHeight, Error> {
+    let revision_number = client_latest_height(client, client_id)
+        .await?
+        .revision_number();
+    IbcHeight::new(revision_number, height.0)
+        .map_err(|err| EncodingError::Conversion(err.to_string()).into())
+}
+
+/// Read the latest height of a Tendermint light client.
+async fn client_latest_height<C: Client + Sync>(
+    client: &C,
+    client_id: &ClientId,
+) -> Result<IbcHeight, Error> {
+    let bytes = query_ibc_bytes(client, &client_state_key(client_id)).await?;
+    let any = Any::decode(&bytes[..])
+        .map_err(|err| EncodingError::Decoding(err.to_string()))?;
+    let client_state = TmClientState::try_from(any).map_err(|err| {
+        EncodingError::Decoding(format!(
+            "The client {client_id} is not a Tendermint client: {err}"
+        ))
+    })?;
+    Ok(client_state.latest_height)
+}
+
+/// Query the full validator set of a chain at the given height.
+async fn query_validator_set<C: Client + Sync>(
+    client: &C,
+    height: BlockHeight,
+) -> Result<ValidatorSet, Error> {
+    let tm_height = crate::tendermint::block::Height::try_from(height.0)
+        .map_err(|err| EncodingError::Conversion(err.to_string()))?;
+    let mut validators = vec![];
+    let mut page = 1_usize;
+    loop {
+        let response = client
+            .perform(validators::Request::new(
+                Some(tm_height),
+                Some(page.into()),
+                Some(VALIDATORS_PER_PAGE.into()),
+            ))
+            .await
+            .map_err(|err| QueryError::General(err.to_string()))?;
+        let total = usize::try_from(response.total).unwrap_or_default();
+        let is_last_page = response.validators.is_empty();
+        validators.extend(response.validators);
+        if is_last_page || validators.len() >= total {
+            break;
+        }
+        page += 1;
+    }
+    Ok(ValidatorSet::without_proposer(validators))
+}
+
+/// Read the next sequence to be received on a channel at the given height.
+async fn query_next_seq_recv<C: Client + Sync>(
+    client: &C,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    height: BlockHeight,
+) -> Result<Sequence, Error> {
+    let key = next_sequence_recv_key(port_id, channel_id);
+    let bytes = query_storage_value_bytes(client, &key, Some(height), false)
+        .await?
+        .0
+        .ok_or_else(|| QueryError::NoSuchKey(key.to_string()))?;
+    let bytes: [u8; 8] = bytes.try_into().map_err(|_| {
+        EncodingError::Decoding(format!(
+            "The next sequence to receive on {port_id}/{channel_id} isn't a \
+             u64"
+        ))
+    })?;
+    Ok(Sequence::from(u64::from_be_bytes(bytes)))
+}
+
+/// Read the raw bytes stored under an IBC key, failing if they are missing.
+async fn query_ibc_bytes<C: Client + Sync>(
+    client: &C,
+    key: &Key,
+) -> Result<Vec<u8>, Error> {
+    query_storage_value_bytes(client, key, None, false)
+        .await?
+        .0
+        .ok_or_else(|| QueryError::NoSuchKey(key.to_string()).into())
+}
+
+/// Query a proof of the (non-)existence of a key at the given height,
+/// encoded as an ICS-23 merkle proof.
+async fn query_proof<C: Client + Sync>(
+    client: &C,
+    key: &Key,
+    height: BlockHeight,
+) -> Result<CommitmentProofBytes, Error> {
+    let (_, proof) =
+        query_storage_value_bytes(client, key, Some(height), true).await?;
+    let proof = proof.ok_or_else(|| {
+        QueryError::General(format!(
+            "The node returned no proof for {key} at height {height}"
+        ))
+    })?;
+    let proofs = proof
+        .ops
+        .iter()
+        .map(|op| {
+            CommitmentProof::decode(op.data.as_slice())
+                .map_err(|err| EncodingError::Decoding(err.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    CommitmentProofBytes::try_from(RawMerkleProof { proofs })
+        .map_err(|err| EncodingError::Conversion(err.to_string()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::ibc::core::channel::types::timeout::{
+        TimeoutHeight, TimeoutTimestamp,
+    };
+
+    fn packet(
+        timeout_height_on_b: TimeoutHeight,
+        timeout_timestamp_on_b: TimeoutTimestamp,
+    ) -> Packet {
+        Packet {
+            seq_on_a: Sequence::from(1),
+            port_id_on_a: PortId::transfer(),
+            chan_id_on_a: ChannelId::new(0),
+            port_id_on_b: PortId::transfer(),
+            chan_id_on_b: ChannelId::new(1),
+            data: vec![],
+            timeout_height_on_b,
+            timeout_timestamp_on_b,
+        }
+    }
+
+    #[test]
+    fn test_relay_path_flip() {
+        let path = RelayPath {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(0),
+            client_id: ClientId::from_str("07-tendermint-0").unwrap(),
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: ChannelId::new(5),
+            counterparty_client_id: ClientId::from_str("07-tendermint-3")
+                .unwrap(),
+        };
+        let flipped = path.flip();
+        assert_eq!(flipped.channel_id, ChannelId::new(5));
+        assert_eq!(
+            flipped.client_id,
+            ClientId::from_str("07-tendermint-3").unwrap()
+        );
+        assert_eq!(flipped.flip(), path);
+    }
+
+    #[test]
+    fn test_classify_packet() {
+        let dst_height = IbcHeight::new(0, 10).unwrap();
+        let dst_timestamp = Timestamp::from_nanoseconds(1_000);
+
+        let live = packet(
+            TimeoutHeight::At(IbcHeight::new(0, 20).unwrap()),
+            TimeoutTimestamp::Never,
+        );
+        assert_eq!(
+            classify_packet(&live, false, dst_height, &dst_timestamp),
+            PacketAction::Receive
+        );
+        assert_eq!(
+            classify_packet(&live, true, dst_height, &dst_timestamp),
+            PacketAction::Acknowledge
+        );
+
+        let expired_height = packet(
+            TimeoutHeight::At(IbcHeight::new(0, 5).unwrap()),
+            TimeoutTimestamp::Never,
+        );
+        assert_eq!(
+            classify_packet(&expired_height, false, dst_height, &dst_timestamp),
+            PacketAction::Timeout
+        );
+
+        let expired_timestamp = packet(
+            TimeoutHeight::Never,
+            TimeoutTimestamp::At(Timestamp::from_nanoseconds(500)),
+        );
+        assert_eq!(
+            classify_packet(
+                &expired_timestamp,
+                false,
+                dst_height,
+                &dst_timestamp
+            ),
+            PacketAction::Timeout
+        );
+        // A received packet is acknowledged, even if it timed out since
+        assert_eq!(
+            classify_packet(
+                &expired_timestamp,
+                true,
+                dst_height,
+                &dst_timestamp
+            ),
+            PacketAction::Acknowledge
+        );
+    }
+}
@@ -23,11 +23,13 @@ pub use {
 };
 
 pub mod eth_bridge;
+pub mod ibc_relayer;
 
 pub mod rpc;
 
 pub mod args;
 pub mod masp;
+pub mod remote_signer;
 pub mod signing;
 #[allow(clippy::result_large_err)]
 pub mod tx;
@@ -36,6 +38,8 @@ pub mod validation;
 
 pub mod error;
 pub mod events;
+#[cfg(not(target_family = "wasm"))]
+pub mod fork;
 pub(crate) mod internal_macros;
 
 #[cfg(feature = "migrations")]
@@ -50,6 +54,7 @@ use namada_core::collections::HashSet;
 use namada_core::dec::Dec;
 use namada_core::ethereum_events::EthAddress;
 use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
+use namada_core::keccak::KeccakHash;
 use namada_core::key::*;
 pub use namada_core::masp::{
     ExtendedSpendingKey, ExtendedViewingKey, PaymentAddress, TransferSource,
@@ -67,14 +72,14 @@ use token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
 use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tx::{
     ProcessTxResponse, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
-    TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+    TX_BRIDGE_POOL_WASM, TX_CANCEL_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
     TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
     TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
-    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-    VP_USER_WASM,
+    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_INIT_VESTING_ACCOUNT_WASM,
+    TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+    TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_STEWARD_COMMISSION,
+    TX_UPDATE_VP_PARAMS_WASM, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM, VP_USER_WASM,
 };
 use wallet::{Wallet, WalletIo, WalletStorage};
 pub use {namada_io as io, namada_wallet as wallet};
@@ -152,6 +157,7 @@ pub trait Namada: NamadaIo {
             memo: None,
             use_device: false,
             device_transport: DeviceTransport::default(),
+            remote_signer: None,
         }
     }
 
@@ -231,6 +237,7 @@ pub trait Namada: NamadaIo {
             tx_code_path: PathBuf::from(TX_INIT_ACCOUNT_WASM),
             public_keys,
             threshold,
+            vesting: None,
         }
     }
 
@@ -352,6 +359,18 @@ pub trait Namada: NamadaIo {
         }
     }
 
+    /// Make a TxUpdateVpParams builder from the given minimum set of arguments
+    fn new_update_vp_params(&self, addr: Address) -> args::TxUpdateVpParams {
+        args::TxUpdateVpParams {
+            addr,
+            vp_params: None,
+            spending_limit: None,
+            bypass_threshold: None,
+            tx_code_path: PathBuf::from(TX_UPDATE_VP_PARAMS_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a VoteProposal builder from the given minimum set of arguments
     fn new_proposal_vote(
         &self,
@@ -561,12 +580,26 @@ pub trait Namada: NamadaIo {
             ),
             fee_payer: None,
             fee_token: self.native_token(),
+            conversion_table: None,
             nut: false,
             code_path: PathBuf::from(TX_BRIDGE_POOL_WASM),
             tx: self.tx_builder(),
         }
     }
 
+    /// Make a CancelEthereumBridgePool builder from the given minimum set of
+    /// arguments
+    fn new_cancel_erc20_transfer(
+        &self,
+        transfer: KeccakHash,
+    ) -> args::CancelEthereumBridgePool {
+        args::CancelEthereumBridgePool {
+            transfer,
+            code_path: PathBuf::from(TX_CANCEL_BRIDGE_POOL_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a ResignSteward builder from the given minimum set of arguments
     fn new_resign_steward(&self, steward: Address) -> args::ResignSteward {
         args::ResignSteward {
@@ -731,6 +764,7 @@ where
                 memo: None,
                 use_device: false,
                 device_transport: DeviceTransport::default(),
+                remote_signer: None,
             },
         }
     }
@@ -858,7 +892,9 @@ pub mod testing {
     use borsh_ext::BorshSerializeExt;
     use governance::ProposalType;
     use masp_primitives::transaction::components::sapling::builder::StoredBuildParams;
-    use namada_account::{InitAccount, UpdateAccount};
+    use namada_account::{
+        InitAccount, InitVestingAccount, UpdateAccount, UpdateVpParams,
+    };
     use namada_core::address::testing::{
         arb_established_address, arb_non_internal_address,
     };
@@ -868,7 +904,7 @@ pub mod testing {
     use namada_core::key::testing::arb_common_keypair;
     use namada_core::masp::AssetData;
     use namada_governance::storage::proposal::testing::{
-        arb_init_proposal, arb_vote_proposal,
+        arb_init_proposal, arb_proposal_type, arb_vote_proposal,
     };
     use namada_governance::{InitProposalData, VoteProposalData};
     use namada_ibc::testing::{arb_ibc_msg_nft_transfer, arb_ibc_msg_transfer};
@@ -884,13 +920,18 @@ pub mod testing {
         MetaDataChange, Redelegation, Unbond, Withdraw,
     };
     use namada_tx::data::{Fee, TxType, WrapperTx};
-    use proptest::prelude::{Just, Strategy};
+    use proptest::prelude::{BoxedStrategy, Just, Strategy};
     use proptest::sample::SizeRange;
+    use proptest::strategy::{Union, ValueTree};
+    use proptest::test_runner::TestRunner;
     use proptest::{arbitrary, collection, option, prop_compose, prop_oneof};
     use token::testing::arb_transparent_transfer;
 
     use super::*;
-    use crate::account::tests::{arb_init_account, arb_update_account};
+    use crate::account::tests::{
+        arb_init_account, arb_init_vesting_account, arb_update_account,
+        arb_update_vp_params,
+    };
     use crate::chain::ChainId;
     use crate::eth_bridge_pool::testing::arb_pending_transfer;
     use crate::key::testing::arb_common_pk;
@@ -905,6 +946,7 @@ pub mod testing {
         Authorization, Code, Commitment, Header, MaspBuilder, Section,
         TxCommitments,
     };
+    use crate::wallet::test_utils::TestWalletUtils;
 
     #[derive(Debug, Clone, BorshDeserialize, BorshSchema, BorshSerialize)]
     #[borsh(crate = "::borsh")]
@@ -918,6 +960,7 @@ pub mod testing {
         ClaimRewards(Withdraw),
         DeactivateValidator(Address),
         InitAccount(InitAccount),
+        InitVestingAccount(InitVestingAccount),
         InitProposal(InitProposalData),
         InitValidator(BecomeValidator),
         ReactivateValidator(Address),
@@ -925,6 +968,7 @@ pub mod testing {
         Unbond(Unbond),
         UnjailValidator(Address),
         UpdateAccount(UpdateAccount),
+        UpdateVpParams(UpdateVpParams),
         VoteProposal(VoteProposalData),
         Withdraw(Withdraw),
         Transfer(Transfer, Option<(StoredBuildParams, String)>),
@@ -1121,12 +1165,36 @@ pub mod testing {
     }
 
     prop_compose! {
-        /// Generate an arbitrary masp transfer transaction
-        pub fn arb_transfer_tx()(
+        /// Generate an arbitrary shielded transfer
+        pub fn arb_shielded_only_transfer()(
+            (transfer, shielded_transfer, asset_types, build_params) in
+                arb_shielded_transfer(0..MAX_ASSETS),
+        ) -> (Transfer, Option<(ShieldedTransfer, HashMap<AssetData, u64>, StoredBuildParams)>) {
+            (transfer, Some((shielded_transfer, asset_types, build_params)))
+        }
+    }
+
+    /// Generate an arbitrary masp transfer transaction
+    pub fn arb_transfer_tx() -> impl Strategy<Value = (Tx, TxData)> {
+        arb_transfer_tx_of(arb_transfer().boxed())
+    }
+
+    /// Generate an arbitrary masp transfer transaction with a shielded
+    /// transfer
+    pub fn arb_shielded_transfer_tx() -> impl Strategy<Value = (Tx, TxData)> {
+        arb_transfer_tx_of(arb_shielded_only_transfer().boxed())
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary masp transfer transaction of the given
+        /// transfers strategy
+        pub fn arb_transfer_tx_of(
+            transfers: BoxedStrategy<(Transfer, Option<(ShieldedTransfer, HashMap<AssetData, u64>, StoredBuildParams)>)>,
+        )(
             mut header in arb_header(),
             wrapper in arb_wrapper_tx(),
             code_hash in arb_hash(),
-            (transfer, aux) in arb_transfer(),
+            (transfer, aux) in transfers,
         ) -> (Tx, TxData) {
             header.tx_type = TxType::Wrapper(Box::new(wrapper));
             let mut tx = Tx { header, sections: vec![] };
@@ -1204,6 +1272,25 @@ pub mod testing {
         }
     }
 
+    prop_compose! {
+        /// Generate an arbitrary vesting account initialization transaction
+        pub fn arb_init_vesting_account_tx()(
+            mut header in arb_header(),
+            wrapper in arb_wrapper_tx(),
+            mut init_vesting_account in arb_init_vesting_account(),
+            extra_data in arb_code(),
+            code_hash in arb_hash(),
+        ) -> (Tx, TxData) {
+            header.tx_type = TxType::Wrapper(Box::new(wrapper));
+            let mut tx = Tx { header, sections: vec![] };
+            let vp_code_hash = tx.add_section(Section::ExtraData(extra_data)).get_hash();
+            init_vesting_account.account.vp_code_hash = vp_code_hash;
+            tx.add_data(init_vesting_account.clone());
+            tx.add_code_from_hash(code_hash, Some(TX_INIT_VESTING_ACCOUNT_WASM.to_owned()));
+            (tx, TxData::InitVestingAccount(init_vesting_account))
+        }
+    }
+
     prop_compose! {
         /// Generate an arbitrary account initialization transaction
         pub fn arb_become_validator_tx()(
@@ -1220,17 +1307,38 @@ pub mod testing {
         }
     }
 
+    /// Generate an arbitrary proposal initialization transaction
+    pub fn arb_init_proposal_tx() -> impl Strategy<Value = (Tx, TxData)> {
+        arb_init_proposal_tx_of(arb_proposal_type().boxed())
+    }
+
+    /// Generate an arbitrary proposal initialization transaction of the
+    /// proposal type matching the given predicate
+    pub fn arb_init_proposal_tx_with(
+        whence: &'static str,
+        matches_type: fn(&ProposalType) -> bool,
+    ) -> impl Strategy<Value = (Tx, TxData)> {
+        arb_init_proposal_tx_of(
+            arb_proposal_type()
+                .prop_filter(whence, matches_type)
+                .boxed(),
+        )
+    }
+
     prop_compose! {
-        /// Generate an arbitrary proposal initialization transaction
-        pub fn arb_init_proposal_tx()(
+        /// Generate an arbitrary proposal initialization transaction of the
+        /// given proposal types strategy
+        pub fn arb_init_proposal_tx_of(types: BoxedStrategy<ProposalType>)(
             mut header in arb_header(),
             wrapper in arb_wrapper_tx(),
             mut init_proposal in arb_init_proposal(),
+            r#type in types,
             content_extra_data in arb_code(),
             type_extra_data in arb_code(),
             code_hash in arb_hash(),
         ) -> (Tx, TxData) {
             header.tx_type = TxType::Wrapper(Box::new(wrapper));
+            init_proposal.r#type = r#type;
             let mut tx = Tx { header, sections: vec![] };
             let content_hash = tx.add_section(Section::ExtraData(content_extra_data)).get_hash();
             init_proposal.content = content_hash;
@@ -1244,10 +1352,16 @@ pub mod testing {
         }
     }
 
+    /// Generate an arbitrary transaction with maybe a valid memo
+    pub fn arb_memoed_tx() -> impl Strategy<Value = (Tx, TxData)> {
+        arb_memoed_tx_of(arb_tx().boxed())
+    }
+
     prop_compose! {
-        /// Generate an arbitrary transaction with maybe a valid memo
-        pub fn arb_memoed_tx()(
-            (mut tx, tx_data) in arb_tx(),
+        /// Generate an arbitrary transaction of the given strategy with maybe a
+        /// valid memo
+        pub fn arb_memoed_tx_of(txs: BoxedStrategy<(Tx, TxData)>)(
+            (mut tx, tx_data) in txs,
             memo in option::of(arb_utf8_code()),
         ) -> (Tx, TxData) {
             // Clean up any previous memo commitments
@@ -1322,6 +1436,22 @@ pub mod testing {
         }
     }
 
+    prop_compose! {
+        /// Generate an arbitrary VP parameters update transaction
+        pub fn arb_update_vp_params_tx()(
+            mut header in arb_header(),
+            wrapper in arb_wrapper_tx(),
+            update_vp_params in arb_update_vp_params(),
+            code_hash in arb_hash(),
+        ) -> (Tx, TxData) {
+            header.tx_type = TxType::Wrapper(Box::new(wrapper));
+            let mut tx = Tx { header, sections: vec![] };
+            tx.add_data(update_vp_params.clone());
+            tx.add_code_from_hash(code_hash, Some(TX_UPDATE_VP_PARAMS_WASM.to_owned()));
+            (tx, TxData::UpdateVpParams(update_vp_params))
+        }
+    }
+
     prop_compose! {
         /// Generate an arbitrary reveal public key transaction
         pub fn arb_withdraw_tx()(
@@ -1532,11 +1662,40 @@ pub mod testing {
     }
 
     prop_compose! {
-        /// Generate an arbitrary IBC any transaction
-        pub fn arb_ibc_msg_transfer_tx()(
+        /// Generate an arbitrary IBC transfer message of a shielded transfer
+        pub fn arb_shielded_msg_transfer()(
+            message in arb_ibc_msg_transfer(),
+            (transfer, aux) in arb_shielded_only_transfer(),
+        ) -> (
+            MsgTransfer<token::Transfer>,
+            Option<(ShieldedTransfer, HashMap<AssetData, u64>, StoredBuildParams)>,
+        ) {
+            (MsgTransfer { message, transfer: Some(transfer) }, aux)
+        }
+    }
+
+    /// Generate an arbitrary IBC any transaction
+    pub fn arb_ibc_msg_transfer_tx() -> impl Strategy<Value = (Tx, TxData)> {
+        arb_ibc_msg_transfer_tx_of(arb_msg_transfer().boxed())
+    }
+
+    /// Generate an arbitrary IBC any transaction with a shielded transfer
+    pub fn arb_ibc_shielding_tx() -> impl Strategy<Value = (Tx, TxData)> {
+        arb_ibc_msg_transfer_tx_of(arb_shielded_msg_transfer().boxed())
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary IBC any transaction of the given messages
+        /// strategy
+        pub fn arb_ibc_msg_transfer_tx_of(
+            msgs: BoxedStrategy<(
+                MsgTransfer<token::Transfer>,
+                Option<(ShieldedTransfer, HashMap<AssetData, u64>, StoredBuildParams)>,
+            )>,
+        )(
             mut header in arb_header(),
             wrapper in arb_wrapper_tx(),
-            (msg_transfer, aux) in arb_msg_transfer(),
+            (msg_transfer, aux) in msgs,
             code_hash in arb_hash(),
         ) -> (Tx, TxData) {
             header.tx_type = TxType::Wrapper(Box::new(wrapper));
@@ -1614,33 +1773,87 @@ pub mod testing {
         }
     }
 
+    /// Generate arbitrary txs of every kind, along with the name of the kind
+    pub fn arb_tx_kinds() -> Vec<(&'static str, BoxedStrategy<(Tx, TxData)>)> {
+        vec![
+            ("transfer", arb_transfer_tx().boxed()),
+            ("shielded_transfer", arb_shielded_transfer_tx().boxed()),
+            ("bond", arb_bond_tx().boxed()),
+            ("unbond", arb_unbond_tx().boxed()),
+            ("init_account", arb_init_account_tx().boxed()),
+            (
+                "init_vesting_account",
+                arb_init_vesting_account_tx().boxed(),
+            ),
+            ("become_validator", arb_become_validator_tx().boxed()),
+            (
+                "init_proposal_default",
+                arb_init_proposal_tx_with("default proposal", |r#type| {
+                    matches!(r#type, ProposalType::Default)
+                })
+                .boxed(),
+            ),
+            (
+                "init_proposal_default_with_wasm",
+                arb_init_proposal_tx_with("proposal with wasm", |r#type| {
+                    matches!(r#type, ProposalType::DefaultWithWasm(_))
+                })
+                .boxed(),
+            ),
+            (
+                "init_proposal_pgf_steward",
+                arb_init_proposal_tx_with("PGF steward proposal", |r#type| {
+                    matches!(r#type, ProposalType::PGFSteward(_))
+                })
+                .boxed(),
+            ),
+            (
+                "init_proposal_pgf_payment",
+                arb_init_proposal_tx_with("PGF payment proposal", |r#type| {
+                    matches!(r#type, ProposalType::PGFPayment(_))
+                })
+                .boxed(),
+            ),
+            ("vote_proposal", arb_vote_proposal_tx().boxed()),
+            ("reveal_pk", arb_reveal_pk_tx().boxed()),
+            ("update_account", arb_update_account_tx().boxed()),
+            ("update_vp_params", arb_update_vp_params_tx().boxed()),
+            ("withdraw", arb_withdraw_tx().boxed()),
+            ("claim_rewards", arb_claim_rewards_tx().boxed()),
+            ("commission_change", arb_commission_change_tx().boxed()),
+            ("metadata_change", arb_metadata_change_tx().boxed()),
+            ("unjail_validator", arb_unjail_validator_tx().boxed()),
+            (
+                "deactivate_validator",
+                arb_deactivate_validator_tx().boxed(),
+            ),
+            (
+                "reactivate_validator",
+                arb_reactivate_validator_tx().boxed(),
+            ),
+            (
+                "consensus_key_change",
+                arb_consensus_key_change_tx().boxed(),
+            ),
+            ("redelegation", arb_redelegation_tx().boxed()),
+            (
+                "update_steward_commission",
+                arb_update_steward_commission_tx().boxed(),
+            ),
+            ("resign_steward", arb_resign_steward_tx().boxed()),
+            ("pending_transfer", arb_pending_transfer_tx().boxed()),
+            ("ibc_msg_transfer", arb_ibc_msg_transfer_tx().boxed()),
+            ("ibc_shielding", arb_ibc_shielding_tx().boxed()),
+            (
+                "ibc_msg_nft_transfer",
+                arb_ibc_msg_nft_transfer_tx().boxed(),
+            ),
+        ]
+    }
+
     /// Generate an arbitrary tx
     pub fn arb_tx() -> impl Strategy<Value = (Tx, TxData)> {
-        prop_oneof![
-            arb_transfer_tx(),
-            arb_bond_tx(),
-            arb_unbond_tx(),
-            arb_init_account_tx(),
-            arb_become_validator_tx(),
-            arb_init_proposal_tx(),
-            arb_vote_proposal_tx(),
-            arb_reveal_pk_tx(),
-            arb_update_account_tx(),
-            arb_withdraw_tx(),
-            arb_claim_rewards_tx(),
-            arb_commission_change_tx(),
-            arb_metadata_change_tx(),
-            arb_unjail_validator_tx(),
-            arb_deactivate_validator_tx(),
-            arb_reactivate_validator_tx(),
-            arb_consensus_key_change_tx(),
-            arb_redelegation_tx(),
-            arb_update_steward_commission_tx(),
-            arb_resign_steward_tx(),
-            arb_pending_transfer_tx(),
-            arb_ibc_msg_transfer_tx(),
-            arb_ibc_msg_nft_transfer_tx(),
-        ]
+        Union::new(arb_tx_kinds().into_iter().map(|(_kind, txs)| txs))
     }
 
     prop_compose! {
@@ -1667,9 +1880,16 @@ pub mod testing {
         }
     }
 
+    /// Generate an arbitrary signed tx
+    pub fn arb_signed_tx() -> impl Strategy<Value = (Tx, TxData)> {
+        arb_signed_tx_of(arb_tx().boxed())
+    }
+
     prop_compose! {
-        /// Generate an arbitrary signed tx
-        pub fn arb_signed_tx()(tx in arb_memoed_tx())(
+        /// Generate an arbitrary signed tx of the given strategy
+        pub fn arb_signed_tx_of(txs: BoxedStrategy<(Tx, TxData)>)(
+            tx in arb_memoed_tx_of(txs),
+        )(
             sigs in collection::vec(arb_signature(tx.0.sechashes()), 0..3),
             mut tx in Just(tx),
         ) -> (Tx, TxData) {
@@ -1796,4 +2016,36 @@ pub mod testing {
             tx
         }
     }
+
+    /// The version of the Ledger test vectors. It must be bumped whenever the
+    /// output rendered for hardware wallets is changed on purpose.
+    pub const LEDGER_VECTORS_VERSION: u32 = 1;
+
+    /// The number of Ledger test vectors generated for each kind of tx
+    pub const LEDGER_VECTORS_PER_KIND: u64 = 10;
+
+    /// Generate the Ledger test vectors of signed txs of the given strategy.
+    /// The generation is deterministic, such that the same vectors are
+    /// generated on every call.
+    pub async fn generate_ledger_vectors(
+        txs: BoxedStrategy<(Tx, TxData)>,
+    ) -> Result<Vec<signing::LedgerVector>, error::Error> {
+        // An empty wallet, such that the tokens are never rendered with an
+        // alias
+        let wallet = Wallet::new(TestWalletUtils, Default::default());
+        let mut runner = TestRunner::deterministic();
+        let txs = arb_signed_tx_of(txs);
+        let mut vectors = vec![];
+        for index in 0..LEDGER_VECTORS_PER_KIND {
+            let (tx, _tx_data) = txs
+                .new_tree(&mut runner)
+                .map_err(|err| error::Error::Other(err.to_string()))?
+                .current();
+            let mut vector = signing::to_ledger_vector(&wallet, &tx).await?;
+            vector.index = index;
+            vector.name = format!("{}_{}", index, vector.name);
+            vectors.push(vector);
+        }
+        Ok(vectors)
+    }
 }
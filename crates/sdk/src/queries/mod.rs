@@ -13,8 +13,9 @@ use vp::{Vp, VP};
 
 pub use self::shell::eth_bridge::{
     Erc20FlowControl, GenBridgePoolProofReq, GenBridgePoolProofRsp,
-    TransferToErcArgs, TransferToEthereumStatus,
+    StalePendingTransfer, TransferToErcArgs, TransferToEthereumStatus,
 };
+pub use self::shell::{TxHistoryEntry, TX_HISTORY_PAGE_SIZE};
 
 #[macro_use]
 mod router;
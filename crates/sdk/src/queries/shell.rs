@@ -19,9 +19,10 @@ use namada_core::storage::{self, BlockResults, KeySeg, PrefixValue};
 use namada_core::time::DurationSecs;
 use namada_core::token::{Denomination, MaspDigitPos};
 use namada_core::uint::Uint;
+use namada_gas::trace::GasTrace;
 use namada_ibc::event::IbcEventType;
 use namada_state::{DBIter, LastBlock, StateRead, StorageHasher, DB};
-use namada_storage::{ResultExt, StorageRead};
+use namada_storage::{tx_history, ResultExt, StorageRead};
 use namada_token::masp::MaspTokenRewardData;
 use namada_token::storage_key::masp_token_map_key;
 use namada_tx::data::DryRunResult;
@@ -36,6 +37,16 @@ use crate::queries::types::{RequestCtx, RequestQuery};
 use crate::queries::{require_latest_height, EncodedResponseQuery};
 use crate::tendermint::merkle::proof::ProofOps;
 
+/// The result of a dry run together with its gas trace
+pub type DryRunTrace = (DryRunResult, GasTrace);
+
+/// The number of transaction hashes in a page of the history query
+pub const TX_HISTORY_PAGE_SIZE: usize = 50;
+
+/// An entry of the transaction history of an account: the height of the
+/// block, the index of the transaction in the block and its hash
+pub type TxHistoryEntry = (BlockHeight, u32, Hash);
+
 type ConversionWithoutPath = (
     Address,
     Denomination,
@@ -84,6 +95,9 @@ router! {SHELL,
     // Dry run a transaction
     ( "dry_run_tx" ) -> DryRunResult = (with_options dry_run_tx),
 
+    // Dry run a transaction with gas tracing
+    ( "dry_run_tx_trace" ) -> DryRunTrace = (with_options dry_run_tx_trace),
+
     // Raw storage access - prefix iterator
     ( "prefix" / [storage_key: storage::Key] )
         -> Vec<PrefixValue> = (with_options storage_prefix),
@@ -113,6 +127,10 @@ router! {SHELL,
     // Query public key revealad
     ( "revealed" / [owner: Address] ) -> bool = revealed,
 
+    // Query a page of the transaction history of an account from a block
+    // height and a transaction index, if the node maintains the index
+    ( "history" / [owner: Address] / [from_height: BlockHeight] / [from_tx_index: u32] ) -> Vec<TxHistoryEntry> = tx_history,
+
     // IBC UpdateClient event
     ( "ibc_client_update" / [client_id: ClientId] / [consensus_height: BlockHeight] ) -> Option<Event> = ibc_client_update,
 
@@ -139,6 +157,17 @@ where
     unimplemented!("Dry running tx requires \"wasm-runtime\" feature.")
 }
 
+fn dry_run_tx_trace<D, H, V, T>(
+    _ctx: RequestCtx<'_, D, H, V, T>,
+    _request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    unimplemented!("Dry running tx requires \"wasm-runtime\" feature.")
+}
+
 /// Return an estimate of the maximum time taken to decide a block
 fn max_block_time<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -636,11 +665,15 @@ where
     if account_exists {
         let public_keys = namada_account::public_keys(ctx.state, &owner)?;
         let threshold = namada_account::threshold(ctx.state, &owner)?;
+        let vp_params = namada_account::vp_params(ctx.state, &owner)?;
+        let vesting = namada_account::vesting_schedule(ctx.state, &owner)?;
 
         Ok(Some(Account {
             public_keys_map: AccountPublicKeysMap::from_iter(public_keys),
             address: owner,
             threshold: threshold.unwrap_or(1),
+            vp_params,
+            vesting,
         }))
     } else {
         Ok(None)
@@ -660,6 +693,37 @@ where
     Ok(!public_keys.is_empty())
 }
 
+/// Query a page of the transactions that moved the tokens of the owner,
+/// ordered by block height and transaction index, starting from the given
+/// block height and transaction index. Empty if the node doesn't maintain the
+/// transaction history index.
+fn tx_history<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+    from_height: BlockHeight,
+    from_tx_index: u32,
+) -> namada_storage::Result<Vec<TxHistoryEntry>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let (iter, _gas) = ctx
+        .state
+        .db_iter_tx_history(&owner, (from_height, from_tx_index));
+    iter.take(TX_HISTORY_PAGE_SIZE)
+        .map(|(key, value, _gas)| {
+            let (height, tx_index) = tx_history::parse_stripped_key(&key)
+                .ok_or_else(|| {
+                    namada_storage::Error::new_alloc(format!(
+                        "Invalid transaction history key {key}"
+                    ))
+                })?;
+            let hash = Hash::try_from_slice(&value).into_storage_result()?;
+            Ok((height, tx_index, hash))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use namada_core::address;
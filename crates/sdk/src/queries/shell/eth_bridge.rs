@@ -10,7 +10,9 @@ use namada_core::arith::checked;
 use namada_core::chain::{BlockHeight, Epoch};
 use namada_core::collections::{HashMap, HashSet};
 use namada_core::eth_abi::{Encode, EncodeCell};
-use namada_core::eth_bridge_pool::{PendingTransfer, PendingTransferAppendix};
+use namada_core::eth_bridge_pool::{
+    PendingTransfer, PendingTransferAppendix, RelayCosts,
+};
 use namada_core::ethereum_events::{
     EthAddress, EthereumEvent, TransferToEthereum,
 };
@@ -23,7 +25,9 @@ use namada_ethereum_bridge::event::{BpTransferStatus, BridgePoolTxHash};
 use namada_ethereum_bridge::protocol::transactions::votes::{
     EpochedVotingPower, EpochedVotingPowerExt,
 };
-use namada_ethereum_bridge::storage::bridge_pool::get_key_from_hash;
+use namada_ethereum_bridge::storage::bridge_pool::{
+    get_key_from_hash, get_relay_costs_key,
+};
 use namada_ethereum_bridge::storage::eth_bridge_queries::EthBridgeQueries;
 use namada_ethereum_bridge::storage::parameters::UpgradeableContract;
 use namada_ethereum_bridge::storage::proof::{sort_sigs, EthereumProof};
@@ -115,6 +119,30 @@ impl Erc20FlowControl {
     }
 }
 
+/// A transfer that has been pending in the Bridge pool for a
+/// long time, and is at risk of expiring.
+#[derive(
+    Debug,
+    Clone,
+    Eq,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshDeserializer,
+    Serialize,
+    Deserialize,
+)]
+pub struct StalePendingTransfer {
+    /// The pending transfer.
+    pub transfer: PendingTransfer,
+    /// The block height at which the transfer was added
+    /// to the Bridge pool.
+    pub inserted_height: BlockHeight,
+    /// The block height starting from which the transfer
+    /// may be refunded, if it has not been relayed.
+    pub expiry_height: BlockHeight,
+}
+
 /// Request data to pass to `generate_bridge_pool_proof`.
 #[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
 pub struct GenBridgePoolProofReq<'transfers, 'relayer> {
@@ -190,6 +218,16 @@ router! {ETH_BRIDGE,
     ( "pool" / "transfer_status" )
         -> TransferToEthereumStatus = (with_options pending_eth_transfer_status),
 
+    // Read the history of the costs paid on Ethereum to relay
+    // Bridge pool batches.
+    ( "pool" / "relay_costs" )
+        -> RelayCosts = read_relay_costs,
+
+    // Get the transfers that have been pending in the Bridge pool
+    // for at least half of the period after which they expire.
+    ( "pool" / "stale_transfers" )
+        -> Vec<StalePendingTransfer> = read_stale_transfers,
+
     // Request a proof of a validator set signed off for
     // the given epoch.
     //
@@ -340,6 +378,67 @@ where
     })
 }
 
+/// Read the history of the costs paid on Ethereum to relay
+/// Bridge pool batches.
+fn read_relay_costs<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<RelayCosts>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    Ok(StorageRead::read(ctx.state, &get_relay_costs_key())?
+        .unwrap_or_default())
+}
+
+/// Get the transfers that have been pending in the Bridge pool
+/// for at least half of the period after which they expire.
+fn read_stale_transfers<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Vec<StalePendingTransfer>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let height = ctx.state.in_mem().get_last_block_height();
+    // NB: transfers are refunded after residing in the Bridge
+    // pool for the minimum number of blocks of an epoch
+    let timeout_offset =
+        namada_parameters::read_epoch_duration_parameter(ctx.state)?
+            .min_num_of_blocks;
+    let stale_offset = timeout_offset / 2;
+
+    let merkle_tree = ctx
+        .state
+        .get_merkle_tree(height, Some(StoreType::BridgePool))
+        .expect("We should always be able to read the database");
+    let stores = merkle_tree.stores();
+    let store = match stores.store(&StoreType::BridgePool) {
+        StoreRef::BridgePool(store) => store,
+        _ => unreachable!(),
+    };
+
+    let mut stale = vec![];
+    for (hash, inserted_height) in store.iter() {
+        if height.0.saturating_sub(inserted_height.0) < stale_offset {
+            continue;
+        }
+        let Some(transfer): Option<PendingTransfer> =
+            StorageRead::read(ctx.state, &get_key_from_hash(hash))?
+        else {
+            continue;
+        };
+        stale.push(StalePendingTransfer {
+            transfer,
+            inserted_height: *inserted_height,
+            expiry_height: BlockHeight(
+                inserted_height.0.saturating_add(timeout_offset),
+            ),
+        });
+    }
+    Ok(stale)
+}
+
 /// Read the total supply and respective cap of some wrapped
 /// ERC20 token in Namada.
 fn get_erc20_flow_control<D, H, V, T>(
@@ -843,7 +942,7 @@ mod test_ethbridge_router {
     use assert_matches::assert_matches;
     use namada_core::address::testing::{established_address_1, nam};
     use namada_core::eth_bridge_pool::{
-        GasFee, TransferToEthereum, TransferToEthereumKind,
+        GasFee, RelayCost, TransferToEthereum, TransferToEthereumKind,
     };
     use namada_core::voting_power::EthBridgeVotingPower;
     use namada_ethereum_bridge::protocol::transactions::validator_set_update::aggregate_votes;
@@ -1444,6 +1543,7 @@ mod test_ethbridge_router {
             nonce: Default::default(),
             transfers: vec![event_transfer.clone()],
             relayer: bertha_address(),
+            relay_cost: Default::default(),
         };
         let eth_msg_key = vote_tallies::Keys::from(&eth_event);
         let voting_power = FractionalVotingPower::HALF;
@@ -1657,6 +1757,113 @@ mod test_ethbridge_router {
         );
     }
 
+    /// Test reading the history of the costs of relaying Bridge
+    /// pool batches.
+    #[tokio::test]
+    async fn test_read_relay_costs() {
+        let mut client = TestClient::new(RPC);
+
+        // nothing has been relayed yet
+        let relay_costs = RPC
+            .shell()
+            .eth_bridge()
+            .read_relay_costs(&client)
+            .await
+            .unwrap();
+        assert_eq!(relay_costs, RelayCosts::default());
+
+        let mut costs = RelayCosts::default();
+        costs.record(RelayCost {
+            transfers: 2,
+            cost: 20.into(),
+        });
+        costs.record(RelayCost {
+            transfers: 1,
+            cost: 30.into(),
+        });
+        client
+            .state
+            .write(&get_relay_costs_key(), &costs)
+            .expect("Test failed");
+
+        // commit the changes and increase block height
+        client.state.commit_block().expect("Test failed");
+        client.state.in_mem_mut().block.height += 1;
+
+        let relay_costs = RPC
+            .shell()
+            .eth_bridge()
+            .read_relay_costs(&client)
+            .await
+            .unwrap();
+        assert_eq!(relay_costs, costs);
+        assert_eq!(relay_costs.cost_per_transfer(), Some(30.into()));
+    }
+
+    /// Test that only transfers which have been in the Bridge
+    /// pool for a long time are reported as stale.
+    #[tokio::test]
+    async fn test_read_stale_transfers() {
+        let mut client = TestClient::new(RPC);
+        let transfer = PendingTransfer {
+            transfer: TransferToEthereum {
+                kind: TransferToEthereumKind::Erc20,
+                asset: EthAddress([0; 20]),
+                recipient: EthAddress([0; 20]),
+                sender: bertha_address(),
+                amount: 0.into(),
+            },
+            gas_fee: GasFee {
+                token: nam(),
+                amount: 0.into(),
+                payer: bertha_address(),
+            },
+        };
+        // transfers become stale after a single block
+        namada_parameters::update_epoch_parameter(
+            &mut client.state,
+            &namada_parameters::EpochDuration {
+                min_num_of_blocks: 2,
+                min_duration: namada_core::time::DurationSecs(5),
+            },
+        )
+        .expect("Test failed");
+
+        // write a transfer into the bridge pool
+        client.state.in_mem_mut().block.height = 1.into();
+        client
+            .state
+            .write(&get_pending_key(&transfer), &transfer)
+            .expect("Test failed");
+        client.state.commit_block().expect("Test failed");
+        client.state.in_mem_mut().block.height += 1;
+
+        // write a newer transfer into the bridge pool
+        let mut transfer2 = transfer.clone();
+        transfer2.transfer.amount = 1.into();
+        client
+            .state
+            .write(&get_pending_key(&transfer2), &transfer2)
+            .expect("Test failed");
+        client.state.commit_block().expect("Test failed");
+        client.state.in_mem_mut().block.height += 1;
+
+        let stale = RPC
+            .shell()
+            .eth_bridge()
+            .read_stale_transfers(&client)
+            .await
+            .unwrap();
+        assert_eq!(
+            stale,
+            vec![StalePendingTransfer {
+                transfer,
+                inserted_height: 1.into(),
+                expiry_height: 3.into(),
+            }]
+        );
+    }
+
     /// Test that querying the status of the Bridge pool
     /// returns the expected keccak hashes.
     #[tokio::test]
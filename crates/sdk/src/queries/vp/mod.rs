@@ -8,6 +8,9 @@ use pos::POS;
 pub use token::Token;
 use token::TOKEN;
 mod governance;
+pub use nft::Nft;
+use nft::NFT;
+mod nft;
 pub use pgf::Pgf;
 use pgf::PGF;
 mod pgf;
@@ -21,4 +24,5 @@ router! {VP,
     ( "token" ) = (sub TOKEN),
     ( "governance" ) = (sub GOV),
     ( "pgf" ) = (sub PGF),
+    ( "nft" ) = (sub NFT),
 }
@@ -0,0 +1,85 @@
+//! IBC NFT queries
+
+use namada_core::address::Address;
+use namada_core::token;
+use namada_ibc::storage::{
+    nft_class_key_by_token, nft_class_prefix, nft_metadata_key_by_token,
+    nft_metadata_prefix,
+};
+use namada_ibc::trace::ibc_token_for_nft;
+use namada_ibc::{NftClass, NftMetadata};
+use namada_state::{DBIter, StorageHasher, StorageRead, DB};
+use namada_token::storage_key::balance_key;
+
+use crate::queries::types::RequestCtx;
+
+// IBC NFT queries
+router! {NFT,
+    ( "classes" ) -> Vec<NftClass> = classes,
+    ( "class" / [class: Address] ) -> Option<NftClass> = class,
+    ( "tokens" / [owner: Address] ) -> Vec<NftMetadata> = tokens,
+    ( "metadata" / [token: Address] ) -> Option<NftMetadata> = metadata,
+}
+
+/// Get all the NFT classes known on chain
+fn classes<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Vec<NftClass>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let prefix = nft_class_prefix();
+    namada_storage::iter_prefix(ctx.state, &prefix)?
+        .map(|res| res.map(|(_key, class)| class))
+        .collect()
+}
+
+/// Get the NFT class with the given IBC token of the class ID
+fn class<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    class: Address,
+) -> namada_storage::Result<Option<NftClass>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    ctx.state.read(&nft_class_key_by_token(&class))
+}
+
+/// Get the metadata of the NFTs owned by the given address, i.e. the NFTs
+/// for which the address holds a balance of the NFT's IBC token
+fn tokens<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+) -> namada_storage::Result<Vec<NftMetadata>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let prefix = nft_metadata_prefix();
+    let mut tokens = vec![];
+    for res in namada_storage::iter_prefix(ctx.state, &prefix)? {
+        let (_key, metadata): (_, NftMetadata) = res?;
+        let ibc_token =
+            ibc_token_for_nft(&metadata.class_id, &metadata.token_id);
+        let balance: Option<token::Amount> =
+            ctx.state.read(&balance_key(&ibc_token, &owner))?;
+        if !balance.unwrap_or_default().is_zero() {
+            tokens.push(metadata);
+        }
+    }
+    Ok(tokens)
+}
+
+/// Get the metadata of the NFT with the given IBC token
+fn metadata<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    token: Address,
+) -> namada_storage::Result<Option<NftMetadata>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    ctx.state.read(&nft_metadata_key_by_token(&token))
+}
@@ -0,0 +1,303 @@
+//! Protocol to request transaction signatures from an external signing
+//! service, e.g. a custody service backed by an HSM.
+//!
+//! A signer is sent the public key of the requested signing key and the hashes
+//! of the transaction sections to sign: the raw header hash for the inner
+//! signatures or [`Tx::sechashes`] for the wrapper signature. It replies with a
+//! signature over the commitment to these hashes, as made by an
+//! [`Authorization`] section. The requests and responses are exchanged as
+//! newline delimited JSON over a TCP or a Unix socket, addressed by
+//! `remote://<host>:<port>` or `remote:///<path>` respectively.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use namada_core::hash::Hash;
+use namada_core::key::{common, SigScheme};
+use namada_tx::{Authorization, Section, Signer, Tx};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::signing::Signable;
+
+/// The scheme prefix of remote signer addresses
+pub const REMOTE_SIGNER_SCHEME: &str = "remote://";
+
+/// A request to sign section hashes with a key held by an external signer
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignRequest {
+    /// The public key of the requested signing key
+    pub public_key: common::PublicKey,
+    /// The hashes of the sections to sign
+    pub targets: Vec<Hash>,
+}
+
+impl SignRequest {
+    /// The commitment to the targets that must be signed. This is the raw
+    /// hash of the authorization section carrying the signature.
+    pub fn commitment(&self) -> Hash {
+        Authorization {
+            targets: self.targets.clone(),
+            signer: Signer::PubKeys(vec![]),
+            signatures: BTreeMap::new(),
+        }
+        .get_raw_hash()
+    }
+}
+
+/// The response of an external signer to a [`SignRequest`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum SignResponse {
+    /// The signature over the commitment of the request
+    Signature(common::Signature),
+    /// The reason why the signer rejected the request
+    Rejected(String),
+}
+
+/// A signer of transaction sections holding keys outside of the wallet
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+pub trait ExternalSigner {
+    /// Request a signature
+    async fn sign(&self, request: SignRequest) -> Result<SignResponse, Error>;
+}
+
+/// The address of a remote signer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RemoteSignerAddr {
+    /// A TCP socket address, given as `<host>:<port>`
+    Tcp(String),
+    /// The path of a Unix socket
+    Unix(PathBuf),
+}
+
+impl FromStr for RemoteSignerAddr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let addr = s.strip_prefix(REMOTE_SIGNER_SCHEME).ok_or_else(|| {
+            format!(
+                "Unexpected remote signer address \"{s}\". It must start with \
+                 \"{REMOTE_SIGNER_SCHEME}\"."
+            )
+        })?;
+        if addr.starts_with('/') {
+            Ok(Self::Unix(PathBuf::from(addr)))
+        } else if addr.contains(':') {
+            Ok(Self::Tcp(addr.to_string()))
+        } else {
+            Err(format!(
+                "Unexpected remote signer address \"{s}\". Valid addresses \
+                 are \"{REMOTE_SIGNER_SCHEME}<host>:<port>\" or \
+                 \"{REMOTE_SIGNER_SCHEME}/<socket path>\"."
+            ))
+        }
+    }
+}
+
+impl fmt::Display for RemoteSignerAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{REMOTE_SIGNER_SCHEME}{addr}"),
+            Self::Unix(path) => {
+                write!(f, "{REMOTE_SIGNER_SCHEME}{}", path.to_string_lossy())
+            }
+        }
+    }
+}
+
+/// A client of a remote signer
+#[derive(Clone, Debug)]
+pub struct RemoteSigner {
+    /// The address of the signer
+    pub addr: RemoteSignerAddr,
+}
+
+impl RemoteSigner {
+    /// Make a client of the signer at the given address
+    pub fn new(addr: RemoteSignerAddr) -> Self {
+        Self { addr }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[cfg_attr(feature = "async-send", async_trait::async_trait)]
+#[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+impl ExternalSigner for RemoteSigner {
+    async fn sign(&self, request: SignRequest) -> Result<SignResponse, Error> {
+        let err = |e: std::io::Error| {
+            Error::Other(format!(
+                "Failed to exchange with the remote signer {}: {e}",
+                self.addr
+            ))
+        };
+        match &self.addr {
+            RemoteSignerAddr::Tcp(addr) => {
+                let stream =
+                    tokio::net::TcpStream::connect(addr).await.map_err(err)?;
+                exchange(stream, &request).await.map_err(err)
+            }
+            #[cfg(unix)]
+            RemoteSignerAddr::Unix(path) => {
+                let stream =
+                    tokio::net::UnixStream::connect(path).await.map_err(err)?;
+                exchange(stream, &request).await.map_err(err)
+            }
+            #[cfg(not(unix))]
+            RemoteSignerAddr::Unix(_) => Err(Error::Other(
+                "Unix sockets are not supported on this platform".to_string(),
+            )),
+        }
+    }
+}
+
+/// Send a message as a line of JSON on the given stream and read the reply
+#[cfg(not(target_family = "wasm"))]
+async fn exchange<S, Req, Resp>(
+    stream: S,
+    request: &Req,
+) -> std::io::Result<Resp>
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+    Req: Serialize,
+    Resp: for<'de> Deserialize<'de>,
+{
+    use tokio::io::AsyncBufReadExt;
+
+    let mut stream = tokio::io::BufStream::new(stream);
+    write_message(&mut stream, request).await?;
+    let mut line = String::new();
+    stream.read_line(&mut line).await?;
+    serde_json::from_str(&line)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+/// Write a message as a line of JSON on the given stream
+#[cfg(not(target_family = "wasm"))]
+pub async fn write_message<S, T>(
+    stream: &mut S,
+    message: &T,
+) -> std::io::Result<()>
+where
+    S: tokio::io::AsyncWrite + Unpin,
+    T: Serialize,
+{
+    use tokio::io::AsyncWriteExt;
+
+    let mut bytes = serde_json::to_vec(message)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    bytes.push(b'\n');
+    stream.write_all(&bytes).await?;
+    stream.flush().await
+}
+
+/// Request a signature over the given targets and check it against the
+/// public key
+async fn request_authorization<S>(
+    signer: &S,
+    public_key: &common::PublicKey,
+    targets: Vec<Hash>,
+) -> Result<Authorization, Error>
+where
+    S: ExternalSigner + ?Sized,
+{
+    let request = SignRequest {
+        public_key: public_key.clone(),
+        targets,
+    };
+    let signature = match signer.sign(request.clone()).await? {
+        SignResponse::Signature(signature) => signature,
+        SignResponse::Rejected(reason) => {
+            return Err(Error::Other(format!(
+                "The external signer rejected the signing request for public \
+                 key {public_key}: {reason}"
+            )));
+        }
+    };
+    common::SigScheme::verify_signature(
+        public_key,
+        &request.commitment(),
+        &signature,
+    )
+    .map_err(|e| {
+        Error::Other(format!(
+            "The external signer returned an invalid signature for public key \
+             {public_key}: {e}"
+        ))
+    })?;
+    Ok(Authorization {
+        targets: request.targets,
+        signer: Signer::PubKeys(vec![public_key.clone()]),
+        signatures: [(0, signature)].into(),
+    })
+}
+
+/// Sign the given parts of a transaction with a key held by an external
+/// signer. This can be used as the signing function of
+/// [`crate::signing::sign_tx`] for the keys that are not in the wallet.
+pub async fn sign_with_external_signer<S>(
+    mut tx: Tx,
+    pubkey: common::PublicKey,
+    parts: HashSet<Signable>,
+    signer: &S,
+) -> Result<Tx, Error>
+where
+    S: ExternalSigner + ?Sized,
+{
+    // The inner tx signers sign the raw header
+    if parts.contains(&Signable::RawHeader) {
+        tx.protocol_filter();
+        let auth =
+            request_authorization(signer, &pubkey, vec![tx.raw_header_hash()])
+                .await?;
+        tx.add_section(Section::Authorization(auth));
+    }
+    // The fee payer signs all the sections, including the inner signatures
+    if parts.contains(&Signable::FeeHeader) {
+        tx.protocol_filter();
+        let auth =
+            request_authorization(signer, &pubkey, tx.sechashes()).await?;
+        tx.add_section(Section::Authorization(auth));
+    }
+    Ok(tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::key::testing::keypair_1;
+    use namada_core::key::RefTo;
+
+    use super::*;
+
+    #[test]
+    fn test_remote_signer_addr() {
+        let tcp: RemoteSignerAddr = "remote://127.0.0.1:7000".parse().unwrap();
+        assert_eq!(tcp, RemoteSignerAddr::Tcp("127.0.0.1:7000".to_string()));
+        assert_eq!(tcp.to_string(), "remote://127.0.0.1:7000");
+
+        let unix: RemoteSignerAddr =
+            "remote:///tmp/signer.sock".parse().unwrap();
+        assert_eq!(unix, RemoteSignerAddr::Unix("/tmp/signer.sock".into()));
+        assert_eq!(unix.to_string(), "remote:///tmp/signer.sock");
+
+        assert!("tcp://127.0.0.1:7000".parse::<RemoteSignerAddr>().is_err());
+        assert!("remote://localhost".parse::<RemoteSignerAddr>().is_err());
+    }
+
+    #[test]
+    fn test_signed_commitment_matches_authorization() {
+        let keypair = keypair_1();
+        let request = SignRequest {
+            public_key: keypair.ref_to(),
+            targets: vec![Hash::sha256(b"section")],
+        };
+        let auth = Authorization::new(
+            request.targets.clone(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        );
+        assert_eq!(request.commitment(), auth.get_raw_hash());
+    }
+}
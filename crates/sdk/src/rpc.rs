@@ -26,6 +26,7 @@ use namada_core::token::{
 };
 use namada_core::{storage, token};
 use namada_gas::event::GasUsed as GasUsedAttr;
+use namada_gas::trace::GasTrace;
 use namada_gas::WholeGas;
 use namada_governance::parameters::GovernanceParameters;
 use namada_governance::pgf::parameters::PgfParameters;
@@ -59,7 +60,7 @@ use crate::internal_macros::echo_error;
 use crate::queries::vp::pos::{
     EnrichedBondsAndUnbondsDetails, ValidatorStateInfo,
 };
-use crate::queries::RPC;
+use crate::queries::{TxHistoryEntry, RPC};
 use crate::tendermint::block::Height;
 use crate::tendermint::merkle::proof::ProofOps;
 use crate::tendermint_rpc::query::Query;
@@ -472,6 +473,20 @@ pub async fn query_storage_value_bytes<C: namada_io::Client + Sync>(
     })
 }
 
+/// Query a range of storage values with a matching prefix without decoding.
+pub async fn query_storage_prefix_bytes<C: namada_io::Client + Sync>(
+    client: &C,
+    key: &storage::Key,
+    height: Option<BlockHeight>,
+) -> Result<Vec<PrefixValue>, error::Error> {
+    let values = convert_response::<C, _>(
+        RPC.shell()
+            .storage_prefix(client, None, height, false, key)
+            .await,
+    )?;
+    Ok(values.data)
+}
+
 /// Query a range of storage values with a matching prefix and decode them with
 /// [`BorshDeserialize`]. Returns an iterator of the storage keys paired with
 /// their associated values.
@@ -616,6 +631,22 @@ pub async fn dry_run_tx<N: Namada>(
     Ok(result)
 }
 
+/// Dry run a transaction with gas tracing enabled. Returns the result of the
+/// dry run together with the trace of the gas consumed by the transaction's
+/// host function calls and by the VPs it triggered.
+pub async fn dry_run_tx_trace<N: Namada>(
+    context: &N,
+    tx_bytes: Vec<u8>,
+) -> Result<(DryRunResult, GasTrace), Error> {
+    let (data, height, prove) = (Some(tx_bytes), None, false);
+    convert_response::<N::Client, _>(
+        RPC.shell()
+            .dry_run_tx_trace(context.client(), data, height, prove)
+            .await,
+    )
+    .map(|response| response.data)
+}
+
 /// Data needed for broadcasting a tx and monitoring its progress on chain.
 ///
 /// Txs may be either a dry run or else they should be included in a wrapper.
@@ -954,6 +985,36 @@ pub async fn is_public_key_revealed<C: namada_io::Client + Sync>(
     convert_response::<C, bool>(RPC.shell().revealed(client, owner).await)
 }
 
+/// Query a page of the transactions that moved the tokens of the owner,
+/// starting from the given block height and transaction index. Empty if the
+/// node doesn't maintain the transaction history index.
+pub async fn query_tx_history<C: namada_io::Client + Sync>(
+    client: &C,
+    owner: &Address,
+    from_height: BlockHeight,
+    from_tx_index: u32,
+) -> Result<Vec<TxHistoryEntry>, error::Error> {
+    convert_response::<C, Vec<TxHistoryEntry>>(
+        RPC.shell()
+            .tx_history(client, owner, &from_height, &from_tx_index)
+            .await,
+    )
+}
+
+/// Check if an implicit account was used on chain, i.e. if its public key is
+/// revealed or if it holds some native tokens
+pub async fn is_implicit_account_used<C: namada_io::Client + Sync>(
+    client: &C,
+    owner: &Address,
+    native_token: &Address,
+) -> Result<bool, error::Error> {
+    if is_public_key_revealed(client, owner).await? {
+        return Ok(true);
+    }
+    let balance = get_token_balance(client, native_token, owner, None).await?;
+    Ok(!balance.is_zero())
+}
+
 /// Query an account substorage at a specific index
 pub async fn get_public_key_at<C: namada_io::Client + Sync>(
     client: &C,
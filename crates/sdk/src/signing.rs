@@ -13,7 +13,10 @@ use masp_primitives::asset_type::AssetType;
 use masp_primitives::transaction::components::sapling::fees::{
     InputView, OutputView,
 };
-use namada_account::{AccountPublicKeysMap, InitAccount, UpdateAccount};
+use namada_account::{
+    AccountPublicKeysMap, InitAccount, InitVestingAccount, UpdateAccount,
+    UpdateVpParams,
+};
 use namada_core::address::{Address, ImplicitAddress, InternalAddress, MASP};
 use namada_core::arith::checked;
 use namada_core::collections::{HashMap, HashSet};
@@ -34,7 +37,9 @@ use namada_token::storage_key::balance_key;
 use namada_tx::data::pgf::UpdateStewardCommission;
 use namada_tx::data::pos::BecomeValidator;
 use namada_tx::data::{pos, Fee};
-use namada_tx::{MaspBuilder, Section, SignatureIndex, Tx};
+use namada_tx::{
+    Authorization, MaspBuilder, Section, SignatureIndex, Signer, Tx,
+};
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
@@ -50,11 +55,11 @@ use crate::tx::{
     TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
     TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
     TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-    TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-    TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-    VP_USER_WASM,
+    TX_INIT_PROPOSAL, TX_INIT_VESTING_ACCOUNT_WASM,
+    TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+    TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_STEWARD_COMMISSION,
+    TX_UPDATE_VP_PARAMS_WASM, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM, VP_USER_WASM,
 };
 pub use crate::wallet::store::AddressVpType;
 use crate::wallet::{Wallet, WalletIo};
@@ -587,6 +592,104 @@ fn other_err<T>(string: String) -> Result<T, Error> {
     Err(Error::Other(string))
 }
 
+/// A session collecting the offline signatures of the owners of a multisig
+/// account over a transaction, until the account threshold is met
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SigningSession {
+    /// The transaction to sign
+    pub tx: Tx,
+    /// The signatures of the raw header of the transaction collected so far
+    pub signatures: Vec<SignatureIndex>,
+}
+
+impl SigningSession {
+    /// Start a session without any signature
+    pub fn new(tx: Tx) -> Self {
+        Self {
+            tx,
+            signatures: vec![],
+        }
+    }
+
+    /// Serialize the session to pretty JSON into an I/O stream
+    pub fn to_writer_json<W>(&self, writer: W) -> serde_json::Result<()>
+    where
+        W: std::io::Write,
+    {
+        serde_json::to_writer_pretty(writer, self)
+    }
+
+    /// Try to parse a session from JSON string bytes
+    pub fn try_from_json_bytes(
+        bytes: &[u8],
+    ) -> Result<SigningSession, serde_json::Error> {
+        serde_json::from_slice::<SigningSession>(bytes)
+    }
+
+    /// Check that the given signature is a valid signature of the raw header
+    /// of the session's transaction
+    pub fn verify_signature(
+        &self,
+        signature: &SignatureIndex,
+    ) -> Result<(), Error> {
+        let commitment = Authorization {
+            targets: vec![self.tx.raw_header_hash()],
+            signer: Signer::PubKeys(vec![]),
+            signatures: BTreeMap::new(),
+        }
+        .get_raw_hash();
+        common::SigScheme::verify_signature(
+            &signature.pubkey,
+            &commitment,
+            &signature.signature,
+        )
+        .map_err(|err| {
+            Error::Other(format!(
+                "Invalid signature from public key {}: {}",
+                signature.pubkey, err
+            ))
+        })
+    }
+
+    /// Add the given signatures to the session after verifying them. A
+    /// signature replaces any previous one made with the same key.
+    pub fn add_signatures(
+        &mut self,
+        signatures: impl IntoIterator<Item = SignatureIndex>,
+    ) -> Result<(), Error> {
+        for signature in signatures {
+            self.verify_signature(&signature)?;
+            self.signatures.retain(|sig| sig.pubkey != signature.pubkey);
+            self.signatures.push(signature);
+        }
+        self.signatures.sort();
+        Ok(())
+    }
+
+    /// Count the valid signatures made by the keys of the given account at
+    /// their index in the account's public keys map
+    pub fn valid_signatures(
+        &self,
+        owner: &Address,
+        account_public_keys_map: &AccountPublicKeysMap,
+    ) -> u8 {
+        let count = self
+            .signatures
+            .iter()
+            .filter(|sig| {
+                let index = account_public_keys_map
+                    .get_index_from_public_key(&sig.pubkey);
+                index.is_some()
+                    && sig.index.as_ref().map_or(true, |(addr, idx)| {
+                        addr == owner && Some(*idx) == index
+                    })
+                    && self.verify_signature(sig).is_ok()
+            })
+            .count();
+        u8::try_from(count).unwrap_or(u8::MAX)
+    }
+}
+
 /// Represents the transaction data that is displayed on a Ledger device
 #[derive(Default, Serialize, Deserialize, Debug, Clone)]
 pub struct LedgerVector {
@@ -1086,6 +1189,40 @@ pub async fn to_ledger_vector(
                 format!("Threshold : {}", init_account.threshold),
                 format!("VP type : {}", HEXLOWER.encode(&extra.code.hash().0)),
             ]);
+        } else if code_sec.tag == Some(TX_INIT_VESTING_ACCOUNT_WASM.to_string())
+        {
+            let InitVestingAccount {
+                account: init_account,
+                vesting,
+            } = InitVestingAccount::try_from_slice(
+                &tx.data(cmt)
+                    .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+            )
+            .map_err(|err| {
+                Error::from(EncodingError::Conversion(err.to_string()))
+            })?;
+            tv.name = "Init_Vesting_Account_0".to_string();
+
+            tv.output
+                .extend(vec![format!("Type : Init Vesting Account")]);
+            let mut lines: Vec<_> = init_account
+                .public_keys
+                .iter()
+                .map(|k| format!("Public key : {}", k))
+                .collect();
+            lines.extend(vec![
+                format!("Threshold : {}", init_account.threshold),
+                format!(
+                    "Vesting amount : {} {}",
+                    vesting.amount.to_string_native(),
+                    vesting.token
+                ),
+                format!("Vesting start : {}", vesting.start),
+                format!("Vesting cliff : {}", vesting.cliff),
+                format!("Vesting end : {}", vesting.end),
+            ]);
+            tv.output.extend(lines.clone());
+            tv.output_expert.extend(lines);
         } else if code_sec.tag == Some(TX_BECOME_VALIDATOR_WASM.to_string()) {
             let init_validator = BecomeValidator::try_from_slice(
                 &tx.data(cmt)
@@ -1328,6 +1465,57 @@ pub async fn to_ledger_vector(
                     HEXLOWER.encode(&extra_code_hash.0)
                 )]);
             }
+        } else if code_sec.tag == Some(TX_UPDATE_VP_PARAMS_WASM.to_string()) {
+            let update_vp_params = UpdateVpParams::try_from_slice(
+                &tx.data(cmt)
+                    .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+            )
+            .map_err(|err| {
+                Error::from(EncodingError::Conversion(err.to_string()))
+            })?;
+            let params = &update_vp_params.vp_params;
+
+            tv.name = "Update_Vp_Params_0".to_string();
+            tv.output.push("Type : Update VP Parameters".to_string());
+
+            let mut lines =
+                vec![format!("Address : {}", update_vp_params.addr)];
+            lines.extend(params.spending_limits.iter().map(
+                |(token, amount)| {
+                    format!(
+                        "Spending limit : {} {}",
+                        amount.to_string_native(),
+                        token
+                    )
+                },
+            ));
+            lines.extend(params.daily_caps.iter().map(|(token, amount)| {
+                format!("Daily cap : {} {}", amount.to_string_native(), token)
+            }));
+            lines.extend(params.withdrawal_limits.iter().map(
+                |(token, limit)| {
+                    format!(
+                        "Withdrawal limit : {} {} per {}s",
+                        limit.amount.to_string_native(),
+                        token,
+                        limit.window.0
+                    )
+                },
+            ));
+            if let Some(time_lock) = &params.time_lock {
+                lines.push(format!("Time lock : {}", time_lock));
+            }
+            lines.extend(
+                params
+                    .allowed_counterparties
+                    .iter()
+                    .map(|addr| format!("Allowed counterparty : {}", addr)),
+            );
+            if let Some(bypass_threshold) = params.bypass_threshold {
+                lines.push(format!("Bypass threshold : {}", bypass_threshold));
+            }
+            tv.output.extend(lines.clone());
+            tv.output_expert.extend(lines);
         } else if code_sec.tag == Some(TX_TRANSFER_WASM.to_string()) {
             let transfer = token::Transfer::try_from_slice(
                 &tx.data(cmt)
@@ -2105,6 +2293,7 @@ mod test_signing {
             memo: None,
             use_device: false,
             device_transport: Default::default(),
+            remote_signer: None,
         }
     }
 
@@ -2970,4 +3159,78 @@ mod test_signing {
         );
         output.clear();
     }
+
+    /// Test that a signing session only counts the valid signatures of the
+    /// account keys
+    #[test]
+    fn test_signing_session() {
+        let owner = namada_core::address::testing::established_address_1();
+        let sk1 = namada_core::key::testing::keypair_1();
+        let sk2 = namada_core::key::testing::keypair_2();
+        let sk3 = namada_core::key::testing::keypair_3();
+        let account_public_keys_map =
+            AccountPublicKeysMap::from_iter([sk1.ref_to(), sk2.ref_to()]);
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None).add_serialized_data(vec![1, 2, 3]);
+        let mut session = SigningSession::new(tx.clone());
+        assert_eq!(
+            session.valid_signatures(&owner, &account_public_keys_map),
+            0
+        );
+
+        // Add the signature of the first key
+        let signatures = tx.compute_section_signature(
+            &[sk1.clone()],
+            &account_public_keys_map,
+            Some(owner.clone()),
+        );
+        session
+            .add_signatures(signatures.clone())
+            .expect("Test failed");
+        assert_eq!(
+            session.valid_signatures(&owner, &account_public_keys_map),
+            1
+        );
+        // Adding it again replaces it
+        session.add_signatures(signatures).expect("Test failed");
+        assert_eq!(session.signatures.len(), 1);
+
+        // A signature of another tx is rejected
+        let mut other_tx = tx.clone();
+        other_tx.add_memo(b"memo");
+        let signatures = other_tx.compute_section_signature(
+            &[sk2.clone()],
+            &account_public_keys_map,
+            Some(owner.clone()),
+        );
+        assert_matches!(session.add_signatures(signatures), Err(_));
+
+        // A signature of a key outside of the account is not counted
+        let signatures = tx.compute_section_signature(
+            &[sk3.clone()],
+            &AccountPublicKeysMap::from_iter([sk3.ref_to()]),
+            None,
+        );
+        session.add_signatures(signatures).expect("Test failed");
+        assert_eq!(
+            session.valid_signatures(&owner, &account_public_keys_map),
+            1
+        );
+
+        // The session meets a threshold of 2 with the second key
+        let signatures = tx.compute_section_signature(
+            &[sk2],
+            &account_public_keys_map,
+            Some(owner.clone()),
+        );
+        session.add_signatures(signatures).expect("Test failed");
+        let mut buffer = vec![];
+        session.to_writer_json(&mut buffer).expect("Test failed");
+        let session =
+            SigningSession::try_from_json_bytes(&buffer).expect("Test failed");
+        assert_eq!(
+            session.valid_signatures(&owner, &account_public_keys_map),
+            2
+        );
+    }
 }
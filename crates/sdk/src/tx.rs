@@ -18,7 +18,10 @@ use masp_primitives::transaction::components::transparent::fees::{
 };
 use masp_primitives::transaction::components::I128Sum;
 use masp_primitives::transaction::Transaction as MaspTransaction;
-use namada_account::{InitAccount, UpdateAccount};
+use namada_account::{
+    InitAccount, InitVestingAccount, UpdateAccount, UpdateVpParams,
+    VestingSchedule, WithdrawalLimit,
+};
 use namada_core::address::{Address, IBC, MASP};
 use namada_core::arith::checked;
 use namada_core::chain::Epoch;
@@ -93,6 +96,8 @@ use crate::{args, Namada};
 
 /// Initialize account transaction WASM
 pub const TX_INIT_ACCOUNT_WASM: &str = "tx_init_account.wasm";
+/// Initialize vesting account transaction WASM
+pub const TX_INIT_VESTING_ACCOUNT_WASM: &str = "tx_init_vesting_account.wasm";
 /// Become validator transaction WASM path
 pub const TX_BECOME_VALIDATOR_WASM: &str = "tx_become_validator.wasm";
 /// Unjail validator transaction WASM path
@@ -109,6 +114,8 @@ pub const TX_VOTE_PROPOSAL: &str = "tx_vote_proposal.wasm";
 pub const TX_REVEAL_PK: &str = "tx_reveal_pk.wasm";
 /// Update validity predicate WASM path
 pub const TX_UPDATE_ACCOUNT_WASM: &str = "tx_update_account.wasm";
+/// Update VP parameters transaction WASM path
+pub const TX_UPDATE_VP_PARAMS_WASM: &str = "tx_update_vp_params.wasm";
 /// Transparent transfer transaction WASM path
 pub const TX_TRANSFER_WASM: &str = "tx_transfer.wasm";
 /// IBC transaction WASM path
@@ -125,6 +132,8 @@ pub const TX_WITHDRAW_WASM: &str = "tx_withdraw.wasm";
 pub const TX_CLAIM_REWARDS_WASM: &str = "tx_claim_rewards.wasm";
 /// Bridge pool WASM path
 pub const TX_BRIDGE_POOL_WASM: &str = "tx_bridge_pool.wasm";
+/// Cancel Bridge pool transfer WASM path
+pub const TX_CANCEL_BRIDGE_POOL_WASM: &str = "tx_cancel_bridge_pool.wasm";
 /// Change commission WASM path
 pub const TX_CHANGE_COMMISSION_WASM: &str =
     "tx_change_validator_commission.wasm";
@@ -214,6 +223,23 @@ pub fn dump_tx<IO: Io>(io: &IO, args: &args::Tx, mut tx: Tx) -> Result<()> {
                 "Transaction serialized to {}.",
                 tx_path.to_string_lossy()
             );
+            // Start a session to collect the signatures of the inner tx
+            if args.dump_tx {
+                let session_path = path.join(format!(
+                    "{}.session",
+                    tx.header_hash().to_string().to_lowercase()
+                ));
+                let out = File::create(&session_path)
+                    .expect("Should be able to create a file to dump session");
+                signing::SigningSession::new(tx)
+                    .to_writer_json(out)
+                    .expect("Should be able to write to file.");
+                display_line!(
+                    io,
+                    "Signing session started in {}.",
+                    session_path.to_string_lossy()
+                );
+            }
         }
         None => {
             let serialized_tx = serde_json::to_string_pretty(&tx)
@@ -3498,6 +3524,7 @@ pub async fn build_init_account(
         tx_code_path,
         public_keys,
         threshold,
+        vesting,
     }: &args::TxInitAccount,
 ) -> Result<(Tx, SigningTxData)> {
     let signing_data =
@@ -3542,6 +3569,38 @@ pub async fn build_init_account(
         }
     };
 
+    let vesting = match vesting {
+        Some(args::Vesting {
+            token,
+            amount,
+            start,
+            cliff,
+            end,
+        }) => {
+            let amount =
+                validate_amount(context, *amount, token, tx_args.force)
+                    .await?
+                    .amount();
+            let schedule = VestingSchedule {
+                token: token.clone(),
+                amount,
+                start: *start,
+                cliff: *cliff,
+                end: *end,
+            };
+            if !schedule.is_valid() {
+                edisplay_line!(
+                    context.io(),
+                    "Invalid vesting schedule: the start must not be after \
+                     the cliff and the cliff must not be after the end."
+                );
+                return Err(Error::from(TxSubmitError::InvalidVestingSchedule));
+            }
+            Some(schedule)
+        }
+        None => None,
+    };
+
     let data = InitAccount {
         public_keys: public_keys.clone(),
         // We will add the hash inside the add_code_hash function
@@ -3557,17 +3616,40 @@ pub async fn build_init_account(
         data.vp_code_hash = extra_section_hash;
         Ok(())
     };
-    build(
-        context,
-        tx_args,
-        tx_code_path.clone(),
-        data,
-        add_code_hash,
-        fee_amount,
-        &signing_data.fee_payer,
-    )
-    .await
-    .map(|tx| (tx, signing_data))
+    let tx = match vesting {
+        // A vesting account is initialized by its own tx
+        Some(vesting) => {
+            let data = InitVestingAccount {
+                account: data,
+                vesting,
+            };
+            build(
+                context,
+                tx_args,
+                tx_code_path.clone(),
+                data,
+                |tx: &mut Tx, data: &mut InitVestingAccount| {
+                    add_code_hash(tx, &mut data.account)
+                },
+                fee_amount,
+                &signing_data.fee_payer,
+            )
+            .await
+        }
+        None => {
+            build(
+                context,
+                tx_args,
+                tx_code_path.clone(),
+                data,
+                add_code_hash,
+                fee_amount,
+                &signing_data.fee_payer,
+            )
+            .await
+        }
+    };
+    tx.map(|tx| (tx, signing_data))
 }
 
 /// Submit a transaction to update a VP
@@ -3697,6 +3779,100 @@ pub async fn build_update_account(
     .map(|tx| (tx, signing_data))
 }
 
+/// Submit a transaction to update the parameters of an account's VP
+pub async fn build_update_vp_params(
+    context: &impl Namada,
+    args::TxUpdateVpParams {
+        tx: tx_args,
+        tx_code_path,
+        addr,
+        vp_params,
+        spending_limit,
+        bypass_threshold,
+    }: &args::TxUpdateVpParams,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(addr.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(addr.clone()),
+        default_signer,
+        vec![],
+        false,
+    )
+    .await?;
+    let (fee_amount, _) =
+        validate_transparent_fee(context, tx_args, &signing_data.fee_payer)
+            .await?;
+
+    let account = if let Some(account) =
+        rpc::get_account_info(context.client(), addr).await?
+    {
+        account
+    } else {
+        return Err(Error::from(TxSubmitError::LocationDoesNotExist(
+            addr.clone(),
+        )));
+    };
+
+    // Merge the limits into the given or the account's current VP parameters
+    let mut vp_params = vp_params
+        .clone()
+        .or_else(|| account.vp_params.clone())
+        .unwrap_or_default();
+    if let Some(args::SpendingLimit {
+        token,
+        amount,
+        window,
+    }) = spending_limit
+    {
+        let amount = validate_amount(context, *amount, token, tx_args.force)
+            .await?
+            .amount();
+        vp_params.withdrawal_limits.insert(
+            token.clone(),
+            WithdrawalLimit {
+                amount,
+                window: *window,
+            },
+        );
+    }
+    if let Some(bypass_threshold) = *bypass_threshold {
+        if bypass_threshold <= account.threshold
+            || account.get_all_public_keys().len() < bypass_threshold as usize
+        {
+            edisplay_line!(
+                context.io(),
+                "Invalid bypass threshold: it must be higher than the \
+                 account threshold and at most the number of public keys."
+            );
+            if !tx_args.force {
+                return Err(Error::from(
+                    TxSubmitError::InvalidAccountThreshold,
+                ));
+            }
+        }
+        vp_params.bypass_threshold = Some(bypass_threshold);
+    }
+
+    let data = UpdateVpParams {
+        addr: account.address,
+        vp_params,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        fee_amount,
+        &signing_data.fee_payer,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit a custom transaction
 pub async fn build_custom(
     context: &impl Namada,
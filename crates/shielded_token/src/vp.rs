@@ -23,7 +23,7 @@ use namada_core::token;
 use namada_core::token::{Amount, MaspDigitPos};
 use namada_core::uint::I320;
 use namada_state::{
-    ConversionState, OptionExt, ReadConversionState, ResultExt,
+    ConversionState, OptionExt, ReadConversionState, ReadGasSchedule, ResultExt,
 };
 use namada_systems::{governance, ibc, parameters, trans_token};
 use namada_tx::BatchedTxRef;
@@ -61,7 +61,8 @@ impl<'view, 'ctx: 'view, CTX, Params, Gov, Ibc, TransToken, Transfer>
 where
     CTX: VpEnv<'ctx>
         + namada_tx::action::Read<Err = Error>
-        + ReadConversionState,
+        + ReadConversionState
+        + ReadGasSchedule,
     Params: parameters::Read<<CTX as VpEnv<'ctx>>::Pre>,
     Gov: governance::Read<<CTX as VpEnv<'ctx>>::Pre>,
     Ibc: ibc::Read<<CTX as VpEnv<'ctx>>::Post>,
@@ -601,7 +602,9 @@ where
         }
 
         // Verify the proofs
-        verify_shielded_tx(&shielded_tx, |gas| ctx.charge_gas(gas))
+        verify_shielded_tx(&shielded_tx, ctx.gas_schedule(), |gas| {
+            ctx.charge_gas(gas)
+        })
     }
 }
 
@@ -44,9 +44,7 @@ pub use namada_core::storage::{
     BlockResults, EthEventsQueue, Key, KeySeg, TxIndex, EPOCH_TYPE_LENGTH,
 };
 use namada_core::tendermint::merkle::proof::ProofOps;
-use namada_gas::{
-    Gas, MEMORY_ACCESS_GAS_PER_BYTE, STORAGE_ACCESS_GAS_PER_BYTE,
-};
+use namada_gas::{Gas, GasSchedule};
 use namada_merkle_tree::Error as MerkleTreeError;
 pub use namada_merkle_tree::{
     self as merkle_tree, ics23_specs, MembershipProof, MerkleTree,
@@ -59,9 +57,9 @@ pub use namada_storage::conversion_state::{
 pub use namada_storage::types::{KVBytes, PatternIterator, PrefixIterator};
 pub use namada_storage::{
     collections, iter_prefix, iter_prefix_bytes, iter_prefix_with_filter,
-    mockdb, tx_queue, BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch,
-    DbError, DbResult, Error, OptionExt, Result, ResultExt, StorageHasher,
-    StorageRead, StorageWrite, DB,
+    mockdb, tx_history, tx_queue, BlockStateRead, BlockStateWrite, DBIter,
+    DBWriteBatch, DbError, DbResult, Error, OptionExt, Result, ResultExt,
+    StorageHasher, StorageRead, StorageWrite, DB,
 };
 use namada_systems::parameters;
 use thiserror::Error;
@@ -92,13 +90,19 @@ pub trait StateRead: StorageRead + Debug {
     /// Try to charge a given gas amount. Returns an error on out-of-gas.
     fn charge_gas(&self, gas: Gas) -> Result<()>;
 
+    /// Borrow the gas schedule used to price storage accesses
+    fn gas_schedule(&self) -> &GasSchedule {
+        self.write_log().gas_schedule()
+    }
+
     /// Check if the given key is present in storage. Returns the result and the
     /// gas cost.
     fn db_has_key(&self, key: &storage::Key) -> Result<(bool, Gas)> {
         let len = key.len() as u64;
+        let cost = self.gas_schedule().storage_access_gas_per_byte;
         Ok((
             self.db().read_subspace_val(key)?.is_some(),
-            checked!(len * STORAGE_ACCESS_GAS_PER_BYTE)?.into(),
+            checked!(len * cost)?.into(),
         ))
     }
 
@@ -106,15 +110,16 @@ pub trait StateRead: StorageRead + Debug {
     fn db_read(&self, key: &storage::Key) -> Result<(Option<Vec<u8>>, Gas)> {
         tracing::trace!("storage read key {}", key);
 
+        let cost = self.gas_schedule().storage_access_gas_per_byte;
         match self.db().read_subspace_val(key)? {
             Some(v) => {
                 let len = checked!(key.len() + v.len())? as u64;
-                let gas = checked!(len * STORAGE_ACCESS_GAS_PER_BYTE)?;
+                let gas = checked!(len * cost)?;
                 Ok((Some(v), gas.into()))
             }
             None => {
                 let len = key.len() as u64;
-                let gas = checked!(len * STORAGE_ACCESS_GAS_PER_BYTE)?;
+                let gas = checked!(len * cost)?;
                 Ok((None, gas.into()))
             }
         }
@@ -130,9 +135,10 @@ pub trait StateRead: StorageRead + Debug {
         prefix: &Key,
     ) -> Result<(<Self::D as DBIter<'_>>::PrefixIter, Gas)> {
         let len = prefix.len() as u64;
+        let cost = self.gas_schedule().storage_access_gas_per_byte;
         Ok((
-            self.db().iter_prefix(Some(prefix)),
-            checked!(len * STORAGE_ACCESS_GAS_PER_BYTE)?.into(),
+            self.db().try_iter_prefix(Some(prefix))?,
+            checked!(len * cost)?.into(),
         ))
     }
 
@@ -141,6 +147,16 @@ pub trait StateRead: StorageRead + Debug {
         (self.db().iter_results(), Gas::default())
     }
 
+    /// Returns an iterator over the transaction history index of an owner,
+    /// starting from the given block height and transaction index
+    fn db_iter_tx_history(
+        &self,
+        owner: &Address,
+        from: (BlockHeight, u32),
+    ) -> (<Self::D as DBIter<'_>>::PrefixIter, Gas) {
+        (self.db().iter_tx_history(owner, from), Gas::default())
+    }
+
     /// Get the hash of a validity predicate for the given account address and
     /// the gas cost for reading it.
     fn validity_predicate<Params: parameters::Keys>(
@@ -166,33 +182,63 @@ pub trait StateRead: StorageRead + Debug {
         &self,
         height: Option<BlockHeight>,
     ) -> Result<(Option<BlockHeader>, Gas)> {
+        let GasSchedule {
+            memory_access_gas_per_byte,
+            storage_access_gas_per_byte,
+            ..
+        } = *self.gas_schedule();
         match height {
             Some(h) if h == self.in_mem().get_block_height().0 => {
                 let header = self.in_mem().header.clone();
                 let gas = if header.is_some() {
                     let len = BlockHeader::encoded_len() as u64;
-                    checked!(len * MEMORY_ACCESS_GAS_PER_BYTE)?
+                    checked!(len * memory_access_gas_per_byte)?
                 } else {
-                    MEMORY_ACCESS_GAS_PER_BYTE
+                    memory_access_gas_per_byte
                 };
                 Ok((header, gas.into()))
             }
             Some(h) => match self.db().read_block_header(h)? {
                 Some(header) => {
                     let len = BlockHeader::encoded_len() as u64;
-                    let gas = checked!(len * STORAGE_ACCESS_GAS_PER_BYTE)?;
+                    let gas = checked!(len * storage_access_gas_per_byte)?;
                     Ok((Some(header), gas.into()))
                 }
-                None => Ok((None, STORAGE_ACCESS_GAS_PER_BYTE.into())),
+                None => Ok((None, storage_access_gas_per_byte.into())),
             },
             None => Ok((
                 self.in_mem().header.clone(),
-                STORAGE_ACCESS_GAS_PER_BYTE.into(),
+                storage_access_gas_per_byte.into(),
             )),
         }
     }
 }
 
+/// Able to read the insertion heights of pending transfers from the Ethereum
+/// bridge pool Merkle tree, along with the committed state its roots are
+/// signed over.
+pub trait ReadBridgePoolTree {
+    /// Get the height at which the pending transfer under the given key was
+    /// committed to the Bridge pool tree. Returns `None` if the transfer has
+    /// not been committed to the tree, e.g. when it was added in the current
+    /// block.
+    fn bridge_pool_insertion_height(
+        &self,
+        key: &Key,
+    ) -> Result<Option<BlockHeight>>;
+
+    /// Read the value under the given key as of the last committed block,
+    /// ignoring the changes made in the current block.
+    fn read_last_committed_bytes(&self, key: &Key) -> Result<Option<Vec<u8>>>;
+}
+
+/// Able to read the gas schedule used to price the operations of the current
+/// block.
+pub trait ReadGasSchedule {
+    /// Borrow the gas schedule
+    fn gas_schedule(&self) -> &GasSchedule;
+}
+
 /// Common trait for write log, DB and in-memory state.
 pub trait State: StateRead + StorageWrite {
     /// Borrow mutable `WriteLog`
@@ -329,7 +375,7 @@ macro_rules! impl_storage_read {
 
             fn get_pred_epochs(&self) -> namada_storage::Result<Epochs> {
                 self.charge_gas(
-                    namada_gas::STORAGE_ACCESS_GAS_PER_BYTE.into(),
+                    self.gas_schedule().storage_access_gas_per_byte.into(),
                 ).into_storage_result()?;
                 Ok(self.in_mem().block.pred_epochs.clone())
             }
@@ -338,14 +384,14 @@ macro_rules! impl_storage_read {
                 &self,
             ) -> std::result::Result<storage::TxIndex, namada_storage::Error> {
                 self.charge_gas(
-                    namada_gas::STORAGE_ACCESS_GAS_PER_BYTE.into(),
+                    self.gas_schedule().storage_access_gas_per_byte.into(),
                 ).into_storage_result()?;
                 Ok(self.in_mem().tx_index)
             }
 
             fn get_native_token(&self) -> namada_storage::Result<Address> {
                 self.charge_gas(
-                    namada_gas::STORAGE_ACCESS_GAS_PER_BYTE.into(),
+                    self.gas_schedule().storage_access_gas_per_byte.into(),
                 ).into_storage_result()?;
                 Ok(self.in_mem().native_token.clone())
             }
@@ -472,15 +518,16 @@ pub fn iter_prefix_pre<'a, D>(
 where
     D: DB + for<'iter> DBIter<'iter>,
 {
-    let storage_iter = db.iter_prefix(Some(prefix)).peekable();
+    let storage_iter = db.try_iter_prefix(Some(prefix))?.peekable();
     let write_log_iter = write_log.iter_prefix_pre(prefix).peekable();
     let len = prefix.len() as u64;
+    let cost = write_log.gas_schedule().storage_access_gas_per_byte;
     Ok((
         PrefixIter::<D> {
             storage_iter,
             write_log_iter,
         },
-        checked!(len * STORAGE_ACCESS_GAS_PER_BYTE)?.into(),
+        checked!(len * cost)?.into(),
     ))
 }
 
@@ -497,15 +544,16 @@ pub fn iter_prefix_post<'a, D>(
 where
     D: DB + for<'iter> DBIter<'iter>,
 {
-    let storage_iter = db.iter_prefix(Some(prefix)).peekable();
+    let storage_iter = db.try_iter_prefix(Some(prefix))?.peekable();
     let write_log_iter = write_log.iter_prefix_post(prefix).peekable();
     let len = prefix.len() as u64;
+    let cost = write_log.gas_schedule().storage_access_gas_per_byte;
     Ok((
         PrefixIter::<D> {
             storage_iter,
             write_log_iter,
         },
-        checked!(len * STORAGE_ACCESS_GAS_PER_BYTE)?.into(),
+        checked!(len * cost)?.into(),
     ))
 }
 
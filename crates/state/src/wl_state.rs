@@ -27,7 +27,7 @@ use crate::{
     is_pending_transfer_key, DBIter, Epoch, Error, Hash, Key, KeySeg,
     LastBlock, MembershipProof, MerkleTree, MerkleTreeError, ProofOps, Result,
     State, StateError, StateRead, StorageHasher, StoreType, TxWrites, DB,
-    EPOCH_SWITCH_BLOCKS_DELAY, STORAGE_ACCESS_GAS_PER_BYTE,
+    EPOCH_SWITCH_BLOCKS_DELAY,
 };
 
 /// Owned state with full R/W access.
@@ -147,21 +147,39 @@ where
         storage_read_past_height_limit: Option<u64>,
         diff_key_filter: fn(&storage::Key) -> bool,
     ) -> Self {
-        let write_log = WriteLog::default();
         let db = D::open(db_path, cache);
+        let mut state = Self::new(
+            db,
+            chain_id,
+            native_token,
+            storage_read_past_height_limit,
+            diff_key_filter,
+        );
+        state.load_last_state();
+        state
+    }
+
+    /// Instantiate a full-access state on top of an already opened DB. Unlike
+    /// [`FullAccessState::open`], the last state is not loaded from the DB.
+    pub fn new(
+        db: D,
+        chain_id: ChainId,
+        native_token: Address,
+        storage_read_past_height_limit: Option<u64>,
+        diff_key_filter: fn(&storage::Key) -> bool,
+    ) -> Self {
+        let write_log = WriteLog::default();
         let in_mem = InMemory::new(
             chain_id,
             native_token,
             storage_read_past_height_limit,
         );
-        let mut state = Self(WlState {
+        Self(WlState {
             write_log,
             db,
             in_mem,
             diff_key_filter,
-        });
-        state.load_last_state();
-        state
+        })
     }
 
     #[allow(dead_code)]
@@ -314,6 +332,16 @@ where
 
         debug_assert!(self.0.write_log.replay_protection.is_empty());
 
+        // The transaction history index
+        let height = self.in_mem.block.height;
+        for (owner, tx_index, hash) in
+            std::mem::take(&mut self.0.write_log.tx_history)
+        {
+            self.db.write_tx_history_entry(
+                batch, &owner, height, tx_index, &hash,
+            )?;
+        }
+
         if let Some(address_gen) = self.0.write_log.block_address_gen.take() {
             self.0.in_mem.address_gen = address_gen
         }
@@ -716,7 +744,9 @@ where
     /// Borrow in-memory state and DB handle with a mutable temporary write-log.
     pub fn with_temp_write_log(&self) -> TempWlState<'_, D, H> {
         TempWlState {
-            write_log: WriteLog::default(),
+            write_log: WriteLog::with_gas_schedule(
+                self.write_log.gas_schedule().clone(),
+            ),
             db: &self.db,
             in_mem: &self.in_mem,
         }
@@ -740,7 +770,9 @@ where
         &self,
     ) -> TempWlState<'static, D, H> {
         TempWlState {
-            write_log: WriteLog::default(),
+            write_log: WriteLog::with_gas_schedule(
+                self.write_log.gas_schedule().clone(),
+            ),
             db: &*(&self.db as *const _),
             in_mem: &*(&self.in_mem as *const _),
         }
@@ -812,6 +844,7 @@ where
                 return Ok((None, Gas::default()));
             }
 
+            let cost = self.gas_schedule().storage_access_gas_per_byte;
             match self.db().read_subspace_val_with_height(
                 key,
                 height,
@@ -819,17 +852,11 @@ where
             )? {
                 Some(v) => {
                     let gas = checked!(key.len() + v.len())? as u64;
-                    Ok((
-                        Some(v),
-                        checked!(gas * STORAGE_ACCESS_GAS_PER_BYTE)?.into(),
-                    ))
+                    Ok((Some(v), checked!(gas * cost)?.into()))
                 }
                 None => {
                     let gas = key.len() as u64;
-                    Ok((
-                        None,
-                        checked!(gas * STORAGE_ACCESS_GAS_PER_BYTE)?.into(),
-                    ))
+                    Ok((None, checked!(gas * cost)?.into()))
                 }
             }
         }
@@ -867,8 +894,8 @@ where
         }
 
         let len = value.len();
-        let gas =
-            (key.len() + len) as u64 * namada_gas::STORAGE_WRITE_GAS_PER_BYTE;
+        let gas = (key.len() + len) as u64
+            * self.gas_schedule().storage_write_gas_per_byte;
         let size_diff = self.db.write_subspace_val(
             self.in_mem.block.height,
             key,
@@ -906,7 +933,7 @@ where
             )?;
         }
         let gas = (key.len() + deleted_bytes_len as usize) as u64
-            * namada_gas::STORAGE_WRITE_GAS_PER_BYTE;
+            * self.gas_schedule().storage_write_gas_per_byte;
         Ok((gas, deleted_bytes_len))
     }
 
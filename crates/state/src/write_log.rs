@@ -10,10 +10,7 @@ use namada_core::collections::{HashMap, HashSet};
 use namada_core::hash::Hash;
 use namada_core::{arith, storage};
 use namada_events::{Event, EventToEmit, EventType};
-use namada_gas::{
-    Gas, MEMORY_ACCESS_GAS_PER_BYTE, STORAGE_DELETE_GAS_PER_BYTE,
-    STORAGE_WRITE_GAS_PER_BYTE,
-};
+use namada_gas::{Gas, GasSchedule};
 use patricia_tree::map::StringPatriciaMap;
 use thiserror::Error;
 
@@ -151,6 +148,13 @@ pub struct WriteLog {
     /// managed in the normal write log because we need to commit them
     /// sometimes even on batch failure
     pub(crate) replay_protection: HashSet<Hash>,
+    /// Entries of the transaction history index of the current block, as the
+    /// owner, the index of the transaction in the block and its hash. These
+    /// are not part of the merkle tree.
+    pub(crate) tx_history: Vec<(Address, u32, Hash)>,
+    /// The gas schedule used to price the accesses to the write log and to
+    /// the storage
+    pub(crate) gas_schedule: GasSchedule,
 }
 
 /// Write log prefix iterator
@@ -177,11 +181,33 @@ impl Default for WriteLog {
             batch_write_log: Vec::with_capacity(5),
             tx_write_log: Default::default(),
             replay_protection: HashSet::with_capacity(1_000),
+            tx_history: Vec::new(),
+            gas_schedule: GasSchedule::default(),
         }
     }
 }
 
 impl WriteLog {
+    /// Create a new empty write log that prices storage accesses with the
+    /// given gas schedule
+    pub fn with_gas_schedule(gas_schedule: GasSchedule) -> Self {
+        Self {
+            gas_schedule,
+            ..Default::default()
+        }
+    }
+
+    /// Get the gas schedule used to price storage accesses
+    pub fn gas_schedule(&self) -> &GasSchedule {
+        &self.gas_schedule
+    }
+
+    /// Replace the gas schedule used to price storage accesses. The new
+    /// schedule applies to all the subsequent operations.
+    pub fn set_gas_schedule(&mut self, gas_schedule: GasSchedule) {
+        self.gas_schedule = gas_schedule;
+    }
+
     /// Read a non-temp value at the given key and return the value and the gas
     /// cost, returns [`None`] if the key is not present in the write log
     pub fn read(
@@ -218,12 +244,21 @@ impl WriteLog {
                 } as u64;
                 Ok((
                     Some(v),
-                    checked!(gas * MEMORY_ACCESS_GAS_PER_BYTE)?.into(),
+                    checked!(
+                        gas * self.gas_schedule.memory_access_gas_per_byte
+                    )?
+                    .into(),
                 ))
             }
             None => {
                 let gas = key.len() as u64;
-                Ok((None, checked!(gas * MEMORY_ACCESS_GAS_PER_BYTE)?.into()))
+                Ok((
+                    None,
+                    checked!(
+                        gas * self.gas_schedule.memory_access_gas_per_byte
+                    )?
+                    .into(),
+                ))
             }
         }
     }
@@ -255,12 +290,19 @@ impl WriteLog {
                 } as u64;
                 return Ok((
                     Some(v),
-                    checked!(gas * MEMORY_ACCESS_GAS_PER_BYTE)?.into(),
+                    checked!(
+                        gas * self.gas_schedule.memory_access_gas_per_byte
+                    )?
+                    .into(),
                 ));
             }
         }
         let gas = key.len() as u64;
-        Ok((None, checked!(gas * MEMORY_ACCESS_GAS_PER_BYTE)?.into()))
+        Ok((
+            None,
+            checked!(gas * self.gas_schedule.memory_access_gas_per_byte)?
+                .into(),
+        ))
     }
 
     /// Read a temp value at the given key and return the value and the gas
@@ -277,12 +319,21 @@ impl WriteLog {
 
                 Ok((
                     Some(value),
-                    checked!(gas * MEMORY_ACCESS_GAS_PER_BYTE)?.into(),
+                    checked!(
+                        gas * self.gas_schedule.memory_access_gas_per_byte
+                    )?
+                    .into(),
                 ))
             }
             None => {
                 let gas = key.len() as u64;
-                Ok((None, checked!(gas * MEMORY_ACCESS_GAS_PER_BYTE)?.into()))
+                Ok((
+                    None,
+                    checked!(
+                        gas * self.gas_schedule.memory_access_gas_per_byte
+                    )?
+                    .into(),
+                ))
             }
         }
     }
@@ -332,7 +383,8 @@ impl WriteLog {
 
         let gas = checked!(key.len() + len)? as u64;
         Ok((
-            checked!(gas * STORAGE_WRITE_GAS_PER_BYTE)?.into(),
+            checked!(gas * self.gas_schedule.storage_write_gas_per_byte)?
+                .into(),
             size_diff,
         ))
     }
@@ -412,7 +464,8 @@ impl WriteLog {
         // accessing storage
         let gas = checked!(key.len() + len)? as u64;
         Ok((
-            checked!(gas * MEMORY_ACCESS_GAS_PER_BYTE)?.into(),
+            checked!(gas * self.gas_schedule.memory_access_gas_per_byte)?
+                .into(),
             size_diff,
         ))
     }
@@ -447,7 +500,8 @@ impl WriteLog {
             .and_then(i64::checked_neg)
             .ok_or(Error::SizeDiffOverflow)?;
         Ok((
-            checked!(gas * STORAGE_DELETE_GAS_PER_BYTE)?.into(),
+            checked!(gas * self.gas_schedule.storage_delete_gas_per_byte)?
+                .into(),
             size_diff,
         ))
     }
@@ -493,7 +547,7 @@ impl WriteLog {
             .len()
             .checked_add(vp_code_hash.len())
             .expect("Cannot overflow")) as u64)
-            .checked_mul(STORAGE_WRITE_GAS_PER_BYTE)
+            .checked_mul(self.gas_schedule.storage_write_gas_per_byte)
             .expect("Canno overflow");
         self.tx_write_log
             .write_log
@@ -505,7 +559,8 @@ impl WriteLog {
     /// overflow.
     pub fn emit_event<E: EventToEmit>(&mut self, event: E) -> Option<Gas> {
         let event = event.into();
-        let gas_cost = event.emission_gas_cost(MEMORY_ACCESS_GAS_PER_BYTE);
+        let gas_cost = event
+            .emission_gas_cost(self.gas_schedule.memory_access_gas_per_byte);
         if gas_cost.as_ref().is_some() {
             let event_type = event.kind().to_string();
             if !self.tx_write_log.events.tree.contains_key(&event_type) {
@@ -748,6 +803,17 @@ impl WriteLog {
         Ok(())
     }
 
+    /// Record that the transaction at the given index of the current block
+    /// concerns the owner, to be written to the transaction history index
+    pub fn write_tx_history(
+        &mut self,
+        owner: Address,
+        tx_index: u32,
+        hash: Hash,
+    ) {
+        self.tx_history.push((owner, tx_index, hash));
+    }
+
     /// Remove the transaction hash because redundant
     pub(crate) fn redundant_tx_hash(&mut self, hash: &Hash) -> Result<()> {
         if !self.replay_protection.swap_remove(hash) {
@@ -765,6 +831,11 @@ impl WriteLog {
 mod tests {
     use assert_matches::assert_matches;
     use namada_core::address;
+    use namada_core::chain::BlockHeight;
+    use namada_gas::{
+        MEMORY_ACCESS_GAS_PER_BYTE, STORAGE_DELETE_GAS_PER_BYTE,
+        STORAGE_WRITE_GAS_PER_BYTE,
+    };
     use pretty_assertions::assert_eq;
     use proptest::prelude::*;
 
@@ -1092,6 +1163,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tx_history_commit() {
+        let mut state = crate::testing::TestState::default();
+        let owner = address::testing::established_address_1();
+        let other_owner = address::testing::established_address_2();
+
+        {
+            let write_log = state.write_log_mut();
+            write_log.write_tx_history(
+                owner.clone(),
+                2,
+                Hash::sha256("tx2".as_bytes()),
+            );
+            write_log.write_tx_history(
+                other_owner.clone(),
+                2,
+                Hash::sha256("tx2".as_bytes()),
+            );
+            write_log.write_tx_history(
+                owner.clone(),
+                1,
+                Hash::sha256("tx1".as_bytes()),
+            );
+        }
+
+        // commit a block
+        state.commit_block().expect("commit failed");
+        assert!(state.write_log.tx_history.is_empty());
+
+        let read_history = |owner: &Address| {
+            let (iter, _gas) =
+                state.db_iter_tx_history(owner, (BlockHeight(0), 0));
+            iter.map(|(_key, value, _gas)| {
+                namada_core::decode::<Hash>(value).expect("decode failed")
+            })
+            .collect::<Vec<_>>()
+        };
+        // The entries are ordered by their index in the block
+        assert_eq!(
+            read_history(&owner),
+            vec![
+                Hash::sha256("tx1".as_bytes()),
+                Hash::sha256("tx2".as_bytes())
+            ]
+        );
+        assert_eq!(
+            read_history(&other_owner),
+            vec![Hash::sha256("tx2".as_bytes())]
+        );
+    }
+
     // Test that writing a value on top of a temporary write is not allowed
     #[test]
     fn test_write_after_temp_disallowed() {
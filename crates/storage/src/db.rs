@@ -2,7 +2,7 @@ use std::fmt::Debug;
 use std::num::TryFromIntError;
 
 use itertools::Either;
-use namada_core::address::EstablishedAddressGen;
+use namada_core::address::{Address, EstablishedAddressGen};
 use namada_core::chain::{BlockHeader, BlockHeight, Epoch, Epochs};
 use namada_core::hash::{Error as HashError, Hash};
 use namada_core::storage::{BlockResults, DbColFam, EthEventsQueue, Key};
@@ -282,6 +282,16 @@ pub trait DB: Debug {
         batch: &mut Self::WriteBatch,
     ) -> Result<()>;
 
+    /// Write an entry of the transaction history index
+    fn write_tx_history_entry(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        owner: &Address,
+        height: BlockHeight,
+        tx_index: u32,
+        hash: &Hash,
+    ) -> Result<()>;
+
     /// Prune non-persisted diffs that are only kept for one block for rollback
     fn prune_non_persisted_diffs(
         &mut self,
@@ -324,6 +334,16 @@ pub trait DBIter<'iter> {
     /// ordered by the storage keys.
     fn iter_prefix(&'iter self, prefix: Option<&Key>) -> Self::PrefixIter;
 
+    /// Same as [`DBIter::iter_prefix`], but returns an error if the values of
+    /// the prefix cannot be read, for DBs that read them from a fallible
+    /// source.
+    fn try_iter_prefix(
+        &'iter self,
+        prefix: Option<&Key>,
+    ) -> Result<Self::PrefixIter> {
+        Ok(self.iter_prefix(prefix))
+    }
+
     /// WARNING: This only works for values that have been committed to DB.
     /// To be able to see values written or deleted, but not yet committed,
     /// use the `StorageWithWriteLog`.
@@ -355,6 +375,15 @@ pub trait DBIter<'iter> {
 
     /// Read replay protection storage from the current bucket
     fn iter_current_replay_protection(&'iter self) -> Self::PrefixIter;
+
+    /// Read the transaction history index of an owner, ordered by block
+    /// height and transaction index, starting from the given block height and
+    /// transaction index. Empty if the DB doesn't have the index.
+    fn iter_tx_history(
+        &'iter self,
+        owner: &Address,
+        from: (BlockHeight, u32),
+    ) -> Self::PrefixIter;
 }
 
 /// Atomic batch write.
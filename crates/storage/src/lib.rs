@@ -29,6 +29,7 @@ pub mod conversion_state;
 mod db;
 mod error;
 pub mod mockdb;
+pub mod tx_history;
 pub mod tx_queue;
 pub mod types;
 
@@ -66,6 +67,32 @@ pub trait StorageRead {
     /// Storage read raw bytes. It will try to read from the storage.
     fn read_bytes(&self, key: &Key) -> Result<Option<Vec<u8>>>;
 
+    /// Storage read Borsh encoded values of many keys at once. The values are
+    /// returned in the order of the given keys.
+    fn read_many<T: BorshDeserialize>(
+        &self,
+        keys: &[Key],
+    ) -> Result<Vec<Option<T>>> {
+        self.read_many_bytes(keys)?
+            .into_iter()
+            .map(|bytes| {
+                bytes
+                    .map(|bytes| {
+                        T::try_from_slice(&bytes).into_storage_result()
+                    })
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// Storage read raw bytes of many keys at once. The values are returned
+    /// in the order of the given keys. By default, the keys are read one by
+    /// one, the wasm environments override it to read all the keys with a
+    /// single host call.
+    fn read_many_bytes(&self, keys: &[Key]) -> Result<Vec<Option<Vec<u8>>>> {
+        keys.iter().map(|key| self.read_bytes(key)).collect()
+    }
+
     /// Storage `has_key` in. It will try to read from the storage.
     fn has_key(&self, key: &Key) -> Result<bool>;
 
@@ -7,6 +7,7 @@ use std::collections::{btree_map, BTreeMap};
 use std::path::Path;
 
 use itertools::Either;
+use namada_core::address::Address;
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::chain::{BlockHeader, BlockHeight, Epoch};
 use namada_core::hash::Hash;
@@ -23,6 +24,7 @@ use regex::Regex;
 use crate::db::{
     BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error, Result, DB,
 };
+use crate::tx_history;
 use crate::types::{KVBytes, PatternIterator, PrefixIterator};
 
 const SUBSPACE_CF: &str = "subspace";
@@ -589,6 +591,22 @@ impl DB for MockDB {
         }
     }
 
+    fn write_tx_history_entry(
+        &mut self,
+        _batch: &mut Self::WriteBatch,
+        owner: &Address,
+        height: BlockHeight,
+        tx_index: u32,
+        hash: &Hash,
+    ) -> Result<()> {
+        let key = Key::parse("tx_history")
+            .map_err(Error::KeyError)?
+            .join(&tx_history::key(owner, height, tx_index));
+
+        self.0.borrow_mut().insert(key.to_string(), encode(hash));
+        Ok(())
+    }
+
     fn move_current_replay_protection_entries(
         &mut self,
         _batch: &mut Self::WriteBatch,
@@ -742,6 +760,22 @@ impl<'iter> DBIter<'iter> for MockDB {
         let iter = self.0.borrow().clone().into_iter();
         MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
     }
+
+    fn iter_tx_history(
+        &'iter self,
+        owner: &Address,
+        (from_height, from_tx_index): (BlockHeight, u32),
+    ) -> Self::PrefixIter {
+        let stripped_prefix =
+            format!("tx_history/{}/", tx_history::prefix(owner));
+        let prefix = stripped_prefix.clone();
+        let from = format!(
+            "tx_history/{}",
+            tx_history::key(owner, from_height, from_tx_index)
+        );
+        let iter = self.0.borrow().clone().split_off(&from).into_iter();
+        MockPrefixIterator::new(MockIterator { prefix, iter }, stripped_prefix)
+    }
 }
 
 /// A prefix iterator base for the [`MockPrefixIterator`].
@@ -0,0 +1,53 @@
+//! Transaction history index keys
+//!
+//! The index maps the addresses of transparent accounts to the hashes of the
+//! transactions that moved their tokens. The entries of an owner are ordered
+//! by the height of the block and the index of the transaction in the block.
+//!
+//! Every entry is also listed under the height of its block, which allows to
+//! delete the entries of a block with a range deletion on rollback.
+
+use namada_core::address::Address;
+use namada_core::chain::BlockHeight;
+use namada_core::storage::{DbKeySeg, Key, KeySeg};
+
+/// The key segment of the entries listed by block height
+const BLOCK_SEG: &str = "block";
+
+/// Get the prefix of all the transaction history entries of an owner
+pub fn prefix(owner: &Address) -> Key {
+    Key::from(owner.to_db_key())
+}
+
+/// Get the key of a transaction history entry. The integer key segments
+/// preserve the sort order of the height and the transaction index.
+pub fn key(owner: &Address, height: BlockHeight, tx_index: u32) -> Key {
+    prefix(owner).with_segment(height.0).with_segment(tx_index)
+}
+
+/// Get the prefix of the listing of the entries written in the block at the
+/// given height
+pub fn block_prefix(height: BlockHeight) -> Key {
+    Key::from(DbKeySeg::StringSeg(BLOCK_SEG.to_string())).with_segment(height.0)
+}
+
+/// Get the key of the listing of an entry under the height of its block. Its
+/// value is the key of the entry.
+pub fn block_key(owner: &Address, height: BlockHeight, tx_index: u32) -> Key {
+    block_prefix(height)
+        .with_segment(owner.clone())
+        .with_segment(tx_index)
+}
+
+/// Parse the height and the transaction index from the key of an entry,
+/// stripped of the owner prefix
+pub fn parse_stripped_key(key: &str) -> Option<(BlockHeight, u32)> {
+    let key = Key::parse(key).ok()?;
+    match &key.segments[..] {
+        [DbKeySeg::StringSeg(height), DbKeySeg::StringSeg(tx_index)] => Some((
+            BlockHeight(u64::parse(height.clone()).ok()?),
+            u32::parse(tx_index.clone()).ok()?,
+        )),
+        _ => None,
+    }
+}
@@ -236,6 +236,7 @@ async fn test_roundtrip_eth_transfer() -> Result<()> {
         }],
         valid_transfers_map: vec![true],
         relayer: berthas_addr.clone(),
+        relay_cost: Default::default(),
     };
 
     client.send(&transfers).await?;
@@ -452,6 +453,7 @@ async fn test_bridge_pool_e2e() {
         }],
         valid_transfers_map: vec![true],
         relayer: berthas_addr,
+        relay_cost: Default::default(),
     };
 
     client.send(&transfers).await.unwrap();
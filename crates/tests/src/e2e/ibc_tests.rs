@@ -1088,6 +1088,162 @@ fn ibc_rate_limit() -> Result<()> {
     Ok(())
 }
 
+/// IBC transfers between two Namada chains relayed with `namadar ibc-relay`.
+/// The channel is created with Hermes, which doesn't relay any packet.
+/// 1. Transfer from chain A to chain B
+///   - Relay the packet to chain B
+///   - Relay the acknowledgement back to chain A
+/// 2. Transfer from chain A to chain B which times out
+///   - Relay the timeout back to chain A, which refunds the transfer
+#[test]
+fn ibc_relay_between_namada_chains() -> Result<()> {
+    let update_genesis = |port_offset: u16| {
+        move |mut genesis: templates::All<templates::Unvalidated>,
+              base_dir: &Path| {
+            genesis.parameters.ibc_params.default_mint_limit =
+                Amount::max_signed();
+            genesis
+                .parameters
+                .ibc_params
+                .default_per_epoch_throughput_limit = Amount::max_signed();
+            setup::set_validators(1, genesis, base_dir, |_| port_offset, vec![])
+        }
+    };
+    let (ledger_a, test_a) = run_namada(update_genesis(0))?;
+    let _bg_ledger_a = ledger_a.background();
+    // Offset the ports of the second chain to avoid conflicts
+    let (ledger_b, test_b) =
+        run_namada(update_genesis(setup::default_port_offset(10)))?;
+    let _bg_ledger_b = ledger_b.background();
+
+    setup_hermes(&test_a, &test_b)?;
+    let port_id: PortId = FT_PORT_ID.parse().unwrap();
+    let (channel_id_a, channel_id_b) =
+        create_channel_with_hermes(&test_a, &test_b, &port_id, &port_id)?;
+
+    // The relayer needs the config and the wallet of the counterparty chain
+    // in its base dir
+    fs_extra::dir::copy(
+        test_b.get_chain_dir(Who::NonValidator),
+        test_a.test_dir.path(),
+        &fs_extra::dir::CopyOptions::default(),
+    )?;
+
+    // 1. Transfer 100 APFEL from chain A to chain B
+    let receiver = find_address(&test_b, ALBERT)?.to_string();
+    transfer(
+        &test_a,
+        ALBERT,
+        &receiver,
+        APFEL,
+        100,
+        Some(ALBERT_KEY),
+        &port_id,
+        &channel_id_a,
+        None,
+        None,
+        None,
+    )?;
+    let mut relayer = relay_with_namadar(&test_a, &test_b, &channel_id_a)?;
+    relayer.exp_string(&format!(
+        "Relayed 1 packet(s) to {port_id}/{channel_id_b}"
+    ))?;
+    relayer.assert_success();
+
+    let token_addr = find_address(&test_a, APFEL)?;
+    let ibc_denom_on_b = format!("{port_id}/{channel_id_b}/{token_addr}");
+    check_balance(&test_b, ALBERT, &ibc_denom_on_b, 100)?;
+    check_balance(&test_a, ALBERT, APFEL, 999_900)?;
+
+    // Relay the acknowledgement written on chain B
+    sleep(5);
+    let mut relayer = relay_with_namadar(&test_a, &test_b, &channel_id_a)?;
+    relayer.exp_string(&format!(
+        "Relayed 1 acknowledgement(s) and 0 timeout(s) to \
+         {port_id}/{channel_id_a}"
+    ))?;
+    relayer.assert_success();
+
+    // 2. Transfer 10 APFEL from chain A to chain B which times out
+    transfer(
+        &test_a,
+        ALBERT,
+        &receiver,
+        APFEL,
+        10,
+        Some(ALBERT_KEY),
+        &port_id,
+        &channel_id_a,
+        Some(Duration::new(5, 0)),
+        None,
+        None,
+    )?;
+    check_balance(&test_a, ALBERT, APFEL, 999_890)?;
+    // Wait for the timeout on chain B
+    sleep(10);
+    let mut relayer = relay_with_namadar(&test_a, &test_b, &channel_id_a)?;
+    relayer.exp_string(&format!(
+        "Relayed 0 acknowledgement(s) and 1 timeout(s) to \
+         {port_id}/{channel_id_a}"
+    ))?;
+    relayer.assert_success();
+
+    // The timed out transfer has been refunded
+    check_balance(&test_a, ALBERT, APFEL, 999_900)?;
+    check_balance(&test_b, ALBERT, &ibc_denom_on_b, 100)?;
+
+    Ok(())
+}
+
+fn run_namada(
+    update_genesis: impl FnMut(
+        templates::All<templates::Unvalidated>,
+        &Path,
+    ) -> templates::All<templates::Unvalidated>,
+) -> Result<(NamadaCmd, Test)> {
+    let test = setup::network(update_genesis, None)?;
+
+    set_ethereum_bridge_mode(
+        &test,
+        &test.net.chain_id,
+        Who::Validator(0),
+        ethereum_bridge::ledger::Mode::Off,
+        None,
+    );
+
+    let ledger = start_namada_ledger_node_wait_wasm(&test, Some(0), Some(40))?;
+
+    Ok((ledger, test))
+}
+
+/// Relay once over the transfer channel between two Namada chains with the
+/// relayer of `namadar`.
+fn relay_with_namadar(
+    test_a: &Test,
+    test_b: &Test,
+    channel_id_a: &ChannelId,
+) -> Result<NamadaCmd> {
+    let rpc_a = get_actor_rpc(test_a, Who::Validator(0));
+    let rpc_b = get_actor_rpc(test_b, Who::Validator(0));
+    let channel_id_a = channel_id_a.to_string();
+    let args = [
+        "ibc-relay",
+        "--counterparty-chain-id",
+        test_b.net.chain_id.as_str(),
+        "--counterparty-node",
+        &rpc_b,
+        "--port-id",
+        FT_PORT_ID,
+        "--channel-id",
+        &channel_id_a,
+        "--signing-keys",
+        ALBERT_KEY,
+        "--node",
+        &rpc_a,
+    ];
+    run!(test_a, Bin::Relayer, args, Some(120))
+}
+
 fn run_namada_cosmos(
     chain_type: CosmosChainType,
     mut update_genesis: impl FnMut(
@@ -87,6 +87,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tx_read_many() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let key_1 = storage::Key::parse("key_1").unwrap();
+        let key_2 = storage::Key::parse("key_2").unwrap();
+        let key_3 = storage::Key::parse("key_3").unwrap();
+        tx::ctx().write(&key_1, "one".to_string()).unwrap();
+        tx::ctx().write(&key_3, "three".to_string()).unwrap();
+
+        let keys = [key_1.clone(), key_2, key_3, key_1];
+        let read_values: Vec<Option<String>> =
+            tx::ctx().read_many(&keys).unwrap();
+        assert_eq!(
+            read_values,
+            vec![
+                Some("one".to_string()),
+                None,
+                Some("three".to_string()),
+                Some("one".to_string())
+            ],
+            "The values should be read in the order of the keys"
+        );
+        let read_values = tx::ctx().read_many_bytes(&[]).unwrap();
+        assert!(read_values.is_empty());
+    }
+
     #[test]
     fn test_tx_has_key() {
         // The environment must be initialized first
@@ -463,6 +491,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_vp_read_many() {
+        let mut tx_env = TestTxEnv::default();
+
+        let addr = address::testing::established_address_1();
+        let addr_key = storage::Key::from(addr.to_db_key());
+
+        // Write some value to storage and commit it
+        let existing_key = addr_key.join(&Key::parse("existing_key").unwrap());
+        tx_env.state.write(&existing_key, "pre").unwrap();
+        tx_env.state.commit_tx_batch();
+
+        let new_key = addr_key.join(&Key::parse("new_key").unwrap());
+
+        // Initialize the VP environment via a transaction
+        vp_host_env::init_from_tx(addr, tx_env, |_addr| {
+            tx::ctx().write(&existing_key, "post").unwrap();
+            tx::ctx().write(&new_key, "new").unwrap();
+        });
+
+        let keys = [existing_key, new_key];
+        let pre_values: Vec<Option<String>> =
+            vp::CTX.pre().read_many(&keys).unwrap();
+        assert_eq!(pre_values, vec![Some("pre".to_string()), None]);
+        let post_values: Vec<Option<String>> =
+            vp::CTX.post().read_many(&keys).unwrap();
+        assert_eq!(
+            post_values,
+            vec![Some("post".to_string()), Some("new".to_string())]
+        );
+    }
+
     #[test]
     fn test_vp_iter_prefix() {
         let mut tx_env = TestTxEnv::default();
@@ -473,6 +473,7 @@ mod native_tx_host_env {
     // Implement all the exported functions from
     // [`namada_vm_env::imports::tx`] `extern "C"` section.
     native_host_fn!(tx_read(key_ptr: u64, key_len: u64) -> i64);
+    native_host_fn!(tx_read_many(keys_ptr: u64, keys_len: u64) -> i64);
     native_host_fn!(tx_read_temp(key_ptr: u64, key_len: u64) -> i64);
     native_host_fn!(tx_result_buffer(result_ptr: u64));
     native_host_fn!(tx_has_key(key_ptr: u64, key_len: u64) -> i64);
@@ -536,6 +537,12 @@ mod native_tx_host_env {
         buf_ptr: u64,
         buf_len: u64,
     ));
+    native_host_fn!(tx_call(
+        code_hash_ptr: u64,
+        code_hash_len: u64,
+        data_ptr: u64,
+        data_len: u64
+    ) -> i64);
 }
 
 #[cfg(test)]
@@ -40,10 +40,30 @@ where
     S: StorageRead + StorageWrite,
     F: FnOnce(token::Amount) -> Result<token::Amount>,
 {
-    let key = balance_key(token, owner);
-    let balance = storage.read::<token::Amount>(&key)?.unwrap_or_default();
+    let balance = read_balance(storage, token, owner)?;
     let new_balance = f(balance)?;
-    storage.write(&key, new_balance)
+    write_balance(storage, token, owner, balance, new_balance)
+}
+
+/// Write the new balance of a given token and owner. A decrease of the
+/// balance is recorded as a debit of the owner, so that the owner's VP can
+/// check it against the limits of the account's VP parameters, whichever
+/// transaction or protocol action debits the owner.
+pub(crate) fn write_balance<S>(
+    storage: &mut S,
+    token: &Address,
+    owner: &Address,
+    balance: token::Amount,
+    new_balance: token::Amount,
+) -> Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&balance_key(token, owner), new_balance)?;
+    if let Some(debited) = balance.checked_sub(new_balance) {
+        namada_account::record_debit(storage, owner, token, debited)?;
+    }
+    Ok(())
 }
 
 /// Increment the balance of a given token and owner.
@@ -239,16 +259,26 @@ where
         return Ok(());
     }
 
-    let src_key = balance_key(token, src);
     let src_balance = read_balance(storage, token, src)?;
     match src_balance.checked_sub(amount) {
         Some(new_src_balance) => {
-            let dest_key = balance_key(token, dest);
             let dest_balance = read_balance(storage, token, dest)?;
             match dest_balance.checked_add(amount) {
                 Some(new_dest_balance) => {
-                    storage.write(&src_key, new_src_balance)?;
-                    storage.write(&dest_key, new_dest_balance)
+                    write_balance(
+                        storage,
+                        token,
+                        src,
+                        src_balance,
+                        new_src_balance,
+                    )?;
+                    write_balance(
+                        storage,
+                        token,
+                        dest,
+                        dest_balance,
+                        new_dest_balance,
+                    )
                 }
                 None => Err(Error::new_alloc(format!(
                     "The transfer would overflow balance of {dest}"
@@ -308,17 +338,14 @@ pub fn burn_tokens<S>(
 where
     S: StorageRead + StorageWrite,
 {
-    let source_balance_key = balance_key(token, source);
     let source_balance = read_balance(storage, token, source)?;
 
-    let amount_to_burn =
-        if let Some(new_amount) = source_balance.checked_sub(amount) {
-            storage.write(&source_balance_key, new_amount)?;
-            amount
-        } else {
-            storage.write(&source_balance_key, token::Amount::zero())?;
-            source_balance
-        };
+    let (amount_to_burn, new_amount) = match source_balance.checked_sub(amount)
+    {
+        Some(new_amount) => (amount, new_amount),
+        None => (source_balance, token::Amount::zero()),
+    };
+    write_balance(storage, token, source, source_balance, new_amount)?;
 
     // Decrement the total supply
     decrement_total_supply(storage, token, amount_to_burn)
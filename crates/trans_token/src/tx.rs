@@ -10,8 +10,7 @@ use namada_state::Error;
 use namada_tx_env::{Result, TxEnv};
 
 use crate::event::{TokenEvent, TokenOperation};
-use crate::storage_key::balance_key;
-use crate::{read_balance, Amount, UserAccount};
+use crate::{read_balance, write_balance, Amount, UserAccount};
 
 /// Multi-transfer credit or debit amounts
 pub trait CreditOrDebit {
@@ -82,7 +81,6 @@ where
         let underflow_err =
             || Error::new_alloc(format!("{owner} has insufficient balance"));
         // Load account balances and deltas
-        let owner_key = balance_key(token, owner);
         let owner_balance = read_balance(env, token, owner)?;
         let src_amt = sources.get(account).cloned().unwrap_or_default();
         let dest_amt = targets.get(account).cloned().unwrap_or_default();
@@ -104,7 +102,7 @@ where
         // Write the new balance
         if new_owner_balance != owner_balance {
             any_balance_changed = true;
-            env.write(&owner_key, new_owner_balance)?;
+            write_balance(env, token, owner, owner_balance, new_owner_balance)?;
         }
     }
 
@@ -30,6 +30,7 @@ pub enum Action {
     Pgf(PgfAction),
     Masp(MaspAction),
     IbcShielding,
+    BridgePool(BridgePoolAction),
 }
 
 /// PoS tx actions.
@@ -75,6 +76,14 @@ pub enum MaspAction {
     MaspAuthorizer(Address),
 }
 
+/// Ethereum bridge pool tx actions.
+#[allow(missing_docs)]
+#[derive(Clone, Debug, BorshDeserialize, BorshSerialize, PartialEq)]
+pub enum BridgePoolAction {
+    /// Cancel a pending transfer, authorized by its sender
+    CancelTransfer(Address),
+}
+
 /// Read actions from temporary storage
 pub trait Read {
     /// Storage access errors
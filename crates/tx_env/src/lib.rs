@@ -22,6 +22,7 @@ pub use namada_core::address::Address;
 pub use namada_core::borsh::{
     BorshDeserialize, BorshSerialize, BorshSerializeExt,
 };
+pub use namada_core::hash::Hash;
 pub use namada_core::masp::MaspTransaction;
 pub use namada_core::storage;
 pub use namada_events::{Event, EventToEmit, EventType};
@@ -109,4 +110,15 @@ pub trait TxEnv: StorageRead + StorageWrite {
     fn update_masp_note_commitment_tree(
         transaction: &MaspTransaction,
     ) -> Result<bool>;
+
+    /// Execute another allowlisted transaction code with the given data, in
+    /// the same write log as the current transaction. Returns an error when
+    /// the call is refused before executing the code, e.g. when the code is
+    /// not allowlisted or the maximum call depth is reached. A failure of the
+    /// called code aborts the current transaction.
+    fn call_tx(
+        &mut self,
+        code_hash: &Hash,
+        data: impl AsRef<[u8]>,
+    ) -> Result<()>;
 }
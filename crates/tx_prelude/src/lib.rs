@@ -53,7 +53,9 @@ use namada_token::MaspTransaction;
 pub use namada_tx::{action, data as transaction, BatchedTx, Section, Tx};
 pub use namada_tx_env::TxEnv;
 use namada_vm_env::tx::*;
-use namada_vm_env::{read_from_buffer, read_key_val_bytes_from_buffer};
+use namada_vm_env::{
+    read_from_buffer, read_key_val_bytes_from_buffer, read_many_from_buffer,
+};
 pub use {
     namada_gas as gas, namada_governance as governance,
     namada_parameters as parameters,
@@ -153,6 +155,20 @@ impl StorageRead for Ctx {
         Ok(read_from_buffer(read_result, namada_tx_result_buffer))
     }
 
+    fn read_many_bytes(
+        &self,
+        keys: &[storage::Key],
+    ) -> Result<Vec<Option<Vec<u8>>>> {
+        let keys = keys.serialize_to_vec();
+        let read_result =
+            unsafe { namada_tx_read_many(keys.as_ptr() as _, keys.len() as _) };
+        read_many_from_buffer(read_result, namada_tx_result_buffer).ok_or(
+            Error::SimpleMessage(
+                "Missing result from `namada_tx_read_many` call",
+            ),
+        )
+    }
+
     fn has_key(&self, key: &storage::Key) -> Result<bool> {
         let key = key.to_string();
         let found =
@@ -412,6 +428,27 @@ impl TxEnv for Ctx {
     ) -> Result<bool> {
         update_masp_note_commitment_tree(transaction)
     }
+
+    fn call_tx(
+        &mut self,
+        code_hash: &hash::Hash,
+        data: impl AsRef<[u8]>,
+    ) -> Result<()> {
+        let data = data.as_ref();
+        let result = unsafe {
+            namada_tx_call(
+                code_hash.0.as_ptr() as _,
+                code_hash.0.len() as _,
+                data.as_ptr() as _,
+                data.len() as _,
+            )
+        };
+        if HostEnvResult::is_success(result) {
+            Ok(())
+        } else {
+            Err(Error::SimpleMessage("The tx call was refused"))
+        }
+    }
 }
 
 impl namada_tx::action::Read for Ctx {
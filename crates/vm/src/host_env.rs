@@ -16,10 +16,7 @@ use namada_core::hash::Hash;
 use namada_core::internal::{HostEnvResult, KeyVal};
 use namada_core::storage::{Key, TxIndex, TX_INDEX_LENGTH};
 use namada_events::{Event, EventTypeBuilder};
-use namada_gas::{
-    self as gas, Gas, GasMetering, TxGasMeter, VpGasMeter,
-    MEMORY_ACCESS_GAS_PER_BYTE,
-};
+use namada_gas::{self as gas, Gas, GasMetering, TxGasMeter, VpGasMeter};
 use namada_state::prefix_iter::{PrefixIteratorId, PrefixIterators};
 use namada_state::write_log::{self, WriteLog};
 use namada_state::{
@@ -73,6 +70,10 @@ pub enum TxRuntimeError {
     NoValueInResultBuffer,
     #[error("VP code is not allowed in allowlist parameter.")]
     DisallowedVp,
+    #[error("Trying to call a transaction with an invalid WASM code hash {0}")]
+    InvalidTxCodeHash(String),
+    #[error("Transaction call failed: {0}")]
+    TxCallError(String),
 }
 
 impl From<TxRuntimeError> for namada_state::Error {
@@ -128,6 +129,9 @@ where
     pub result_buffer: HostRef<RwAccess, Option<Vec<u8>>>,
     /// Storage for byte buffer values yielded from the guest.
     pub yielded_value: HostRef<RwAccess, Option<Vec<u8>>>,
+    /// The number of [`tx_call`] calls that led to the execution of the
+    /// transaction code, `0` for the code of an inner transaction
+    pub call_depth: u8,
     /// VP WASM compilation cache (this is available in tx context, because
     /// we're pre-compiling VPs from [`tx_init_account`])
     #[cfg(feature = "wasm-runtime")]
@@ -200,6 +204,7 @@ where
             verifiers,
             result_buffer,
             yielded_value,
+            call_depth: 0,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache,
             #[cfg(feature = "wasm-runtime")]
@@ -284,6 +289,7 @@ where
             verifiers: self.verifiers,
             result_buffer: self.result_buffer,
             yielded_value: self.yielded_value,
+            call_depth: self.call_depth,
             #[cfg(feature = "wasm-runtime")]
             vp_wasm_cache: self.vp_wasm_cache,
             #[cfg(feature = "wasm-runtime")]
@@ -705,6 +711,50 @@ where
     }
 }
 
+/// Storage read of many keys at once, exposed to the wasm VM Tx environment.
+/// The keys are given as a Borsh encoded `Vec<Key>` and each of them is read
+/// like with [`tx_read`], so the gas charged is proportional to the number of
+/// keys and the size of their values.
+///
+/// Returns the length of the Borsh encoded `Vec<Option<Vec<u8>>>` of the
+/// values, in the order of the keys, which is placed in the result buffer.
+pub fn tx_read_many<MEM, D, H, CA>(
+    env: &mut TxVmEnv<MEM, D, H, CA>,
+    keys_ptr: u64,
+    keys_len: u64,
+) -> TxResult<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (keys, gas) = env
+        .memory
+        .read_bytes(keys_ptr, keys_len.try_into()?)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    consume_tx_gas::<MEM, D, H, CA>(env, gas)?;
+    let keys = Vec::<Key>::try_from_slice(&keys)
+        .map_err(TxRuntimeError::EncodingError)?;
+
+    tracing::debug!("tx_read_many {} keys", keys.len());
+
+    let mut state = env.state();
+    let mut values = Vec::with_capacity(keys.len());
+    for key in &keys {
+        state.write_log_mut().record_read(key);
+        values.push(state.read_bytes(key)?);
+    }
+    let value = values.serialize_to_vec();
+    let len: i64 = value
+        .len()
+        .try_into()
+        .map_err(TxRuntimeError::NumConversionError)?;
+    let result_buffer = unsafe { env.ctx.result_buffer.get_mut() };
+    result_buffer.replace(value);
+    Ok(len)
+}
+
 /// Read temporary value (not committed to storage) from the given key function
 /// exposed to the wasm VM Tx environment. It will try to read from the write
 /// log only.
@@ -1185,6 +1235,90 @@ where
     })
 }
 
+/// Storage read of many keys at once in the prior state (before tx
+/// execution), exposed to the wasm VM VP environment. The keys are given as a
+/// Borsh encoded `Vec<Key>` and each of them is read like with
+/// [`vp_read_pre`], so the gas charged is proportional to the number of keys
+/// and the size of their values.
+///
+/// Returns the length of the Borsh encoded `Vec<Option<Vec<u8>>>` of the
+/// values, in the order of the keys, which is placed in the result buffer.
+pub fn vp_read_many_pre<MEM, D, H, EVAL, CA>(
+    env: &mut VpVmEnv<MEM, D, H, EVAL, CA>,
+    keys_ptr: u64,
+    keys_len: u64,
+) -> Result<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let (keys, gas) = env
+        .memory
+        .read_bytes(keys_ptr, keys_len.try_into()?)
+        .map_err(Into::into)?;
+    let gas_meter = env.ctx.gas_meter();
+    vp_host_fns::add_gas(gas_meter, gas)?;
+    let keys: Vec<Key> = decode(keys)?;
+
+    tracing::debug!("vp_read_many_pre {} keys", keys.len());
+
+    let state = env.state();
+    let values = keys
+        .iter()
+        .map(|key| vp_host_fns::read_pre(gas_meter, &state, key))
+        .collect::<Result<Vec<_>>>()?;
+    let value = values.serialize_to_vec();
+    let len: i64 = value.len().try_into()?;
+    let result_buffer = unsafe { env.ctx.result_buffer.get_mut() };
+    result_buffer.replace(value);
+    Ok(len)
+}
+
+/// Storage read of many keys at once in the posterior state (after tx
+/// execution), exposed to the wasm VM VP environment. The keys are given as a
+/// Borsh encoded `Vec<Key>` and each of them is read like with
+/// [`vp_read_post`], so the gas charged is proportional to the number of keys
+/// and the size of their values.
+///
+/// Returns the length of the Borsh encoded `Vec<Option<Vec<u8>>>` of the
+/// values, in the order of the keys, which is placed in the result buffer.
+pub fn vp_read_many_post<MEM, D, H, EVAL, CA>(
+    env: &mut VpVmEnv<MEM, D, H, EVAL, CA>,
+    keys_ptr: u64,
+    keys_len: u64,
+) -> Result<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let (keys, gas) = env
+        .memory
+        .read_bytes(keys_ptr, keys_len.try_into()?)
+        .map_err(Into::into)?;
+    let gas_meter = env.ctx.gas_meter();
+    vp_host_fns::add_gas(gas_meter, gas)?;
+    let keys: Vec<Key> = decode(keys)?;
+
+    tracing::debug!("vp_read_many_post {} keys", keys.len());
+
+    let state = env.state();
+    let values = keys
+        .iter()
+        .map(|key| vp_host_fns::read_post(gas_meter, &state, key))
+        .collect::<Result<Vec<_>>>()?;
+    let value = values.serialize_to_vec();
+    let len: i64 = value.len().try_into()?;
+    let result_buffer = unsafe { env.ctx.result_buffer.get_mut() };
+    result_buffer.replace(value);
+    Ok(len)
+}
+
 /// Storage read temporary state (after tx execution) function exposed to the
 /// wasm VM VP environment. It will try to read from only the write log.
 ///
@@ -1453,10 +1587,8 @@ where
     let verifiers = unsafe { env.ctx.verifiers.get_mut() };
     // This is not a storage write, use the same multiplier used for a storage
     // read
-    consume_tx_gas::<MEM, D, H, CA>(
-        env,
-        checked!(addr_len * MEMORY_ACCESS_GAS_PER_BYTE)?.into(),
-    )?;
+    let cost = env.state().gas_schedule().memory_access_gas_per_byte;
+    consume_tx_gas::<MEM, D, H, CA>(env, checked!(addr_len * cost)?.into())?;
     verifiers.insert(addr);
 
     Ok(())
@@ -1617,13 +1749,9 @@ where
     H: 'static + StorageHasher,
     CA: WasmCacheAccess,
 {
-    consume_tx_gas::<MEM, D, H, CA>(
-        env,
-        (TX_INDEX_LENGTH as u64)
-            .checked_mul(MEMORY_ACCESS_GAS_PER_BYTE)
-            .expect("Consts mul that cannot overflow")
-            .into(),
-    )?;
+    let len = TX_INDEX_LENGTH as u64;
+    let cost = env.state().gas_schedule().memory_access_gas_per_byte;
+    consume_tx_gas::<MEM, D, H, CA>(env, checked!(len * cost)?.into())?;
     let tx_index = unsafe { env.ctx.tx_index.get() };
     Ok(tx_index.0)
 }
@@ -1683,10 +1811,8 @@ where
         .try_into()
         .map_err(TxRuntimeError::NumConversionError)?;
     let len_u64 = u64::try_from(len)?;
-    consume_tx_gas::<MEM, D, H, CA>(
-        env,
-        checked!(MEMORY_ACCESS_GAS_PER_BYTE * len_u64)?.into(),
-    )?;
+    let cost = env.state().gas_schedule().memory_access_gas_per_byte;
+    consume_tx_gas::<MEM, D, H, CA>(env, checked!(cost * len_u64)?.into())?;
     let result_buffer = unsafe { env.ctx.result_buffer.get_mut() };
     result_buffer.replace(bytes);
     Ok(len)
@@ -1704,13 +1830,9 @@ where
     CA: WasmCacheAccess,
 {
     // Gas for getting the native token address from storage
-    consume_tx_gas::<MEM, D, H, CA>(
-        env,
-        (ESTABLISHED_ADDRESS_BYTES_LEN as u64)
-            .checked_mul(MEMORY_ACCESS_GAS_PER_BYTE)
-            .expect("Consts mul that cannot overflow")
-            .into(),
-    )?;
+    let len = ESTABLISHED_ADDRESS_BYTES_LEN as u64;
+    let cost = env.state().gas_schedule().memory_access_gas_per_byte;
+    consume_tx_gas::<MEM, D, H, CA>(env, checked!(len * cost)?.into())?;
     let state = env.state();
     let native_token = state.in_mem().native_token.clone();
     let native_token_string = native_token.encode();
@@ -1973,9 +2095,9 @@ where
         &Some(signer),
         threshold,
         || {
-            gas_meter
-                .borrow_mut()
-                .consume(gas::VERIFY_TX_SIG_GAS.into())
+            let mut gas_meter = gas_meter.borrow_mut();
+            let cost = gas_meter.gas_schedule().verify_tx_sig_gas;
+            gas_meter.consume(cost.into())
         },
     ) {
         Ok(_) => Ok(()),
@@ -2129,9 +2251,9 @@ where
         &None,
         threshold,
         || {
-            gas_meter
-                .borrow_mut()
-                .consume(gas::VERIFY_TX_SIG_GAS.into())
+            let mut gas_meter = gas_meter.borrow_mut();
+            let cost = gas_meter.gas_schedule().verify_tx_sig_gas;
+            gas_meter.consume(cost.into())
         },
     ) {
         Ok(_) => Ok(HostEnvResult::Success.to_i64()),
@@ -2206,6 +2328,56 @@ where
     Ok(())
 }
 
+/// Execute another allowlisted transaction code with the given data, in the
+/// same write log as the calling transaction. The gas used by the called code,
+/// including the cost of loading and compiling it, is charged to the calling
+/// transaction. The calls can be nested up to
+/// [`MAX_TX_CALL_DEPTH`](crate::wasm::run::MAX_TX_CALL_DEPTH) times.
+///
+/// Returns [`HostEnvResult::Fail`] when the call is refused before executing
+/// the code. The storage modifications of a failed call cannot be reverted
+/// separately, so a failure of the called code aborts the calling transaction.
+#[cfg(feature = "wasm-runtime")]
+pub fn tx_call<MEM, D, H, CA>(
+    env: &mut TxVmEnv<MEM, D, H, CA>,
+    code_hash_ptr: u64,
+    code_hash_len: u64,
+    data_ptr: u64,
+    data_len: u64,
+) -> TxResult<i64>
+where
+    MEM: VmMemory,
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    let (code_hash, gas) = env
+        .memory
+        .read_bytes(code_hash_ptr, code_hash_len.try_into()?)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    consume_tx_gas::<MEM, D, H, CA>(env, gas)?;
+    let code_hash = Hash::try_from(&code_hash[..])
+        .map_err(|e| TxRuntimeError::InvalidTxCodeHash(e.to_string()))?;
+
+    let (data, gas) = env
+        .memory
+        .read_bytes(data_ptr, data_len.try_into()?)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    consume_tx_gas::<MEM, D, H, CA>(env, gas)?;
+
+    tracing::debug!("tx_call {}, depth {}", code_hash, env.ctx.call_depth);
+
+    super::wasm::run::call_tx(&env.ctx, code_hash, data)
+        .map(HostEnvResult::to_i64)
+        .map_err(|err| {
+            if let super::wasm::run::Error::GasError(_) = err {
+                let (_gas_meter, sentinel) = env.ctx.gas_meter_and_sentinel();
+                sentinel.borrow_mut().set_out_of_gas();
+            }
+            TxRuntimeError::TxCallError(err.to_string()).into()
+        })
+}
+
 /// Evaluate a validity predicate with the given input data.
 pub fn vp_eval<MEM, D, H, EVAL, CA>(
     env: &mut VpVmEnv<MEM, D, H, EVAL, CA>,
@@ -2404,9 +2576,11 @@ pub mod testing {
                 .unwrap()
         };
 
+        let memory_access_gas_per_byte =
+            gas_meter.borrow().gas_schedule().memory_access_gas_per_byte;
         let (write_log, in_mem, db) = state.split_borrow();
         let mut env = TxVmEnv::new(
-            WasmMemory::new(Rc::downgrade(&store)),
+            WasmMemory::new(Rc::downgrade(&store), memory_access_gas_per_byte),
             write_log,
             in_mem,
             db,
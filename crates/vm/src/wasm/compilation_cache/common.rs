@@ -8,6 +8,7 @@ use std::fs;
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 use std::thread::sleep;
 use std::time::Duration;
@@ -93,8 +94,15 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
             wasmer::Target::default().hash(&mut hasher);
             hasher.finish()
         };
+        // The modules compiled with different gas metering backends are not
+        // interchangeable
+        let metering = if cfg!(feature = "wasmer-metering") {
+            "_fuel"
+        } else {
+            ""
+        };
         let version = format!(
-            "{}_{:x}",
+            "{}_{:x}{metering}",
             concat!(env!("CARGO_PKG_VERSION"), "_", env!("RUSTUP_TOOLCHAIN")),
             target_hash,
         );
@@ -141,6 +149,68 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
         self.in_memory.read().unwrap().weight()
     }
 
+    /// Get the number of modules whose compilation is done and the total
+    /// number of modules known to the cache, including the ones that are still
+    /// being compiled.
+    pub fn compilation_progress(&self) -> (usize, usize) {
+        let progress = self.progress.read().unwrap();
+        let done = progress
+            .values()
+            .filter(|compilation| matches!(compilation, Compilation::Done))
+            .count();
+        (done, progress.len())
+    }
+
+    /// Check the compiled modules stored in the cache directory against the
+    /// checksums that were written with them and remove the ones that don't
+    /// match, so that they get compiled again when needed. Returns the hashes
+    /// of the removed modules.
+    pub fn verify_integrity(&self) -> Vec<Hash> {
+        if !A::is_read_write() {
+            return vec![];
+        }
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                tracing::error!(
+                    "Failed to read the {} directory: {err}.",
+                    N::name()
+                );
+                return vec![];
+            }
+        };
+        let mut removed = vec![];
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(hash) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| Hash::from_str(name).ok())
+            else {
+                continue;
+            };
+            if module_file_is_valid(&self.dir, &hash) {
+                continue;
+            }
+            tracing::warn!(
+                "Removing the invalid {} {} from the file cache.",
+                N::name(),
+                hash.to_string()
+            );
+            if let Err(err) = fs::remove_dir_all(&path) {
+                tracing::error!(
+                    "Failed to remove {} {}: {err}.",
+                    N::name(),
+                    hash.to_string()
+                );
+                continue;
+            }
+            self.progress.write().unwrap().swap_remove(&hash);
+            removed.push(hash);
+        }
+        removed
+    }
+
     /// Get a WASM module from LRU cache, from a file or compile it and cache
     /// it. Updates the position in the LRU cache.
     fn get(&mut self, hash: &Hash) -> Result<Option<Module>, wasm::run::Error> {
@@ -534,8 +604,22 @@ pub(crate) fn store() -> Store {
 
 fn file_write_module(dir: impl AsRef<Path>, module: &Module, hash: &Hash) {
     use wasmer_cache::Cache;
-    let mut fs_cache = fs_cache(dir, hash);
+    let mut fs_cache = fs_cache(&dir, hash);
     fs_cache.store(CacheHash::new(hash.0), module).unwrap();
+
+    // Write the checksum of the module's file to be able to detect corrupted
+    // files
+    let checksum = fs::read(module_file_path(&dir, hash))
+        .map(|bytes| Hash::sha256(bytes).to_string())
+        .and_then(|checksum| {
+            fs::write(checksum_file_path(&dir, hash), checksum)
+        });
+    if let Err(err) = checksum {
+        tracing::error!(
+            "Failed to write the checksum of the cached wasm {}: {err}.",
+            hash.to_string()
+        );
+    }
 }
 
 fn file_load_module(
@@ -563,16 +647,36 @@ fn fs_cache(dir: impl AsRef<Path>, hash: &Hash) -> FileSystemCache {
     fs_cache
 }
 
+fn module_file_path(dir: impl AsRef<Path>, hash: &Hash) -> PathBuf {
+    dir.as_ref()
+        .join(hash.to_string().to_lowercase())
+        .join(format!(
+            "{}.{}",
+            hash.to_string().to_lowercase(),
+            file_ext()
+        ))
+}
+
+fn checksum_file_path(dir: impl AsRef<Path>, hash: &Hash) -> PathBuf {
+    module_file_path(dir, hash).with_extension(format!("{}.sha256", file_ext()))
+}
+
 fn module_file_exists(dir: impl AsRef<Path>, hash: &Hash) -> bool {
-    let file =
-        dir.as_ref()
-            .join(hash.to_string().to_lowercase())
-            .join(format!(
-                "{}.{}",
-                hash.to_string().to_lowercase(),
-                file_ext()
-            ));
-    file.exists()
+    module_file_path(dir, hash).exists()
+}
+
+/// Check that the module's file matches its checksum. Modules cached before the
+/// checksums were written are checked by deserializing them instead.
+fn module_file_is_valid(dir: impl AsRef<Path>, hash: &Hash) -> bool {
+    let Ok(bytes) = fs::read(module_file_path(&dir, hash)) else {
+        return false;
+    };
+    match fs::read_to_string(checksum_file_path(&dir, hash)) {
+        Ok(checksum) => Hash::from_str(checksum.trim())
+            .map(|checksum| checksum == Hash::sha256(bytes))
+            .unwrap_or(false),
+        Err(_) => file_load_module(&dir, hash, &store()).is_ok(),
+    }
 }
 
 /// A universal engine compilation. The module can be serialized to/from bytes.
@@ -1014,6 +1118,36 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_verify_integrity() {
+        let tx_no_op = load_wasm(TestWasms::TxNoOp.path());
+        let tx_read_storage_key = load_wasm(TestWasms::TxReadStorageKey.path());
+        let (mut cache, _tmp_dir) = cache(10);
+
+        cache.compile_or_fetch(&tx_no_op.code).unwrap().unwrap();
+        cache
+            .compile_or_fetch(&tx_read_storage_key.code)
+            .unwrap()
+            .unwrap();
+        assert_eq!(cache.compilation_progress(), (2, 2));
+        assert!(
+            cache.verify_integrity().is_empty(),
+            "Untouched modules must be valid"
+        );
+
+        // Corrupt one of the modules
+        let path = module_file_path(&cache.dir, &tx_no_op.hash);
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.last_mut().unwrap();
+        *last = last.wrapping_add(1);
+        fs::write(&path, bytes).unwrap();
+
+        assert_eq!(cache.verify_integrity(), vec![tx_no_op.hash]);
+        assert!(!module_file_exists(&cache.dir, &tx_no_op.hash));
+        assert!(module_file_exists(&cache.dir, &tx_read_storage_key.hash));
+        assert_eq!(cache.compilation_progress(), (1, 1));
+    }
+
     #[test]
     fn test_pre_compile_invalid_wasm() {
         // Some random bytes
@@ -3,6 +3,7 @@
 //! Here, we expose the host functions into wasm's
 //! imports, so they can be called from inside the wasm.
 
+use namada_gas::GasMetering;
 use namada_state::{DBIter, StorageHasher, DB};
 use wasmer::{Function, FunctionEnv, Imports};
 
@@ -28,34 +29,36 @@ where
         // Default namespace
         "env" => {
             // Gas injection hook
-            "gas" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_charge_gas)),
+            "gas" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1("gas", host_env::tx_charge_gas)),
             // Tx Host functions
-            "namada_tx_delete" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_delete)),
-            "namada_tx_emit_event" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_emit_event)),
-            "namada_tx_get_block_epoch" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0(host_env::tx_get_block_epoch)),
-            "namada_tx_get_block_header" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_get_block_header)),
-            "namada_tx_get_block_height" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0(host_env::tx_get_block_height)),
-            "namada_tx_get_chain_id" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_get_chain_id)),
-            "namada_tx_get_events" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_get_events)),
-            "namada_tx_get_native_token" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_get_native_token)),
-            "namada_tx_get_pred_epochs" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0(host_env::tx_get_pred_epochs)),
-            "namada_tx_get_tx_index" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0(host_env::tx_get_tx_index)),
-            "namada_tx_has_key" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_has_key)),
-            "namada_tx_init_account" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_7(host_env::tx_init_account)),
-            "namada_tx_insert_verifier" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_insert_verifier)),
-            "namada_tx_iter_next" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_iter_next)),
-            "namada_tx_iter_prefix" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_iter_prefix)),
-            "namada_tx_log_string" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_log_string)),
-            "namada_tx_read" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_read)),
-            "namada_tx_read_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_read_temp)),
-            "namada_tx_result_buffer" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1(host_env::tx_result_buffer)),
-            "namada_tx_set_commitment_sentinel" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0(host_env::tx_set_commitment_sentinel)),
-            "namada_tx_update_masp_note_commitment_tree" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_update_masp_note_commitment_tree)),
-            "namada_tx_update_validity_predicate" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_6(host_env::tx_update_validity_predicate)),
-            "namada_tx_verify_tx_section_signature" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_5(host_env::tx_verify_tx_section_signature)),
-            "namada_tx_write" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_4(host_env::tx_write)),
-            "namada_tx_write_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_4(host_env::tx_write_temp)),
-            "namada_tx_yield_value" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2(host_env::tx_yield_value)),
+            "namada_tx_call" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_4("namada_tx_call", host_env::tx_call)),
+            "namada_tx_delete" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_delete", host_env::tx_delete)),
+            "namada_tx_emit_event" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_emit_event", host_env::tx_emit_event)),
+            "namada_tx_get_block_epoch" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0("namada_tx_get_block_epoch", host_env::tx_get_block_epoch)),
+            "namada_tx_get_block_header" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1("namada_tx_get_block_header", host_env::tx_get_block_header)),
+            "namada_tx_get_block_height" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0("namada_tx_get_block_height", host_env::tx_get_block_height)),
+            "namada_tx_get_chain_id" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1("namada_tx_get_chain_id", host_env::tx_get_chain_id)),
+            "namada_tx_get_events" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_get_events", host_env::tx_get_events)),
+            "namada_tx_get_native_token" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1("namada_tx_get_native_token", host_env::tx_get_native_token)),
+            "namada_tx_get_pred_epochs" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0("namada_tx_get_pred_epochs", host_env::tx_get_pred_epochs)),
+            "namada_tx_get_tx_index" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0("namada_tx_get_tx_index", host_env::tx_get_tx_index)),
+            "namada_tx_has_key" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_has_key", host_env::tx_has_key)),
+            "namada_tx_init_account" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_7("namada_tx_init_account", host_env::tx_init_account)),
+            "namada_tx_insert_verifier" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_insert_verifier", host_env::tx_insert_verifier)),
+            "namada_tx_iter_next" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1("namada_tx_iter_next", host_env::tx_iter_next)),
+            "namada_tx_iter_prefix" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_iter_prefix", host_env::tx_iter_prefix)),
+            "namada_tx_log_string" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_log_string", host_env::tx_log_string)),
+            "namada_tx_read" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_read", host_env::tx_read)),
+            "namada_tx_read_many" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_read_many", host_env::tx_read_many)),
+            "namada_tx_read_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_read_temp", host_env::tx_read_temp)),
+            "namada_tx_result_buffer" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_1("namada_tx_result_buffer", host_env::tx_result_buffer)),
+            "namada_tx_set_commitment_sentinel" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_0("namada_tx_set_commitment_sentinel", host_env::tx_set_commitment_sentinel)),
+            "namada_tx_update_masp_note_commitment_tree" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_update_masp_note_commitment_tree", host_env::tx_update_masp_note_commitment_tree)),
+            "namada_tx_update_validity_predicate" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_6("namada_tx_update_validity_predicate", host_env::tx_update_validity_predicate)),
+            "namada_tx_verify_tx_section_signature" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_5("namada_tx_verify_tx_section_signature", host_env::tx_verify_tx_section_signature)),
+            "namada_tx_write" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_4("namada_tx_write", host_env::tx_write)),
+            "namada_tx_write_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_4("namada_tx_write_temp", host_env::tx_write_temp)),
+            "namada_tx_yield_value" => Function::new_typed_with_env(wasm_store, &env, wrap_tx::_2("namada_tx_yield_value", host_env::tx_yield_value)),
         },
     }
 }
@@ -78,33 +81,133 @@ where
         // Default namespace
         "env" => {
             // Gas injection hook
-            "gas" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_charge_gas)),
+            "gas" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("gas", host_env::vp_charge_gas)),
             // VP Host functions
-            "namada_vp_eval" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_4(host_env::vp_eval)),
-            "namada_vp_get_block_header" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_get_block_header)),
-            "namada_vp_get_block_height" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0(host_env::vp_get_block_height)),
-            "namada_vp_get_chain_id" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_get_chain_id)),
-            "namada_vp_get_events" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_get_events)),
-            "namada_vp_get_native_token" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_get_native_token)),
-            "namada_vp_get_pred_epochs" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0(host_env::vp_get_pred_epochs)),
-            "namada_vp_get_tx_code_hash" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_get_tx_code_hash)),
-            "namada_vp_get_tx_index" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0(host_env::vp_get_tx_index)),
-            "namada_vp_has_key_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_has_key_post)),
-            "namada_vp_has_key_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_has_key_pre)),
-            "namada_vp_iter_next" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_iter_next)),
-            "namada_vp_iter_prefix_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_iter_prefix_post)),
-            "namada_vp_iter_prefix_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_iter_prefix_pre)),
-            "namada_vp_log_string" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_log_string)),
-            "namada_vp_read_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_read_post)),
-            "namada_vp_read_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_read_pre)),
-            "namada_vp_read_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_read_temp)),
-            "namada_vp_result_buffer" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1(host_env::vp_result_buffer)),
-            "namada_vp_verify_tx_section_signature" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_7(host_env::vp_verify_tx_section_signature)),
-            "namada_vp_yield_value" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2(host_env::vp_yield_value)),
+            "namada_vp_eval" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_4("namada_vp_eval", host_env::vp_eval)),
+            "namada_vp_get_block_header" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("namada_vp_get_block_header", host_env::vp_get_block_header)),
+            "namada_vp_get_block_height" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0("namada_vp_get_block_height", host_env::vp_get_block_height)),
+            "namada_vp_get_chain_id" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("namada_vp_get_chain_id", host_env::vp_get_chain_id)),
+            "namada_vp_get_events" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_get_events", host_env::vp_get_events)),
+            "namada_vp_get_native_token" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("namada_vp_get_native_token", host_env::vp_get_native_token)),
+            "namada_vp_get_pred_epochs" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0("namada_vp_get_pred_epochs", host_env::vp_get_pred_epochs)),
+            "namada_vp_get_tx_code_hash" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("namada_vp_get_tx_code_hash", host_env::vp_get_tx_code_hash)),
+            "namada_vp_get_tx_index" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_0("namada_vp_get_tx_index", host_env::vp_get_tx_index)),
+            "namada_vp_has_key_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_has_key_post", host_env::vp_has_key_post)),
+            "namada_vp_has_key_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_has_key_pre", host_env::vp_has_key_pre)),
+            "namada_vp_iter_next" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("namada_vp_iter_next", host_env::vp_iter_next)),
+            "namada_vp_iter_prefix_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_iter_prefix_post", host_env::vp_iter_prefix_post)),
+            "namada_vp_iter_prefix_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_iter_prefix_pre", host_env::vp_iter_prefix_pre)),
+            "namada_vp_log_string" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_log_string", host_env::vp_log_string)),
+            "namada_vp_read_many_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_read_many_post", host_env::vp_read_many_post)),
+            "namada_vp_read_many_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_read_many_pre", host_env::vp_read_many_pre)),
+            "namada_vp_read_post" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_read_post", host_env::vp_read_post)),
+            "namada_vp_read_pre" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_read_pre", host_env::vp_read_pre)),
+            "namada_vp_read_temp" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_read_temp", host_env::vp_read_temp)),
+            "namada_vp_result_buffer" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_1("namada_vp_result_buffer", host_env::vp_result_buffer)),
+            "namada_vp_verify_tx_section_signature" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_7("namada_vp_verify_tx_section_signature", host_env::vp_verify_tx_section_signature)),
+            "namada_vp_yield_value" => Function::new_typed_with_env(wasm_store, &env, wrap_vp::_2("namada_vp_yield_value", host_env::vp_yield_value)),
         },
     }
 }
 
+/// Call a tx host function, recording it in the gas trace if tracing is
+/// enabled
+fn traced_tx<RET, D, H, CA>(
+    env: &mut TxVmEnv<WasmMemory, D, H, CA>,
+    name: &'static str,
+    call: impl FnOnce(&mut TxVmEnv<WasmMemory, D, H, CA>) -> RET,
+) -> RET
+where
+    D: DB + for<'iter> DBIter<'iter> + 'static,
+    H: StorageHasher + 'static,
+    CA: WasmCacheAccess + 'static,
+{
+    #[cfg(feature = "wasmer-metering")]
+    sync_fuel_before_call(&env.memory, env.ctx.gas_meter_and_sentinel().0);
+    let gas_before = {
+        let (gas_meter, _) = env.ctx.gas_meter_and_sentinel();
+        let gas_meter = gas_meter.borrow();
+        gas_meter
+            .is_tracing()
+            .then(|| gas_meter.get_tx_consumed_gas())
+    };
+    let ret = match gas_before {
+        Some(gas_before) => {
+            let accessed_before = env.memory.accessed();
+            let ret = call(env);
+            let memory = env.memory.accessed().since(accessed_before);
+            let (gas_meter, _) = env.ctx.gas_meter_and_sentinel();
+            let mut gas_meter = gas_meter.borrow_mut();
+            let gas = gas_meter
+                .get_tx_consumed_gas()
+                .checked_sub(gas_before)
+                .unwrap_or_default();
+            gas_meter.trace_host_call(name, memory, gas);
+            ret
+        }
+        None => call(env),
+    };
+    #[cfg(feature = "wasmer-metering")]
+    env.memory.refuel(env.ctx.gas_meter_and_sentinel().0);
+    ret
+}
+
+/// Call a VP host function, recording it in the gas trace if tracing is
+/// enabled
+fn traced_vp<RET, D, H, EVAL, CA>(
+    env: &mut VpVmEnv<WasmMemory, D, H, EVAL, CA>,
+    name: &'static str,
+    call: impl FnOnce(&mut VpVmEnv<WasmMemory, D, H, EVAL, CA>) -> RET,
+) -> RET
+where
+    D: DB + for<'iter> DBIter<'iter> + 'static,
+    H: StorageHasher + 'static,
+    EVAL: VpEvaluator<Db = D, H = H, Eval = EVAL, CA = CA> + 'static,
+    CA: WasmCacheAccess + 'static,
+{
+    #[cfg(feature = "wasmer-metering")]
+    sync_fuel_before_call(&env.memory, env.ctx.gas_meter());
+    let gas_before = {
+        let gas_meter = env.ctx.gas_meter().borrow();
+        gas_meter
+            .is_tracing()
+            .then(|| gas_meter.get_vp_consumed_gas())
+    };
+    let ret = match gas_before {
+        Some(gas_before) => {
+            let accessed_before = env.memory.accessed();
+            let ret = call(env);
+            let memory = env.memory.accessed().since(accessed_before);
+            let mut gas_meter = env.ctx.gas_meter().borrow_mut();
+            let gas = gas_meter
+                .get_vp_consumed_gas()
+                .checked_sub(gas_before)
+                .unwrap_or_default();
+            gas_meter.trace_host_call(name, memory, gas);
+            ret
+        }
+        None => call(env),
+    };
+    #[cfg(feature = "wasmer-metering")]
+    env.memory.refuel(env.ctx.gas_meter());
+    ret
+}
+
+/// Charge the fuel consumed by the wasm code before calling a host function,
+/// so that the gas meter is up to date when the host function checks it
+#[cfg(feature = "wasmer-metering")]
+fn sync_fuel_before_call(
+    memory: &WasmMemory,
+    gas_meter: &std::cell::RefCell<impl GasMetering>,
+) {
+    // The fuel never exceeds the gas available in the gas meter, so this can
+    // only fail on an overflow of the gas meter, which is then reported by
+    // the host function's own gas charge
+    if let Err(err) = memory.charge_fuel(gas_meter) {
+        tracing::debug!("Failed to charge the wasm fuel: {err}");
+    }
+}
+
 // TODO(namada#3313): Attempt to reduce the boilerplate of this module with
 // macros, traits or something of this sort...
 mod wrap_tx {
@@ -121,6 +224,7 @@ mod wrap_tx {
     use crate::WasmCacheAccess;
 
     pub(super) fn _0<F, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>) -> RET
     where
@@ -129,10 +233,11 @@ mod wrap_tx {
         CA: WasmCacheAccess + 'static,
         F: Fn(&mut TxVmEnv<WasmMemory, D, H, CA>) -> RET,
     {
-        move |mut env| f(env.data_mut())
+        move |mut env| super::traced_tx(env.data_mut(), name, |env| f(env))
     }
 
     pub(super) fn _1<F, ARG0, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>, ARG0) -> RET
     where
@@ -141,10 +246,13 @@ mod wrap_tx {
         CA: WasmCacheAccess + 'static,
         F: Fn(&mut TxVmEnv<WasmMemory, D, H, CA>, ARG0) -> RET,
     {
-        move |mut env, arg0| f(env.data_mut(), arg0)
+        move |mut env, arg0| {
+            super::traced_tx(env.data_mut(), name, |env| f(env, arg0))
+        }
     }
 
     pub(super) fn _2<F, ARG0, ARG1, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>, ARG0, ARG1) -> RET
     where
@@ -153,10 +261,13 @@ mod wrap_tx {
         CA: WasmCacheAccess + 'static,
         F: Fn(&mut TxVmEnv<WasmMemory, D, H, CA>, ARG0, ARG1) -> RET,
     {
-        move |mut env, arg0, arg1| f(env.data_mut(), arg0, arg1)
+        move |mut env, arg0, arg1| {
+            super::traced_tx(env.data_mut(), name, |env| f(env, arg0, arg1))
+        }
     }
 
     pub(super) fn _4<F, ARG0, ARG1, ARG2, ARG3, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>,
@@ -178,11 +289,14 @@ mod wrap_tx {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3)
+            super::traced_tx(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3)
+            })
         }
     }
 
     pub(super) fn _5<F, ARG0, ARG1, ARG2, ARG3, ARG4, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>,
@@ -206,11 +320,14 @@ mod wrap_tx {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3, arg4| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3, arg4)
+            super::traced_tx(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3, arg4)
+            })
         }
     }
 
     pub(super) fn _6<F, ARG0, ARG1, ARG2, ARG3, ARG4, ARG5, RET, D, H, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>,
@@ -236,7 +353,9 @@ mod wrap_tx {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3, arg4, arg5| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3, arg4, arg5)
+            super::traced_tx(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3, arg4, arg5)
+            })
         }
     }
 
@@ -254,6 +373,7 @@ mod wrap_tx {
         H,
         CA,
     >(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, TxVmEnv<WasmMemory, D, H, CA>>,
@@ -281,7 +401,9 @@ mod wrap_tx {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3, arg4, arg5, arg6| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3, arg4, arg5, arg6)
+            super::traced_tx(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3, arg4, arg5, arg6)
+            })
         }
     }
 }
@@ -302,6 +424,7 @@ mod wrap_vp {
     use crate::WasmCacheAccess;
 
     pub(super) fn _0<F, RET, D, H, EVAL, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>) -> RET
     where
@@ -311,10 +434,11 @@ mod wrap_vp {
         EVAL: VpEvaluator<Db = D, H = H, Eval = EVAL, CA = CA> + 'static,
         F: Fn(&mut VpVmEnv<WasmMemory, D, H, EVAL, CA>) -> RET,
     {
-        move |mut env| f(env.data_mut())
+        move |mut env| super::traced_vp(env.data_mut(), name, |env| f(env))
     }
 
     pub(super) fn _1<F, ARG0, RET, D, H, EVAL, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>, ARG0) -> RET
     where
@@ -324,10 +448,13 @@ mod wrap_vp {
         EVAL: VpEvaluator<Db = D, H = H, Eval = EVAL, CA = CA> + 'static,
         F: Fn(&mut VpVmEnv<WasmMemory, D, H, EVAL, CA>, ARG0) -> RET,
     {
-        move |mut env, arg0| f(env.data_mut(), arg0)
+        move |mut env, arg0| {
+            super::traced_vp(env.data_mut(), name, |env| f(env, arg0))
+        }
     }
 
     pub(super) fn _2<F, ARG0, ARG1, RET, D, H, EVAL, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>,
@@ -341,10 +468,13 @@ mod wrap_vp {
         EVAL: VpEvaluator<Db = D, H = H, Eval = EVAL, CA = CA> + 'static,
         F: Fn(&mut VpVmEnv<WasmMemory, D, H, EVAL, CA>, ARG0, ARG1) -> RET,
     {
-        move |mut env, arg0, arg1| f(env.data_mut(), arg0, arg1)
+        move |mut env, arg0, arg1| {
+            super::traced_vp(env.data_mut(), name, |env| f(env, arg0, arg1))
+        }
     }
 
     pub(super) fn _4<F, ARG0, ARG1, ARG2, ARG3, RET, D, H, EVAL, CA>(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>,
@@ -367,7 +497,9 @@ mod wrap_vp {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3)
+            super::traced_vp(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3)
+            })
         }
     }
 
@@ -386,6 +518,7 @@ mod wrap_vp {
         EVAL,
         CA,
     >(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>,
@@ -414,7 +547,9 @@ mod wrap_vp {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3, arg4, arg5, arg6| {
-            f(env.data_mut(), arg0, arg1, arg2, arg3, arg4, arg5, arg6)
+            super::traced_vp(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3, arg4, arg5, arg6)
+            })
         }
     }
 
@@ -435,6 +570,7 @@ mod wrap_vp {
         EVAL,
         CA,
     >(
+        name: &'static str,
         f: F,
     ) -> impl Fn(
         FunctionEnvMut<'_, VpVmEnv<WasmMemory, D, H, EVAL, CA>>,
@@ -467,18 +603,9 @@ mod wrap_vp {
         ) -> RET,
     {
         move |mut env, arg0, arg1, arg2, arg3, arg4, arg5, arg6, arg7, arg8| {
-            f(
-                env.data_mut(),
-                arg0,
-                arg1,
-                arg2,
-                arg3,
-                arg4,
-                arg5,
-                arg6,
-                arg7,
-                arg8,
-            )
+            super::traced_vp(env.data_mut(), name, |env| {
+                f(env, arg0, arg1, arg2, arg3, arg4, arg5, arg6, arg7, arg8)
+            })
         }
     }
 }
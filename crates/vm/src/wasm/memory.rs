@@ -1,14 +1,17 @@
 //! Wasm memory is used for bi-directionally passing data between the host and a
 //! wasm instance.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::ptr::NonNull;
 use std::rc::{self, Rc};
 use std::str::Utf8Error;
 
 use namada_core::arith::{self, checked};
 use namada_core::borsh::BorshSerializeExt;
-use namada_gas::{Gas, MEMORY_ACCESS_GAS_PER_BYTE};
+use namada_gas::trace::MemoryAccess;
+use namada_gas::Gas;
+#[cfg(feature = "wasmer-metering")]
+use namada_gas::GasMetering;
 use namada_tx::BatchedTxRef;
 use thiserror::Error;
 use wasmer::sys::BaseTunables;
@@ -22,6 +25,8 @@ use wasmer_vm::{
 
 use crate::memory::VmMemory;
 use crate::types::VpInput;
+#[cfg(feature = "wasmer-metering")]
+use crate::wasm::metering::FuelMeter;
 
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
@@ -305,6 +310,13 @@ fn write_memory_bytes(
 pub struct WasmMemory {
     store: rc::Weak<RefCell<Store>>,
     memory: Rc<RefCell<Option<wasmer::Memory>>>,
+    /// The cost of accessing the memory, per byte
+    memory_access_gas_per_byte: u64,
+    /// The number of bytes accessed by the host, for the gas trace
+    accessed: Rc<Cell<MemoryAccess>>,
+    /// The metering of the execution by the metering middleware
+    #[cfg(feature = "wasmer-metering")]
+    fuel_meter: Rc<RefCell<Option<FuelMeter>>>,
 }
 
 // TODO(namada#3313): Wasm memory is neither `Send` nor `Sync`, but we must
@@ -313,11 +325,18 @@ unsafe impl Send for WasmMemory {}
 unsafe impl Sync for WasmMemory {}
 
 impl WasmMemory {
-    /// Build a new wasm memory.
-    pub fn new(store: rc::Weak<RefCell<Store>>) -> Self {
+    /// Build a new wasm memory, charging the given cost per byte accessed.
+    pub fn new(
+        store: rc::Weak<RefCell<Store>>,
+        memory_access_gas_per_byte: u64,
+    ) -> Self {
         Self {
             store,
             memory: Rc::new(RefCell::new(None)),
+            memory_access_gas_per_byte,
+            accessed: Rc::new(Cell::new(MemoryAccess::default())),
+            #[cfg(feature = "wasmer-metering")]
+            fuel_meter: Rc::new(RefCell::new(None)),
         }
     }
 
@@ -330,6 +349,65 @@ impl WasmMemory {
         *self.memory.borrow_mut() = Some(memory.clone());
     }
 
+    /// The total number of bytes read from and written to the memory by the
+    /// host
+    pub fn accessed(&self) -> MemoryAccess {
+        self.accessed.get()
+    }
+
+    /// Start metering the execution of the instance with the metering
+    /// middleware. Must be called after the memory is initialized.
+    #[cfg(feature = "wasmer-metering")]
+    pub fn start_fuel_metering(
+        &self,
+        instance: &wasmer::Instance,
+        gas_meter: &RefCell<impl GasMetering>,
+    ) -> Result<()> {
+        self.access(|memory| {
+            let store = self
+                .store
+                .upgrade()
+                .expect("Store must be accessible while the WASM is running");
+            let mut store = store.borrow_mut();
+            *self.fuel_meter.borrow_mut() =
+                Some(FuelMeter::start(&mut store, instance, memory, gas_meter));
+            Ok(())
+        })
+    }
+
+    /// Charge the fuel consumed by the execution since the last sync to the
+    /// gas meter. This is a no-op if fuel metering hasn't started.
+    #[cfg(feature = "wasmer-metering")]
+    pub fn charge_fuel(
+        &self,
+        gas_meter: &RefCell<impl GasMetering>,
+    ) -> crate::wasm::run::Result<()> {
+        match self.fuel_meter.borrow_mut().as_mut() {
+            Some(fuel_meter) => {
+                let store = self.store.upgrade().expect(
+                    "Store must be accessible while the WASM is running",
+                );
+                let mut store = store.borrow_mut();
+                fuel_meter.charge(&mut store, gas_meter)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Set the fuel of the execution to the gas still available in the gas
+    /// meter. This is a no-op if fuel metering hasn't started.
+    #[cfg(feature = "wasmer-metering")]
+    pub fn refuel(&self, gas_meter: &RefCell<impl GasMetering>) {
+        if let Some(fuel_meter) = self.fuel_meter.borrow_mut().as_mut() {
+            let store = self
+                .store
+                .upgrade()
+                .expect("Store must be accessible while the WASM is running");
+            let mut store = store.borrow_mut();
+            fuel_meter.refuel(&mut store, gas_meter);
+        }
+    }
+
     /// Access the inner [`Memory`].
     #[inline]
     fn access<F, T>(&self, f: F) -> Result<T>
@@ -360,7 +438,10 @@ impl VmMemory for WasmMemory {
             let mut store = store.borrow_mut();
             let bytes = read_memory_bytes(&mut *store, memory, offset, len)?;
             let len = bytes.len() as u64;
-            let gas = checked!(len * MEMORY_ACCESS_GAS_PER_BYTE)?;
+            let gas = checked!(len * self.memory_access_gas_per_byte)?;
+            let mut accessed = self.accessed.get();
+            accessed.read_bytes = accessed.read_bytes.saturating_add(len);
+            self.accessed.set(accessed);
             Ok((bytes, gas.into()))
         })
     }
@@ -376,13 +457,16 @@ impl VmMemory for WasmMemory {
             // only writing to memory and we already charge gas for
             // every memory page allocated
             let len = bytes.as_ref().len() as u64;
-            let gas = checked!(len * MEMORY_ACCESS_GAS_PER_BYTE)?;
+            let gas = checked!(len * self.memory_access_gas_per_byte)?;
             let store = self
                 .store
                 .upgrade()
                 .expect("Store must be accessible while the WASM is running");
             let mut store = store.borrow_mut();
             write_memory_bytes(&mut *store, memory, offset, bytes)?;
+            let mut accessed = self.accessed.get();
+            accessed.written_bytes = accessed.written_bytes.saturating_add(len);
+            self.accessed.set(accessed);
             Ok(gas.into())
         })
     }
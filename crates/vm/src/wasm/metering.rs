@@ -0,0 +1,361 @@
+//! Gas metering of wasm code with wasmer's metering middleware, used instead
+//! of the injection of calls to the gas host function when the
+//! `wasmer-metering` feature is enabled.
+//!
+//! The middleware counts the cost of the instructions of each basic block of
+//! the compiled code, with the same costs as the injected instrumentation, and
+//! traps once its fuel is exhausted. The fuel is kept in sync with the gas
+//! meter: it's set to the gas still available before the execution and after
+//! every call to a host function, and the fuel consumed is charged to the gas
+//! meter, together with the cost of the grown memory pages, before every call
+//! to a host function and when the execution is done.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use namada_gas::trace::{MemoryAccess, WASM_GAS_HOOK};
+use namada_gas::{GasMetering, WASM_MEMORY_PAGE_GAS};
+use wasmer::wasmparser::Operator;
+use wasmer::{Instance, Memory, Store};
+use wasmer_middlewares::metering::{
+    get_remaining_points, set_remaining_points, MeteringPoints,
+};
+use wasmer_middlewares::Metering;
+
+use crate::wasm::run::{operator_cost, Error, Result};
+
+/// The metering middleware to add to the compiler
+pub fn middleware() -> Arc<Metering<fn(&Operator<'_>) -> u64>> {
+    // The fuel is set on each instance before it's executed
+    Arc::new(Metering::new(0, operator_cost as fn(&Operator<'_>) -> u64))
+}
+
+/// The metering of the execution of an instance
+#[derive(Debug)]
+pub struct FuelMeter {
+    instance: Instance,
+    memory: Memory,
+    /// The fuel set or left at the last sync with the gas meter
+    fuel: u64,
+    /// The memory pages at the last sync with the gas meter
+    pages: u32,
+}
+
+impl FuelMeter {
+    /// Start metering the execution of the instance, with the gas still
+    /// available in the gas meter as fuel
+    pub fn start(
+        store: &mut Store,
+        instance: &Instance,
+        memory: &Memory,
+        gas_meter: &RefCell<impl GasMetering>,
+    ) -> Self {
+        let mut fuel_meter = Self {
+            instance: instance.clone(),
+            memory: memory.clone(),
+            fuel: 0,
+            pages: memory.view(&*store).size().0,
+        };
+        fuel_meter.refuel(store, gas_meter);
+        fuel_meter
+    }
+
+    /// Set the fuel to the gas still available in the gas meter, e.g. after
+    /// a host function has charged gas
+    pub fn refuel(
+        &mut self,
+        store: &mut Store,
+        gas_meter: &RefCell<impl GasMetering>,
+    ) {
+        let fuel = gas_meter.borrow().get_available_gas().into();
+        set_remaining_points(store, &self.instance, fuel);
+        self.fuel = fuel;
+    }
+
+    /// Charge the fuel consumed and the memory pages grown since the last
+    /// sync to the gas meter
+    pub fn charge(
+        &mut self,
+        store: &mut Store,
+        gas_meter: &RefCell<impl GasMetering>,
+    ) -> Result<()> {
+        let (consumed, exhausted) =
+            match get_remaining_points(store, &self.instance) {
+                MeteringPoints::Remaining(remaining) => {
+                    (self.fuel.saturating_sub(remaining), false)
+                }
+                MeteringPoints::Exhausted => (self.fuel, true),
+            };
+        self.fuel = self.fuel.saturating_sub(consumed);
+        // Wasm memory cannot shrink
+        let pages = self.memory.view(&*store).size().0;
+        let grown_pages = pages.saturating_sub(self.pages);
+        self.pages = pages;
+        let gas = u64::from(grown_pages)
+            .saturating_mul(WASM_MEMORY_PAGE_GAS.into())
+            .saturating_add(consumed);
+
+        let mut gas_meter = gas_meter.borrow_mut();
+        if gas_meter.is_tracing() {
+            gas_meter.trace_host_call(
+                WASM_GAS_HOOK,
+                MemoryAccess::default(),
+                gas.into(),
+            );
+        }
+        gas_meter
+            .consume(gas.into())
+            .map_err(|e| Error::GasError(e.to_string()))?;
+        if exhausted {
+            return Err(Error::GasError(
+                "Wasm execution exhausted the gas limit".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_test_utils::TestWasms;
+    use parity_wasm::elements;
+    use wasm_instrument::gas_metering::host_function::Injector;
+    use wasm_instrument::gas_metering::Rules;
+    use wasmer::{
+        Function, FunctionEnv, FunctionEnvMut, FunctionType, Imports, Module,
+        RuntimeError, Type, Value,
+    };
+
+    use super::*;
+    use crate::wasm::memory;
+    use crate::wasm::run::{
+        prepare_wasm_code_with, wasm_store, GasRules, WASM_STACK_LIMIT,
+    };
+
+    /// The gas limit of the executions
+    const GAS_LIMIT: u64 = 100_000_000;
+
+    /// The outcome of running the entrypoint of a wasm
+    #[derive(Debug, PartialEq, Eq)]
+    enum Outcome {
+        Returned(u64),
+        Trapped,
+        OutOfGas,
+    }
+
+    /// Inject the stack limiter and then the gas metering instrumentation,
+    /// so that the instrumentation also meters the stack limiter's
+    /// instructions, like the metering middleware does
+    fn instrument_with_metered_stack_limiter(code: &[u8]) -> Vec<u8> {
+        let module: elements::Module =
+            elements::deserialize_buffer(code).unwrap();
+        let module =
+            wasm_instrument::inject_stack_limiter(module, WASM_STACK_LIMIT)
+                .unwrap_or_else(|_| panic!("Failed to inject the limiter"));
+        let module = wasm_instrument::gas_metering::inject(
+            module,
+            Injector::new("env", WASM_GAS_HOOK),
+            &GasRules,
+        )
+        .unwrap_or_else(|_| panic!("Failed to inject the gas metering"));
+        elements::serialize(module).unwrap()
+    }
+
+    /// The maximum difference of gas between the two backends for an
+    /// execution of the given code that traps: the cost of all the
+    /// instructions of every function, including the stack limiter's.
+    ///
+    /// The difference is the cost of the rest of the block that's being
+    /// executed in every frame of the call stack, so this bounds it as long as
+    /// no function is on the call stack more than once when it traps.
+    fn max_trap_gas_diff(code: &[u8]) -> u64 {
+        let module: elements::Module =
+            elements::deserialize_buffer(code).unwrap();
+        let module =
+            wasm_instrument::inject_stack_limiter(module, WASM_STACK_LIMIT)
+                .unwrap_or_else(|_| panic!("Failed to inject the limiter"));
+        module
+            .code_section()
+            .map(|section| section.bodies())
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|body| body.code().elements())
+            .map(|instruction| {
+                u64::from(GasRules.instruction_cost(instruction).unwrap_or(0))
+            })
+            .sum()
+    }
+
+    /// Run the entrypoint of the given code with the host functions stubbed
+    /// out, metered either with the injected instrumentation or with the
+    /// metering middleware. Returns the outcome and the consumed gas.
+    fn run(code: &[u8], fuel_metering: bool) -> (Outcome, u64) {
+        let code = if fuel_metering {
+            prepare_wasm_code_with(code, false).unwrap()
+        } else {
+            instrument_with_metered_stack_limiter(code)
+        };
+        let mut store = wasm_store(memory::vp_limit(), fuel_metering);
+        let module = Module::new(&store, code).unwrap();
+
+        // The gas consumed through the injected calls to the gas host function
+        let env = FunctionEnv::new(&mut store, 0_u64);
+        let mut imports = Imports::new();
+        for import in module.imports() {
+            let Some(ty) = import.ty().func().cloned() else {
+                continue;
+            };
+            let function = if import.name() == WASM_GAS_HOOK {
+                Function::new_with_env(
+                    &mut store,
+                    &env,
+                    ty,
+                    |mut env: FunctionEnvMut<'_, u64>, args: &[Value]| {
+                        let used = args[0].unwrap_i64() as u64;
+                        let consumed = env.data_mut();
+                        *consumed = consumed.saturating_add(used);
+                        if *consumed > GAS_LIMIT {
+                            return Err(RuntimeError::new("out of gas"));
+                        }
+                        Ok(vec![])
+                    },
+                )
+            } else {
+                stub(&mut store, ty)
+            };
+            imports.define(import.module(), import.name(), function);
+        }
+        let instance = Instance::new(&mut store, &module, &imports).unwrap();
+        let memory = instance.exports.get_memory("memory").unwrap().clone();
+        let entrypoint = instance
+            .exports
+            .iter()
+            .functions()
+            .find(|(name, _)| {
+                name.as_str() == "_apply_tx" || name.as_str() == "_validate_tx"
+            })
+            .map(|(_, function)| function.clone())
+            .unwrap();
+
+        let fuel_meter = fuel_metering.then(|| {
+            set_remaining_points(&mut store, &instance, GAS_LIMIT);
+            memory.view(&store).size().0
+        });
+        let args: Vec<Value> = entrypoint
+            .ty(&store)
+            .params()
+            .iter()
+            .map(|_| Value::I64(0))
+            .collect();
+        let result = entrypoint.call(&mut store, &args);
+
+        let (consumed, exhausted) = match fuel_meter {
+            Some(initial_pages) => {
+                let grown_pages =
+                    memory.view(&store).size().0.saturating_sub(initial_pages);
+                let memory_gas =
+                    u64::from(grown_pages) * u64::from(WASM_MEMORY_PAGE_GAS);
+                match get_remaining_points(&mut store, &instance) {
+                    MeteringPoints::Remaining(remaining) => {
+                        (GAS_LIMIT - remaining + memory_gas, false)
+                    }
+                    MeteringPoints::Exhausted => (GAS_LIMIT, true),
+                }
+            }
+            None => {
+                let consumed = *env.as_ref(&store);
+                (consumed, consumed > GAS_LIMIT)
+            }
+        };
+        let outcome = match result {
+            _ if exhausted => Outcome::OutOfGas,
+            Ok(values) => Outcome::Returned(
+                values.first().map(|v| v.unwrap_i64() as u64).unwrap_or(0),
+            ),
+            Err(_) => Outcome::Trapped,
+        };
+        (outcome, consumed)
+    }
+
+    /// A host function that returns zeroes
+    fn stub(store: &mut Store, ty: FunctionType) -> Function {
+        let results: Vec<Value> = ty
+            .results()
+            .iter()
+            .map(|ty| match ty {
+                Type::I32 => Value::I32(0),
+                Type::I64 => Value::I64(0),
+                Type::F32 => Value::F32(0.0),
+                Type::F64 => Value::F64(0.0),
+                _ => unreachable!("Forbidden by the wasm validation"),
+            })
+            .collect();
+        Function::new(store, ty, move |_args| Ok(results.clone()))
+    }
+
+    /// Run all the wasms for tests with both the injected instrumentation and
+    /// the metering middleware and check that they have the same outcome and
+    /// consume the same gas.
+    ///
+    /// In the ledger, the instrumentation doesn't meter the stack limiter,
+    /// which is injected after it, so it's injected before the gas metering
+    /// here. Both backends then charge the same cost for every basic block,
+    /// but the instrumentation charges a block when entering it while the
+    /// middleware charges it at the branch that ends it. The gas therefore
+    /// only differs for an execution that traps, by the cost of the rest of
+    /// the blocks of the functions on the call stack, which is bounded by
+    /// [`max_trap_gas_diff`] of the wasm.
+    #[test]
+    fn test_metering_backends_are_equivalent() {
+        const CORPUS: [TestWasms; 22] = [
+            TestWasms::TxFail,
+            TestWasms::TxFailEvent,
+            TestWasms::TxMemoryLimit,
+            TestWasms::TxNoOp,
+            TestWasms::TxNoOpEvent,
+            TestWasms::TxInvalidData,
+            TestWasms::TxInfiniteGuestGas,
+            TestWasms::TxInfiniteHostGas,
+            TestWasms::TxProposalCode,
+            TestWasms::TxProposalMaspRewards,
+            TestWasms::TxProposalIbcTokenInflation,
+            TestWasms::TxProposalTokenGas,
+            TestWasms::TxReadStorageKey,
+            TestWasms::TxWriteStorageKey,
+            TestWasms::VpAlwaysFalse,
+            TestWasms::VpAlwaysTrue,
+            TestWasms::VpEval,
+            TestWasms::VpInfiniteGuestGas,
+            TestWasms::VpInfiniteHostGas,
+            TestWasms::VpMemoryLimit,
+            TestWasms::VpReadStorageKey,
+            TestWasms::VpVerifySignature,
+        ];
+        for wasm in CORPUS {
+            let code = wasm.read_bytes();
+            let (instrumented, instrumented_gas) = run(&code, false);
+            let (metered, metered_gas) = run(&code, true);
+            assert_eq!(
+                instrumented, metered,
+                "Different outcomes for {wasm:?}"
+            );
+            match metered {
+                Outcome::Returned(_) => assert_eq!(
+                    instrumented_gas, metered_gas,
+                    "Different gas for {wasm:?}"
+                ),
+                Outcome::Trapped => {
+                    let max_diff = max_trap_gas_diff(&code);
+                    assert!(
+                        metered_gas <= instrumented_gas
+                            && instrumented_gas - metered_gas <= max_diff,
+                        "Gas out of bounds for {wasm:?}: {instrumented_gas} \
+                         with the instrumentation, {metered_gas} with the \
+                         middleware, {max_diff} at most apart"
+                    )
+                }
+                Outcome::OutOfGas => {}
+            }
+        }
+    }
+}
@@ -3,6 +3,8 @@
 pub mod compilation_cache;
 pub mod host_env;
 pub mod memory;
+#[cfg(feature = "wasmer-metering")]
+mod metering;
 pub mod run;
 
 pub use compilation_cache::common::{Cache, CacheName};
@@ -14,11 +14,12 @@ use namada_core::hash::{Error as TxHashError, Hash};
 use namada_core::internal::HostEnvResult;
 use namada_core::storage::{Key, TxIndex};
 use namada_core::validity_predicate::VpError;
+use namada_gas::trace::WASM_GAS_HOOK;
 use namada_gas::{GasMetering, TxGasMeter, VpGasMeter, WASM_MEMORY_PAGE_GAS};
 use namada_state::prefix_iter::PrefixIterators;
 use namada_state::{DBIter, State, StateRead, StorageHasher, StorageRead, DB};
 use namada_tx::data::{TxSentinel, TxType};
-use namada_tx::{BatchedTxRef, Commitment, Section, Tx, TxCommitments};
+use namada_tx::{BatchedTxRef, Commitment, Data, Section, Tx, TxCommitments};
 use namada_vp::vp_host_fns;
 use parity_wasm::elements::Instruction::*;
 use parity_wasm::elements::{self, SignExtInstruction};
@@ -28,7 +29,7 @@ use wasmer::{Engine, Module, NativeEngineExt, Store, Target};
 
 use super::memory::{Limit, WasmMemory};
 use super::TxCache;
-use crate::host_env::{TxVmEnv, VpCtx, VpEvaluator, VpVmEnv};
+use crate::host_env::{TxCtx, TxVmEnv, VpCtx, VpEvaluator, VpVmEnv};
 use crate::types::VpInput;
 use crate::wasm::host_env::{tx_imports, vp_imports};
 use crate::wasm::{memory, Cache, CacheName, VpCache};
@@ -39,7 +40,9 @@ use crate::{
 
 const TX_ENTRYPOINT: &str = "_apply_tx";
 const VP_ENTRYPOINT: &str = "_validate_tx";
-const WASM_STACK_LIMIT: u32 = u16::MAX as u32;
+pub(super) const WASM_STACK_LIMIT: u32 = u16::MAX as u32;
+/// The maximum depth of nested `namada_tx_call` calls
+pub const MAX_TX_CALL_DEPTH: u8 = 4;
 
 /// The error type returned by transactions.
 // TODO(namada#2980): move this to `core`, to be shared with the wasm vm,
@@ -102,6 +105,8 @@ pub enum Error {
     DisallowedTx,
     #[error("Invalid transaction section signature: {0}")]
     InvalidSectionSignature(String),
+    #[error("Exceeded the maximum depth of {0} nested tx calls")]
+    TxCallDepthExceeded(u8),
 }
 
 /// Result for functions that may fail
@@ -192,18 +197,148 @@ where
 
     let (module, store) =
         fetch_or_compile(tx_wasm_cache, &tx_code.code, state, gas_meter)?;
+    let mut verifiers = BTreeSet::new();
+    execute_tx(
+        state,
+        module,
+        store,
+        gas_meter,
+        tx_index,
+        tx,
+        cmt,
+        &mut verifiers,
+        vp_wasm_cache,
+        tx_wasm_cache,
+        0,
+    )?;
+    Ok(verifiers)
+}
+
+/// Execute another allowlisted transaction code called from a transaction with
+/// the `namada_tx_call` host function. The code runs in the same write log and
+/// with the same gas meter and verifiers as the calling transaction, with the
+/// given data as the data of its inner transaction.
+///
+/// Returns [`HostEnvResult::Fail`] when the call is refused before executing
+/// the code, e.g. because the code is not allowlisted or the maximum call
+/// depth is reached. An error of the called code is returned as an error, as
+/// its storage modifications cannot be reverted separately.
+pub(crate) fn call_tx<D, H, CA>(
+    ctx: &TxCtx<D, H, CA>,
+    code_hash: Hash,
+    data: Vec<u8>,
+) -> Result<HostEnvResult>
+where
+    D: DB + for<'iter> DBIter<'iter> + 'static,
+    H: StorageHasher + 'static,
+    CA: 'static + WasmCacheAccess,
+{
+    // The calls refused before executing any code leave the storage
+    // untouched, so the calling tx can handle them
+    let refuse = |err: Error| {
+        tracing::debug!("Refused tx call of {code_hash}: {err}");
+        Ok(HostEnvResult::Fail)
+    };
+
+    let Some(call_depth) = ctx
+        .call_depth
+        .checked_add(1)
+        .filter(|depth| *depth <= MAX_TX_CALL_DEPTH)
+    else {
+        return refuse(Error::TxCallDepthExceeded(MAX_TX_CALL_DEPTH));
+    };
+
+    let mut state = ctx.state();
+    if !namada_parameters::is_tx_allowed(&state, &code_hash)
+        .map_err(|e| Error::Error(e.to_string()))?
+    {
+        return refuse(Error::DisallowedTx);
+    }
+
+    let gas_meter = unsafe { ctx.gas_meter.get() };
+    let tx_wasm_cache = unsafe { ctx.tx_wasm_cache.get_mut() };
+    let (module, store) = match fetch_or_compile(
+        tx_wasm_cache,
+        &Commitment::Hash(code_hash),
+        &state,
+        gas_meter,
+    ) {
+        Ok(compiled) => compiled,
+        Err(err @ Error::GasError(_)) => return Err(err),
+        Err(err) => return refuse(err),
+    };
+
+    // The called code gets the data in a new section of the calling tx, which
+    // keeps the header and hence the signatures of the tx unchanged. The copy
+    // of the calling tx is charged like any other memory access.
+    let calling_tx = unsafe { ctx.tx.get() };
+    let tx_len = borsh::object_length(calling_tx)
+        .map_err(|e| Error::Error(e.to_string()))?;
+    let copy_gas = (tx_len as u64)
+        .checked_mul(
+            gas_meter.borrow().gas_schedule().memory_access_gas_per_byte,
+        )
+        .ok_or_else(|| Error::GasError("Overflow in tx copy gas".into()))?;
+    gas_meter
+        .borrow_mut()
+        .consume(copy_gas.into())
+        .map_err(|e| Error::GasError(e.to_string()))?;
+    let mut tx = calling_tx.clone();
+    let data_hash = tx.add_section(Section::Data(Data::new(data))).get_hash();
+    let cmt = TxCommitments {
+        data_hash,
+        ..unsafe { ctx.cmt.get() }.clone()
+    };
+    execute_tx(
+        &mut state,
+        module,
+        store,
+        gas_meter,
+        unsafe { ctx.tx_index.get() },
+        &tx,
+        &cmt,
+        unsafe { ctx.verifiers.get_mut() },
+        unsafe { ctx.vp_wasm_cache.get_mut() },
+        tx_wasm_cache,
+        call_depth,
+    )?;
+    Ok(HostEnvResult::Success)
+}
+
+/// Execute a compiled transaction code, `call_depth` being the number of
+/// `namada_tx_call` calls that led to its execution.
+#[allow(clippy::too_many_arguments)]
+fn execute_tx<S, CA>(
+    state: &mut S,
+    module: Module,
+    store: Store,
+    gas_meter: &RefCell<TxGasMeter>,
+    tx_index: &TxIndex,
+    tx: &Tx,
+    cmt: &TxCommitments,
+    verifiers: &mut BTreeSet<Address>,
+    vp_wasm_cache: &mut VpCache<CA>,
+    tx_wasm_cache: &mut TxCache<CA>,
+    call_depth: u8,
+) -> Result<()>
+where
+    S: StateRead + State + StorageRead,
+    CA: 'static + WasmCacheAccess,
+{
+    let batched_tx = tx.batch_ref_tx(cmt);
     let store = Rc::new(RefCell::new(store));
 
     let mut iterators: PrefixIterators<'_, <S as StateRead>::D> =
         PrefixIterators::default();
-    let mut verifiers = BTreeSet::new();
     let mut result_buffer: Option<Vec<u8>> = None;
     let mut yielded_value: Option<Vec<u8>> = None;
 
     let sentinel = RefCell::new(TxSentinel::default());
+    let memory_access_gas_per_byte =
+        gas_meter.borrow().gas_schedule().memory_access_gas_per_byte;
     let (write_log, in_mem, db) = state.split_borrow();
     let mut env = TxVmEnv::new(
-        WasmMemory::new(Rc::downgrade(&store)),
+        WasmMemory::new(Rc::downgrade(&store), memory_access_gas_per_byte),
         write_log,
         in_mem,
         db,
@@ -213,12 +348,13 @@ where
         tx,
         cmt,
         tx_index,
-        &mut verifiers,
+        verifiers,
         &mut result_buffer,
         &mut yielded_value,
         vp_wasm_cache,
         tx_wasm_cache,
     );
+    env.ctx.call_depth = call_depth;
 
     // Instantiate the wasm module
     let instance = {
@@ -260,22 +396,27 @@ where
                 error,
             })?
     };
-    let ok = apply_tx
-        .call(
-            unsafe { &mut *RefCell::as_ptr(&*store) },
-            tx_data_ptr,
-            tx_data_len,
-        )
-        .map_err(|err| {
-            tracing::debug!("Tx WASM failed with {}", err);
-            match *sentinel.borrow() {
-                TxSentinel::None => Error::RuntimeError(err),
-                TxSentinel::OutOfGas => Error::GasError(err.to_string()),
-                TxSentinel::InvalidCommitment => {
-                    Error::MissingSection(err.to_string())
-                }
+    #[cfg(feature = "wasmer-metering")]
+    env.memory
+        .start_fuel_metering(&instance, gas_meter)
+        .map_err(Error::MemoryError)?;
+    let result = apply_tx.call(
+        unsafe { &mut *RefCell::as_ptr(&*store) },
+        tx_data_ptr,
+        tx_data_len,
+    );
+    #[cfg(feature = "wasmer-metering")]
+    env.memory.charge_fuel(gas_meter)?;
+    let ok = result.map_err(|err| {
+        tracing::debug!("Tx WASM failed with {}", err);
+        match *sentinel.borrow() {
+            TxSentinel::None => Error::RuntimeError(err),
+            TxSentinel::OutOfGas => Error::GasError(err.to_string()),
+            TxSentinel::InvalidCommitment => {
+                Error::MissingSection(err.to_string())
             }
-        })?;
+        }
+    })?;
 
     // NB: early drop this data to avoid memory errors
     _ = (instance, env);
@@ -284,7 +425,7 @@ where
         let store = Rc::into_inner(store)
             .expect("The store must be dropped after execution to avoid leaks");
         let _store = RefCell::into_inner(store);
-        Ok(verifiers)
+        Ok(())
     } else {
         let err = yielded_value.take().map_or_else(
             || Ok("Execution ended abruptly with an unknown error".to_owned()),
@@ -342,8 +483,10 @@ where
             cache_access: PhantomData,
         };
     let BatchedTxRef { tx, cmt } = batched_tx;
-    let mut env = VpVmEnv::new(
-        WasmMemory::new(Rc::downgrade(&store)),
+    let memory_access_gas_per_byte =
+        gas_meter.borrow().gas_schedule().memory_access_gas_per_byte;
+    let env = VpVmEnv::new(
+        WasmMemory::new(Rc::downgrade(&store), memory_access_gas_per_byte),
         address,
         state.write_log(),
         state.in_mem(),
@@ -377,13 +520,14 @@ where
         address,
         keys_changed,
         verifiers,
+        gas_meter,
         yielded_value_borrow,
-        |guest_memory| env.memory.init_from(guest_memory),
+        env.memory.clone(),
     )
 }
 
 #[allow(clippy::too_many_arguments)]
-fn run_vp<F>(
+fn run_vp(
     store: Rc<RefCell<wasmer::Store>>,
     module: wasmer::Module,
     vp_imports: wasmer::Imports,
@@ -392,12 +536,11 @@ fn run_vp<F>(
     address: &Address,
     keys_changed: &BTreeSet<Key>,
     verifiers: &BTreeSet<Address>,
+    #[cfg_attr(not(feature = "wasmer-metering"), allow(unused_variables))]
+    gas_meter: &RefCell<VpGasMeter>,
     yielded_value: HostRef<RwAccess, Option<Vec<u8>>>,
-    mut init_memory_callback: F,
-) -> Result<()>
-where
-    F: FnMut(&wasmer::Memory),
-{
+    mut wasm_memory: WasmMemory,
+) -> Result<()> {
     let input: VpInput<'_> = VpInput {
         addr: address,
         data: input_data,
@@ -418,7 +561,7 @@ where
         .get_memory("memory")
         .map_err(Error::MissingModuleMemory)?;
 
-    init_memory_callback(guest_memory);
+    wasm_memory.init_from(guest_memory);
 
     // Write the inputs in the memory exported from the wasm
     // module
@@ -450,41 +593,44 @@ where
                 error,
             })?
     };
-    let is_valid = validate_tx
-        .call(
-            unsafe { &mut *RefCell::as_ptr(&*store) },
-            addr_ptr,
-            addr_len,
-            data_ptr,
-            data_len,
-            keys_changed_ptr,
-            keys_changed_len,
-            verifiers_ptr,
-            verifiers_len,
-        )
-        .map_err(|rt_error| {
-            let downcasted_err = || {
-                let source_err = rt_error.source()?;
-                let downcasted_vp_err =
-                    source_err.downcast_ref::<vp_host_fns::Error>()?;
-                let downcasted_vp_rt_err = downcasted_vp_err
-                    .downcast_ref::<vp_host_fns::RuntimeError>(
-                )?;
-
-                match downcasted_vp_rt_err {
-                    vp_host_fns::RuntimeError::OutOfGas(_) => {
-                        Some(Error::GasError(rt_error.to_string()))
-                    }
-                    vp_host_fns::RuntimeError::InvalidSectionSignature(_) => {
-                        Some(Error::InvalidSectionSignature(
-                            rt_error.to_string(),
-                        ))
-                    }
-                    _ => None,
+    #[cfg(feature = "wasmer-metering")]
+    wasm_memory
+        .start_fuel_metering(&instance, gas_meter)
+        .map_err(Error::MemoryError)?;
+    let result = validate_tx.call(
+        unsafe { &mut *RefCell::as_ptr(&*store) },
+        addr_ptr,
+        addr_len,
+        data_ptr,
+        data_len,
+        keys_changed_ptr,
+        keys_changed_len,
+        verifiers_ptr,
+        verifiers_len,
+    );
+    #[cfg(feature = "wasmer-metering")]
+    wasm_memory.charge_fuel(gas_meter)?;
+    let is_valid = result.map_err(|rt_error| {
+        let downcasted_err = || {
+            let source_err = rt_error.source()?;
+            let downcasted_vp_err =
+                source_err.downcast_ref::<vp_host_fns::Error>()?;
+            let downcasted_vp_rt_err = downcasted_vp_err
+                .downcast_ref::<vp_host_fns::RuntimeError>(
+            )?;
+
+            match downcasted_vp_rt_err {
+                vp_host_fns::RuntimeError::OutOfGas(_) => {
+                    Some(Error::GasError(rt_error.to_string()))
                 }
-            };
-            downcasted_err().unwrap_or(Error::RuntimeError(rt_error))
-        })?;
+                vp_host_fns::RuntimeError::InvalidSectionSignature(_) => {
+                    Some(Error::InvalidSectionSignature(rt_error.to_string()))
+                }
+                _ => None,
+            }
+        };
+        downcasted_err().unwrap_or(Error::RuntimeError(rt_error))
+    })?;
     tracing::debug!(
         is_valid,
         %vp_code_hash,
@@ -634,8 +780,13 @@ where
         )?;
         let store = Rc::new(RefCell::new(store));
 
-        let mut env = VpVmEnv {
-            memory: WasmMemory::new(Rc::downgrade(&store)),
+        let memory_access_gas_per_byte =
+            gas_meter.borrow().gas_schedule().memory_access_gas_per_byte;
+        let env = VpVmEnv {
+            memory: WasmMemory::new(
+                Rc::downgrade(&store),
+                memory_access_gas_per_byte,
+            ),
             ctx,
         };
         let yielded_value_borrow = env.ctx.yielded_value;
@@ -653,16 +804,36 @@ where
             address,
             keys_changed,
             verifiers,
+            gas_meter,
             yielded_value_borrow,
-            |guest_memory| env.memory.init_from(guest_memory),
+            env.memory.clone(),
         )
     }
 }
 
 /// Prepare a wasm store for untrusted code.
 pub fn untrusted_wasm_store(limit: Limit<BaseTunables>) -> wasmer::Store {
+    wasm_store(limit, cfg!(feature = "wasmer-metering"))
+}
+
+/// Prepare a wasm store, optionally with the gas metering middleware
+pub(crate) fn wasm_store(
+    limit: Limit<BaseTunables>,
+    fuel_metering: bool,
+) -> wasmer::Store {
     // Use Singlepass compiler with the default settings
-    let compiler = wasmer_compiler_singlepass::Singlepass::default();
+    #[allow(unused_mut)]
+    let mut compiler = wasmer_compiler_singlepass::Singlepass::default();
+    #[cfg(feature = "wasmer-metering")]
+    if fuel_metering {
+        use wasmer::CompilerConfig;
+        compiler.push_middleware(super::metering::middleware());
+    }
+    #[cfg(not(feature = "wasmer-metering"))]
+    assert!(
+        !fuel_metering,
+        "The `wasmer-metering` feature is not enabled"
+    );
     let mut engine = <Engine as NativeEngineExt>::new(
         Box::new(compiler),
         // NB: The default target corresponds to the host's triplet
@@ -675,18 +846,34 @@ pub fn untrusted_wasm_store(limit: Limit<BaseTunables>) -> wasmer::Store {
     wasmer::Store::new(engine)
 }
 
-/// Inject gas counter and stack-height limiter into the given wasm code
+/// Inject gas counter and stack-height limiter into the given wasm code. With
+/// the `wasmer-metering` feature, the gas is metered by the compiler's
+/// middleware instead and only the stack-height limiter is injected.
 pub fn prepare_wasm_code<T: AsRef<[u8]>>(code: T) -> Result<Vec<u8>> {
+    prepare_wasm_code_with(code, !cfg!(feature = "wasmer-metering"))
+}
+
+/// Inject the stack-height limiter and optionally the gas counter into the
+/// given wasm code
+pub(crate) fn prepare_wasm_code_with<T: AsRef<[u8]>>(
+    code: T,
+    inject_gas: bool,
+) -> Result<Vec<u8>> {
     let module: elements::Module = elements::deserialize_buffer(code.as_ref())
         .map_err(Error::DeserializationError)?;
-    let module = wasm_instrument::gas_metering::inject(
-        module,
-        wasm_instrument::gas_metering::host_function::Injector::new(
-            "env", "gas",
-        ),
-        &GasRules,
-    )
-    .map_err(|_original_module| Error::GasMeterInjection)?;
+    let module = if inject_gas {
+        wasm_instrument::gas_metering::inject(
+            module,
+            wasm_instrument::gas_metering::host_function::Injector::new(
+                "env",
+                WASM_GAS_HOOK,
+            ),
+            &GasRules,
+        )
+        .map_err(|_original_module| Error::GasMeterInjection)?
+    } else {
+        module
+    };
     let module =
         wasm_instrument::inject_stack_limiter(module, WASM_STACK_LIMIT)
             .map_err(|_original_module| Error::StackLimiterInjection)?;
@@ -783,209 +970,240 @@ where
     }
 }
 
-struct GasRules;
+/// Define the gas costs of the wasm instructions for both gas metering
+/// backends from a single table. Each row gives the instruction as named by
+/// `parity_wasm`, for the injected instrumentation, and by `wasmparser`, for
+/// the metering middleware, followed by its cost.
+macro_rules! instruction_costs {
+    ($($instruction:pat, $operator:pat => $cost:literal,)*) => {
+        /// The cost of an instruction for the injected instrumentation
+        fn instruction_cost(instruction: &elements::Instruction) -> u32 {
+            match instruction {
+                $($instruction => $cost,)*
+            }
+        }
+
+        /// The cost of an instruction for the metering middleware
+        #[cfg(feature = "wasmer-metering")]
+        pub(super) fn operator_cost(
+            operator: &wasmer::wasmparser::Operator<'_>,
+        ) -> u64 {
+            use wasmer::wasmparser::Operator::*;
+
+            match operator {
+                $($operator => $cost,)*
+                // Instructions of the wasm features that are forbidden, these
+                // are rejected at validation time
+                _ => 1,
+            }
+        }
+    };
+}
+
+// NOTE: costs set to 0 don't actually trigger the injection of a call to the
+// gas host function (no useless instructions are injected)
+// NOTE: these costs are taken from the benchmarks crate. None of them should
+// be zero
+instruction_costs! {
+    // NOTE: the real cost of this operation is 57_330 but because of
+    // the behavior of the instrumentaiton tools which doesn't account
+    // for traps in called functions we need to reduce it to 1 otherwise
+    // the gas costs explode
+    Unreachable, Unreachable => 1,
+    // Just a label, aribitrary cost of 1
+    End, End => 1,
+    // Just a label, aribitrary cost of 1
+    Else, Else => 1,
+    Nop, Nop => 1,
+    // Just a label, cost of 1
+    Block(_), Block { .. } => 1,
+    // Just a label, cost of 1
+    Loop(_), Loop { .. } => 1,
+    If(_), If { .. } => 5,
+    Br(_), Br { .. } => 14,
+    BrIf(_), BrIf { .. } => 14,
+    BrTable(_), BrTable { .. } => 56,
+    Return, Return => 4,
+    Call(_), Call { .. } => 16,
+    CallIndirect(_, _), CallIndirect { .. } => 28,
+    Drop, Drop => 1,
+    Select, Select => 11,
+    GetLocal(_), LocalGet { .. } => 1,
+    SetLocal(_), LocalSet { .. } => 2,
+    TeeLocal(_), LocalTee { .. } => 2,
+    GetGlobal(_), GlobalGet { .. } => 4,
+    SetGlobal(_), GlobalSet { .. } => 5,
+    I32Load(_, _), I32Load { .. } => 8,
+    I64Load(_, _), I64Load { .. } => 8,
+    F32Load(_, _), F32Load { .. } => 9,
+    F64Load(_, _), F64Load { .. } => 9,
+    I32Load8S(_, _), I32Load8S { .. } => 8,
+    I32Load8U(_, _), I32Load8U { .. } => 8,
+    I32Load16S(_, _), I32Load16S { .. } => 8,
+    I32Load16U(_, _), I32Load16U { .. } => 8,
+    I64Load8S(_, _), I64Load8S { .. } => 8,
+    I64Load8U(_, _), I64Load8U { .. } => 8,
+    I64Load16S(_, _), I64Load16S { .. } => 8,
+    I64Load16U(_, _), I64Load16U { .. } => 8,
+    I64Load32S(_, _), I64Load32S { .. } => 7,
+    I64Load32U(_, _), I64Load32U { .. } => 7,
+    I32Store(_, _), I32Store { .. } => 8,
+    I64Store(_, _), I64Store { .. } => 9,
+    F32Store(_, _), F32Store { .. } => 8,
+    F64Store(_, _), F64Store { .. } => 9,
+    I32Store8(_, _), I32Store8 { .. } => 7,
+    I32Store16(_, _), I32Store16 { .. } => 13,
+    I64Store8(_, _), I64Store8 { .. } => 7,
+    I64Store16(_, _), I64Store16 { .. } => 12,
+    I64Store32(_, _), I64Store32 { .. } => 8,
+    CurrentMemory(_), MemorySize { .. } => 110,
+    // The grown pages are charged separately
+    GrowMemory(_), MemoryGrow { .. } => 194,
+    I32Const(_), I32Const { .. } => 1,
+    I64Const(_), I64Const { .. } => 1,
+    F32Const(_), F32Const { .. } => 1,
+    F64Const(_), F64Const { .. } => 1,
+    I32Eqz, I32Eqz => 6,
+    I32Eq, I32Eq => 6,
+    I32Ne, I32Ne => 6,
+    I32LtS, I32LtS => 6,
+    I32LtU, I32LtU => 6,
+    I32GtS, I32GtS => 6,
+    I32GtU, I32GtU => 6,
+    I32LeS, I32LeS => 6,
+    I32LeU, I32LeU => 6,
+    I32GeS, I32GeS => 6,
+    I32GeU, I32GeU => 6,
+    I64Eqz, I64Eqz => 8,
+    I64Eq, I64Eq => 8,
+    I64Ne, I64Ne => 8,
+    I64LtS, I64LtS => 8,
+    I64LtU, I64LtU => 8,
+    I64GtS, I64GtS => 8,
+    I64GtU, I64GtU => 8,
+    I64LeS, I64LeS => 8,
+    I64LeU, I64LeU => 8,
+    I64GeS, I64GeS => 8,
+    I64GeU, I64GeU => 8,
+    F32Eq, F32Eq => 10,
+    F32Ne, F32Ne => 10,
+    F32Lt, F32Lt => 10,
+    F32Gt, F32Gt => 9,
+    F32Le, F32Le => 10,
+    F32Ge, F32Ge => 10,
+    F64Eq, F64Eq => 11,
+    F64Ne, F64Ne => 11,
+    F64Lt, F64Lt => 11,
+    F64Gt, F64Gt => 12,
+    F64Le, F64Le => 11,
+    F64Ge, F64Ge => 11,
+    I32Clz, I32Clz => 3,
+    I32Ctz, I32Ctz => 3,
+    I32Popcnt, I32Popcnt => 3,
+    I32Add, I32Add => 4,
+    I32Sub, I32Sub => 4,
+    I32Mul, I32Mul => 6,
+    I32DivS, I32DivS => 18,
+    I32DivU, I32DivU => 18,
+    I32RemS, I32RemS => 18,
+    I32RemU, I32RemU => 18,
+    I32And, I32And => 4,
+    I32Or, I32Or => 4,
+    I32Xor, I32Xor => 4,
+    I32Shl, I32Shl => 4,
+    I32ShrS, I32ShrS => 4,
+    I32ShrU, I32ShrU => 4,
+    I32Rotl, I32Rotl => 4,
+    I32Rotr, I32Rotr => 4,
+    I64Clz, I64Clz => 4,
+    I64Ctz, I64Ctz => 4,
+    I64Popcnt, I64Popcnt => 4,
+    I64Add, I64Add => 7,
+    I64Sub, I64Sub => 7,
+    I64Mul, I64Mul => 8,
+    I64DivS, I64DivS => 30,
+    I64DivU, I64DivU => 30,
+    I64RemS, I64RemS => 31,
+    I64RemU, I64RemU => 30,
+    I64And, I64And => 7,
+    I64Or, I64Or => 7,
+    I64Xor, I64Xor => 7,
+    I64Shl, I64Shl => 6,
+    I64ShrS, I64ShrS => 6,
+    I64ShrU, I64ShrU => 6,
+    I64Rotl, I64Rotl => 6,
+    I64Rotr, I64Rotr => 6,
+    F32Abs, F32Abs => 5,
+    F32Neg, F32Neg => 4,
+    F32Ceil, F32Ceil => 7,
+    F32Floor, F32Floor => 7,
+    F32Trunc, F32Trunc => 7,
+    F32Nearest, F32Nearest => 7,
+    F32Sqrt, F32Sqrt => 10,
+    F32Add, F32Add => 7,
+    F32Sub, F32Sub => 7,
+    F32Mul, F32Mul => 7,
+    F32Div, F32Div => 10,
+    F32Min, F32Min => 21,
+    F32Max, F32Max => 19,
+    F32Copysign, F32Copysign => 9,
+    F64Abs, F64Abs => 7,
+    F64Neg, F64Neg => 5,
+    F64Ceil, F64Ceil => 9,
+    F64Floor, F64Floor => 9,
+    F64Trunc, F64Trunc => 9,
+    F64Nearest, F64Nearest => 9,
+    F64Sqrt, F64Sqrt => 19,
+    F64Add, F64Add => 9,
+    F64Sub, F64Sub => 9,
+    F64Mul, F64Mul => 9,
+    F64Div, F64Div => 12,
+    F64Min, F64Min => 24,
+    F64Max, F64Max => 31,
+    F64Copysign, F64Copysign => 13,
+    I32WrapI64, I32WrapI64 => 2,
+    I32TruncSF32, I32TruncF32S => 24,
+    I32TruncUF32, I32TruncF32U => 25,
+    I32TruncSF64, I32TruncF64S => 28,
+    I32TruncUF64, I32TruncF64U => 27,
+    I64ExtendSI32, I64ExtendI32S => 3,
+    I64ExtendUI32, I64ExtendI32U => 2,
+    I64TruncSF32, I64TruncF32S => 24,
+    I64TruncUF32, I64TruncF32U => 39,
+    I64TruncSF64, I64TruncF64S => 27,
+    I64TruncUF64, I64TruncF64U => 46,
+    F32ConvertSI32, F32ConvertI32S => 12,
+    F32ConvertUI32, F32ConvertI32U => 6,
+    F32ConvertSI64, F32ConvertI64S => 6,
+    F32ConvertUI64, F32ConvertI64U => 12,
+    F32DemoteF64, F32DemoteF64 => 9,
+    F64ConvertSI32, F64ConvertI32S => 12,
+    F64ConvertUI32, F64ConvertI32U => 12,
+    F64ConvertSI64, F64ConvertI64S => 12,
+    F64ConvertUI64, F64ConvertI64U => 12,
+    F64PromoteF32, F64PromoteF32 => 9,
+    I32ReinterpretF32, I32ReinterpretF32 => 2,
+    I64ReinterpretF64, I64ReinterpretF64 => 3,
+    F32ReinterpretI32, F32ReinterpretI32 => 3,
+    F64ReinterpretI64, F64ReinterpretI64 => 4,
+    SignExt(SignExtInstruction::I32Extend8S), I32Extend8S => 1,
+    SignExt(SignExtInstruction::I32Extend16S), I32Extend16S => 1,
+    SignExt(SignExtInstruction::I64Extend8S), I64Extend8S => 1,
+    SignExt(SignExtInstruction::I64Extend16S), I64Extend16S => 1,
+    SignExt(SignExtInstruction::I64Extend32S), I64Extend32S => 1,
+}
+
+/// The gas rules of the injected instrumentation
+pub(super) struct GasRules;
 
 impl wasm_instrument::gas_metering::Rules for GasRules {
     fn instruction_cost(
         &self,
         instruction: &wasm_instrument::parity_wasm::elements::Instruction,
     ) -> Option<u32> {
-        // NOTE: costs set to 0 don't actually trigger the injection of a call
-        // to the gas host function (no useless instructions are
-        // injected)
-        // NOTE: these costs are taken from the benchmarks crate. None of them
-        // should be zero
-        let gas = match instruction {
-            // NOTE: the real cost of this operation is 57_330 but because of
-            // the behavior of the instrumentaiton tools which doesn't account
-            // for traps in called functions we need to reduce it to 1 otherwise
-            // the gas costs explode
-            Unreachable => 1,
-            // Just a label, aribitrary cost of 1
-            End => 1,
-            // Just a label, aribitrary cost of 1
-            Else => 1,
-            Nop => 1,
-            // Just a label, cost of 1
-            Block(_) => 1,
-            // Just a label, cost of 1
-            Loop(_) => 1,
-            If(_) => 5,
-            Br(_) => 14,
-            BrIf(_) => 14,
-            BrTable(_) => 56,
-            Return => 4,
-            Call(_) => 16,
-            CallIndirect(_, _) => 28,
-            Drop => 1,
-            Select => 11,
-            GetLocal(_) => 1,
-            SetLocal(_) => 2,
-            TeeLocal(_) => 2,
-            GetGlobal(_) => 4,
-            SetGlobal(_) => 5,
-            I32Load(_, _) => 8,
-            I64Load(_, _) => 8,
-            F32Load(_, _) => 9,
-            F64Load(_, _) => 9,
-            I32Load8S(_, _) => 8,
-            I32Load8U(_, _) => 8,
-            I32Load16S(_, _) => 8,
-            I32Load16U(_, _) => 8,
-            I64Load8S(_, _) => 8,
-            I64Load8U(_, _) => 8,
-            I64Load16S(_, _) => 8,
-            I64Load16U(_, _) => 8,
-            I64Load32S(_, _) => 7,
-            I64Load32U(_, _) => 7,
-            I32Store(_, _) => 8,
-            I64Store(_, _) => 9,
-            F32Store(_, _) => 8,
-            F64Store(_, _) => 9,
-            I32Store8(_, _) => 7,
-            I32Store16(_, _) => 13,
-            I64Store8(_, _) => 7,
-            I64Store16(_, _) => 12,
-            I64Store32(_, _) => 8,
-            CurrentMemory(_) => 110,
-            GrowMemory(_) => 194,
-            I32Const(_) => 1,
-            I64Const(_) => 1,
-            F32Const(_) => 1,
-            F64Const(_) => 1,
-            I32Eqz => 6,
-            I32Eq => 6,
-            I32Ne => 6,
-            I32LtS => 6,
-            I32LtU => 6,
-            I32GtS => 6,
-            I32GtU => 6,
-            I32LeS => 6,
-            I32LeU => 6,
-            I32GeS => 6,
-            I32GeU => 6,
-            I64Eqz => 8,
-            I64Eq => 8,
-            I64Ne => 8,
-            I64LtS => 8,
-            I64LtU => 8,
-            I64GtS => 8,
-            I64GtU => 8,
-            I64LeS => 8,
-            I64LeU => 8,
-            I64GeS => 8,
-            I64GeU => 8,
-            F32Eq => 10,
-            F32Ne => 10,
-            F32Lt => 10,
-            F32Gt => 9,
-            F32Le => 10,
-            F32Ge => 10,
-            F64Eq => 11,
-            F64Ne => 11,
-            F64Lt => 11,
-            F64Gt => 12,
-            F64Le => 11,
-            F64Ge => 11,
-            I32Clz => 3,
-            I32Ctz => 3,
-            I32Popcnt => 3,
-            I32Add => 4,
-            I32Sub => 4,
-            I32Mul => 6,
-            I32DivS => 18,
-            I32DivU => 18,
-            I32RemS => 18,
-            I32RemU => 18,
-            I32And => 4,
-            I32Or => 4,
-            I32Xor => 4,
-            I32Shl => 4,
-            I32ShrS => 4,
-            I32ShrU => 4,
-            I32Rotl => 4,
-            I32Rotr => 4,
-            I64Clz => 4,
-            I64Ctz => 4,
-            I64Popcnt => 4,
-            I64Add => 7,
-            I64Sub => 7,
-            I64Mul => 8,
-            I64DivS => 30,
-            I64DivU => 30,
-            I64RemS => 31,
-            I64RemU => 30,
-            I64And => 7,
-            I64Or => 7,
-            I64Xor => 7,
-            I64Shl => 6,
-            I64ShrS => 6,
-            I64ShrU => 6,
-            I64Rotl => 6,
-            I64Rotr => 6,
-            F32Abs => 5,
-            F32Neg => 4,
-            F32Ceil => 7,
-            F32Floor => 7,
-            F32Trunc => 7,
-            F32Nearest => 7,
-            F32Sqrt => 10,
-            F32Add => 7,
-            F32Sub => 7,
-            F32Mul => 7,
-            F32Div => 10,
-            F32Min => 21,
-            F32Max => 19,
-            F32Copysign => 9,
-            F64Abs => 7,
-            F64Neg => 5,
-            F64Ceil => 9,
-            F64Floor => 9,
-            F64Trunc => 9,
-            F64Nearest => 9,
-            F64Sqrt => 19,
-            F64Add => 9,
-            F64Sub => 9,
-            F64Mul => 9,
-            F64Div => 12,
-            F64Min => 24,
-            F64Max => 31,
-            F64Copysign => 13,
-            I32WrapI64 => 2,
-            I32TruncSF32 => 24,
-            I32TruncUF32 => 25,
-            I32TruncSF64 => 28,
-            I32TruncUF64 => 27,
-            I64ExtendSI32 => 3,
-            I64ExtendUI32 => 2,
-            I64TruncSF32 => 24,
-            I64TruncUF32 => 39,
-            I64TruncSF64 => 27,
-            I64TruncUF64 => 46,
-            F32ConvertSI32 => 12,
-            F32ConvertUI32 => 6,
-            F32ConvertSI64 => 6,
-            F32ConvertUI64 => 12,
-            F32DemoteF64 => 9,
-            F64ConvertSI32 => 12,
-            F64ConvertUI32 => 12,
-            F64ConvertSI64 => 12,
-            F64ConvertUI64 => 12,
-            F64PromoteF32 => 9,
-            I32ReinterpretF32 => 2,
-            I64ReinterpretF64 => 3,
-            F32ReinterpretI32 => 3,
-            F64ReinterpretI64 => 4,
-            SignExt(SignExtInstruction::I32Extend8S) => 1,
-            SignExt(SignExtInstruction::I32Extend16S) => 1,
-            SignExt(SignExtInstruction::I64Extend8S) => 1,
-            SignExt(SignExtInstruction::I64Extend16S) => 1,
-            SignExt(SignExtInstruction::I64Extend32S) => 1,
-        };
-
         // We always return a cost, forbidden instructions should be rejected at
         // validation time not here
-        Some(gas)
+        Some(instruction_cost(instruction))
     }
 
     fn memory_grow_cost(
@@ -1011,6 +1229,7 @@ mod tests {
     use itertools::Either;
     use namada_core::arith::checked;
     use namada_core::borsh::BorshSerializeExt;
+    use namada_gas::Gas;
     use namada_state::testing::TestState;
     use namada_state::StorageWrite;
     use namada_test_utils::TestWasms;
@@ -1579,6 +1798,147 @@ mod tests {
         assert_stack_overflow(&error);
     }
 
+    /// Test that with gas tracing enabled, the tx host function calls are
+    /// recorded with the gas they charged.
+    #[test]
+    fn test_tx_gas_tracing() {
+        let mut state = TestState::default();
+        let mut gas_meter = TxGasMeter::new(TX_GAS_LIMIT);
+        gas_meter.enable_tracing();
+        let gas_meter = RefCell::new(gas_meter);
+        let tx_index = TxIndex::default();
+
+        let tx_read_key = TestWasms::TxReadStorageKey.read_bytes();
+        let code_hash = Hash::sha256(&tx_read_key);
+        let code_len = (tx_read_key.len() as u64).serialize_to_vec();
+        let key = Key::wasm_code(&code_hash);
+        let len_key = Key::wasm_code_len(&code_hash);
+        let _ = state
+            .write_log_mut()
+            .write(&key, tx_read_key.clone())
+            .unwrap();
+        let _ = state.write_log_mut().write(&len_key, code_len).unwrap();
+
+        let key = Key::parse("key").unwrap();
+        state.write(&key, vec![6_u8; 32]).unwrap();
+        let tx_data = key.serialize_to_vec();
+        let (mut vp_cache, _) =
+            wasm::compilation_cache::common::testing::vp_cache();
+        let (mut tx_cache, _) =
+            wasm::compilation_cache::common::testing::tx_cache();
+        let mut outer_tx = Tx::from_type(TxType::Raw);
+        outer_tx.set_code(Code::new(tx_read_key, None));
+        outer_tx.set_data(Data::new(tx_data));
+        let batched_tx = outer_tx.batch_ref_first_tx().unwrap();
+        tx(
+            &mut state,
+            &gas_meter,
+            &tx_index,
+            batched_tx.tx,
+            batched_tx.cmt,
+            &mut vp_cache,
+            &mut tx_cache,
+        )
+        .unwrap();
+
+        let trace = gas_meter.borrow().gas_trace().unwrap();
+        assert!(trace.tx.wasm_gas > Gas::default());
+        let read = trace
+            .tx
+            .host_calls
+            .iter()
+            .find(|call| call.name == "namada_tx_read")
+            .expect("The storage read must be traced");
+        // The key is read from the wasm memory
+        assert_eq!(read.memory.read_bytes, key.to_string().len() as u64);
+        assert!(read.gas > Gas::default());
+        assert!(
+            trace.tx.total_gas() <= gas_meter.borrow().get_tx_consumed_gas()
+        );
+    }
+
+    /// Test that a tx can call another tx code, which writes to the same write
+    /// log.
+    #[test]
+    fn test_tx_call() {
+        let mut state = TestState::default();
+        let callee_code = TestWasms::TxWriteStorageKey.read_bytes();
+        let callee_hash = store_tx_code(&mut state, &callee_code);
+
+        let key = Key::parse("written_by_callee").unwrap();
+        let value = b"value".to_vec();
+        let callee_data = namada_test_utils::tx_data::TxWriteData {
+            key: key.clone(),
+            value: value.clone(),
+        };
+        state
+            .write_bytes(&Key::parse("callee").unwrap(), callee_hash.0)
+            .unwrap();
+        state
+            .write_bytes(
+                &Key::parse("callee_data").unwrap(),
+                callee_data.serialize_to_vec(),
+            )
+            .unwrap();
+
+        execute_tx_calling_tx(&mut state).unwrap();
+        assert_eq!(state.read_bytes(&key).unwrap(), Some(value));
+        assert!(!state.has_key(&Key::parse("refused").unwrap()).unwrap());
+    }
+
+    /// Test that a call of a tx code that is not allowlisted is refused and
+    /// that the calling tx can handle the refusal.
+    #[test]
+    fn test_tx_call_disallowed() {
+        let mut state = TestState::default();
+        let callee_code = TestWasms::TxWriteStorageKey.read_bytes();
+        let callee_hash = store_tx_code(&mut state, &callee_code);
+        let allowed_hash = Hash::sha256(tx_calling_tx_wasm());
+        namada_parameters::update_tx_allowlist_parameter(
+            &mut state,
+            vec![allowed_hash.to_string()],
+        )
+        .unwrap();
+        state.commit_tx_batch();
+
+        let key = Key::parse("written_by_callee").unwrap();
+        let callee_data = namada_test_utils::tx_data::TxWriteData {
+            key: key.clone(),
+            value: b"value".to_vec(),
+        };
+        state
+            .write_bytes(&Key::parse("callee").unwrap(), callee_hash.0)
+            .unwrap();
+        state
+            .write_bytes(
+                &Key::parse("callee_data").unwrap(),
+                callee_data.serialize_to_vec(),
+            )
+            .unwrap();
+
+        execute_tx_calling_tx(&mut state).unwrap();
+        assert!(!state.has_key(&key).unwrap());
+        assert!(state.has_key(&Key::parse("refused").unwrap()).unwrap());
+    }
+
+    /// Test that the depth of nested tx calls is limited.
+    #[test]
+    fn test_tx_call_depth_limit() {
+        let mut state = TestState::default();
+        // The tx calls itself
+        let code_hash = store_tx_code(&mut state, &tx_calling_tx_wasm());
+        state
+            .write_bytes(&Key::parse("callee").unwrap(), code_hash.0)
+            .unwrap();
+        state
+            .write_bytes(&Key::parse("callee_data").unwrap(), [])
+            .unwrap();
+
+        // The deepest call is refused
+        execute_tx_calling_tx(&mut state).unwrap();
+        assert!(state.has_key(&Key::parse("refused").unwrap()).unwrap());
+    }
+
     /// Test that when a validity predicate wasm goes over the wasm memory limit
     /// in the value returned from host environment call during wasm
     /// execution, the execution is aborted.
@@ -2164,6 +2524,92 @@ mod tests {
         )
     }
 
+    fn store_tx_code(state: &mut TestState, tx_code: &[u8]) -> Hash {
+        let code_hash = Hash::sha256(tx_code);
+        let code_len = (tx_code.len() as u64).serialize_to_vec();
+        let key = Key::wasm_code(&code_hash);
+        let len_key = Key::wasm_code_len(&code_hash);
+        let _ = state
+            .write_log_mut()
+            .write(&key, tx_code.serialize_to_vec())
+            .unwrap();
+        let _ = state.write_log_mut().write(&len_key, code_len).unwrap();
+        code_hash
+    }
+
+    /// A transaction that calls the tx code whose hash is stored under the
+    /// "callee" key, with the data stored under the "callee_data" key. When
+    /// the call is refused, it writes to the "refused" key.
+    fn tx_calling_tx_wasm() -> Vec<u8> {
+        wasmer::wat2wasm(
+            r#"
+            (module
+                (import "env" "namada_tx_read" (func $read (param i64 i64) (result i64)))
+                (import "env" "namada_tx_result_buffer" (func $result_buffer (param i64)))
+                (import "env" "namada_tx_write" (func $write (param i64 i64 i64 i64)))
+                (import "env" "namada_tx_call" (func $call (param i64 i64 i64 i64) (result i64)))
+
+                (func $_apply_tx (param i64 i64) (result i64)
+                (local $data_len i64)
+                ;; read the code hash into memory
+                (drop (call $read (i64.const 0) (i64.const 6)))
+                (call $result_buffer (i64.const 1024))
+                ;; read the data into memory
+                (local.set $data_len (call $read (i64.const 8) (i64.const 11)))
+                (call $result_buffer (i64.const 2048))
+                (if
+                (i64.ne
+                    (call $call
+                        (i64.const 1024)
+                        (i64.const 32)
+                        (i64.const 2048)
+                        (local.get $data_len))
+                    (i64.const 1))
+                (then
+                    (call $write
+                        (i64.const 24)
+                        (i64.const 7)
+                        (i64.const 0)
+                        (i64.const 1))))
+                (i64.const 1))
+
+                (memory (;0;) 1)
+                (data (i32.const 0) "callee")
+                (data (i32.const 8) "callee_data")
+                (data (i32.const 24) "refused")
+                (export "memory" (memory 0))
+                (export "_apply_tx" (func $_apply_tx)))
+            "#
+            .as_bytes(),
+        )
+        .expect("unexpected error converting wat2wasm")
+        .into_owned()
+    }
+
+    fn execute_tx_calling_tx(
+        state: &mut TestState,
+    ) -> Result<BTreeSet<Address>> {
+        let gas_meter = RefCell::new(TxGasMeter::new(TX_GAS_LIMIT));
+        let tx_index = TxIndex::default();
+        let (mut vp_cache, _) =
+            wasm::compilation_cache::common::testing::vp_cache();
+        let (mut tx_cache, _) =
+            wasm::compilation_cache::common::testing::tx_cache();
+        let mut outer_tx = Tx::from_type(TxType::Raw);
+        outer_tx.set_code(Code::new(tx_calling_tx_wasm(), None));
+        outer_tx.set_data(Data::new(vec![]));
+        let batched_tx = outer_tx.batch_ref_first_tx().unwrap();
+        tx(
+            state,
+            &gas_meter,
+            &tx_index,
+            batched_tx.tx,
+            batched_tx.cmt,
+            &mut vp_cache,
+            &mut tx_cache,
+        )
+    }
+
     fn loop_in_tx_wasm(loops: u32) -> Result<BTreeSet<Address>> {
         // A transaction with a recursive loop.
         // The boilerplate code is generated from tx_template.wasm using
@@ -32,6 +32,12 @@ pub mod tx {
         /// its size.
         pub fn namada_tx_read(key_ptr: u64, key_len: u64) -> i64;
 
+        /// Read the values of many keys at once. The keys are a Borsh encoded
+        /// `Vec<Key>`. Returns the size of the Borsh encoded
+        /// `Vec<Option<Vec<u8>>>` of the values, which will be placed in the
+        /// result buffer.
+        pub fn namada_tx_read_many(keys_ptr: u64, keys_len: u64) -> i64;
+
         /// Read variable-length temporary state when we don't know the size
         /// up-front, returns the size of the value (can be 0), or -1 if
         /// the key is not present. If a value is found, it will be placed in
@@ -137,6 +143,17 @@ pub mod tx {
         /// Set the sentinel for a wrong tx section commitment
         pub fn namada_tx_set_commitment_sentinel();
 
+        /// Execute another allowlisted tx code with the given data, in the
+        /// same write log. Returns `HostEnvResult::Fail` when the call is
+        /// refused before executing the code. A failure of the called code
+        /// aborts the calling tx.
+        pub fn namada_tx_call(
+            code_hash_ptr: u64,
+            code_hash_len: u64,
+            data_ptr: u64,
+            data_len: u64,
+        ) -> i64;
+
         /// Verify the signatures of a tx
         pub fn namada_tx_verify_tx_section_signature(
             hash_list_ptr: u64,
@@ -176,6 +193,18 @@ pub mod vp {
         /// it before we know its size.
         pub fn namada_vp_read_post(key_ptr: u64, key_len: u64) -> i64;
 
+        /// Read the prior state of many keys at once. The keys are a Borsh
+        /// encoded `Vec<Key>`. Returns the size of the Borsh encoded
+        /// `Vec<Option<Vec<u8>>>` of the values, which will be placed in the
+        /// result buffer.
+        pub fn namada_vp_read_many_pre(keys_ptr: u64, keys_len: u64) -> i64;
+
+        /// Read the posterior state of many keys at once. The keys are a Borsh
+        /// encoded `Vec<Key>`. Returns the size of the Borsh encoded
+        /// `Vec<Option<Vec<u8>>>` of the values, which will be placed in the
+        /// result buffer.
+        pub fn namada_vp_read_many_post(keys_ptr: u64, keys_len: u64) -> i64;
+
         /// Read variable-length temporary state when we don't know the size
         /// up-front, returns the size of the value (can be 0), or -1 if
         /// the key is not present. If a value is found, it will be placed in
@@ -306,3 +335,13 @@ pub fn read_key_val_bytes_from_buffer(
         .and_then(|t| KeyVal::try_from_slice(&t[..]).ok());
     key_val.map(|key_val| (key_val.key, key_val.val))
 }
+
+/// This function is a helper to handle the second step of reading the values
+/// of many keys at once from the host.
+pub fn read_many_from_buffer(
+    read_result: i64,
+    result_buffer: unsafe extern "C" fn(u64),
+) -> Option<Vec<Option<Vec<u8>>>> {
+    read_from_buffer(read_result, result_buffer)
+        .and_then(|t| Vec::<Option<Vec<u8>>>::try_from_slice(&t[..]).ok())
+}
@@ -226,6 +226,7 @@ mod tests {
             nonce: 2u64.into(),
             transfers: vec![],
             relayer: address::testing::established_address_1(),
+            relay_cost: Default::default(),
         };
 
         let validator_1 = address::testing::established_address_1();
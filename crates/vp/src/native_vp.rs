@@ -10,8 +10,10 @@ use std::marker::PhantomData;
 use namada_core::borsh;
 use namada_core::borsh::BorshDeserialize;
 use namada_core::chain::{ChainId, Epochs};
-use namada_gas::{Gas, GasMetering, VpGasMeter};
-use namada_state::{ConversionState, ReadConversionState};
+use namada_gas::{Gas, GasMetering, GasSchedule, VpGasMeter};
+use namada_state::{
+    ConversionState, ReadBridgePoolTree, ReadConversionState, ReadGasSchedule,
+};
 use namada_tx::{BatchedTxRef, Tx, TxCommitments};
 
 use super::vp_host_fns;
@@ -547,3 +549,39 @@ where
         self.state.conversion_state()
     }
 }
+
+impl<'a, S, CA, EVAL> ReadBridgePoolTree for Ctx<'a, S, CA, EVAL>
+where
+    S: StateRead,
+    EVAL: 'static + VpEvaluator<'a, S, CA, EVAL>,
+    CA: 'static + Clone,
+{
+    fn bridge_pool_insertion_height(
+        &self,
+        key: &Key,
+    ) -> Result<Option<BlockHeight>> {
+        self.state
+            .in_mem()
+            .block
+            .tree
+            .get_bridge_pool_insertion_height(key)
+            .into_storage_result()
+    }
+
+    fn read_last_committed_bytes(&self, key: &Key) -> Result<Option<Vec<u8>>> {
+        let (value, gas) = self.state.db_read(key)?;
+        vp_host_fns::add_gas(self.gas_meter, gas)?;
+        Ok(value)
+    }
+}
+
+impl<'a, S, CA, EVAL> ReadGasSchedule for Ctx<'a, S, CA, EVAL>
+where
+    S: StateRead,
+    EVAL: 'static + VpEvaluator<'a, S, CA, EVAL>,
+    CA: 'static + Clone,
+{
+    fn gas_schedule(&self) -> &GasSchedule {
+        self.state.gas_schedule()
+    }
+}
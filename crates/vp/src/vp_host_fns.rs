@@ -9,9 +9,7 @@ use namada_core::chain::{BlockHeader, BlockHeight, ChainId, Epoch, Epochs};
 use namada_core::hash::{Hash, HASH_LENGTH};
 use namada_core::storage::{Key, TxIndex, TX_INDEX_LENGTH};
 use namada_events::{Event, EventTypeBuilder};
-use namada_gas::{
-    self as gas, Gas, GasMetering, VpGasMeter, MEMORY_ACCESS_GAS_PER_BYTE,
-};
+use namada_gas::{self as gas, Gas, GasMetering, VpGasMeter};
 use namada_tx::{BatchedTxRef, Section};
 use thiserror::Error;
 
@@ -244,13 +242,9 @@ pub fn get_tx_code_hash(
     gas_meter: &RefCell<VpGasMeter>,
     batched_tx: &BatchedTxRef<'_>,
 ) -> Result<Option<Hash>> {
-    add_gas(
-        gas_meter,
-        (HASH_LENGTH as u64)
-            .checked_mul(MEMORY_ACCESS_GAS_PER_BYTE)
-            .expect("Consts mul that cannot overflow")
-            .into(),
-    )?;
+    let len = HASH_LENGTH as u64;
+    let cost = gas_meter.borrow().gas_schedule().memory_access_gas_per_byte;
+    add_gas(gas_meter, checked!(len * cost)?.into())?;
     let hash = batched_tx
         .tx
         .get_section(batched_tx.cmt.code_sechash())
@@ -279,13 +273,9 @@ pub fn get_tx_index(
     gas_meter: &RefCell<VpGasMeter>,
     tx_index: &TxIndex,
 ) -> Result<TxIndex> {
-    add_gas(
-        gas_meter,
-        (TX_INDEX_LENGTH as u64)
-            .checked_mul(MEMORY_ACCESS_GAS_PER_BYTE)
-            .expect("Consts mul that cannot overflow")
-            .into(),
-    )?;
+    let len = TX_INDEX_LENGTH as u64;
+    let cost = gas_meter.borrow().gas_schedule().memory_access_gas_per_byte;
+    add_gas(gas_meter, checked!(len * cost)?.into())?;
     Ok(*tx_index)
 }
 
@@ -297,13 +287,9 @@ pub fn get_native_token<S>(
 where
     S: StateRead + Debug,
 {
-    add_gas(
-        gas_meter,
-        (ESTABLISHED_ADDRESS_BYTES_LEN as u64)
-            .checked_mul(MEMORY_ACCESS_GAS_PER_BYTE)
-            .expect("Consts mul that cannot overflow")
-            .into(),
-    )?;
+    let len = ESTABLISHED_ADDRESS_BYTES_LEN as u64;
+    let cost = gas_meter.borrow().gas_schedule().memory_access_gas_per_byte;
+    add_gas(gas_meter, checked!(len * cost)?.into())?;
     Ok(state.in_mem().native_token.clone())
 }
 
@@ -316,10 +302,8 @@ where
     S: StateRead + Debug,
 {
     let len = state.in_mem().block.pred_epochs.first_block_heights.len() as u64;
-    add_gas(
-        gas_meter,
-        checked!(len * 8 * MEMORY_ACCESS_GAS_PER_BYTE)?.into(),
-    )?;
+    let cost = gas_meter.borrow().gas_schedule().memory_access_gas_per_byte;
+    add_gas(gas_meter, checked!(len * 8 * cost)?.into())?;
     Ok(state.in_mem().block.pred_epochs.clone())
 }
 
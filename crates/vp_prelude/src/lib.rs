@@ -21,6 +21,7 @@ pub mod ibc {
 
 // used in the VP input
 use core::slice;
+use std::collections::BTreeMap;
 pub use std::collections::BTreeSet;
 use std::marker::PhantomData;
 use std::str::FromStr;
@@ -49,7 +50,9 @@ pub use namada_storage::{
 };
 pub use namada_tx::{BatchedTx, Section, Tx};
 use namada_vm_env::vp::*;
-use namada_vm_env::{read_from_buffer, read_key_val_bytes_from_buffer};
+use namada_vm_env::{
+    read_from_buffer, read_key_val_bytes_from_buffer, read_many_from_buffer,
+};
 pub use namada_vp_env::{collection_validation, VpEnv};
 pub use sha2::{Digest, Sha256, Sha384, Sha512};
 use tx::{BatchedTxRef, TxCommitments};
@@ -85,11 +88,23 @@ pub fn is_proposal_accepted(ctx: &Ctx, proposal_id: u64) -> VpEnvResult<bool> {
 #[cold]
 #[inline(never)]
 fn verify_signatures(ctx: &Ctx, tx: &Tx, owner: &Address) -> VpResult {
-    let public_keys_index_map =
-        account::public_keys_index_map(&ctx.pre(), owner).into_vp_error()?;
     let threshold = account::threshold(&ctx.pre(), owner)
         .into_vp_error()?
         .unwrap_or(1);
+    verify_signatures_with_threshold(ctx, tx, owner, threshold)
+}
+
+/// Verify section signatures against the given threshold
+#[cold]
+#[inline(never)]
+fn verify_signatures_with_threshold(
+    ctx: &Ctx,
+    tx: &Tx,
+    owner: &Address,
+    threshold: u8,
+) -> VpResult {
+    let public_keys_index_map =
+        account::public_keys_index_map(&ctx.pre(), owner).into_vp_error()?;
 
     // Serialize parameters
     let public_keys_map = public_keys_index_map.serialize_to_vec();
@@ -172,6 +187,106 @@ impl VerifySigGadget {
     }
 }
 
+/// Check the tokens debited from an account against the policies configured
+/// by the account's VP parameters, if any. This allows the user VPs to be used
+/// as templates whose behaviour is configured per account. The debits and
+/// their counterparties are found from the changed token balances, so the
+/// counterparties include the internal addresses receiving tokens, e.g. PoS
+/// for bonds. A transaction exceeding the account's limits must be signed by
+/// the bypass threshold of the account's keys, as must a change of the VP
+/// parameters when the bypass threshold is set. The debits of a vesting
+/// account are also checked against its vesting schedule, and the VP
+/// parameters of a time locked or vesting account cannot be relaxed.
+pub fn check_account_policies(
+    ctx: &Ctx,
+    tx: &Tx,
+    owner: &Address,
+    keys_changed: &BTreeSet<storage::Key>,
+) -> VpResult {
+    account::check_debit_records(&ctx.pre(), owner, keys_changed)
+        .into_vp_error()?;
+    if let Some(threshold) = account::check_vp_params_change(
+        &ctx.pre(),
+        &ctx.post(),
+        owner,
+        keys_changed,
+    )
+    .into_vp_error()?
+    {
+        verify_signatures_with_threshold(ctx, tx, owner, threshold)?;
+    }
+    let has_params = ctx
+        .has_key_pre(&account::vp_params_key(owner))
+        .into_vp_error()?;
+    let vesting_key = account::vesting_schedule_key(owner);
+    let has_vesting = keys_changed.contains(&vesting_key)
+        || ctx.has_key_pre(&vesting_key).into_vp_error()?;
+    if !has_params && !has_vesting {
+        return Ok(());
+    }
+    let mut debits: BTreeMap<Address, account::TokenDebit> = BTreeMap::new();
+    let mut credits: BTreeMap<Address, BTreeSet<Address>> = BTreeMap::new();
+    for key in keys_changed {
+        let Some([token, balance_owner]) =
+            token::storage_key::is_any_token_balance_key(key)
+        else {
+            continue;
+        };
+        let pre: token::Amount =
+            ctx.read_pre(key).into_vp_error()?.unwrap_or_default();
+        let post: token::Amount =
+            ctx.read_post(key).into_vp_error()?.unwrap_or_default();
+        if balance_owner == owner {
+            if let Some(amount) = pre.checked_sub(post) {
+                let debit = debits.entry(token.clone()).or_default();
+                debit.amount = amount;
+                debit.balance = post;
+            }
+        } else if post > pre {
+            credits
+                .entry(token.clone())
+                .or_default()
+                .insert(balance_owner.clone());
+        }
+    }
+    for (token, debit) in debits.iter_mut() {
+        debit.counterparties = credits.remove(token).unwrap_or_default();
+    }
+    // The tokens held by PoS only count toward a vesting native token
+    let native_token = ctx.get_native_token().into_vp_error()?;
+    let bonded = if has_vesting && debits.contains_key(&native_token) {
+        proof_of_stake::queries::find_bonded_and_unbonded_total(
+            &ctx.post(),
+            owner,
+        )
+        .into_vp_error()?
+    } else {
+        token::Amount::zero()
+    };
+    account::check_vesting(
+        &ctx.pre(),
+        &ctx.post(),
+        owner,
+        keys_changed,
+        &debits,
+        bonded,
+    )
+    .into_vp_error()?;
+    let exceeded =
+        account::check_debits(&ctx.pre(), &ctx.post(), owner, &debits)
+            .into_vp_error()?;
+    match exceeded {
+        None => Ok(()),
+        Some(account::LimitExceeded {
+            bypass_threshold: Some(threshold),
+            ..
+        }) => verify_signatures_with_threshold(ctx, tx, owner, threshold),
+        Some(account::LimitExceeded { reason, .. }) => {
+            Err(VpError::Erased(reason))
+        }
+    }
+}
+
 /// Format and log a string in a debug build.
 ///
 /// In WASM target debug build, the message will be printed at the
@@ -437,6 +552,21 @@ impl StorageRead for CtxPreStorageRead<'_> {
         Ok(read_from_buffer(read_result, namada_vp_result_buffer))
     }
 
+    fn read_many_bytes(
+        &self,
+        keys: &[storage::Key],
+    ) -> Result<Vec<Option<Vec<u8>>>, Error> {
+        let keys = keys.serialize_to_vec();
+        let read_result = unsafe {
+            namada_vp_read_many_pre(keys.as_ptr() as _, keys.len() as _)
+        };
+        read_many_from_buffer(read_result, namada_vp_result_buffer).ok_or(
+            Error::SimpleMessage(
+                "Missing result from `namada_vp_read_many_pre` call",
+            ),
+        )
+    }
+
     fn has_key(&self, key: &storage::Key) -> Result<bool, Error> {
         let key = key.to_string();
         let found =
@@ -506,6 +636,21 @@ impl StorageRead for CtxPostStorageRead<'_> {
         Ok(read_from_buffer(read_result, namada_vp_result_buffer))
     }
 
+    fn read_many_bytes(
+        &self,
+        keys: &[storage::Key],
+    ) -> Result<Vec<Option<Vec<u8>>>, Error> {
+        let keys = keys.serialize_to_vec();
+        let read_result = unsafe {
+            namada_vp_read_many_post(keys.as_ptr() as _, keys.len() as _)
+        };
+        read_many_from_buffer(read_result, namada_vp_result_buffer).ok_or(
+            Error::SimpleMessage(
+                "Missing result from `namada_vp_read_many_post` call",
+            ),
+        )
+    }
+
     fn has_key(&self, key: &storage::Key) -> Result<bool, Error> {
         let key = key.to_string();
         let found = unsafe {
@@ -212,6 +212,18 @@ impl DerivationPath {
         Self::zip32(0, None)
     }
 
+    /// The derivation path of the transparent key at the given address index
+    /// of the first account
+    pub fn transparent_at_index(scheme: SchemeType, index: u32) -> Self {
+        let path = Self::bip44(scheme, 0, 0, index);
+        path.hardened(scheme)
+    }
+
+    /// The derivation path of the shielded key of the given account
+    pub fn shielded_at_index(account: u32) -> Self {
+        Self::zip32(account, None)
+    }
+
     /// Try to parse a derivation path from a string
     pub fn from_path_string(path: &str) -> Result<Self, DerivationPathError> {
         let inner = DerivationPathInner::from_str(path).map_err(|err| {
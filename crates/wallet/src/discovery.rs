@@ -0,0 +1,152 @@
+//! Discovery of the used keys of an HD wallet. The keys are derived from the
+//! seed at sequential indices until a gap of consecutive unused indices is
+//! found, so that a wallet restored from a mnemonic code can recover all the
+//! keys that were in use.
+
+use std::ops::Range;
+
+use bip39::Seed;
+use namada_core::key::{common, SchemeType};
+use namada_core::masp::ExtendedSpendingKey;
+use zeroize::Zeroizing;
+
+use crate::store::{derive_hd_secret_key, derive_hd_spending_key};
+use crate::{DerivationPath, WalletIo};
+
+/// The default number of consecutive unused indices ending a discovery
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// A scan of the derivation indices that ends once a given number of
+/// consecutive indices after the last used one are unused
+#[derive(Clone, Debug)]
+pub struct GapLimitScan {
+    gap_limit: u32,
+    next_index: u32,
+    last_used: Option<u32>,
+    used: Vec<u32>,
+}
+
+impl GapLimitScan {
+    /// Start a scan with the given gap limit
+    pub fn new(gap_limit: u32) -> Self {
+        Self {
+            gap_limit,
+            next_index: 0,
+            last_used: None,
+            used: vec![],
+        }
+    }
+
+    /// The indices to scan next, covering the gap after the last used index.
+    /// The returned range is empty once the scan is complete.
+    pub fn next_batch(&mut self) -> Range<u32> {
+        let end = self
+            .last_used
+            .map_or(0, |index| index.saturating_add(1))
+            .saturating_add(self.gap_limit);
+        let start = self.next_index;
+        self.next_index = end.max(start);
+        start..self.next_index
+    }
+
+    /// Record that the key at the given index is used
+    pub fn mark_used(&mut self, index: u32) {
+        if !self.used.contains(&index) {
+            self.used.push(index);
+            self.used.sort_unstable();
+        }
+        self.last_used = self.last_used.max(Some(index));
+    }
+
+    /// The used indices found so far, in increasing order
+    pub fn used(&self) -> &[u32] {
+        &self.used
+    }
+}
+
+/// Derives the keys to discover from the seed of a mnemonic code
+pub struct HdKeyDeriver {
+    seed: Seed,
+}
+
+impl HdKeyDeriver {
+    /// Read the mnemonic code and, if requested, the BIP39 passphrase from
+    /// stdin
+    pub fn read_mnemonic_code<U: WalletIo>(
+        prompt_bip39_passphrase: bool,
+    ) -> Option<Self> {
+        let mnemonic = U::read_mnemonic_code()?;
+        let passphrase = if prompt_bip39_passphrase {
+            U::read_mnemonic_passphrase(false)
+        } else {
+            Zeroizing::default()
+        };
+        Some(Self {
+            seed: Seed::new(&mnemonic, &passphrase),
+        })
+    }
+
+    /// Derive the transparent key at the given address index
+    pub fn transparent_key(
+        &self,
+        scheme: SchemeType,
+        index: u32,
+    ) -> (DerivationPath, common::SecretKey) {
+        let path = DerivationPath::transparent_at_index(scheme, index);
+        let sk =
+            derive_hd_secret_key(scheme, self.seed.as_bytes(), path.clone());
+        (path, sk)
+    }
+
+    /// Derive the spending key of the given shielded account
+    pub fn spending_key(
+        &self,
+        account: u32,
+    ) -> (DerivationPath, ExtendedSpendingKey) {
+        let path = DerivationPath::shielded_at_index(account);
+        let spend_key =
+            derive_hd_spending_key(self.seed.as_bytes(), path.clone());
+        (path, spend_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gap_limit_scan() {
+        let mut scan = GapLimitScan::new(3);
+        assert_eq!(scan.next_batch(), 0..3);
+        scan.mark_used(1);
+        // The gap after the used index is extended
+        assert_eq!(scan.next_batch(), 3..5);
+        scan.mark_used(4);
+        assert_eq!(scan.next_batch(), 5..8);
+        // No more used indices, the scan is complete
+        assert_eq!(scan.next_batch(), 8..8);
+        assert_eq!(scan.used(), &[1, 4]);
+    }
+
+    #[test]
+    fn test_indexed_paths_match_defaults() {
+        for scheme in [SchemeType::Ed25519, SchemeType::Secp256k1] {
+            assert_eq!(
+                DerivationPath::transparent_at_index(scheme, 0).to_string(),
+                DerivationPath::default_for_transparent_scheme(scheme)
+                    .to_string()
+            );
+            assert!(
+                DerivationPath::transparent_at_index(scheme, 5)
+                    .is_namada_transparent_compliant(scheme)
+            );
+        }
+        assert_eq!(
+            DerivationPath::shielded_at_index(0).to_string(),
+            DerivationPath::default_for_shielded().to_string()
+        );
+        assert!(
+            DerivationPath::shielded_at_index(5).is_namada_shielded_compliant()
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Encryption at rest of the whole wallet store. An encrypted store is saved
+//! as an envelope holding the hex encoded encryption salt and ciphertext of
+//! the encoded store, so that no alias, address or viewing key is readable
+//! without the password.
+
+use data_encoding::HEXLOWER;
+use orion::aead;
+
+use crate::keys::{encryption_key, encryption_salt};
+use crate::DecryptionError;
+
+/// The prefix of an encrypted wallet store file
+const ENVELOPE_PREFIX: &str = "namada-encrypted-wallet:";
+
+/// Check if the given wallet store file contents are an encrypted envelope
+pub fn is_sealed(data: &[u8]) -> bool {
+    data.starts_with(ENVELOPE_PREFIX.as_bytes())
+}
+
+/// Encrypt the encoded wallet store with the given password
+pub fn seal(data: &[u8], password: &str) -> Vec<u8> {
+    let salt = encryption_salt();
+    let encryption_key = encryption_key(&salt, password);
+    let cipher = aead::seal(&encryption_key, data)
+        .expect("Encryption of data shouldn't fail");
+    let envelope = [salt.as_ref(), &cipher].concat();
+    format!("{}{}\n", ENVELOPE_PREFIX, HEXLOWER.encode(&envelope)).into_bytes()
+}
+
+/// Decrypt an encrypted wallet store envelope with the given password
+pub fn open(data: &[u8], password: &str) -> Result<Vec<u8>, DecryptionError> {
+    let encoded = std::str::from_utf8(data)
+        .ok()
+        .and_then(|data| data.trim().strip_prefix(ENVELOPE_PREFIX))
+        .ok_or(DecryptionError::DeserializingError)?;
+    let envelope = HEXLOWER
+        .decode(encoded.as_bytes())
+        .map_err(|_| DecryptionError::DeserializingError)?;
+    let salt_len = encryption_salt().len();
+    if envelope.len() < salt_len {
+        return Err(DecryptionError::BadSalt);
+    }
+    let (raw_salt, cipher) = envelope.split_at(salt_len);
+    let salt = orion::kdf::Salt::from_slice(raw_salt)
+        .map_err(|_| DecryptionError::BadSalt)?;
+    let encryption_key = encryption_key(&salt, password);
+    aead::open(&encryption_key, cipher)
+        .map_err(|_| DecryptionError::DecryptionError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_roundtrip() {
+        let data = b"[view_keys]\n".to_vec();
+        let envelope = seal(&data, "password");
+        assert!(is_sealed(&envelope));
+        assert!(!is_sealed(&data));
+        assert_eq!(open(&envelope, "password").unwrap(), data);
+        assert!(matches!(
+            open(&envelope, "wrong password"),
+            Err(DecryptionError::DecryptionError)
+        ));
+    }
+}
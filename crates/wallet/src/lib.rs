@@ -3,6 +3,8 @@
 //! Provides functionality for managing keys and addresses for a user
 pub mod alias;
 mod derivation_path;
+pub mod discovery;
+pub mod envelope;
 mod keys;
 pub mod pre_genesis;
 pub mod store;
@@ -106,6 +108,9 @@ pub enum LoadStoreError {
     /// Wallet store decoding error
     #[error("Failed decoding the wallet store: {0}")]
     Decode(toml::de::Error),
+    /// Wallet store decryption error
+    #[error("Failed decrypting the wallet store: {0}")]
+    Decrypt(DecryptionError),
     /// Wallet store reading error
     #[error("Failed to read the wallet store from {0}: {1}")]
     ReadWallet(String, String),
@@ -117,10 +122,16 @@ pub enum LoadStoreError {
 /// Captures the permanent storage parts of the wallet's functioning
 pub trait WalletStorage: Sized + Clone {
     /// Save the wallet store to a file.
-    fn save<U>(&self, wallet: &Wallet<U>) -> Result<(), LoadStoreError>;
+    fn save<U: WalletIo>(
+        &self,
+        wallet: &Wallet<U>,
+    ) -> Result<(), LoadStoreError>;
 
     /// Load a wallet from the store file.
-    fn load<U>(&self, wallet: &mut Wallet<U>) -> Result<(), LoadStoreError>;
+    fn load<U: WalletIo>(
+        &self,
+        wallet: &mut Wallet<U>,
+    ) -> Result<(), LoadStoreError>;
 }
 
 #[cfg(feature = "std")]
@@ -145,8 +156,15 @@ pub mod fs {
     const FILE_NAME: &str = "wallet.toml";
 
     impl<F: FsWalletStorage> WalletStorage for F {
-        fn save<U>(&self, wallet: &Wallet<U>) -> Result<(), LoadStoreError> {
+        fn save<U: WalletIo>(
+            &self,
+            wallet: &Wallet<U>,
+        ) -> Result<(), LoadStoreError> {
             let data = wallet.store.encode();
+            let data = match &wallet.store_password {
+                Some(password) => envelope::seal(&data, password),
+                None => data,
+            };
             let wallet_path = self.store_dir().join(FILE_NAME);
             // Make sure the dir exists
             let wallet_dir = wallet_path.parent().unwrap();
@@ -168,7 +186,7 @@ pub mod fs {
                 .map_err(|err| LoadStoreError::StoreNewWallet(err.to_string()))
         }
 
-        fn load<U>(
+        fn load<U: WalletIo>(
             &self,
             wallet: &mut Wallet<U>,
         ) -> Result<(), LoadStoreError> {
@@ -201,6 +219,18 @@ pub mod fs {
                     err.to_string(),
                 )
             })?;
+            // Decrypt the whole store if it's saved in an encrypted envelope
+            if envelope::is_sealed(&store) {
+                let password = match wallet.store_password.clone() {
+                    Some(password) => password,
+                    None => U::read_password(false, Some("the wallet")),
+                };
+                store = envelope::open(&store, &password)
+                    .map_err(LoadStoreError::Decrypt)?;
+                wallet.store_password = Some(password);
+            } else {
+                wallet.store_password = None;
+            }
             wallet.store =
                 Store::decode(store).map_err(LoadStoreError::Decode)?;
             Ok(())
@@ -273,6 +303,8 @@ pub struct Wallet<U> {
     /// Location where this shielded context is saved
     utils: U,
     store: Store,
+    /// The password of the whole store encryption, if it's encrypted at rest
+    store_password: Option<Zeroizing<String>>,
     decrypted_key_cache: HashMap<Alias, common::SecretKey>,
     decrypted_spendkey_cache: HashMap<Alias, DatedSpendingKey>,
 }
@@ -289,11 +321,23 @@ impl<U> Wallet<U> {
         Self {
             utils,
             store,
+            store_password: None,
             decrypted_key_cache: HashMap::default(),
             decrypted_spendkey_cache: HashMap::default(),
         }
     }
 
+    /// Check if the whole store is encrypted when saved
+    pub fn is_store_encrypted(&self) -> bool {
+        self.store_password.is_some()
+    }
+
+    /// Encrypt the whole store with the given password when it's saved, or
+    /// save it in plaintext if no password is given
+    pub fn set_store_password(&mut self, password: Option<Zeroizing<String>>) {
+        self.store_password = password;
+    }
+
     /// Add validator data to the store
     pub fn add_validator_data(
         &mut self,
@@ -541,7 +585,7 @@ impl<U> Wallet<U> {
     }
 }
 
-impl<U: WalletStorage> Wallet<U> {
+impl<U: WalletStorage + WalletIo> Wallet<U> {
     /// Load a wallet from the store file.
     pub fn load(&mut self) -> Result<(), LoadStoreError> {
         self.utils.clone().load(self)
@@ -1243,11 +1287,17 @@ pub mod test_utils {
     }
 
     impl WalletStorage for TestWalletUtils {
-        fn save<U>(&self, _: &Wallet<U>) -> Result<(), LoadStoreError> {
+        fn save<U: WalletIo>(
+            &self,
+            _: &Wallet<U>,
+        ) -> Result<(), LoadStoreError> {
             unimplemented!()
         }
 
-        fn load<U>(&self, _: &mut Wallet<U>) -> Result<(), LoadStoreError> {
+        fn load<U: WalletIo>(
+            &self,
+            _: &mut Wallet<U>,
+        ) -> Result<(), LoadStoreError> {
             unimplemented!()
         }
     }
@@ -1289,6 +1339,7 @@ mod tests {
         let mut wallet = Wallet {
             utils: TestWalletUtils,
             store: Default::default(),
+            store_password: None,
             decrypted_key_cache: Default::default(),
             decrypted_spendkey_cache: Default::default(),
         };
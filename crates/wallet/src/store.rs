@@ -2,7 +2,7 @@
 
 #![allow(clippy::print_stdout, clippy::print_stderr)]
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -625,6 +625,96 @@ impl Store {
         address_vp_types.extend(store.address_vp_types);
     }
 
+    /// Extend this store from another store (typically a backup), resolving
+    /// the conflicts of the aliases used with different entries in both
+    /// stores. The conflicting entries of this store are removed on
+    /// [`ConfirmationResponse::Replace`], the ones of the other store are
+    /// dropped on [`ConfirmationResponse::Skip`] and added under the new alias
+    /// on [`ConfirmationResponse::Reselect`]. Note that this method ignores
+    /// `validator_data` if any.
+    pub fn extend_resolving_conflicts(
+        &mut self,
+        mut store: Store,
+        mut resolve: impl FnMut(&Alias) -> ConfirmationResponse,
+    ) {
+        let existing_aliases = self.aliases();
+        for mut alias in store.aliases() {
+            while existing_aliases.contains(&alias)
+                && !self.has_same_alias_entries(&store, &alias)
+            {
+                match resolve(&alias) {
+                    ConfirmationResponse::Replace => {
+                        self.remove_alias(&alias);
+                        break;
+                    }
+                    ConfirmationResponse::Skip => {
+                        store.remove_alias(&alias);
+                        break;
+                    }
+                    ConfirmationResponse::Reselect(new_alias) => {
+                        store.rename_alias(&alias, new_alias.clone());
+                        alias = new_alias;
+                    }
+                }
+            }
+        }
+        self.extend(store);
+    }
+
+    /// Get all the aliases of the entries of this store
+    fn aliases(&self) -> BTreeSet<Alias> {
+        self.view_keys
+            .keys()
+            .chain(self.spend_keys.keys())
+            .chain(self.payment_addrs.left_values())
+            .chain(self.secret_keys.keys())
+            .chain(self.public_keys.keys())
+            .chain(self.addresses.left_values())
+            .cloned()
+            .collect()
+    }
+
+    /// Check if an alias refers to the same entries in both stores
+    fn has_same_alias_entries(&self, other: &Store, alias: &Alias) -> bool {
+        self.addresses.get_by_left(alias) == other.addresses.get_by_left(alias)
+            && self.public_keys.get(alias) == other.public_keys.get(alias)
+            && self.view_keys.get(alias) == other.view_keys.get(alias)
+            && self.payment_addrs.get_by_left(alias)
+                == other.payment_addrs.get_by_left(alias)
+    }
+
+    /// Move all the entries of an alias to another alias
+    fn rename_alias(&mut self, alias: &Alias, new_alias: Alias) {
+        if let Some(view_key) = self.view_keys.remove(alias) {
+            self.view_keys.insert(new_alias.clone(), view_key);
+        }
+        if let Some(spend_key) = self.spend_keys.remove(alias) {
+            self.spend_keys.insert(new_alias.clone(), spend_key);
+        }
+        if let Some((_, payment_addr)) =
+            self.payment_addrs.remove_by_left(alias)
+        {
+            self.payment_addrs.insert(new_alias.clone(), payment_addr);
+        }
+        if let Some(secret_key) = self.secret_keys.remove(alias) {
+            self.secret_keys.insert(new_alias.clone(), secret_key);
+        }
+        if let Some(public_key) = self.public_keys.remove(alias) {
+            self.public_keys.insert(new_alias.clone(), public_key);
+        }
+        if let Some(path) = self.derivation_paths.remove(alias) {
+            self.derivation_paths.insert(new_alias.clone(), path);
+        }
+        if let Some((_, address)) = self.addresses.remove_by_left(alias) {
+            self.addresses.insert(new_alias.clone(), address);
+        }
+        for pkh_alias in self.pkhs.values_mut() {
+            if pkh_alias == alias {
+                *pkh_alias = new_alias.clone();
+            }
+        }
+    }
+
     /// Extend this store from pre-genesis validator wallet.
     pub fn extend_from_pre_genesis_validator(
         &mut self,
@@ -815,6 +905,58 @@ mod test_wallet {
 
     use super::*;
 
+    #[test]
+    fn test_extend_resolving_conflicts() {
+        use namada_core::key::testing::{
+            keypair_1, keypair_2, keypair_3, keypair_4,
+        };
+
+        let mut store = Store::default();
+        for (alias, sk) in [
+            ("same", keypair_1()),
+            ("replace", keypair_2()),
+            ("skip", keypair_2()),
+            ("rename", keypair_2()),
+        ] {
+            store.public_keys.insert(alias.into(), sk.ref_to());
+        }
+        let mut backup = Store::default();
+        for (alias, sk) in [
+            ("same", keypair_1()),
+            ("replace", keypair_3()),
+            ("skip", keypair_3()),
+            ("rename", keypair_3()),
+            ("new", keypair_4()),
+        ] {
+            backup.public_keys.insert(alias.into(), sk.ref_to());
+        }
+
+        let mut conflicts = vec![];
+        store.extend_resolving_conflicts(backup, |alias| {
+            conflicts.push(alias.clone());
+            match alias.normalize().as_str() {
+                "replace" => ConfirmationResponse::Replace,
+                "skip" => ConfirmationResponse::Skip,
+                _ => ConfirmationResponse::Reselect("renamed".into()),
+            }
+        });
+        conflicts.sort();
+        assert_eq!(
+            conflicts,
+            vec![Alias::from("rename"), "replace".into(), "skip".into()]
+        );
+        for (alias, sk) in [
+            ("same", keypair_1()),
+            ("replace", keypair_3()),
+            ("skip", keypair_2()),
+            ("rename", keypair_2()),
+            ("renamed", keypair_3()),
+            ("new", keypair_4()),
+        ] {
+            assert_eq!(store.public_keys[&alias.into()], sk.ref_to());
+        }
+    }
+
     #[test]
     fn gen_sk_from_mnemonic_code_secp256k1() {
         const SCHEME: SchemeType = SchemeType::Secp256k1;
@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use namada_sdk::testing::{
+    arb_tx_kinds, generate_ledger_vectors, LEDGER_VECTORS_VERSION,
+};
+use proptest::test_runner::Reason;
+
+#[tokio::main]
+async fn main() -> Result<(), Reason> {
+    let args: Vec<_> = std::env::args().collect();
+    if args.len() < 2 {
+        eprintln!("Usage: generate-ledger-vectors <output directory>");
+        return Result::Err(Reason::from("Incorrect command line arguments."));
+    }
+    let dir =
+        PathBuf::from(&args[1]).join(format!("v{}", LEDGER_VECTORS_VERSION));
+    std::fs::create_dir_all(&dir).expect("unable to create the directory");
+    for (kind, txs) in arb_tx_kinds() {
+        let vectors = generate_ledger_vectors(txs)
+            .await
+            .expect("unable to construct test vectors");
+        let json = serde_json::to_string_pretty(&vectors)
+            .expect("unable to serialize test vectors");
+        std::fs::write(dir.join(format!("{kind}.json")), json)
+            .expect("unable to save test vectors");
+    }
+    Ok(())
+}
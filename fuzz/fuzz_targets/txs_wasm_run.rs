@@ -39,6 +39,7 @@ lazy_static! {
 #[derive(Arbitrary, Debug)]
 enum TxKind {
     InitAccount(account::InitAccount),
+    InitVestingAccount(account::InitVestingAccount),
     BecomeValidator(pos::BecomeValidator),
     UnjailValidator(Address),
     DeactivateValidator(Address),
@@ -47,6 +48,7 @@ enum TxKind {
     VoteProposal(governance::VoteProposalData),
     RevealPk(common::PublicKey),
     UpdateAccount(account::UpdateAccount),
+    UpdateVpParams(account::UpdateVpParams),
     Transfer(token::Transfer),
     Ibc(IbcData),
     Bond(pos::Bond),
@@ -89,6 +91,10 @@ fn run(kinds: NonEmptyVec<TxKind>) {
                 tx.add_data(data);
                 tx::TX_INIT_ACCOUNT_WASM
             }
+            InitVestingAccount(data) => {
+                tx.add_data(data);
+                tx::TX_INIT_VESTING_ACCOUNT_WASM
+            }
             BecomeValidator(data) => {
                 tx.add_data(data);
                 tx::TX_BECOME_VALIDATOR_WASM
@@ -121,6 +127,10 @@ fn run(kinds: NonEmptyVec<TxKind>) {
                 tx.add_data(data);
                 tx::TX_UPDATE_ACCOUNT_WASM
             }
+            UpdateVpParams(data) => {
+                tx.add_data(data);
+                tx::TX_UPDATE_VP_PARAMS_WASM
+            }
             Transfer(data) => {
                 tx.add_data(data);
                 tx::TX_TRANSFER_WASM
@@ -0,0 +1,65 @@
+//! A tx for cancelling a transfer across the Ethereum bridge
+//! that is still pending in the bridge pool.
+use namada_tx_prelude::action::{Action, BridgePoolAction, Write};
+use namada_tx_prelude::eth_bridge_pool::{
+    get_pending_key, GasFee, PendingTransfer, TransferToEthereum,
+    BRIDGE_POOL_ADDRESS,
+};
+use namada_tx_prelude::parameters::native_erc20_key;
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let transfer = PendingTransfer::try_from_slice(&data[..])
+        .map_err(|e| Error::wrap("Error deserializing PendingTransfer", e))?;
+    debug_log!("Received transfer to cancel from Bridge pool");
+
+    // The cancellation must be authorized by the sender of the transfer
+    let sender = transfer.transfer.sender.clone();
+    ctx.insert_verifier(&sender)?;
+    ctx.push_action(Action::BridgePool(BridgePoolAction::CancelTransfer(
+        sender,
+    )))?;
+
+    // remove the transfer from the pool
+    let pending_key = get_pending_key(&transfer);
+    if !ctx.has_key(&pending_key)? {
+        return Err(Error::new_const(
+            "The transfer is not pending in the Bridge pool",
+        ));
+    }
+    ctx.delete(&pending_key)
+        .wrap_err("Could not remove transfer from bridge pool")?;
+
+    // refund the escrowed tokens
+    let TransferToEthereum {
+        asset,
+        ref sender,
+        amount,
+        ..
+    } = transfer.transfer;
+    if asset == native_erc20_address(ctx)? {
+        let nam_addr = ctx.get_native_token()?;
+        token::transfer(ctx, &address::ETH_BRIDGE, sender, &nam_addr, amount)?;
+    } else {
+        let token = transfer.token_address();
+        token::transfer(ctx, &BRIDGE_POOL_ADDRESS, sender, &token, amount)?;
+    }
+    debug_log!("Bridge pool escrow refund succeeded");
+    // refund the gas fees
+    let GasFee {
+        token: ref fee_token_addr,
+        amount,
+        ref payer,
+    } = transfer.gas_fee;
+    token::transfer(ctx, &BRIDGE_POOL_ADDRESS, payer, fee_token_addr, amount)?;
+    debug_log!("Bridge pool gas fee refund succeeded");
+    Ok(())
+}
+
+fn native_erc20_address(ctx: &mut Ctx) -> Result<EthAddress> {
+    ctx.read(&native_erc20_key())
+        .wrap_err("Could not read wrapped NAM address")?
+        .ok_or_err_msg("Wrapped NAM address must be present in storage")
+}
@@ -0,0 +1,64 @@
+//! A tx to initialize a new established vesting account with a given public
+//! key and the user validity predicate, which enforces the vesting schedule.
+
+use namada_tx_prelude::*;
+
+const HASH_LEN: usize = hash::HASH_LENGTH;
+const VP_USER_WASM: &str = "vp_user.wasm";
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, tx_data: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&tx_data)?;
+    let BatchedTx {
+        tx: signed,
+        ref cmt,
+    } = tx_data;
+    let tx_data = account::InitVestingAccount::try_from_slice(&data[..])
+        .wrap_err("Failed to decode InitVestingAccount tx data")?;
+    debug_log!("apply_tx called to init a new established vesting account");
+
+    let vp_code_sec = signed
+        .get_section(&tx_data.account.vp_code_hash)
+        .ok_or_err_msg("VP code section not found in tx")
+        .inspect_err(|_| {
+            ctx.set_commitment_sentinel();
+        })?
+        .extra_data_sec()
+        .ok_or_err_msg("VP code section must be tagged as extra")
+        .inspect_err(|_| {
+            ctx.set_commitment_sentinel();
+        })?;
+
+    // Only the user VP enforces the vesting schedule
+    let vp_user_hash: Option<hash::Hash> =
+        ctx.read(&storage::Key::wasm_hash(VP_USER_WASM))?;
+    if vp_user_hash != Some(vp_code_sec.code.hash()) {
+        return Err(Error::new_const(
+            "The VP of a vesting account must be the user VP",
+        ));
+    }
+
+    let entropy = {
+        let mut buffer = [0u8; HASH_LEN * 2];
+
+        // Add code hash as entropy
+        buffer[..HASH_LEN].copy_from_slice(&cmt.code_sechash().0);
+
+        // Add data hash as entropy
+        buffer[HASH_LEN..].copy_from_slice(&cmt.data_sechash().0);
+
+        buffer
+    };
+
+    let address = ctx
+        .init_account(vp_code_sec.code.hash(), &vp_code_sec.tag, &entropy)
+        .wrap_err("Failed to generate a new established account address")?;
+
+    account::init_account(ctx, &address, tx_data.account)
+        .wrap_err("Account creation failed")?;
+    account::init_vesting_schedule(ctx, &address, &tx_data.vesting)
+        .wrap_err("Failed to set the vesting schedule")?;
+
+    debug_log!("Created vesting account {address}");
+    Ok(())
+}
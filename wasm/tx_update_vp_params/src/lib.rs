@@ -0,0 +1,22 @@
+//! A tx for updating the parameters of an account's validity predicate.
+//! This tx uses `account::UpdateVpParams` as its input.
+
+use namada_tx_prelude::*;
+
+#[transaction]
+fn apply_tx(ctx: &mut Ctx, batched_tx: BatchedTx) -> TxResult {
+    let data = ctx.get_tx_data(&batched_tx)?;
+    let tx_data = account::UpdateVpParams::try_from_slice(&data[..])
+        .wrap_err("Failed to decode UpdateVpParams tx data")?;
+
+    let owner = &tx_data.addr;
+    debug_log!("update VP params for: {:#?}", tx_data.addr);
+
+    // The tx must be authorized by the source address
+    ctx.insert_verifier(owner)?;
+
+    account::write_vp_params(ctx, owner, &tx_data.vp_params)
+        .wrap_err("Failed to update the account's VP parameters")?;
+
+    Ok(())
+}
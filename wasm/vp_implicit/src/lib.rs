@@ -115,9 +115,21 @@ fn validate_tx(
                 )?,
             Action::Masp(MaspAction::MaspSectionRef(_)) => (),
             Action::IbcShielding => (),
+            Action::BridgePool(BridgePoolAction::CancelTransfer(source)) => {
+                gadget.verify_signatures_when(
+                    || source == addr,
+                    ctx,
+                    &tx,
+                    cmt,
+                    &addr,
+                )?
+            }
         }
     }
 
+    // Enforce the policies configured by the account's VP parameters
+    check_account_policies(ctx, &tx, &addr, &keys_changed)?;
+
     keys_changed.iter().try_for_each(|key| {
         let key_type: KeyType = key.into();
         let mut validate_change = || match key_type {
@@ -206,6 +218,17 @@ fn validate_tx(
                 cmt,
                 &addr,
             ),
+            KeyType::VpParams(owner) => gadget.verify_signatures_when(
+                // NB: the bypass threshold, when set, is also checked with
+                // the account's policies
+                || owner == &addr,
+                ctx,
+                &tx,
+                cmt,
+                &addr,
+            ),
+            // Checked against the debits with the account's policies
+            KeyType::DebitRecord => Ok(()),
             KeyType::Masp | KeyType::Ibc => Ok(()),
             KeyType::Unknown => {
                 // Unknown changes require a valid signature
@@ -228,6 +251,8 @@ enum KeyType<'a> {
     },
     TokenMinted,
     TokenMinter(&'a Address),
+    VpParams(&'a Address),
+    DebitRecord,
     Masp,
     Ibc,
     Unknown,
@@ -246,6 +271,12 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
         } else if let Some(minter) = token::storage_key::is_any_minter_key(key)
         {
             Self::TokenMinter(minter)
+        } else if let Some(owner) = account::is_vp_params_key(key) {
+            Self::VpParams(owner)
+        } else if account::is_daily_debit_key(key).is_some()
+            || account::is_withdrawals_key(key).is_some()
+        {
+            Self::DebitRecord
         } else if token::storage_key::is_masp_key(key) {
             Self::Masp
         } else if ibc::is_ibc_key(key) {
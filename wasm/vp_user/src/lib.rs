@@ -114,9 +114,21 @@ fn validate_tx(
                 )?,
             Action::Masp(MaspAction::MaspSectionRef(_)) => (),
             Action::IbcShielding => (),
+            Action::BridgePool(BridgePoolAction::CancelTransfer(source)) => {
+                gadget.verify_signatures_when(
+                    || source == addr,
+                    ctx,
+                    &tx,
+                    cmt,
+                    &addr,
+                )?
+            }
         }
     }
 
+    // Enforce the policies configured by the account's VP parameters
+    check_account_policies(ctx, &tx, &addr, &keys_changed)?;
+
     keys_changed.iter().try_for_each(|key| {
         let key_type: KeyType = key.into();
         let mut validate_change = || match key_type {
@@ -177,6 +189,17 @@ fn validate_tx(
                     &addr,
                 )
             }
+            KeyType::VpParams(owner) => gadget.verify_signatures_when(
+                // NB: the bypass threshold, when set, is also checked with
+                // the account's policies
+                || owner == &addr,
+                ctx,
+                &tx,
+                cmt,
+                &addr,
+            ),
+            // Checked against the debits with the account's policies
+            KeyType::DebitRecord => Ok(()),
             KeyType::Masp | KeyType::Ibc => Ok(()),
             KeyType::Unknown => {
                 // Unknown changes require a valid signature
@@ -196,6 +219,8 @@ enum KeyType<'a> {
     TokenMinted,
     TokenMinter(&'a Address),
     Vp(&'a Address),
+    VpParams(&'a Address),
+    DebitRecord,
     Masp,
     Ibc,
     Unknown,
@@ -214,6 +239,12 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
             Self::TokenMinter(minter)
         } else if let Some(address) = key.is_validity_predicate() {
             Self::Vp(address)
+        } else if let Some(owner) = account::is_vp_params_key(key) {
+            Self::VpParams(owner)
+        } else if account::is_daily_debit_key(key).is_some()
+            || account::is_withdrawals_key(key).is_some()
+        {
+            Self::DebitRecord
         } else if token::storage_key::is_masp_key(key) {
             Self::Masp
         } else if ibc::is_ibc_key(key) {
@@ -481,6 +512,283 @@ mod tests {
         );
     }
 
+    /// Test that a signed debit transfer exceeding the spending limit set in
+    /// the account's VP parameters is rejected.
+    #[test]
+    fn test_debit_transfer_over_spending_limit_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let target = address::testing::established_address_2();
+        let token = address::testing::nam();
+        let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &target, &token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+        // Credit the tokens to the VP owner before running the transaction to
+        // be able to transfer from it
+        tx_env.credit_tokens(&vp_owner, &token, amount);
+        // write the denomination of NAM into storage
+        token::write_denom(
+            &mut tx_env.state,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+        // Limit the spending of the VP owner to less than the amount
+        let params = account::VpParams {
+            spending_limits: [(
+                token.clone(),
+                token::Amount::from_uint(10_000_000, 0).unwrap(),
+            )]
+            .into(),
+            ..Default::default()
+        };
+        account::write_vp_params(&mut tx_env.state, &vp_owner, &params)
+            .unwrap();
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Apply transfer in a transaction
+            tx_host_env::token::transfer(
+                tx::ctx(),
+                address,
+                &target,
+                &token,
+                amount,
+            )
+            .unwrap();
+        });
+
+        let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.batched_tx.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Authorization(Authorization::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.batch_first_tx();
+        vp_env.batched_tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                .is_err()
+        );
+    }
+
+    /// Test that a debit transfer exceeding the spending limit set in the
+    /// account's VP parameters is accepted when signed by the bypass threshold
+    /// of keys, but not with only the account's threshold.
+    #[test]
+    fn test_debit_transfer_over_spending_limit_bypassed() {
+        let token = address::testing::nam();
+        let target = address::testing::established_address_2();
+        let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+        // Limit the spending of the VP owner to less than the amount, with a
+        // bypass threshold above the account's threshold
+        let params = account::VpParams {
+            spending_limits: [(
+                token.clone(),
+                token::Amount::from_uint(10_000_000, 0).unwrap(),
+            )]
+            .into(),
+            bypass_threshold: Some(2),
+            ..Default::default()
+        };
+        let validate_signed_by = |keypairs| {
+            validate_with_vp_params(
+                &params,
+                keypairs,
+                |tx_env, address| {
+                    // Credit the tokens to the VP owner before running the
+                    // transaction to be able to transfer from it
+                    tx_env.spawn_accounts([&target, &token]);
+                    tx_env.credit_tokens(address, &token, amount);
+                    token::write_denom(
+                        &mut tx_env.state,
+                        &token,
+                        token::NATIVE_MAX_DECIMAL_PLACES.into(),
+                    )
+                    .unwrap();
+                },
+                |address| {
+                    // Apply transfer in a transaction
+                    tx_host_env::token::transfer(
+                        tx::ctx(),
+                        address,
+                        &target,
+                        &token,
+                        amount,
+                    )
+                    .unwrap();
+                },
+            )
+        };
+
+        let keypair_1 = key::testing::keypair_1();
+        let keypair_2 = key::testing::keypair_2();
+        // The account's threshold is not enough to exceed the limit
+        let one_sig = vec![keypair_1.clone()];
+        assert!(is_rejected(|| validate_signed_by(one_sig)));
+        assert!(validate_signed_by(vec![keypair_1, keypair_2]).is_ok());
+    }
+
+    /// Test that a change of the VP parameters of an account with a bypass
+    /// threshold must be signed by the bypass threshold of keys.
+    #[test]
+    fn test_vp_params_change_requires_bypass_threshold() {
+        let token = address::testing::nam();
+        let params = account::VpParams {
+            spending_limits: [(
+                token.clone(),
+                token::Amount::from_uint(10_000_000, 0).unwrap(),
+            )]
+            .into(),
+            bypass_threshold: Some(2),
+            ..Default::default()
+        };
+        let validate_signed_by = |keypairs| {
+            validate_with_vp_params(
+                &params,
+                keypairs,
+                |_, _| {},
+                |address| {
+                    // Remove the spending limit in a transaction
+                    let params = account::VpParams {
+                        bypass_threshold: Some(2),
+                        ..Default::default()
+                    };
+                    account::write_vp_params(tx::ctx(), address, &params)
+                        .unwrap();
+                },
+            )
+        };
+
+        let keypair_1 = key::testing::keypair_1();
+        let keypair_2 = key::testing::keypair_2();
+        // A tx signed by the account's threshold cannot change the params
+        let one_sig = vec![keypair_1.clone()];
+        assert!(is_rejected(|| validate_signed_by(one_sig)));
+        assert!(validate_signed_by(vec![keypair_1, keypair_2]).is_ok());
+    }
+
+    /// Check if a VP rejects a transaction, either with an error or with a
+    /// panic of the test host env on invalid signatures
+    fn is_rejected(validate: impl FnOnce() -> VpResult) -> bool {
+        panic::catch_unwind(panic::AssertUnwindSafe(validate))
+            .map_or(true, |result| result.is_err())
+    }
+
+    /// Run the VP of an account with the given VP parameters, two keys and a
+    /// threshold of 1 on a transaction applied by `apply_tx` and signed by
+    /// the given keys. The `init` function can be used to set up the storage
+    /// before the transaction.
+    fn validate_with_vp_params(
+        params: &account::VpParams,
+        keypairs: Vec<key::common::SecretKey>,
+        init: impl FnOnce(&mut TestTxEnv, &Address),
+        apply_tx: impl FnMut(&Address),
+    ) -> VpResult {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let public_keys = vec![
+            key::testing::keypair_1().ref_to(),
+            key::testing::keypair_2().ref_to(),
+        ];
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner]);
+        tx_env.init_account_storage(&vp_owner, public_keys.clone(), 1);
+        account::write_vp_params(&mut tx_env.state, &vp_owner, params).unwrap();
+        init(&mut tx_env, &vp_owner);
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, apply_tx);
+
+        let pks_map = AccountPublicKeysMap::from_iter(public_keys);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.batched_tx.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Authorization(Authorization::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(keypairs),
+            None,
+        )));
+        let signed_tx = tx.batch_first_tx();
+        vp_env.batched_tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+    }
+
+    /// Test that a direct write to the records of the debits of an account is
+    /// rejected, even when signed.
+    #[test]
+    fn test_debit_record_write_rejected() {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let token = address::testing::nam();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, &token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+            // Reset the withdrawals of a token without a withdrawal limit
+            tx::ctx()
+                .write(
+                    &account::withdrawals_key(address, &token),
+                    Vec::<account::Withdrawal>::new(),
+                )
+                .unwrap();
+        });
+
+        let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.batched_tx.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Authorization(Authorization::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.batch_first_tx();
+        vp_env.batched_tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        assert!(
+            validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                .is_err()
+        );
+    }
+
     /// Test that a non-validator PoS action that must be authorized is rejected
     /// without a valid signature.
     #[test]